@@ -0,0 +1,11 @@
+-- Log chunks can now be stored zstd-compressed. Existing rows stay as plain JSONL.
+ALTER TABLE execution_process_logs ADD COLUMN compression TEXT NOT NULL DEFAULT 'none'
+    CHECK (compression IN ('none', 'zstd'));
+
+-- Set once the raw stdout/stderr of a process has been replaced by its normalized entries
+-- by the retention job.
+ALTER TABLE execution_process_logs ADD COLUMN raw_pruned BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_execution_process_logs_compression
+    ON execution_process_logs (execution_id)
+    WHERE compression = 'none';
@@ -0,0 +1,79 @@
+-- Full-text index over task titles/descriptions, coding agent prompts/summaries and
+-- normalized conversation entries. Ids are stored unindexed so hits can be joined back
+-- to their task, session and execution process.
+CREATE VIRTUAL TABLE search_index USING fts5(
+    content,
+    kind UNINDEXED,
+    project_id UNINDEXED,
+    task_id UNINDEXED,
+    session_id UNINDEXED,
+    execution_process_id UNINDEXED,
+    created_at UNINDEXED,
+    tokenize = 'porter unicode61'
+);
+
+CREATE TRIGGER search_index_tasks_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO search_index (content, kind, project_id, task_id, created_at)
+    VALUES (NEW.title || char(10) || COALESCE(NEW.description, ''), 'task', NEW.project_id, NEW.id, NEW.created_at);
+END;
+
+CREATE TRIGGER search_index_tasks_update AFTER UPDATE OF title, description ON tasks
+BEGIN
+    DELETE FROM search_index WHERE kind = 'task' AND task_id = OLD.id;
+    INSERT INTO search_index (content, kind, project_id, task_id, created_at)
+    VALUES (NEW.title || char(10) || COALESCE(NEW.description, ''), 'task', NEW.project_id, NEW.id, NEW.updated_at);
+END;
+
+CREATE TRIGGER search_index_tasks_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM search_index WHERE task_id = OLD.id;
+END;
+
+CREATE TRIGGER search_index_turns_insert AFTER INSERT ON coding_agent_turns
+WHEN NEW.prompt IS NOT NULL
+BEGIN
+    INSERT INTO search_index (content, kind, project_id, task_id, session_id, execution_process_id, created_at)
+    SELECT NEW.prompt, 'prompt', t.project_id, t.id, s.id, ep.id, NEW.created_at
+    FROM execution_processes ep
+    JOIN sessions s ON s.id = ep.session_id
+    JOIN workspaces w ON w.id = s.workspace_id
+    JOIN tasks t ON t.id = w.task_id
+    WHERE ep.id = NEW.execution_process_id;
+END;
+
+CREATE TRIGGER search_index_turns_summary AFTER UPDATE OF summary ON coding_agent_turns
+WHEN NEW.summary IS NOT NULL
+BEGIN
+    DELETE FROM search_index WHERE kind = 'summary' AND execution_process_id = NEW.execution_process_id;
+    INSERT INTO search_index (content, kind, project_id, task_id, session_id, execution_process_id, created_at)
+    SELECT NEW.summary, 'summary', t.project_id, t.id, s.id, ep.id, NEW.updated_at
+    FROM execution_processes ep
+    JOIN sessions s ON s.id = ep.session_id
+    JOIN workspaces w ON w.id = s.workspace_id
+    JOIN tasks t ON t.id = w.task_id
+    WHERE ep.id = NEW.execution_process_id;
+END;
+
+-- Backfill existing data. Conversation entries are indexed as executions finish.
+INSERT INTO search_index (content, kind, project_id, task_id, created_at)
+SELECT title || char(10) || COALESCE(description, ''), 'task', project_id, id, updated_at
+FROM tasks;
+
+INSERT INTO search_index (content, kind, project_id, task_id, session_id, execution_process_id, created_at)
+SELECT cat.prompt, 'prompt', t.project_id, t.id, s.id, ep.id, cat.created_at
+FROM coding_agent_turns cat
+JOIN execution_processes ep ON ep.id = cat.execution_process_id
+JOIN sessions s ON s.id = ep.session_id
+JOIN workspaces w ON w.id = s.workspace_id
+JOIN tasks t ON t.id = w.task_id
+WHERE cat.prompt IS NOT NULL;
+
+INSERT INTO search_index (content, kind, project_id, task_id, session_id, execution_process_id, created_at)
+SELECT cat.summary, 'summary', t.project_id, t.id, s.id, ep.id, cat.updated_at
+FROM coding_agent_turns cat
+JOIN execution_processes ep ON ep.id = cat.execution_process_id
+JOIN sessions s ON s.id = ep.session_id
+JOIN workspaces w ON w.id = s.workspace_id
+JOIN tasks t ON t.id = w.task_id
+WHERE cat.summary IS NOT NULL;
@@ -0,0 +1,21 @@
+-- Per-project rules that decide tool approval requests without user input.
+-- Rules are evaluated in ascending position; the first match wins.
+CREATE TABLE approval_policy_rules (
+    id              BLOB PRIMARY KEY,
+    project_id      BLOB NOT NULL,
+    position        INTEGER NOT NULL,
+    name            TEXT NOT NULL,
+    tool_name       TEXT,
+    pattern         TEXT,
+    pattern_kind    TEXT NOT NULL DEFAULT 'glob'
+                       CHECK (pattern_kind IN ('glob', 'regex')),
+    action          TEXT NOT NULL
+                       CHECK (action IN ('approve', 'deny')),
+    enabled         BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at      TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_approval_policy_rules_project_position
+    ON approval_policy_rules(project_id, position);
@@ -0,0 +1,24 @@
+-- Audit trail of tool approval requests and how they were resolved
+CREATE TABLE approvals (
+    id                    TEXT PRIMARY KEY,
+    execution_process_id  BLOB NOT NULL,
+    tool_name             TEXT NOT NULL,
+    tool_input            TEXT NOT NULL DEFAULT 'null',
+    tool_call_id          TEXT NOT NULL,
+    status                TEXT NOT NULL DEFAULT 'pending'
+                             CHECK (status IN ('pending', 'approved', 'denied', 'timed_out')),
+    denial_reason         TEXT,
+    decided_by            TEXT
+                             CHECK (decided_by IN ('user', 'policy', 'timeout')),
+    rule_id               BLOB,
+    responder             TEXT,
+    latency_ms            INTEGER,
+    created_at            TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    timeout_at            TEXT NOT NULL,
+    responded_at          TEXT,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE,
+    FOREIGN KEY (rule_id) REFERENCES approval_policy_rules(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_approvals_execution_process_id ON approvals(execution_process_id);
+CREATE INDEX idx_approvals_pending ON approvals(status) WHERE status = 'pending';
@@ -0,0 +1,34 @@
+-- Outbound webhook subscriptions for task, execution and PR lifecycle events.
+-- An empty event list subscribes to every event; a NULL project_id to every project.
+CREATE TABLE webhook_subscriptions (
+    id          BLOB PRIMARY KEY,
+    name        TEXT NOT NULL,
+    url         TEXT NOT NULL,
+    secret      TEXT,
+    events      TEXT NOT NULL DEFAULT '[]',
+    project_id  BLOB,
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at  TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+-- One row per event sent to a subscription, updated after every attempt
+CREATE TABLE webhook_deliveries (
+    id               BLOB PRIMARY KEY,
+    subscription_id  BLOB NOT NULL,
+    event_type       TEXT NOT NULL,
+    payload          TEXT NOT NULL,
+    status           TEXT NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts         INTEGER NOT NULL DEFAULT 0,
+    response_status  INTEGER,
+    last_error       TEXT,
+    created_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    delivered_at     TEXT,
+    FOREIGN KEY (subscription_id) REFERENCES webhook_subscriptions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_webhook_deliveries_subscription_created
+    ON webhook_deliveries(subscription_id, created_at);
@@ -0,0 +1,23 @@
+-- Access tokens for remote access mode. Only a SHA-256 hash of each token is stored;
+-- the prefix is kept so tokens can be told apart in the UI.
+CREATE TABLE access_tokens (
+    id            BLOB PRIMARY KEY,
+    name          TEXT NOT NULL,
+    token_hash    TEXT NOT NULL UNIQUE,
+    token_prefix  TEXT NOT NULL,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    last_used_at  TEXT
+);
+
+-- Browser sessions issued by exchanging an access token at login. Revoking the token
+-- ends its sessions.
+CREATE TABLE auth_sessions (
+    id            BLOB PRIMARY KEY,
+    token_id      BLOB NOT NULL,
+    session_hash  TEXT NOT NULL UNIQUE,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    expires_at    TEXT NOT NULL,
+    FOREIGN KEY (token_id) REFERENCES access_tokens(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_auth_sessions_token_id ON auth_sessions(token_id);
@@ -0,0 +1,20 @@
+-- Which plan phase each imported task came from. `synced_status` is the plan status last
+-- written to either side, so plan/task sync can tell which side changed and doesn't echo
+-- its own writes back.
+CREATE TABLE plan_task_links (
+    id             BLOB PRIMARY KEY,
+    task_id        BLOB NOT NULL UNIQUE,
+    project_id     BLOB NOT NULL,
+    plan_id        TEXT NOT NULL,
+    plan_path      TEXT NOT NULL,
+    phase          INTEGER,
+    phase_file     TEXT,
+    synced_status  TEXT NOT NULL,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_plan_task_links_project_plan ON plan_task_links(project_id, plan_id);
+CREATE UNIQUE INDEX idx_plan_task_links_phase_file ON plan_task_links(project_id, phase_file);
@@ -0,0 +1,19 @@
+-- GitHub issues imported as tasks. `state` is the issue state last seen on GitHub
+-- ('open' or 'closed'); closing the issue moves the task to done.
+CREATE TABLE task_github_issues (
+    id            BLOB PRIMARY KEY,
+    task_id       BLOB NOT NULL UNIQUE,
+    repo_id       BLOB NOT NULL,
+    owner         TEXT NOT NULL,
+    repo_name     TEXT NOT NULL,
+    issue_number  INTEGER NOT NULL,
+    issue_url     TEXT NOT NULL,
+    state         TEXT NOT NULL DEFAULT 'open',
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (repo_id) REFERENCES repos(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_github_issues_issue ON task_github_issues(owner, repo_name, issue_number);
+CREATE INDEX idx_task_github_issues_state ON task_github_issues(state);
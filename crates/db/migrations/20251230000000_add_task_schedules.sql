@@ -0,0 +1,48 @@
+-- Schedules that start coding agents on a cron expression, either on a fresh task created
+-- from the schedule's title and description or as a new attempt on an existing task.
+CREATE TABLE task_schedules (
+    id                   BLOB PRIMARY KEY,
+    project_id           BLOB NOT NULL,
+    name                 TEXT NOT NULL,
+    cron_expression      TEXT NOT NULL,
+    timezone             TEXT NOT NULL DEFAULT 'UTC',
+    action               TEXT NOT NULL DEFAULT 'create_task'
+                            CHECK (action IN ('create_task', 'restart_attempt')),
+    -- Title and description of the tasks created by 'create_task' schedules
+    title                TEXT,
+    description          TEXT,
+    -- Task that 'restart_attempt' schedules start new attempts on
+    task_id              BLOB,
+    executor_profile_id  TEXT NOT NULL,
+    -- Repositories and target branches; empty reuses those of the task's latest attempt
+    repos                TEXT NOT NULL DEFAULT '[]',
+    enabled              BOOLEAN NOT NULL DEFAULT TRUE,
+    next_run_at          TEXT,
+    last_run_at          TEXT,
+    created_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at           TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_schedules_next_run_at ON task_schedules(enabled, next_run_at);
+
+-- One row per time a schedule came due, including runs skipped because the previous one
+-- was still going
+CREATE TABLE task_schedule_runs (
+    id             BLOB PRIMARY KEY,
+    schedule_id    BLOB NOT NULL,
+    scheduled_for  TEXT NOT NULL,
+    status         TEXT NOT NULL
+                      CHECK (status IN ('started', 'skipped', 'failed')),
+    task_id        BLOB,
+    workspace_id   BLOB,
+    error          TEXT,
+    created_at     TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (schedule_id) REFERENCES task_schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE SET NULL,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_schedule_runs_schedule_created
+    ON task_schedule_runs(schedule_id, created_at);
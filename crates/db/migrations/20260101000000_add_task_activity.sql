@@ -0,0 +1,103 @@
+-- Per-task activity timeline. System events are recorded by triggers so every write path
+-- (routes, MCP, background services) is covered; comments are inserted by the API.
+CREATE TABLE task_activity (
+    id            BLOB PRIMARY KEY,
+    task_id       BLOB NOT NULL,
+    workspace_id  BLOB,
+    kind          TEXT NOT NULL
+                     CHECK (kind IN ('status_changed', 'attempt_created', 'attempt_finished',
+                                     'merged', 'approval', 'comment')),
+    details       TEXT NOT NULL DEFAULT '{}',
+    body          TEXT,
+    author        TEXT,
+    created_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    updated_at    TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    FOREIGN KEY (task_id) REFERENCES tasks(id) ON DELETE CASCADE,
+    FOREIGN KEY (workspace_id) REFERENCES workspaces(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_task_activity_task_id_created_at ON task_activity(task_id, created_at);
+
+CREATE TABLE task_activity_images (
+    activity_id  BLOB NOT NULL,
+    image_id     BLOB NOT NULL,
+    created_at   TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    PRIMARY KEY (activity_id, image_id),
+    FOREIGN KEY (activity_id) REFERENCES task_activity(id) ON DELETE CASCADE,
+    FOREIGN KEY (image_id) REFERENCES images(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_activity_images_image_id ON task_activity_images(image_id);
+
+CREATE TRIGGER task_activity_status_changed AFTER UPDATE OF status ON tasks
+WHEN OLD.status IS NOT NEW.status
+BEGIN
+    INSERT INTO task_activity (id, task_id, kind, details)
+    VALUES (
+        randomblob(16), NEW.id, 'status_changed',
+        json_object('from_status', OLD.status, 'to_status', NEW.status)
+    );
+END;
+
+CREATE TRIGGER task_activity_attempt_created AFTER INSERT ON workspaces
+BEGIN
+    INSERT INTO task_activity (id, task_id, workspace_id, kind, details, created_at, updated_at)
+    VALUES (
+        randomblob(16), NEW.task_id, NEW.id, 'attempt_created',
+        json_object('branch', NEW.branch), NEW.created_at, NEW.created_at
+    );
+END;
+
+CREATE TRIGGER task_activity_attempt_finished AFTER UPDATE OF status ON execution_processes
+WHEN OLD.status = 'running' AND NEW.status <> 'running' AND NEW.run_reason = 'codingagent'
+BEGIN
+    INSERT INTO task_activity (id, task_id, workspace_id, kind, details)
+    SELECT randomblob(16), w.task_id, w.id, 'attempt_finished',
+           json_object('execution_process_id', lower(hex(NEW.id)), 'outcome', NEW.status)
+    FROM sessions s
+    JOIN workspaces w ON w.id = s.workspace_id
+    WHERE s.id = NEW.session_id;
+END;
+
+CREATE TRIGGER task_activity_merged AFTER INSERT ON merges
+WHEN NEW.merge_type = 'direct' OR NEW.pr_status = 'merged'
+BEGIN
+    INSERT INTO task_activity (id, task_id, workspace_id, kind, details)
+    SELECT randomblob(16), w.task_id, w.id, 'merged',
+           json_object('merge_commit', COALESCE(NEW.merge_commit, NEW.pr_merge_commit_sha),
+                       'pr_number', NEW.pr_number, 'pr_url', NEW.pr_url)
+    FROM workspaces w
+    WHERE w.id = NEW.workspace_id;
+END;
+
+CREATE TRIGGER task_activity_pr_merged AFTER UPDATE OF pr_status ON merges
+WHEN NEW.pr_status = 'merged' AND OLD.pr_status IS NOT 'merged'
+BEGIN
+    INSERT INTO task_activity (id, task_id, workspace_id, kind, details)
+    SELECT randomblob(16), w.task_id, w.id, 'merged',
+           json_object('merge_commit', NEW.pr_merge_commit_sha,
+                       'pr_number', NEW.pr_number, 'pr_url', NEW.pr_url)
+    FROM workspaces w
+    WHERE w.id = NEW.workspace_id;
+END;
+
+CREATE TRIGGER task_activity_approval AFTER UPDATE OF status ON approvals
+WHEN OLD.status = 'pending' AND NEW.status <> 'pending'
+BEGIN
+    INSERT INTO task_activity (id, task_id, workspace_id, kind, details, author)
+    SELECT randomblob(16), w.task_id, w.id, 'approval',
+           json_object('execution_process_id', lower(hex(NEW.execution_process_id)),
+                       'tool_name', NEW.tool_name, 'outcome', NEW.status,
+                       'decided_by', NEW.decided_by, 'denial_reason', NEW.denial_reason),
+           NEW.responder
+    FROM execution_processes ep
+    JOIN sessions s ON s.id = ep.session_id
+    JOIN workspaces w ON w.id = s.workspace_id
+    WHERE ep.id = NEW.execution_process_id;
+END;
+
+-- Backfill attempts so existing tasks don't start with an empty timeline.
+INSERT INTO task_activity (id, task_id, workspace_id, kind, details, created_at, updated_at)
+SELECT randomblob(16), task_id, id, 'attempt_created', json_object('branch', branch),
+       created_at, created_at
+FROM workspaces;
@@ -0,0 +1,7 @@
+-- Archived tasks and projects are hidden from the board but keep their attempts, logs and
+-- merges until they are restored or purged.
+ALTER TABLE tasks ADD COLUMN archived_at TEXT;
+ALTER TABLE projects ADD COLUMN archived_at TEXT;
+
+CREATE INDEX idx_tasks_archived_at ON tasks(archived_at) WHERE archived_at IS NOT NULL;
+CREATE INDEX idx_projects_archived_at ON projects(archived_at) WHERE archived_at IS NOT NULL;
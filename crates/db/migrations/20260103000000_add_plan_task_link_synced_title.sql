@@ -0,0 +1,9 @@
+-- Task title last written from the plan, so plan edits that leave a phase's title alone don't
+-- overwrite a task renamed on the board. Existing links start from the task's current title.
+ALTER TABLE plan_task_links ADD COLUMN synced_title TEXT NOT NULL DEFAULT '';
+
+UPDATE plan_task_links
+SET synced_title = COALESCE(
+    (SELECT title FROM tasks WHERE tasks.id = plan_task_links.task_id),
+    ''
+);
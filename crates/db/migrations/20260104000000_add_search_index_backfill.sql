@@ -0,0 +1,12 @@
+-- Coding agent executions that finished before their conversations were indexed, or before
+-- file edits were. A startup job indexes their stored logs and removes them from this queue
+-- as it goes, so an interrupted backfill resumes where it stopped.
+CREATE TABLE search_index_backfill (
+    execution_process_id  BLOB PRIMARY KEY,
+    FOREIGN KEY (execution_process_id) REFERENCES execution_processes(id) ON DELETE CASCADE
+);
+
+INSERT INTO search_index_backfill (execution_process_id)
+SELECT id
+FROM execution_processes
+WHERE run_reason = 'codingagent' AND status <> 'running';
@@ -0,0 +1,81 @@
+//! Online copy of the live database through SQLite's backup API. sqlx doesn't wrap the API,
+//! so this talks to SQLite directly over dedicated connections.
+
+use std::{
+    ffi::{CStr, CString},
+    io,
+    path::Path,
+    ptr, thread,
+    time::Duration,
+};
+
+use libsqlite3_sys as ffi;
+
+/// Pages copied per step. The source is only locked while a step runs, so the server keeps
+/// writing in between.
+const PAGES_PER_STEP: i32 = 256;
+const STEP_PAUSE: Duration = Duration::from_millis(10);
+const BUSY_TIMEOUT_MS: i32 = 5_000;
+
+/// Copy the database at `source` into a new database file at `dest`. Blocks until done.
+pub fn backup_database(source: &Path, dest: &Path) -> io::Result<()> {
+    let src = Connection::open(source, ffi::SQLITE_OPEN_READONLY)?;
+    let dst = Connection::open(dest, ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE)?;
+
+    // SAFETY: both handles are open for the lifetime of the backup, which is finished before
+    // they are dropped, and neither is shared with another thread
+    unsafe {
+        ffi::sqlite3_busy_timeout(src.0, BUSY_TIMEOUT_MS);
+        let backup = ffi::sqlite3_backup_init(dst.0, c"main".as_ptr(), src.0, c"main".as_ptr());
+        if backup.is_null() {
+            return Err(dst.error());
+        }
+        loop {
+            match ffi::sqlite3_backup_step(backup, PAGES_PER_STEP) {
+                ffi::SQLITE_DONE => break,
+                ffi::SQLITE_OK | ffi::SQLITE_BUSY | ffi::SQLITE_LOCKED => thread::sleep(STEP_PAUSE),
+                // Any other result is an error, reported by `sqlite3_backup_finish`
+                _ => break,
+            }
+        }
+        if ffi::sqlite3_backup_finish(backup) != ffi::SQLITE_OK {
+            return Err(dst.error());
+        }
+    }
+    Ok(())
+}
+
+struct Connection(*mut ffi::sqlite3);
+
+impl Connection {
+    fn open(path: &Path, flags: i32) -> io::Result<Self> {
+        let c_path = CString::new(path.to_string_lossy().as_bytes()).map_err(io::Error::other)?;
+        let mut handle = ptr::null_mut();
+        // SAFETY: `c_path` outlives the call; the handle is closed on drop even when opening
+        // fails, as SQLite requires
+        let rc = unsafe { ffi::sqlite3_open_v2(c_path.as_ptr(), &mut handle, flags, ptr::null()) };
+        let conn = Connection(handle);
+        if rc != ffi::SQLITE_OK {
+            return Err(conn.error());
+        }
+        Ok(conn)
+    }
+
+    fn error(&self) -> io::Error {
+        // SAFETY: SQLite returns a valid message for any handle, including a null one
+        let message = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(self.0)) };
+        io::Error::other(format!(
+            "SQLite backup failed: {}",
+            message.to_string_lossy()
+        ))
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // SAFETY: closing a null handle is a no-op
+        unsafe {
+            ffi::sqlite3_close(self.0);
+        }
+    }
+}
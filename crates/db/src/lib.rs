@@ -1,12 +1,26 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 use sqlx::{
-    Error, Pool, Sqlite, SqlitePool,
+    Connection, Error, Pool, Sqlite, SqlitePool,
     sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePoolOptions},
 };
 use utils::assets::asset_dir;
 
+mod backup;
 pub mod models;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+pub use backup::backup_database;
+
+/// Location of the database file
+pub fn database_path() -> PathBuf {
+    asset_dir().join("db.sqlite")
+}
 
 #[derive(Clone)]
 pub struct DBService {
@@ -15,16 +29,73 @@ pub struct DBService {
 
 impl DBService {
     pub async fn new() -> Result<DBService, Error> {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
+        let database_url = format!("sqlite://{}", database_path().to_string_lossy());
         let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
         let pool = SqlitePool::connect_with(options).await?;
         sqlx::migrate!("./migrations").run(&pool).await?;
         Ok(DBService { pool })
     }
 
+    /// Rebuild the database file to return pages freed by deleted rows to the filesystem
+    pub async fn vacuum(&self) -> Result<(), Error> {
+        sqlx::query!("VACUUM").execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Current size of the database file in bytes, as reported by SQLite
+    pub async fn size_bytes(&self) -> Result<i64, Error> {
+        sqlx::query_scalar!(
+            r#"SELECT page_count * page_size as "size!: i64"
+               FROM pragma_page_count(), pragma_page_size()"#
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Bytes held by free pages in the database file, which a `VACUUM` would return to the
+    /// filesystem
+    pub async fn free_bytes(&self) -> Result<i64, Error> {
+        sqlx::query_scalar!(
+            r#"SELECT freelist_count * page_size as "size!: i64"
+               FROM pragma_freelist_count(), pragma_page_size()"#
+        )
+        .fetch_one(&self.pool)
+        .await
+    }
+
+    /// Newest migration shipped with this build
+    pub fn latest_migration_version() -> i64 {
+        sqlx::migrate!("./migrations")
+            .iter()
+            .map(|migration| migration.version)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Newest migration applied to the database file at `path`, which is opened read-only
+    pub async fn applied_migration_version(path: &Path) -> Result<Option<i64>, Error> {
+        let options = SqliteConnectOptions::new().filename(path).read_only(true);
+        let mut conn = SqliteConnection::connect_with(&options).await?;
+        // Not checked at compile time: the table belongs to the migrator, not to our schema
+        let version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+                .fetch_one(&mut conn)
+                .await?;
+        conn.close().await?;
+        Ok(version)
+    }
+
+    /// Whether `PRAGMA integrity_check` passes for the database file at `path`
+    pub async fn check_integrity(path: &Path) -> Result<bool, Error> {
+        let options = SqliteConnectOptions::new().filename(path).read_only(true);
+        let mut conn = SqliteConnection::connect_with(&options).await?;
+        let result: String = sqlx::query_scalar("PRAGMA integrity_check")
+            .fetch_one(&mut conn)
+            .await?;
+        conn.close().await?;
+        Ok(result == "ok")
+    }
+
     pub async fn new_with_after_connect<F>(after_connect: F) -> Result<DBService, Error>
     where
         F: for<'a> Fn(
@@ -49,10 +120,7 @@ impl DBService {
             + Sync
             + 'static,
     {
-        let database_url = format!(
-            "sqlite://{}",
-            asset_dir().join("db.sqlite").to_string_lossy()
-        );
+        let database_url = format!("sqlite://{}", database_path().to_string_lossy());
         let options = SqliteConnectOptions::from_str(&database_url)?.create_if_missing(true);
 
         let pool = if let Some(hook) = after_connect {
@@ -0,0 +1,209 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// How often `last_used_at` is written for a token in active use
+const LAST_USED_RESOLUTION: Duration = Duration::minutes(1);
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct AccessToken {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub token_hash: String,
+    /// First characters of the token, to tell tokens apart
+    pub token_prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateAccessToken {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct AuthSession {
+    pub id: Uuid,
+    pub token_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl AccessToken {
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccessToken,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      token_hash,
+                      token_prefix,
+                      created_at as "created_at!: DateTime<Utc>",
+                      last_used_at as "last_used_at: DateTime<Utc>"
+               FROM access_tokens
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccessToken,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      token_hash,
+                      token_prefix,
+                      created_at as "created_at!: DateTime<Utc>",
+                      last_used_at as "last_used_at: DateTime<Utc>"
+               FROM access_tokens
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_hash(
+        pool: &SqlitePool,
+        token_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            AccessToken,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      token_hash,
+                      token_prefix,
+                      created_at as "created_at!: DateTime<Utc>",
+                      last_used_at as "last_used_at: DateTime<Utc>"
+               FROM access_tokens
+               WHERE token_hash = $1"#,
+            token_hash
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn count(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(r#"SELECT COUNT(*) as "count!: i64" FROM access_tokens"#)
+            .fetch_one(pool)
+            .await
+    }
+
+    /// Store a new token; the caller generates it and only passes its hash
+    pub async fn create(
+        pool: &SqlitePool,
+        name: &str,
+        token_hash: &str,
+        token_prefix: &str,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AccessToken,
+            r#"INSERT INTO access_tokens (id, name, token_hash, token_prefix)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         token_hash,
+                         token_prefix,
+                         created_at as "created_at!: DateTime<Utc>",
+                         last_used_at as "last_used_at: DateTime<Utc>""#,
+            id,
+            name,
+            token_hash,
+            token_prefix
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Record use of the token, at most once per [`LAST_USED_RESOLUTION`]
+    pub async fn touch(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let stale_before = now - LAST_USED_RESOLUTION;
+        sqlx::query!(
+            r#"UPDATE access_tokens
+               SET last_used_at = $2
+               WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < $3)"#,
+            id,
+            now,
+            stale_before
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Revoke a token, ending every session issued for it
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM access_tokens WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl AuthSession {
+    pub async fn create(
+        pool: &SqlitePool,
+        token_id: Uuid,
+        session_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            AuthSession,
+            r#"INSERT INTO auth_sessions (id, token_id, session_hash, expires_at)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         token_id as "token_id!: Uuid",
+                         expires_at as "expires_at!: DateTime<Utc>""#,
+            id,
+            token_id,
+            session_hash,
+            expires_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Look up an unexpired session by the hash of its cookie value
+    pub async fn find_valid_by_hash(
+        pool: &SqlitePool,
+        session_hash: &str,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query_as!(
+            AuthSession,
+            r#"SELECT id as "id!: Uuid",
+                      token_id as "token_id!: Uuid",
+                      expires_at as "expires_at!: DateTime<Utc>"
+               FROM auth_sessions
+               WHERE session_hash = $1 AND expires_at > $2"#,
+            session_hash,
+            now
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn delete_by_hash(pool: &SqlitePool, session_hash: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM auth_sessions WHERE session_hash = $1",
+            session_hash
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_expired(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!("DELETE FROM auth_sessions WHERE expires_at <= $1", now)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
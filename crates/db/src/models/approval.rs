@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "approval_outcome", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApprovalOutcome {
+    Pending,
+    Approved,
+    Denied,
+    TimedOut,
+}
+
+/// Who resolved an approval request
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "approval_decision_source", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ApprovalDecisionSource {
+    User,
+    Policy,
+    Timeout,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalRecord {
+    pub id: String,
+    pub execution_process_id: Uuid,
+    pub tool_name: String,
+    #[ts(type = "JsonValue")]
+    pub tool_input: sqlx::types::Json<serde_json::Value>,
+    pub tool_call_id: String,
+    pub status: ApprovalOutcome,
+    pub denial_reason: Option<String>,
+    pub decided_by: Option<ApprovalDecisionSource>,
+    /// Approval policy rule that decided the request, if any
+    pub rule_id: Option<Uuid>,
+    pub responder: Option<String>,
+    /// Time from request to decision
+    pub latency_ms: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub timeout_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateApprovalRecord<'a> {
+    pub id: &'a str,
+    pub execution_process_id: Uuid,
+    pub tool_name: &'a str,
+    pub tool_input: &'a serde_json::Value,
+    pub tool_call_id: &'a str,
+    pub created_at: DateTime<Utc>,
+    pub timeout_at: DateTime<Utc>,
+}
+
+/// How a request was resolved
+#[derive(Debug, Clone)]
+pub struct ApprovalResolution {
+    pub status: ApprovalOutcome,
+    pub denial_reason: Option<String>,
+    pub decided_by: ApprovalDecisionSource,
+    pub rule_id: Option<Uuid>,
+    pub responder: Option<String>,
+}
+
+/// Approval counts and total wait time for one outcome/decision source combination
+#[derive(Debug, Clone, FromRow)]
+pub struct ApprovalWaitStats {
+    pub status: String,
+    pub decided_by: Option<String>,
+    pub count: i64,
+    /// Summed request-to-decision latency; zero for pending requests
+    pub wait_seconds: f64,
+}
+
+impl ApprovalRecord {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateApprovalRecord<'_>,
+    ) -> Result<(), sqlx::Error> {
+        let tool_input = sqlx::types::Json(data.tool_input);
+        sqlx::query!(
+            r#"INSERT INTO approvals
+                   (id, execution_process_id, tool_name, tool_input, tool_call_id, created_at, timeout_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+            data.id,
+            data.execution_process_id,
+            data.tool_name,
+            tool_input,
+            data.tool_call_id,
+            data.created_at,
+            data.timeout_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record the outcome of a pending request; latency is measured from `created_at`
+    pub async fn resolve(
+        pool: &SqlitePool,
+        id: &str,
+        resolution: &ApprovalResolution,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        sqlx::query!(
+            r#"UPDATE approvals
+               SET status = $2,
+                   denial_reason = $3,
+                   decided_by = $4,
+                   rule_id = $5,
+                   responder = $6,
+                   responded_at = $7,
+                   latency_ms = CAST(ROUND((julianday($7) - julianday(created_at)) * 86400000) AS INTEGER)
+               WHERE id = $1 AND status = 'pending'"#,
+            id,
+            resolution.status,
+            resolution.denial_reason,
+            resolution.decided_by,
+            resolution.rule_id,
+            resolution.responder,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Push back the deadline of a request that keeps waiting after a timeout
+    pub async fn extend_timeout(
+        pool: &SqlitePool,
+        id: &str,
+        timeout_at: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "UPDATE approvals SET timeout_at = $2 WHERE id = $1 AND status = 'pending'",
+            id,
+            timeout_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Requests left pending by a previous run can no longer be answered
+    pub async fn expire_pending(pool: &SqlitePool) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query!(
+            r#"UPDATE approvals
+               SET status = 'timed_out', decided_by = 'timeout', responded_at = $1
+               WHERE status = 'pending'"#,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRecord,
+            r#"SELECT a.id as "id!",
+                      a.execution_process_id as "execution_process_id!: Uuid",
+                      a.tool_name,
+                      a.tool_input as "tool_input!: sqlx::types::Json<serde_json::Value>",
+                      a.tool_call_id,
+                      a.status as "status!: ApprovalOutcome",
+                      a.denial_reason,
+                      a.decided_by as "decided_by: ApprovalDecisionSource",
+                      a.rule_id as "rule_id: Uuid",
+                      a.responder,
+                      a.latency_ms,
+                      a.created_at as "created_at!: DateTime<Utc>",
+                      a.timeout_at as "timeout_at!: DateTime<Utc>",
+                      a.responded_at as "responded_at: DateTime<Utc>"
+               FROM approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               WHERE w.task_id = $1
+               ORDER BY a.created_at DESC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Aggregate approval counts and wait times, for metrics
+    pub async fn wait_stats(pool: &SqlitePool) -> Result<Vec<ApprovalWaitStats>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalWaitStats,
+            r#"SELECT status as "status!",
+                      decided_by,
+                      COUNT(*) as "count!: i64",
+                      COALESCE(SUM(latency_ms), 0) / 1000.0 as "wait_seconds!: f64"
+               FROM approvals
+               GROUP BY status, decided_by"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_session_id(
+        pool: &SqlitePool,
+        session_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalRecord,
+            r#"SELECT a.id as "id!",
+                      a.execution_process_id as "execution_process_id!: Uuid",
+                      a.tool_name,
+                      a.tool_input as "tool_input!: sqlx::types::Json<serde_json::Value>",
+                      a.tool_call_id,
+                      a.status as "status!: ApprovalOutcome",
+                      a.denial_reason,
+                      a.decided_by as "decided_by: ApprovalDecisionSource",
+                      a.rule_id as "rule_id: Uuid",
+                      a.responder,
+                      a.latency_ms,
+                      a.created_at as "created_at!: DateTime<Utc>",
+                      a.timeout_at as "timeout_at!: DateTime<Utc>",
+                      a.responded_at as "responded_at: DateTime<Utc>"
+               FROM approvals a
+               JOIN execution_processes ep ON ep.id = a.execution_process_id
+               WHERE ep.session_id = $1
+               ORDER BY a.created_at DESC"#,
+            session_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
@@ -0,0 +1,259 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display, Default)]
+#[sqlx(type_name = "approval_policy_action", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ApprovalPolicyAction {
+    #[default]
+    Approve,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Default)]
+#[sqlx(type_name = "approval_pattern_kind", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum ApprovalPatternKind {
+    /// `*` matches any run of characters, `?` a single character; the whole input must match
+    #[default]
+    Glob,
+    /// Regular expression searched anywhere in the input
+    Regex,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ApprovalPolicyRule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Evaluation order within the project, lowest first
+    pub position: i64,
+    pub name: String,
+    /// Glob on the tool name (case-insensitive); matches every tool when unset
+    pub tool_name: Option<String>,
+    /// Pattern on the tool input: each segment of a command line, file paths relative to
+    /// the worktree, and (deny rules only) the JSON input; matches every input when unset
+    pub pattern: Option<String>,
+    pub pattern_kind: ApprovalPatternKind,
+    pub action: ApprovalPolicyAction,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateApprovalPolicyRule {
+    pub name: String,
+    pub tool_name: Option<String>,
+    pub pattern: Option<String>,
+    #[serde(default)]
+    pub pattern_kind: ApprovalPatternKind,
+    pub action: ApprovalPolicyAction,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateApprovalPolicyRule {
+    pub name: Option<String>,
+    pub tool_name: Option<String>,
+    pub pattern: Option<String>,
+    pub pattern_kind: Option<ApprovalPatternKind>,
+    pub action: Option<ApprovalPolicyAction>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct ReorderApprovalPolicyRules {
+    /// Rule ids in their new evaluation order
+    pub rule_ids: Vec<Uuid>,
+}
+
+impl ApprovalPolicyRule {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalPolicyRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      position,
+                      name,
+                      tool_name,
+                      pattern,
+                      pattern_kind as "pattern_kind!: ApprovalPatternKind",
+                      action as "action!: ApprovalPolicyAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_policy_rules
+               WHERE project_id = $1
+               ORDER BY position ASC, created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ApprovalPolicyRule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      position,
+                      name,
+                      tool_name,
+                      pattern,
+                      pattern_kind as "pattern_kind!: ApprovalPatternKind",
+                      action as "action!: ApprovalPolicyAction",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM approval_policy_rules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Create a rule at the end of the project's evaluation order
+    pub async fn create(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        data: &CreateApprovalPolicyRule,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            ApprovalPolicyRule,
+            r#"INSERT INTO approval_policy_rules
+                   (id, project_id, position, name, tool_name, pattern, pattern_kind, action)
+               VALUES (
+                   $1, $2,
+                   (SELECT COALESCE(MAX(position), -1) + 1 FROM approval_policy_rules WHERE project_id = $2),
+                   $3, $4, $5, $6, $7
+               )
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         position,
+                         name,
+                         tool_name,
+                         pattern,
+                         pattern_kind as "pattern_kind!: ApprovalPatternKind",
+                         action as "action!: ApprovalPolicyAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            project_id,
+            data.name,
+            data.tool_name,
+            data.pattern,
+            data.pattern_kind,
+            data.action
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Update a rule. Empty `tool_name`/`pattern` strings clear the filter.
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateApprovalPolicyRule,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let tool_name = match &data.tool_name {
+            Some(s) if s.trim().is_empty() => None,
+            Some(s) => Some(s.clone()),
+            None => existing.tool_name,
+        };
+        let pattern = match &data.pattern {
+            Some(s) if s.is_empty() => None,
+            Some(s) => Some(s.clone()),
+            None => existing.pattern,
+        };
+        let pattern_kind = data.pattern_kind.unwrap_or(existing.pattern_kind);
+        let action = data.action.unwrap_or(existing.action);
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+
+        sqlx::query_as!(
+            ApprovalPolicyRule,
+            r#"UPDATE approval_policy_rules
+               SET name = $2, tool_name = $3, pattern = $4, pattern_kind = $5, action = $6,
+                   enabled = $7, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         position,
+                         name,
+                         tool_name,
+                         pattern,
+                         pattern_kind as "pattern_kind!: ApprovalPatternKind",
+                         action as "action!: ApprovalPolicyAction",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            tool_name,
+            pattern,
+            pattern_kind,
+            action,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Set the evaluation order of a project's rules. Rules not listed keep their relative
+    /// order after the listed ones.
+    pub async fn reorder(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        rule_ids: &[Uuid],
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let existing = Self::find_by_project_id(pool, project_id).await?;
+        let ordered = rule_ids
+            .iter()
+            .filter(|id| existing.iter().any(|rule| rule.id == **id))
+            .copied()
+            .chain(
+                existing
+                    .iter()
+                    .map(|rule| rule.id)
+                    .filter(|id| !rule_ids.contains(id)),
+            );
+
+        let mut tx = pool.begin().await?;
+        for (position, id) in ordered.enumerate() {
+            let position = position as i64;
+            sqlx::query!(
+                r#"UPDATE approval_policy_rules
+                   SET position = $2, updated_at = datetime('now', 'subsec')
+                   WHERE id = $1"#,
+                id,
+                position
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Self::find_by_project_id(pool, project_id).await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM approval_policy_rules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
@@ -5,7 +5,7 @@ use executors::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -108,6 +108,17 @@ pub enum ExecutorActionField {
     Other(Value),
 }
 
+/// Execution counts and total run time for one executor/run reason/status combination
+#[derive(Debug, Clone, FromRow)]
+pub struct ExecutionProcessStats {
+    pub executor: Option<String>,
+    pub run_reason: String,
+    pub status: String,
+    pub count: i64,
+    /// Summed `completed_at - started_at`; zero for running processes
+    pub duration_seconds: f64,
+}
+
 #[derive(Debug, Clone)]
 pub struct MissingBeforeContext {
     pub id: Uuid,
@@ -270,6 +281,26 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Aggregate counts and durations of all execution processes, for metrics
+    pub async fn stats(pool: &SqlitePool) -> Result<Vec<ExecutionProcessStats>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcessStats,
+            r#"SELECT s.executor,
+                      ep.run_reason as "run_reason!",
+                      ep.status as "status!",
+                      COUNT(*) as "count!: i64",
+                      COALESCE(
+                          SUM((julianday(ep.completed_at) - julianday(ep.started_at)) * 86400.0),
+                          0.0
+                      ) as "duration_seconds!: f64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               GROUP BY s.executor, ep.run_reason, ep.status"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find running dev servers for a specific project
     pub async fn find_running_dev_servers_by_project(
         pool: &SqlitePool,
@@ -476,6 +507,34 @@ impl ExecutionProcess {
             .ok_or(sqlx::Error::RowNotFound)
     }
 
+    /// Insert a finished process carried over from another installation as is, keeping its
+    /// status and timestamps. No repo states are recorded for it.
+    pub async fn create_imported(
+        executor: impl Executor<'_, Database = Sqlite>,
+        process: &Self,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"INSERT INTO execution_processes (
+                    id, session_id, run_reason, executor_action, status, exit_code, dropped,
+                    started_at, completed_at, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            process.id,
+            process.session_id,
+            process.run_reason,
+            process.executor_action,
+            process.status,
+            process.exit_code,
+            process.dropped,
+            process.started_at,
+            process.completed_at,
+            process.created_at,
+            process.updated_at
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
+
     pub async fn was_stopped(pool: &SqlitePool, id: Uuid) -> bool {
         if let Ok(exp_process) = Self::find_by_id(pool, id).await
             && exp_process.is_some_and(|ep| {
@@ -1,18 +1,42 @@
+use std::io;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Acquire, FromRow, Sqlite, SqlitePool, Type};
 use ts_rs::TS;
 use utils::log_msg::LogMsg;
 use uuid::Uuid;
 
+/// zstd level used when compacting log chunks. Level 3 is zstd's default and
+/// gives most of the ratio on JSONL at a fraction of the CPU of higher levels.
+const ZSTD_LEVEL: i32 = 3;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[sqlx(type_name = "log_compression", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum LogCompression {
+    None,
+    Zstd,
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct ExecutionProcessLogs {
     pub execution_id: Uuid,
-    pub logs: String, // JSONL format
+    pub logs: Vec<u8>, // JSONL format, optionally compressed
+    pub compression: LogCompression,
+    /// True once raw stdout/stderr has been replaced by normalized entries
+    pub raw_pruned: bool,
     pub byte_size: i64,
     pub inserted_at: DateTime<Utc>,
 }
 
+/// Finished execution process whose raw logs are eligible for pruning
+#[derive(Debug, Clone)]
+pub struct PrunableExecution {
+    pub execution_id: Uuid,
+    pub completed_at: DateTime<Utc>,
+}
+
 impl ExecutionProcessLogs {
     /// Find logs by execution process ID
     pub async fn find_by_execution_id(
@@ -21,12 +45,14 @@ impl ExecutionProcessLogs {
     ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             ExecutionProcessLogs,
-            r#"SELECT 
+            r#"SELECT
                 execution_id as "execution_id!: Uuid",
-                logs,
+                CAST(logs AS BLOB) as "logs!: Vec<u8>",
+                compression as "compression!: LogCompression",
+                raw_pruned as "raw_pruned!: bool",
                 byte_size,
                 inserted_at as "inserted_at!: DateTime<Utc>"
-               FROM execution_process_logs 
+               FROM execution_process_logs
                WHERE execution_id = $1
                ORDER BY inserted_at ASC"#,
             execution_id
@@ -35,13 +61,25 @@ impl ExecutionProcessLogs {
         .await
     }
 
-    /// Parse JSONL logs back into Vec<LogMsg>
+    /// Decompressed JSONL text of this record
+    pub fn text(&self) -> io::Result<String> {
+        let bytes = match self.compression {
+            LogCompression::None => self.logs.clone(),
+            LogCompression::Zstd => zstd::decode_all(self.logs.as_slice())?,
+        };
+        String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Parse JSONL logs back into Vec<LogMsg>, decompressing records as needed
     pub fn parse_logs(records: &[Self]) -> Result<Vec<LogMsg>, serde_json::Error> {
         let mut messages = Vec::new();
-        for line in records.iter().flat_map(|record| record.logs.lines()) {
-            if !line.trim().is_empty() {
-                let msg: LogMsg = serde_json::from_str(line)?;
-                messages.push(msg);
+        for record in records {
+            let text = record.text().map_err(serde_json::Error::io)?;
+            for line in text.lines() {
+                if !line.trim().is_empty() {
+                    let msg: LogMsg = serde_json::from_str(line)?;
+                    messages.push(msg);
+                }
             }
         }
         Ok(messages)
@@ -66,4 +104,171 @@ impl ExecutionProcessLogs {
 
         Ok(())
     }
+
+    /// Bytes currently used on disk by the logs of an execution process
+    pub async fn stored_size(pool: &SqlitePool, execution_id: Uuid) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT COALESCE(SUM(length(logs)), 0) as "size!: i64"
+               FROM execution_process_logs
+               WHERE execution_id = $1"#,
+            execution_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Replace all log rows of an execution process with a single zstd-compressed chunk.
+    /// Returns the number of bytes reclaimed.
+    pub async fn compact(pool: &SqlitePool, execution_id: Uuid) -> Result<i64, sqlx::Error> {
+        let records = Self::find_by_execution_id(pool, execution_id).await?;
+        if records.is_empty()
+            || (records.len() == 1 && records[0].compression == LogCompression::Zstd)
+        {
+            return Ok(0);
+        }
+
+        let mut jsonl = String::new();
+        for record in &records {
+            jsonl.push_str(
+                &record
+                    .text()
+                    .map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+            );
+        }
+        let raw_pruned = records.iter().all(|r| r.raw_pruned);
+        let before: i64 = records.iter().map(|r| r.logs.len() as i64).sum();
+        let after = Self::replace(pool, execution_id, &jsonl, raw_pruned).await?;
+
+        Ok(before - after)
+    }
+
+    /// Atomically replace all log rows of an execution process with `jsonl`, stored compressed.
+    /// Returns the number of bytes stored.
+    pub async fn replace<'a, A>(
+        conn: A,
+        execution_id: Uuid,
+        jsonl: &str,
+        raw_pruned: bool,
+    ) -> Result<i64, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let compressed = zstd::encode_all(jsonl.as_bytes(), ZSTD_LEVEL)
+            .map_err(|e| sqlx::Error::Encode(Box::new(e)))?;
+        let byte_size = jsonl.len() as i64;
+        let compression = LogCompression::Zstd;
+
+        let mut tx = conn.begin().await?;
+        sqlx::query!(
+            "DELETE FROM execution_process_logs WHERE execution_id = $1",
+            execution_id
+        )
+        .execute(&mut *tx)
+        .await?;
+        if !jsonl.is_empty() {
+            sqlx::query!(
+                r#"INSERT INTO execution_process_logs
+                       (execution_id, logs, compression, raw_pruned, byte_size, inserted_at)
+                   VALUES ($1, $2, $3, $4, $5, datetime('now', 'subsec'))"#,
+                execution_id,
+                compressed,
+                compression,
+                raw_pruned,
+                byte_size
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(if jsonl.is_empty() {
+            0
+        } else {
+            compressed.len() as i64
+        })
+    }
+
+    /// Finished execution processes that still have uncompressed log rows
+    pub async fn find_uncompacted_execution_ids(
+        pool: &SqlitePool,
+    ) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT epl.execution_id as "execution_id!: Uuid"
+               FROM execution_process_logs epl
+               JOIN execution_processes ep ON ep.id = epl.execution_id
+               WHERE epl.compression = 'none'
+                 AND ep.status != 'running'"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Finished execution processes completed before `cutoff` whose raw logs are still kept,
+    /// restricted to tasks that are Done
+    pub async fn find_prunable(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<PrunableExecution>, sqlx::Error> {
+        sqlx::query_as!(
+            PrunableExecution,
+            r#"SELECT
+                ep.id           as "execution_id!: Uuid",
+                ep.completed_at as "completed_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON s.id = ep.session_id
+               JOIN workspaces w ON w.id = s.workspace_id
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.status = 'done'
+                 AND ep.run_reason = 'codingagent'
+                 AND ep.status != 'running'
+                 AND ep.completed_at IS NOT NULL
+                 AND ep.completed_at < $1
+                 AND EXISTS (
+                     SELECT 1 FROM execution_process_logs epl
+                     WHERE epl.execution_id = ep.id AND epl.raw_pruned = FALSE
+                 )
+               ORDER BY ep.completed_at ASC"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_record(logs: Vec<u8>, compression: LogCompression) -> ExecutionProcessLogs {
+        ExecutionProcessLogs {
+            execution_id: Uuid::new_v4(),
+            byte_size: logs.len() as i64,
+            logs,
+            compression,
+            raw_pruned: false,
+            inserted_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_parse_logs_mixes_plain_and_compressed_records() {
+        let plain = "{\"Stdout\":\"hello\"}\n".as_bytes().to_vec();
+        let compressed =
+            zstd::encode_all("{\"Stderr\":\"oops\"}\n".as_bytes(), ZSTD_LEVEL).unwrap();
+
+        let messages = ExecutionProcessLogs::parse_logs(&[
+            make_record(plain, LogCompression::None),
+            make_record(compressed, LogCompression::Zstd),
+        ])
+        .unwrap();
+
+        assert!(matches!(&messages[0], LogMsg::Stdout(s) if s == "hello"));
+        assert!(matches!(&messages[1], LogMsg::Stderr(s) if s == "oops"));
+    }
+
+    #[test]
+    fn test_parse_logs_rejects_corrupt_compressed_record() {
+        let record = make_record(b"not zstd".to_vec(), LogCompression::Zstd);
+        assert!(ExecutionProcessLogs::parse_logs(&[record]).is_err());
+    }
 }
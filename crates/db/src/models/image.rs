@@ -149,6 +149,32 @@ impl Image {
         .await
     }
 
+    /// Images attached to comments on a task
+    pub async fn find_by_task_comments(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Image,
+            r#"SELECT DISTINCT i.id as "id!: Uuid",
+                      i.file_path as "file_path!",
+                      i.original_name as "original_name!",
+                      i.mime_type,
+                      i.size_bytes as "size_bytes!",
+                      i.hash as "hash!",
+                      i.created_at as "created_at!: DateTime<Utc>",
+                      i.updated_at as "updated_at!: DateTime<Utc>"
+               FROM images i
+               JOIN task_activity_images tai ON i.id = tai.image_id
+               JOIN task_activity ta ON ta.id = tai.activity_id
+               WHERE ta.task_id = $1
+               ORDER BY i.created_at"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
         sqlx::query!(r#"DELETE FROM images WHERE id = $1"#, id)
             .execute(pool)
@@ -169,7 +195,10 @@ impl Image {
                       i.updated_at as "updated_at!: DateTime<Utc>"
                FROM images i
                LEFT JOIN task_images ti ON i.id = ti.image_id
-               WHERE ti.task_id IS NULL"#
+               WHERE ti.task_id IS NULL
+                 AND NOT EXISTS (
+                     SELECT 1 FROM task_activity_images tai WHERE tai.image_id = i.id
+                 )"#
         )
         .fetch_all(pool)
         .await
@@ -208,7 +237,8 @@ impl TaskImage {
         Ok(())
     }
 
-    /// Check if an image is associated with a specific task.
+    /// Check if an image is associated with a specific task, directly or through one of
+    /// its comments.
     pub async fn is_associated(
         pool: &SqlitePool,
         task_id: Uuid,
@@ -219,6 +249,11 @@ impl TaskImage {
                 SELECT 1
                 FROM task_images
                 WHERE task_id = $1 AND image_id = $2
+                UNION ALL
+                SELECT 1
+                FROM task_activity_images tai
+                JOIN task_activity ta ON ta.id = tai.activity_id
+                WHERE ta.task_id = $1 AND tai.image_id = $2
                ) AS "exists!: bool"
             "#,
             task_id,
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Acquire, Executor, FromRow, Sqlite, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+/// A project-scoped label that tasks can carry any number of
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct Label {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    /// Unique within the project, ignoring case
+    pub name: String,
+    /// CSS color, e.g. `#d73a4a`
+    pub color: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateLabel {
+    pub project_id: Uuid,
+    pub name: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateLabel {
+    pub name: Option<String>,
+    /// An empty string removes the color
+    pub color: Option<String>,
+}
+
+impl Label {
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      color,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE project_id = $1
+               ORDER BY name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      color,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_task_id<'e, E>(
+        executor: E,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        sqlx::query_as!(
+            Label,
+            r#"SELECT l.id as "id!: Uuid",
+                      l.project_id as "project_id!: Uuid",
+                      l.name,
+                      l.color,
+                      l.created_at as "created_at!: DateTime<Utc>",
+                      l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM labels l
+               JOIN task_labels tl ON tl.label_id = l.id
+               WHERE tl.task_id = $1
+               ORDER BY l.name ASC"#,
+            task_id
+        )
+        .fetch_all(executor)
+        .await
+    }
+
+    /// Labels of every task in a project, by task ID
+    pub async fn find_by_task_for_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<HashMap<Uuid, Vec<Self>>, sqlx::Error> {
+        let rows = sqlx::query!(
+            r#"SELECT tl.task_id as "task_id!: Uuid",
+                      l.id as "id!: Uuid",
+                      l.project_id as "project_id!: Uuid",
+                      l.name,
+                      l.color,
+                      l.created_at as "created_at!: DateTime<Utc>",
+                      l.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_labels tl
+               JOIN labels l ON l.id = tl.label_id
+               WHERE l.project_id = $1
+               ORDER BY l.name ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut by_task: HashMap<Uuid, Vec<Self>> = HashMap::new();
+        for row in rows {
+            by_task.entry(row.task_id).or_default().push(Label {
+                id: row.id,
+                project_id: row.project_id,
+                name: row.name,
+                color: row.color,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            });
+        }
+        Ok(by_task)
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreateLabel) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let name = data.name.trim();
+        sqlx::query_as!(
+            Label,
+            r#"INSERT INTO labels (id, project_id, name, color)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         color,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            name,
+            data.color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateLabel,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data
+            .name
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or(&existing.name);
+        let color = match &data.color {
+            Some(c) if c.trim().is_empty() => None,
+            Some(c) => Some(c.clone()),
+            None => existing.color,
+        };
+
+        sqlx::query_as!(
+            Label,
+            r#"UPDATE labels
+               SET name = $2, color = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         color,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            color
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM labels WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Replace a task's labels with the named ones, creating labels the project doesn't
+    /// have yet. Names are matched ignoring case.
+    pub async fn set_for_task<'a, A>(
+        conn: A,
+        project_id: Uuid,
+        task_id: Uuid,
+        names: &[String],
+    ) -> Result<Vec<Self>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut tx = conn.begin().await?;
+
+        sqlx::query!("DELETE FROM task_labels WHERE task_id = $1", task_id)
+            .execute(&mut *tx)
+            .await?;
+        for name in names.iter().map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            let new_id = Uuid::new_v4();
+            sqlx::query!(
+                r#"INSERT INTO labels (id, project_id, name)
+                   VALUES ($1, $2, $3)
+                   ON CONFLICT (project_id, name) DO NOTHING"#,
+                new_id,
+                project_id,
+                name
+            )
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query!(
+                r#"INSERT OR IGNORE INTO task_labels (task_id, label_id)
+                   SELECT $1, id FROM labels WHERE project_id = $2 AND name = $3"#,
+                task_id,
+                project_id,
+                name
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        // Touch the task so board streams pick up the new labels
+        sqlx::query!(
+            "UPDATE tasks SET updated_at = datetime('now', 'subsec') WHERE id = $1",
+            task_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let labels = Self::find_by_task_id(&mut *tx, task_id).await?;
+        tx.commit().await?;
+        Ok(labels)
+    }
+}
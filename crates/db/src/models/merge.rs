@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -84,7 +84,7 @@ impl Merge {
 
     /// Create a direct merge record
     pub async fn create_direct(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         workspace_id: Uuid,
         repo_id: Uuid,
         target_branch_name: &str,
@@ -119,13 +119,13 @@ impl Merge {
             now,
             target_branch_name
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
         .map(Into::into)
     }
     /// Create a new PR record (when PR is opened)
     pub async fn create_pr(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         workspace_id: Uuid,
         repo_id: Uuid,
         target_branch_name: &str,
@@ -162,7 +162,7 @@ impl Merge {
             now,
             target_branch_name
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
         .map(Into::into)
     }
@@ -196,7 +196,7 @@ impl Merge {
 
     /// Update PR status for a workspace
     pub async fn update_status(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         merge_id: Uuid,
         pr_status: MergeStatus,
         merge_commit_sha: Option<String>,
@@ -218,7 +218,7 @@ impl Merge {
             merged_at,
             merge_id
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
 
         Ok(())
@@ -1,15 +1,25 @@
+pub mod access_token;
+pub mod approval;
+pub mod approval_policy;
 pub mod coding_agent_turn;
 pub mod execution_process;
 pub mod execution_process_logs;
 pub mod execution_process_repo_state;
 pub mod image;
+pub mod label;
 pub mod merge;
+pub mod plan_task_link;
 pub mod project;
 pub mod project_repo;
 pub mod repo;
 pub mod scratch;
+pub mod search;
 pub mod session;
 pub mod tag;
 pub mod task;
+pub mod task_activity;
+pub mod task_github_issue;
+pub mod task_schedule;
+pub mod webhook;
 pub mod workspace;
 pub mod workspace_repo;
@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, SqlitePool};
+use uuid::Uuid;
+
+/// Links a task to the plan phase it was imported from. Plans without phases link their
+/// single task with no `phase`/`phase_file`.
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct PlanTaskLink {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    /// Plan directory name under `plans/`
+    pub plan_id: String,
+    /// Path of the plan's `plan.md`
+    pub plan_path: String,
+    pub phase: Option<i64>,
+    pub phase_file: Option<String>,
+    /// Plan status last written to either the plan or the task
+    pub synced_status: String,
+    /// Task title last written from the plan
+    pub synced_title: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreatePlanTaskLink {
+    pub task_id: Uuid,
+    pub project_id: Uuid,
+    pub plan_id: String,
+    pub plan_path: String,
+    pub phase: Option<i64>,
+    pub phase_file: Option<String>,
+    pub synced_status: String,
+    pub synced_title: String,
+}
+
+impl PlanTaskLink {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanTaskLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      plan_id,
+                      plan_path,
+                      phase,
+                      phase_file,
+                      synced_status,
+                      synced_title,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM plan_task_links
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_plan(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        plan_id: &str,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            PlanTaskLink,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      plan_id,
+                      plan_path,
+                      phase,
+                      phase_file,
+                      synced_status,
+                      synced_title,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM plan_task_links
+               WHERE project_id = $1 AND plan_id = $2
+               ORDER BY phase ASC"#,
+            project_id,
+            plan_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Projects with at least one imported plan
+    pub async fn find_project_ids(pool: &SqlitePool) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT DISTINCT project_id as "project_id!: Uuid" FROM plan_task_links"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(pool: &SqlitePool, data: &CreatePlanTaskLink) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            PlanTaskLink,
+            r#"INSERT INTO plan_task_links
+                   (id, task_id, project_id, plan_id, plan_path, phase, phase_file, synced_status,
+                    synced_title)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         plan_id,
+                         plan_path,
+                         phase,
+                         phase_file,
+                         synced_status,
+                         synced_title,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            data.project_id,
+            data.plan_id,
+            data.plan_path,
+            data.phase,
+            data.phase_file,
+            data.synced_status,
+            data.synced_title
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_synced_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        synced_status: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE plan_task_links
+               SET synced_status = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            synced_status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Record a plan-side change written to the task
+    pub async fn set_synced(
+        pool: &SqlitePool,
+        id: Uuid,
+        synced_status: &str,
+        synced_title: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE plan_task_links
+               SET synced_status = $2, synced_title = $3, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            synced_status,
+            synced_title
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
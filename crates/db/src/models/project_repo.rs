@@ -43,7 +43,7 @@ pub struct ProjectRepoWithName {
     pub parallel_setup_script: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct CreateProjectRepo {
     pub display_name: String,
     pub git_repo_path: String,
@@ -148,7 +148,7 @@ impl ProjectRepo {
     }
 
     pub async fn find_by_project_and_repo(
-        pool: &SqlitePool,
+        executor: impl sqlx::Executor<'_, Database = sqlx::Sqlite>,
         project_id: Uuid,
         repo_id: Uuid,
     ) -> Result<Option<Self>, sqlx::Error> {
@@ -166,7 +166,7 @@ impl ProjectRepo {
             project_id,
             repo_id
         )
-        .fetch_optional(pool)
+        .fetch_optional(executor)
         .await
     }
 
@@ -244,13 +244,17 @@ impl ProjectRepo {
         .await
     }
 
-    pub async fn update(
-        pool: &SqlitePool,
+    pub async fn update<'a, A>(
+        conn: A,
         project_id: Uuid,
         repo_id: Uuid,
         payload: &UpdateProjectRepo,
-    ) -> Result<Self, ProjectRepoError> {
-        let existing = Self::find_by_project_and_repo(pool, project_id, repo_id).await?;
+    ) -> Result<Self, ProjectRepoError>
+    where
+        A: sqlx::Acquire<'a, Database = sqlx::Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
+        let existing = Self::find_by_project_and_repo(&mut *conn, project_id, repo_id).await?;
         let existing = existing.ok_or(ProjectRepoError::NotFound)?;
 
         let setup_script = payload.setup_script.clone();
@@ -282,7 +286,7 @@ impl ProjectRepo {
             project_id,
             repo_id
         )
-        .fetch_one(pool)
+        .fetch_one(&mut *conn)
         .await
         .map_err(ProjectRepoError::from)
     }
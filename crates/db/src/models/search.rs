@@ -0,0 +1,210 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, Display)]
+#[sqlx(type_name = "search_hit_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SearchHitKind {
+    Task,
+    Prompt,
+    Summary,
+    AssistantMessage,
+    Command,
+    /// Path of a file the coding agent edited
+    FileEdit,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub project_id: Uuid,
+    pub task_id: Uuid,
+    pub task_title: String,
+    pub task_status: TaskStatus,
+    pub workspace_id: Option<Uuid>,
+    pub session_id: Option<Uuid>,
+    pub execution_process_id: Option<Uuid>,
+    /// Matching excerpt with hits wrapped in `**`
+    pub snippet: String,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct SearchIndex;
+
+impl SearchIndex {
+    /// Build an FTS5 match expression from free text. Every whitespace separated term is
+    /// quoted (so punctuation can't break the query syntax) and prefix-matched.
+    pub fn match_expression(query: &str) -> Option<String> {
+        let terms: Vec<String> = query
+            .split_whitespace()
+            .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+            .collect();
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        }
+    }
+
+    /// Full-text search across tasks, agent prompts/summaries and conversation entries,
+    /// best matches first. Archived tasks and projects are left out.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        project_id: Option<Uuid>,
+        since: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>, sqlx::Error> {
+        let Some(match_expression) = Self::match_expression(query) else {
+            return Ok(Vec::new());
+        };
+
+        sqlx::query_as!(
+            SearchHit,
+            r#"SELECT
+                search_index.kind                 AS "kind!: SearchHitKind",
+                t.project_id                      AS "project_id!: Uuid",
+                t.id                              AS "task_id!: Uuid",
+                t.title                           AS "task_title!",
+                t.status                          AS "task_status!: TaskStatus",
+                s.workspace_id                    AS "workspace_id: Uuid",
+                search_index.session_id           AS "session_id: Uuid",
+                search_index.execution_process_id AS "execution_process_id: Uuid",
+                snippet(search_index, 0, '**', '**', '…', 16) AS "snippet!: String",
+                search_index.created_at           AS "created_at!: DateTime<Utc>"
+               FROM search_index
+               JOIN tasks t ON t.id = search_index.task_id
+               JOIN projects p ON p.id = t.project_id
+               LEFT JOIN sessions s ON s.id = search_index.session_id
+               WHERE search_index MATCH $1
+                 AND t.archived_at IS NULL
+                 AND p.archived_at IS NULL
+                 AND ($2 IS NULL OR t.project_id = $2)
+                 AND ($3 IS NULL OR datetime(search_index.created_at) >= datetime($3))
+               ORDER BY search_index.rank
+               LIMIT $4"#,
+            match_expression,
+            project_id,
+            since,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replace the indexed assistant messages, commands and edited file paths of a coding
+    /// agent execution
+    pub async fn index_conversation(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+        assistant_messages: &[String],
+        commands: &[String],
+        file_edits: &[String],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            r#"DELETE FROM search_index
+               WHERE execution_process_id = $1
+                 AND kind IN ('assistant_message', 'command', 'file_edit')"#,
+            execution_process_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        let entries = assistant_messages
+            .iter()
+            .map(|content| (SearchHitKind::AssistantMessage, content))
+            .chain(
+                commands
+                    .iter()
+                    .map(|content| (SearchHitKind::Command, content)),
+            )
+            .chain(
+                file_edits
+                    .iter()
+                    .map(|content| (SearchHitKind::FileEdit, content)),
+            );
+        for (kind, content) in entries {
+            sqlx::query!(
+                r#"INSERT INTO search_index
+                       (content, kind, project_id, task_id, session_id, execution_process_id, created_at)
+                   SELECT $1, $2, t.project_id, t.id, s.id, ep.id, ep.created_at
+                   FROM execution_processes ep
+                   JOIN sessions s ON s.id = ep.session_id
+                   JOIN workspaces w ON w.id = s.workspace_id
+                   JOIN tasks t ON t.id = w.task_id
+                   WHERE ep.id = $3"#,
+                content,
+                kind,
+                execution_process_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await
+    }
+
+    /// Executions still waiting for the conversation backfill, oldest first
+    pub async fn pending_backfill(pool: &SqlitePool, limit: i64) -> Result<Vec<Uuid>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT b.execution_process_id AS "execution_process_id!: Uuid"
+               FROM search_index_backfill b
+               JOIN execution_processes ep ON ep.id = b.execution_process_id
+               ORDER BY ep.created_at
+               LIMIT $1"#,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Take an execution off the backfill queue
+    pub async fn finish_backfill(
+        pool: &SqlitePool,
+        execution_process_id: Uuid,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            "DELETE FROM search_index_backfill WHERE execution_process_id = $1",
+            execution_process_id
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_expression_quotes_and_prefixes_terms() {
+        assert_eq!(
+            SearchIndex::match_expression("auth middleware"),
+            Some("\"auth\"* \"middleware\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_expression_escapes_quotes() {
+        assert_eq!(
+            SearchIndex::match_expression("say \"hi\""),
+            Some("\"say\"* \"\"\"hi\"\"\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_match_expression_empty_query() {
+        assert_eq!(SearchIndex::match_expression("   "), None);
+    }
+}
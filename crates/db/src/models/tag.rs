@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -26,14 +26,16 @@ pub struct UpdateTag {
 }
 
 impl Tag {
-    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+    pub async fn find_all(
+        executor: impl Executor<'_, Database = Sqlite>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Tag,
             r#"SELECT id as "id!: Uuid", tag_name, content as "content!", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tags
                ORDER BY tag_name ASC"#
         )
-        .fetch_all(pool)
+        .fetch_all(executor)
         .await
     }
 
@@ -49,7 +51,10 @@ impl Tag {
         .await
     }
 
-    pub async fn create(pool: &SqlitePool, data: &CreateTag) -> Result<Self, sqlx::Error> {
+    pub async fn create(
+        executor: impl Executor<'_, Database = Sqlite>,
+        data: &CreateTag,
+    ) -> Result<Self, sqlx::Error> {
         let id = Uuid::new_v4();
         sqlx::query_as!(
             Tag,
@@ -60,7 +65,7 @@ impl Tag {
             data.tag_name,
             data.content
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
@@ -1,3 +1,5 @@
+use std::cmp::Ordering;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
@@ -5,7 +7,7 @@ use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
 
-use super::{project::Project, workspace::Workspace};
+use super::{label::Label, project::Project, workspace::Workspace};
 
 #[derive(
     Debug, Clone, Type, Serialize, Deserialize, PartialEq, TS, EnumString, Display, Default,
@@ -22,6 +24,31 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+#[derive(
+    Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display,
+)]
+#[sqlx(type_name = "task_priority", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TaskPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+impl TaskPriority {
+    /// Higher is more urgent
+    pub fn rank(self) -> u8 {
+        match self {
+            TaskPriority::Low => 0,
+            TaskPriority::Medium => 1,
+            TaskPriority::High => 2,
+            TaskPriority::Urgent => 3,
+        }
+    }
+}
+
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Task {
     pub id: Uuid,
@@ -29,8 +56,14 @@ pub struct Task {
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<DateTime<Utc>>,
+    /// Estimated effort in minutes
+    pub estimate_minutes: Option<i64>,
     pub parent_workspace_id: Option<Uuid>, // Foreign key to parent Workspace
     pub shared_task_id: Option<Uuid>,
+    /// Set while the task is archived; archived tasks are hidden from the board
+    pub archived_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -45,6 +78,7 @@ pub struct TaskWithAttemptStatus {
     pub executor: String,
     /// Suggested init prompt for task execution
     pub suggested_prompt: String,
+    pub labels: Vec<Label>,
 }
 
 impl std::ops::Deref for TaskWithAttemptStatus {
@@ -73,6 +107,11 @@ pub struct CreateTask {
     pub title: String,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i64>,
+    /// Label names; labels the project doesn't have yet are created
+    pub labels: Option<Vec<String>>,
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
     pub shared_task_id: Option<Uuid>,
@@ -89,6 +128,10 @@ impl CreateTask {
             title,
             description,
             status: Some(TaskStatus::Todo),
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            labels: None,
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: None,
@@ -107,6 +150,10 @@ impl CreateTask {
             title,
             description,
             status: Some(status),
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            labels: None,
             parent_workspace_id: None,
             image_ids: None,
             shared_task_id: Some(shared_task_id),
@@ -119,10 +166,131 @@ pub struct UpdateTask {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i64>,
+    /// Remove the priority, due date and estimate whose field is `true`
+    #[serde(default)]
+    pub clear: TaskFieldsToClear,
+    /// Replaces the task's labels when present
+    pub labels: Option<Vec<String>>,
     pub parent_workspace_id: Option<Uuid>,
     pub image_ids: Option<Vec<Uuid>>,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default)]
+pub struct TaskFieldsToClear {
+    pub priority: bool,
+    pub due_date: bool,
+    pub estimate_minutes: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskSortField {
+    CreatedAt,
+    UpdatedAt,
+    Priority,
+    DueDate,
+    Title,
+}
+
+impl TaskSortField {
+    /// Most urgent, soonest due, alphabetical and newest first
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            TaskSortField::DueDate | TaskSortField::Title => SortOrder::Asc,
+            TaskSortField::CreatedAt | TaskSortField::UpdatedAt | TaskSortField::Priority => {
+                SortOrder::Desc
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, EnumString, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Filters and ordering applied to a project's task list
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TaskFilter {
+    pub status: Option<TaskStatus>,
+    pub priority: Option<TaskPriority>,
+    /// Label name, matched case-insensitively
+    pub label: Option<String>,
+    /// Only tasks due strictly before this time
+    pub due_before: Option<DateTime<Utc>>,
+    pub sort: Option<TaskSortField>,
+    /// Defaults to the natural order of `sort`
+    pub order: Option<SortOrder>,
+}
+
+impl TaskFilter {
+    /// Keep the matching tasks and sort them. Tasks without a value for the sort field come last
+    /// in either order; without `sort` the incoming order is kept.
+    pub fn apply(&self, tasks: Vec<TaskWithAttemptStatus>) -> Vec<TaskWithAttemptStatus> {
+        let mut tasks: Vec<TaskWithAttemptStatus> = tasks
+            .into_iter()
+            .filter(|task| self.status.as_ref().is_none_or(|s| &task.status == s))
+            .filter(|task| self.priority.is_none_or(|p| task.priority == Some(p)))
+            .filter(|task| {
+                self.label.as_deref().is_none_or(|label| {
+                    task.labels
+                        .iter()
+                        .any(|l| l.name.eq_ignore_ascii_case(label.trim()))
+                })
+            })
+            .filter(|task| {
+                self.due_before
+                    .is_none_or(|before| task.due_date.is_some_and(|due| due < before))
+            })
+            .collect();
+
+        let Some(sort) = self.sort else {
+            return tasks;
+        };
+        let order = self.order.unwrap_or(sort.default_order());
+        tasks.sort_by(|a, b| match sort {
+            TaskSortField::CreatedAt => {
+                compare_optional(Some(a.created_at), Some(b.created_at), order)
+            }
+            TaskSortField::UpdatedAt => {
+                compare_optional(Some(a.updated_at), Some(b.updated_at), order)
+            }
+            TaskSortField::Title => compare_optional(
+                Some(a.title.to_lowercase()),
+                Some(b.title.to_lowercase()),
+                order,
+            ),
+            TaskSortField::Priority => compare_optional(
+                a.priority.map(TaskPriority::rank),
+                b.priority.map(TaskPriority::rank),
+                order,
+            ),
+            TaskSortField::DueDate => compare_optional(a.due_date, b.due_date, order),
+        });
+        tasks
+    }
+}
+
+fn compare_optional<T: Ord>(a: Option<T>, b: Option<T>, order: SortOrder) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => match order {
+            SortOrder::Asc => a.cmp(&b),
+            SortOrder::Desc => b.cmp(&a),
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
 impl Task {
     pub fn to_prompt(&self) -> String {
         if let Some(description) = self.description.as_ref().filter(|d| !d.trim().is_empty()) {
@@ -167,8 +335,12 @@ impl Task {
   t.title,
   t.description,
   t.status                        AS "status!: TaskStatus",
+  t.priority                      AS "priority: TaskPriority",
+  t.due_date                      AS "due_date: DateTime<Utc>",
+  t.estimate_minutes,
   t.parent_workspace_id           AS "parent_workspace_id: Uuid",
   t.shared_task_id                AS "shared_task_id: Uuid",
+  t.archived_at                   AS "archived_at: DateTime<Utc>",
   t.created_at                    AS "created_at!: DateTime<Utc>",
   t.updated_at                    AS "updated_at!: DateTime<Utc>",
 
@@ -204,12 +376,13 @@ impl Task {
     )                               AS "executor!: String"
 
 FROM tasks t
-WHERE t.project_id = $1
+WHERE t.project_id = $1 AND t.archived_at IS NULL
 ORDER BY t.created_at DESC"#,
             project_id
         )
         .fetch_all(pool)
         .await?;
+        let mut labels = Label::find_by_task_for_project(pool, project_id).await?;
 
         let tasks = records
             .into_iter()
@@ -220,13 +393,18 @@ ORDER BY t.created_at DESC"#,
                     title: rec.title,
                     description: rec.description,
                     status: rec.status,
+                    priority: rec.priority,
+                    due_date: rec.due_date,
+                    estimate_minutes: rec.estimate_minutes,
                     parent_workspace_id: rec.parent_workspace_id,
                     shared_task_id: rec.shared_task_id,
+                    archived_at: rec.archived_at,
                     created_at: rec.created_at,
                     updated_at: rec.updated_at,
                 };
                 let suggested_prompt = task.suggested_prompt();
                 TaskWithAttemptStatus {
+                    labels: labels.remove(&task.id).unwrap_or_default(),
                     task,
                     has_in_progress_attempt: rec.has_in_progress_attempt != 0,
                     last_attempt_failed: rec.last_attempt_failed != 0,
@@ -242,7 +420,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE id = $1"#,
             id
@@ -254,7 +432,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE rowid = $1"#,
             rowid
@@ -263,6 +441,15 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    pub async fn find_rowid(pool: &SqlitePool, id: Uuid) -> Result<Option<i64>, sqlx::Error> {
+        sqlx::query_scalar!(
+            r#"SELECT rowid as "rowid!: i64" FROM tasks WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
     pub async fn find_by_shared_task_id<'e, E>(
         executor: E,
         shared_task_id: Uuid,
@@ -272,7 +459,7 @@ ORDER BY t.created_at DESC"#,
     {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id = $1
                LIMIT 1"#,
@@ -285,7 +472,7 @@ ORDER BY t.created_at DESC"#,
     pub async fn find_all_shared(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE shared_task_id IS NOT NULL"#
         )
@@ -293,73 +480,144 @@ ORDER BY t.created_at DESC"#,
         .await
     }
 
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn create(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         data: &CreateTask,
         task_id: Uuid,
     ) -> Result<Self, sqlx::Error> {
         let status = data.status.clone().unwrap_or_default();
         sqlx::query_as!(
             Task,
-            r#"INSERT INTO tasks (id, project_id, title, description, status, parent_workspace_id, shared_task_id)
-               VALUES ($1, $2, $3, $4, $5, $6, $7)
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            r#"INSERT INTO tasks (id, project_id, title, description, status, priority, due_date, estimate_minutes, parent_workspace_id, shared_task_id)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
             task_id,
             data.project_id,
             data.title,
             data.description,
             status,
+            data.priority,
+            data.due_date,
+            data.estimate_minutes,
             data.parent_workspace_id,
             data.shared_task_id
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await
     }
 
-    pub async fn update(
-        pool: &SqlitePool,
-        id: Uuid,
-        project_id: Uuid,
-        title: String,
-        description: Option<String>,
-        status: TaskStatus,
-        parent_workspace_id: Option<Uuid>,
-    ) -> Result<Self, sqlx::Error> {
+    /// Save the editable fields of `task`
+    pub async fn update(pool: &SqlitePool, task: &Task) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             Task,
             r#"UPDATE tasks
-               SET title = $3, description = $4, status = $5, parent_workspace_id = $6
+               SET title = $3, description = $4, status = $5, priority = $6, due_date = $7,
+                   estimate_minutes = $8, parent_workspace_id = $9
                WHERE id = $1 AND project_id = $2
-               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
-            id,
-            project_id,
-            title,
-            description,
-            status,
-            parent_workspace_id
+               RETURNING id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>""#,
+            task.id,
+            task.project_id,
+            task.title,
+            task.description,
+            task.status,
+            task.priority,
+            task.due_date,
+            task.estimate_minutes,
+            task.parent_workspace_id
         )
         .fetch_one(pool)
         .await
     }
 
-    pub async fn update_status(
-        pool: &SqlitePool,
+    pub async fn update_status<'e, E>(
+        executor: E,
         id: Uuid,
         status: TaskStatus,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         sqlx::query!(
             "UPDATE tasks SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE id = $1",
             id,
             status
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
         Ok(())
     }
 
+    /// Hide the task from the board. Its workspaces, logs and merges are kept.
+    pub async fn archive(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND archived_at IS NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn restore(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!(
+            r#"UPDATE tasks
+               SET archived_at = NULL, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND archived_at IS NOT NULL"#,
+            id
+        )
+        .execute(pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Archived tasks of a project, most recently archived first
+    pub async fn find_archived_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE project_id = $1 AND archived_at IS NOT NULL
+               ORDER BY archived_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Tasks archived before `cutoff`, which are due to be purged
+    pub async fn find_archived_before(
+        pool: &SqlitePool,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Task,
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+               FROM tasks
+               WHERE archived_at IS NOT NULL AND julianday(archived_at) < julianday($1)"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Update the parent_workspace_id field for a task
     pub async fn update_parent_workspace_id(
-        pool: &SqlitePool,
+        executor: impl Executor<'_, Database = Sqlite>,
         task_id: Uuid,
         parent_workspace_id: Option<Uuid>,
     ) -> Result<(), sqlx::Error> {
@@ -368,7 +626,7 @@ ORDER BY t.created_at DESC"#,
             task_id,
             parent_workspace_id
         )
-        .execute(pool)
+        .execute(executor)
         .await?;
         Ok(())
     }
@@ -473,7 +731,7 @@ ORDER BY t.created_at DESC"#,
         // Find only child tasks that have this workspace as their parent
         sqlx::query_as!(
             Task,
-            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
+            r#"SELECT id as "id!: Uuid", project_id as "project_id!: Uuid", title, description, status as "status!: TaskStatus", priority as "priority: TaskPriority", due_date as "due_date: DateTime<Utc>", estimate_minutes, parent_workspace_id as "parent_workspace_id: Uuid", shared_task_id as "shared_task_id: Uuid", archived_at as "archived_at: DateTime<Utc>", created_at as "created_at!: DateTime<Utc>", updated_at as "updated_at!: DateTime<Utc>"
                FROM tasks
                WHERE parent_workspace_id = $1
                ORDER BY created_at DESC"#,
@@ -529,8 +787,12 @@ mod tests {
             title: title.to_string(),
             description: description.map(|s| s.to_string()),
             status: TaskStatus::Todo,
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
             parent_workspace_id: None,
             shared_task_id: None,
+            archived_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -577,4 +839,103 @@ mod tests {
         let task_no_desc = make_task("Test Task", None);
         assert_eq!(task_no_desc.suggested_prompt(), "Test Task");
     }
+
+    fn with_status(task: Task, labels: &[&str]) -> TaskWithAttemptStatus {
+        let project_id = task.project_id;
+        TaskWithAttemptStatus {
+            task,
+            has_in_progress_attempt: false,
+            last_attempt_failed: false,
+            executor: String::new(),
+            suggested_prompt: String::new(),
+            labels: labels
+                .iter()
+                .map(|name| Label {
+                    id: Uuid::new_v4(),
+                    project_id,
+                    name: name.to_string(),
+                    color: None,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_priority_label_and_due_date() {
+        let now = Utc::now();
+        let mut urgent = make_task("urgent", None);
+        urgent.priority = Some(TaskPriority::Urgent);
+        urgent.due_date = Some(now - chrono::Duration::days(1));
+        let mut later = make_task("later", None);
+        later.priority = Some(TaskPriority::Urgent);
+        later.due_date = Some(now + chrono::Duration::days(7));
+        let undated = make_task("undated", None);
+
+        let tasks = vec![
+            with_status(urgent, &["Backend"]),
+            with_status(later, &["frontend"]),
+            with_status(undated, &["backend"]),
+        ];
+
+        let filter = TaskFilter {
+            priority: Some(TaskPriority::Urgent),
+            label: Some("BACKEND".to_string()),
+            ..Default::default()
+        };
+        let titles: Vec<String> = filter
+            .apply(tasks.clone())
+            .into_iter()
+            .map(|t| t.task.title)
+            .collect();
+        assert_eq!(titles, vec!["urgent"]);
+
+        let filter = TaskFilter {
+            due_before: Some(now),
+            ..Default::default()
+        };
+        assert_eq!(filter.apply(tasks).len(), 1);
+    }
+
+    #[test]
+    fn test_sort_puts_missing_values_last() {
+        let mut low = make_task("b", None);
+        low.priority = Some(TaskPriority::Low);
+        let mut high = make_task("a", None);
+        high.priority = Some(TaskPriority::High);
+        let none = make_task("c", None);
+        let tasks = vec![
+            with_status(none, &[]),
+            with_status(low, &[]),
+            with_status(high, &[]),
+        ];
+
+        let titles = |filter: TaskFilter| -> Vec<String> {
+            filter
+                .apply(tasks.clone())
+                .into_iter()
+                .map(|t| t.task.title)
+                .collect()
+        };
+        let by_priority = TaskFilter {
+            sort: Some(TaskSortField::Priority),
+            ..Default::default()
+        };
+        assert_eq!(titles(by_priority.clone()), vec!["a", "b", "c"]);
+        assert_eq!(
+            titles(TaskFilter {
+                order: Some(SortOrder::Asc),
+                ..by_priority
+            }),
+            vec!["b", "a", "c"]
+        );
+        assert_eq!(
+            titles(TaskFilter {
+                sort: Some(TaskSortField::Title),
+                ..Default::default()
+            }),
+            vec!["a", "b", "c"]
+        );
+    }
 }
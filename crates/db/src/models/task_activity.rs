@@ -0,0 +1,305 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::task::TaskStatus;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "task_activity_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskActivityKind {
+    StatusChanged,
+    AttemptCreated,
+    /// A coding agent run of an attempt stopped running
+    AttemptFinished,
+    Merged,
+    /// A tool approval request was resolved
+    Approval,
+    Comment,
+}
+
+/// Kind-specific fields of an activity entry. System entries are written by database
+/// triggers, so every field is optional.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+pub struct TaskActivityDetails {
+    pub from_status: Option<TaskStatus>,
+    pub to_status: Option<TaskStatus>,
+    pub branch: Option<String>,
+    pub execution_process_id: Option<Uuid>,
+    /// Final status of the execution process, or how the approval was resolved
+    pub outcome: Option<String>,
+    pub tool_name: Option<String>,
+    pub decided_by: Option<String>,
+    pub denial_reason: Option<String>,
+    pub merge_commit: Option<String>,
+    pub pr_number: Option<i64>,
+    pub pr_url: Option<String>,
+}
+
+/// An entry on a task's activity timeline
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskActivity {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub workspace_id: Option<Uuid>,
+    pub kind: TaskActivityKind,
+    #[ts(type = "TaskActivityDetails")]
+    pub details: Json<TaskActivityDetails>,
+    /// Markdown body of a comment
+    pub body: Option<String>,
+    /// GitHub username of the commenter, or the responder of an approval
+    pub author: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct CreateTaskComment {
+    pub body: String,
+    /// Uploaded images referenced by the comment
+    #[serde(default)]
+    pub image_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct UpdateTaskComment {
+    pub body: String,
+}
+
+impl TaskActivity {
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      kind as "kind!: TaskActivityKind",
+                      details as "details!: Json<TaskActivityDetails>",
+                      body,
+                      author,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE task_id = $1
+               ORDER BY created_at ASC"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      kind as "kind!: TaskActivityKind",
+                      details as "details!: Json<TaskActivityDetails>",
+                      body,
+                      author,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_rowid(pool: &SqlitePool, rowid: i64) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      kind as "kind!: TaskActivityKind",
+                      details as "details!: Json<TaskActivityDetails>",
+                      body,
+                      author,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE rowid = $1"#,
+            rowid
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Comments on a task posted after `since`, oldest first
+    pub async fn find_comments_since(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      kind as "kind!: TaskActivityKind",
+                      details as "details!: Json<TaskActivityDetails>",
+                      body,
+                      author,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_activity
+               WHERE task_id = $1 AND kind = 'comment' AND julianday(created_at) > julianday($2)
+               ORDER BY created_at ASC"#,
+            task_id,
+            since
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create_comment(
+        pool: &SqlitePool,
+        task_id: Uuid,
+        author: Option<&str>,
+        data: &CreateTaskComment,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let mut tx = pool.begin().await?;
+
+        sqlx::query!(
+            r#"INSERT INTO task_activity (id, task_id, kind, body, author)
+               VALUES ($1, $2, 'comment', $3, $4)"#,
+            id,
+            task_id,
+            data.body,
+            author
+        )
+        .execute(&mut *tx)
+        .await?;
+        for image_id in &data.image_ids {
+            sqlx::query!(
+                r#"INSERT OR IGNORE INTO task_activity_images (activity_id, image_id)
+                   VALUES ($1, $2)"#,
+                id,
+                image_id
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)
+    }
+
+    pub async fn update_comment(
+        pool: &SqlitePool,
+        id: Uuid,
+        body: &str,
+    ) -> Result<Self, sqlx::Error> {
+        sqlx::query_as!(
+            TaskActivity,
+            r#"UPDATE task_activity
+               SET body = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1 AND kind = 'comment'
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         workspace_id as "workspace_id: Uuid",
+                         kind as "kind!: TaskActivityKind",
+                         details as "details!: Json<TaskActivityDetails>",
+                         body,
+                         author,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            body
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_activity WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Render comments as a prompt section for the coding agent, or `None` when there are
+    /// none to forward
+    pub fn comments_prompt(comments: &[Self]) -> Option<String> {
+        let entries: Vec<String> = comments
+            .iter()
+            .filter(|c| c.kind == TaskActivityKind::Comment)
+            .filter_map(|c| {
+                let body = c.body.as_deref()?.trim();
+                if body.is_empty() {
+                    return None;
+                }
+                let author = c.author.as_deref().unwrap_or("user");
+                Some(format!("{author} commented:\n{body}"))
+            })
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "New comments on this task since your last run:\n\n{}",
+            entries.join("\n\n")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: TaskActivityKind, body: Option<&str>, author: Option<&str>) -> TaskActivity {
+        let now = Utc::now();
+        TaskActivity {
+            id: Uuid::new_v4(),
+            task_id: Uuid::nil(),
+            workspace_id: None,
+            kind,
+            details: Json(TaskActivityDetails::default()),
+            body: body.map(str::to_string),
+            author: author.map(str::to_string),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_comments_prompt_skips_system_and_empty_entries() {
+        let entries = vec![
+            entry(TaskActivityKind::StatusChanged, None, None),
+            entry(
+                TaskActivityKind::Comment,
+                Some("Use the v2 API"),
+                Some("octocat"),
+            ),
+            entry(TaskActivityKind::Comment, Some("   "), Some("octocat")),
+            entry(TaskActivityKind::Comment, Some("Keep the tests"), None),
+        ];
+
+        let prompt = TaskActivity::comments_prompt(&entries).unwrap();
+        assert_eq!(
+            prompt,
+            "New comments on this task since your last run:\n\n\
+             octocat commented:\nUse the v2 API\n\n\
+             user commented:\nKeep the tests"
+        );
+    }
+
+    #[test]
+    fn test_comments_prompt_without_comments() {
+        let entries = vec![entry(TaskActivityKind::AttemptCreated, None, None)];
+        assert!(TaskActivity::comments_prompt(&entries).is_none());
+    }
+}
@@ -0,0 +1,168 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool};
+use ts_rs::TS;
+use uuid::Uuid;
+
+pub const ISSUE_STATE_OPEN: &str = "open";
+pub const ISSUE_STATE_CLOSED: &str = "closed";
+
+/// The GitHub issue a task was imported from
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskGitHubIssue {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub repo_id: Uuid,
+    pub owner: String,
+    pub repo_name: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+    /// `open` or `closed`, as last seen on GitHub
+    pub state: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTaskGitHubIssue {
+    pub task_id: Uuid,
+    pub repo_id: Uuid,
+    pub owner: String,
+    pub repo_name: String,
+    pub issue_number: i64,
+    pub issue_url: String,
+}
+
+impl TaskGitHubIssue {
+    /// How a pull request in `owner/repo_name` refers to this issue: `#N` in the same
+    /// repository, `owner/repo#N` elsewhere
+    pub fn reference_from(&self, owner: &str, repo_name: &str) -> String {
+        if self.owner.eq_ignore_ascii_case(owner) && self.repo_name.eq_ignore_ascii_case(repo_name)
+        {
+            format!("#{}", self.issue_number)
+        } else {
+            format!("{}/{}#{}", self.owner, self.repo_name, self.issue_number)
+        }
+    }
+
+    pub async fn find_by_task_id(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"SELECT id as "id!: Uuid",
+                      task_id as "task_id!: Uuid",
+                      repo_id as "repo_id!: Uuid",
+                      owner,
+                      repo_name,
+                      issue_number,
+                      issue_url,
+                      state,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_github_issues
+               WHERE task_id = $1"#,
+            task_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"SELECT i.id as "id!: Uuid",
+                      i.task_id as "task_id!: Uuid",
+                      i.repo_id as "repo_id!: Uuid",
+                      i.owner,
+                      i.repo_name,
+                      i.issue_number,
+                      i.issue_url,
+                      i.state,
+                      i.created_at as "created_at!: DateTime<Utc>",
+                      i.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_github_issues i
+               JOIN tasks t ON t.id = i.task_id
+               WHERE t.project_id = $1
+               ORDER BY i.issue_number ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Open issues whose task isn't finished yet; these are polled for closure
+    pub async fn find_open_with_active_tasks(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"SELECT i.id as "id!: Uuid",
+                      i.task_id as "task_id!: Uuid",
+                      i.repo_id as "repo_id!: Uuid",
+                      i.owner,
+                      i.repo_name,
+                      i.issue_number,
+                      i.issue_url,
+                      i.state,
+                      i.created_at as "created_at!: DateTime<Utc>",
+                      i.updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_github_issues i
+               JOIN tasks t ON t.id = i.task_id
+               JOIN projects p ON p.id = t.project_id
+               WHERE i.state = 'open'
+                 AND t.status NOT IN ('done', 'cancelled')
+                 AND t.archived_at IS NULL
+                 AND p.archived_at IS NULL"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskGitHubIssue,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskGitHubIssue,
+            r#"INSERT INTO task_github_issues
+                   (id, task_id, repo_id, owner, repo_name, issue_number, issue_url)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         task_id as "task_id!: Uuid",
+                         repo_id as "repo_id!: Uuid",
+                         owner,
+                         repo_name,
+                         issue_number,
+                         issue_url,
+                         state,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.task_id,
+            data.repo_id,
+            data.owner,
+            data.repo_name,
+            data.issue_number,
+            data.issue_url
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_state(pool: &SqlitePool, id: Uuid, state: &str) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_github_issues
+               SET state = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            state
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
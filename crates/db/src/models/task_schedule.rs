@@ -0,0 +1,444 @@
+use chrono::{DateTime, Utc};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use super::workspace_repo::CreateWorkspaceRepo;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display, Default)]
+#[sqlx(type_name = "task_schedule_action", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TaskScheduleAction {
+    /// Create a new task from the schedule's title and description and start an attempt on it
+    #[default]
+    CreateTask,
+    /// Start a new attempt on the schedule's task
+    RestartAttempt,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "task_schedule_run_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TaskScheduleRunStatus {
+    Started,
+    /// The attempt started by the previous run was still running
+    Skipped,
+    Failed,
+}
+
+/// Starts a coding agent on a cron schedule
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskSchedule {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    /// Standard five-field cron expression, e.g. `0 3 * * MON`
+    pub cron_expression: String,
+    /// IANA timezone the cron expression is evaluated in, e.g. `Europe/Berlin`
+    pub timezone: String,
+    pub action: TaskScheduleAction,
+    /// Title of the tasks created by `create_task` schedules
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Task that `restart_attempt` schedules start attempts on
+    pub task_id: Option<Uuid>,
+    #[ts(type = "ExecutorProfileId")]
+    pub executor_profile_id: Json<ExecutorProfileId>,
+    /// Repositories and target branches of each attempt; empty reuses those of the task's
+    /// latest attempt
+    #[ts(type = "Array<CreateWorkspaceRepo>")]
+    pub repos: Json<Vec<CreateWorkspaceRepo>>,
+    pub enabled: bool,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateTaskSchedule {
+    pub project_id: Uuid,
+    pub name: String,
+    pub cron_expression: String,
+    /// Defaults to UTC
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub action: TaskScheduleAction,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub executor_profile_id: ExecutorProfileId,
+    #[serde(default)]
+    pub repos: Vec<CreateWorkspaceRepo>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateTaskSchedule {
+    pub name: Option<String>,
+    pub cron_expression: Option<String>,
+    pub timezone: Option<String>,
+    pub action: Option<TaskScheduleAction>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub task_id: Option<Uuid>,
+    pub executor_profile_id: Option<ExecutorProfileId>,
+    pub repos: Option<Vec<CreateWorkspaceRepo>>,
+    pub enabled: Option<bool>,
+}
+
+/// One time a schedule came due
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct TaskScheduleRun {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub scheduled_for: DateTime<Utc>,
+    pub status: TaskScheduleRunStatus,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateTaskScheduleRun {
+    pub schedule_id: Uuid,
+    pub scheduled_for: DateTime<Utc>,
+    pub status: TaskScheduleRunStatus,
+    pub task_id: Option<Uuid>,
+    pub workspace_id: Option<Uuid>,
+    pub error: Option<String>,
+}
+
+impl TaskSchedule {
+    pub async fn find_all(
+        pool: &SqlitePool,
+        project_id: Option<Uuid>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      cron_expression,
+                      timezone,
+                      action as "action!: TaskScheduleAction",
+                      title,
+                      description,
+                      task_id as "task_id: Uuid",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<CreateWorkspaceRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE $1 IS NULL OR project_id = $1
+               ORDER BY created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      cron_expression,
+                      timezone,
+                      action as "action!: TaskScheduleAction",
+                      title,
+                      description,
+                      task_id as "task_id: Uuid",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<CreateWorkspaceRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Enabled schedules whose next run is at or before `now`
+    pub async fn find_due(pool: &SqlitePool, now: DateTime<Utc>) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"SELECT id as "id!: Uuid",
+                      project_id as "project_id!: Uuid",
+                      name,
+                      cron_expression,
+                      timezone,
+                      action as "action!: TaskScheduleAction",
+                      title,
+                      description,
+                      task_id as "task_id: Uuid",
+                      executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                      repos as "repos!: Json<Vec<CreateWorkspaceRepo>>",
+                      enabled as "enabled!: bool",
+                      next_run_at as "next_run_at: DateTime<Utc>",
+                      last_run_at as "last_run_at: DateTime<Utc>",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM task_schedules
+               WHERE enabled = TRUE AND next_run_at IS NOT NULL AND next_run_at <= $1
+               ORDER BY next_run_at ASC"#,
+            now
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskSchedule,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let timezone = data.timezone.as_deref().unwrap_or("UTC");
+        let executor_profile_id = Json(&data.executor_profile_id);
+        let repos = Json(&data.repos);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"INSERT INTO task_schedules
+                   (id, project_id, name, cron_expression, timezone, action, title, description,
+                    task_id, executor_profile_id, repos, next_run_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         cron_expression,
+                         timezone,
+                         action as "action!: TaskScheduleAction",
+                         title,
+                         description,
+                         task_id as "task_id: Uuid",
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         repos as "repos!: Json<Vec<CreateWorkspaceRepo>>",
+                         enabled as "enabled!: bool",
+                         next_run_at as "next_run_at: DateTime<Utc>",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.project_id,
+            data.name,
+            data.cron_expression,
+            timezone,
+            data.action,
+            data.title,
+            data.description,
+            data.task_id,
+            executor_profile_id,
+            repos,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Apply `data` on top of `existing`, returning the schedule as it would be saved
+    pub fn merged(existing: &Self, data: &UpdateTaskSchedule) -> Self {
+        Self {
+            name: data.name.clone().unwrap_or_else(|| existing.name.clone()),
+            cron_expression: data
+                .cron_expression
+                .clone()
+                .unwrap_or_else(|| existing.cron_expression.clone()),
+            timezone: data
+                .timezone
+                .clone()
+                .unwrap_or_else(|| existing.timezone.clone()),
+            action: data.action.unwrap_or(existing.action),
+            title: data.title.clone().or_else(|| existing.title.clone()),
+            description: data
+                .description
+                .clone()
+                .or_else(|| existing.description.clone()),
+            task_id: data.task_id.or(existing.task_id),
+            executor_profile_id: Json(
+                data.executor_profile_id
+                    .clone()
+                    .unwrap_or_else(|| existing.executor_profile_id.0.clone()),
+            ),
+            repos: Json(
+                data.repos
+                    .clone()
+                    .unwrap_or_else(|| existing.repos.0.clone()),
+            ),
+            enabled: data.enabled.unwrap_or(existing.enabled),
+            ..existing.clone()
+        }
+    }
+
+    /// Save everything but the run times, IDs and timestamps of `schedule`
+    pub async fn update(
+        pool: &SqlitePool,
+        schedule: &Self,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, sqlx::Error> {
+        let executor_profile_id = Json(&schedule.executor_profile_id.0);
+        let repos = Json(&schedule.repos.0);
+        sqlx::query_as!(
+            TaskSchedule,
+            r#"UPDATE task_schedules
+               SET name = $2, cron_expression = $3, timezone = $4, action = $5, title = $6,
+                   description = $7, task_id = $8, executor_profile_id = $9, repos = $10,
+                   enabled = $11, next_run_at = $12, updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         project_id as "project_id!: Uuid",
+                         name,
+                         cron_expression,
+                         timezone,
+                         action as "action!: TaskScheduleAction",
+                         title,
+                         description,
+                         task_id as "task_id: Uuid",
+                         executor_profile_id as "executor_profile_id!: Json<ExecutorProfileId>",
+                         repos as "repos!: Json<Vec<CreateWorkspaceRepo>>",
+                         enabled as "enabled!: bool",
+                         next_run_at as "next_run_at: DateTime<Utc>",
+                         last_run_at as "last_run_at: DateTime<Utc>",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            schedule.id,
+            schedule.name,
+            schedule.cron_expression,
+            schedule.timezone,
+            schedule.action,
+            schedule.title,
+            schedule.description,
+            schedule.task_id,
+            executor_profile_id,
+            repos,
+            schedule.enabled,
+            next_run_at
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn set_run_times(
+        pool: &SqlitePool,
+        id: Uuid,
+        last_run_at: DateTime<Utc>,
+        next_run_at: Option<DateTime<Utc>>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE task_schedules
+               SET last_run_at = $2, next_run_at = $3
+               WHERE id = $1"#,
+            id,
+            last_run_at,
+            next_run_at
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM task_schedules WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl TaskScheduleRun {
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateTaskScheduleRun,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"INSERT INTO task_schedule_runs
+                   (id, schedule_id, scheduled_for, status, task_id, workspace_id, error)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               RETURNING id as "id!: Uuid",
+                         schedule_id as "schedule_id!: Uuid",
+                         scheduled_for as "scheduled_for!: DateTime<Utc>",
+                         status as "status!: TaskScheduleRunStatus",
+                         task_id as "task_id: Uuid",
+                         workspace_id as "workspace_id: Uuid",
+                         error,
+                         created_at as "created_at!: DateTime<Utc>""#,
+            id,
+            data.schedule_id,
+            data.scheduled_for,
+            data.status,
+            data.task_id,
+            data.workspace_id,
+            data.error
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    /// Most recent runs of a schedule, newest first
+    pub async fn find_by_schedule_id(
+        pool: &SqlitePool,
+        schedule_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"SELECT id as "id!: Uuid",
+                      schedule_id as "schedule_id!: Uuid",
+                      scheduled_for as "scheduled_for!: DateTime<Utc>",
+                      status as "status!: TaskScheduleRunStatus",
+                      task_id as "task_id: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      error,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_schedule_runs
+               WHERE schedule_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            schedule_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// The latest run of a schedule that started an attempt
+    pub async fn find_latest_started(
+        pool: &SqlitePool,
+        schedule_id: Uuid,
+    ) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            TaskScheduleRun,
+            r#"SELECT id as "id!: Uuid",
+                      schedule_id as "schedule_id!: Uuid",
+                      scheduled_for as "scheduled_for!: DateTime<Utc>",
+                      status as "status!: TaskScheduleRunStatus",
+                      task_id as "task_id: Uuid",
+                      workspace_id as "workspace_id: Uuid",
+                      error,
+                      created_at as "created_at!: DateTime<Utc>"
+               FROM task_schedule_runs
+               WHERE schedule_id = $1 AND status = 'started'
+               ORDER BY created_at DESC
+               LIMIT 1"#,
+            schedule_id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+}
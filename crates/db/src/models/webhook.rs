@@ -0,0 +1,361 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{FromRow, SqlitePool, Type, types::Json};
+use strum_macros::Display;
+use ts_rs::TS;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "webhook_event_type")]
+pub enum WebhookEventType {
+    /// A task moved to another column
+    #[sqlx(rename = "task.status_changed")]
+    #[serde(rename = "task.status_changed")]
+    #[strum(serialize = "task.status_changed")]
+    TaskStatusChanged,
+    /// A coding agent run finished and the task went to review
+    #[sqlx(rename = "execution.finished")]
+    #[serde(rename = "execution.finished")]
+    #[strum(serialize = "execution.finished")]
+    ExecutionFinished,
+    #[sqlx(rename = "pr.opened")]
+    #[serde(rename = "pr.opened")]
+    #[strum(serialize = "pr.opened")]
+    PrOpened,
+    #[sqlx(rename = "pr.merged")]
+    #[serde(rename = "pr.merged")]
+    #[strum(serialize = "pr.merged")]
+    PrMerged,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub name: String,
+    pub url: String,
+    /// Key for the `X-Vibe-Kanban-Signature` HMAC; never returned by the API
+    #[serde(skip_serializing, default)]
+    #[ts(skip)]
+    pub secret: Option<String>,
+    /// Events to deliver; empty means every event
+    #[ts(type = "Array<WebhookEventType>")]
+    pub events: Json<Vec<WebhookEventType>>,
+    /// Only deliver events for this project; `null` means every project
+    pub project_id: Option<Uuid>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct CreateWebhookSubscription {
+    pub name: String,
+    pub url: String,
+    pub secret: Option<String>,
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Deserialize, TS)]
+pub struct UpdateWebhookSubscription {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    /// An empty string removes the secret
+    pub secret: Option<String>,
+    pub events: Option<Vec<WebhookEventType>>,
+    pub project_id: Option<Uuid>,
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Type, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[sqlx(type_name = "webhook_delivery_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: WebhookEventType,
+    /// The JSON body that was sent
+    #[ts(type = "JsonValue")]
+    pub payload: Json<Value>,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i64,
+    /// HTTP status of the last attempt, if the endpoint answered
+    pub response_status: Option<i64>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookSubscription {
+    pub fn subscribes_to(&self, event: WebhookEventType, project_id: Option<Uuid>) -> bool {
+        self.enabled
+            && (self.events.is_empty() || self.events.contains(&event))
+            && (self.project_id.is_none() || self.project_id == project_id)
+    }
+
+    pub async fn find_all(pool: &SqlitePool) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookSubscription,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      url,
+                      secret,
+                      events as "events!: Json<Vec<WebhookEventType>>",
+                      project_id as "project_id: Uuid",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhook_subscriptions
+               ORDER BY created_at ASC"#
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookSubscription,
+            r#"SELECT id as "id!: Uuid",
+                      name,
+                      url,
+                      secret,
+                      events as "events!: Json<Vec<WebhookEventType>>",
+                      project_id as "project_id: Uuid",
+                      enabled as "enabled!: bool",
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>"
+               FROM webhook_subscriptions
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    pub async fn create(
+        pool: &SqlitePool,
+        data: &CreateWebhookSubscription,
+    ) -> Result<Self, sqlx::Error> {
+        let id = Uuid::new_v4();
+        let events = Json(&data.events);
+        sqlx::query_as!(
+            WebhookSubscription,
+            r#"INSERT INTO webhook_subscriptions (id, name, url, secret, events, project_id)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING id as "id!: Uuid",
+                         name,
+                         url,
+                         secret,
+                         events as "events!: Json<Vec<WebhookEventType>>",
+                         project_id as "project_id: Uuid",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            data.name,
+            data.url,
+            data.secret,
+            events,
+            data.project_id
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn update(
+        pool: &SqlitePool,
+        id: Uuid,
+        data: &UpdateWebhookSubscription,
+    ) -> Result<Self, sqlx::Error> {
+        let existing = Self::find_by_id(pool, id)
+            .await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let name = data.name.as_ref().unwrap_or(&existing.name);
+        let url = data.url.as_ref().unwrap_or(&existing.url);
+        let secret = match &data.secret {
+            Some(s) if s.is_empty() => None,
+            Some(s) => Some(s.clone()),
+            None => existing.secret,
+        };
+        let events = Json(data.events.clone().unwrap_or(existing.events.0));
+        let project_id = data.project_id.or(existing.project_id);
+        let enabled = data.enabled.unwrap_or(existing.enabled);
+
+        sqlx::query_as!(
+            WebhookSubscription,
+            r#"UPDATE webhook_subscriptions
+               SET name = $2, url = $3, secret = $4, events = $5, project_id = $6, enabled = $7,
+                   updated_at = datetime('now', 'subsec')
+               WHERE id = $1
+               RETURNING id as "id!: Uuid",
+                         name,
+                         url,
+                         secret,
+                         events as "events!: Json<Vec<WebhookEventType>>",
+                         project_id as "project_id: Uuid",
+                         enabled as "enabled!: bool",
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>""#,
+            id,
+            name,
+            url,
+            secret,
+            events,
+            project_id,
+            enabled
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn delete(pool: &SqlitePool, id: Uuid) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query!("DELETE FROM webhook_subscriptions WHERE id = $1", id)
+            .execute(pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl WebhookDelivery {
+    pub async fn create(
+        pool: &SqlitePool,
+        id: Uuid,
+        subscription_id: Uuid,
+        event_type: WebhookEventType,
+        payload: &Value,
+    ) -> Result<Self, sqlx::Error> {
+        let payload = Json(payload);
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"INSERT INTO webhook_deliveries (id, subscription_id, event_type, payload)
+               VALUES ($1, $2, $3, $4)
+               RETURNING id as "id!: Uuid",
+                         subscription_id as "subscription_id!: Uuid",
+                         event_type as "event_type!: WebhookEventType",
+                         payload as "payload!: Json<Value>",
+                         status as "status!: WebhookDeliveryStatus",
+                         attempts,
+                         response_status,
+                         last_error,
+                         created_at as "created_at!: DateTime<Utc>",
+                         updated_at as "updated_at!: DateTime<Utc>",
+                         delivered_at as "delivered_at: DateTime<Utc>""#,
+            id,
+            subscription_id,
+            event_type,
+            payload
+        )
+        .fetch_one(pool)
+        .await
+    }
+
+    pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"SELECT id as "id!: Uuid",
+                      subscription_id as "subscription_id!: Uuid",
+                      event_type as "event_type!: WebhookEventType",
+                      payload as "payload!: Json<Value>",
+                      status as "status!: WebhookDeliveryStatus",
+                      attempts,
+                      response_status,
+                      last_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>",
+                      delivered_at as "delivered_at: DateTime<Utc>"
+               FROM webhook_deliveries
+               WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Most recent deliveries for a subscription, newest first
+    pub async fn find_by_subscription_id(
+        pool: &SqlitePool,
+        subscription_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            WebhookDelivery,
+            r#"SELECT id as "id!: Uuid",
+                      subscription_id as "subscription_id!: Uuid",
+                      event_type as "event_type!: WebhookEventType",
+                      payload as "payload!: Json<Value>",
+                      status as "status!: WebhookDeliveryStatus",
+                      attempts,
+                      response_status,
+                      last_error,
+                      created_at as "created_at!: DateTime<Utc>",
+                      updated_at as "updated_at!: DateTime<Utc>",
+                      delivered_at as "delivered_at: DateTime<Utc>"
+               FROM webhook_deliveries
+               WHERE subscription_id = $1
+               ORDER BY created_at DESC
+               LIMIT $2"#,
+            subscription_id,
+            limit
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Record the outcome of one attempt
+    pub async fn record_attempt(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: WebhookDeliveryStatus,
+        response_status: Option<i64>,
+        last_error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        let delivered_at = (status == WebhookDeliveryStatus::Succeeded).then_some(now);
+        sqlx::query!(
+            r#"UPDATE webhook_deliveries
+               SET status = $2, attempts = attempts + 1, response_status = $3, last_error = $4,
+                   delivered_at = COALESCE($5, delivered_at), updated_at = $6
+               WHERE id = $1"#,
+            id,
+            status,
+            response_status,
+            last_error,
+            delivered_at,
+            now
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Set the status without counting an attempt, e.g. before a redelivery or after the
+    /// last retry failed
+    pub async fn set_status(
+        pool: &SqlitePool,
+        id: Uuid,
+        status: WebhookDeliveryStatus,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"UPDATE webhook_deliveries
+               SET status = $2, updated_at = datetime('now', 'subsec')
+               WHERE id = $1"#,
+            id,
+            status
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -143,6 +143,31 @@ impl Workspace {
         Ok(workspaces)
     }
 
+    /// Fetch the workspaces of every task in a project, including archived tasks
+    pub async fn find_by_project_id(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            Workspace,
+            r#"SELECT w.id AS "id!: Uuid",
+                      w.task_id AS "task_id!: Uuid",
+                      w.container_ref,
+                      w.branch,
+                      w.agent_working_dir,
+                      w.setup_completed_at AS "setup_completed_at: DateTime<Utc>",
+                      w.created_at AS "created_at!: DateTime<Utc>",
+                      w.updated_at AS "updated_at!: DateTime<Utc>"
+               FROM workspaces w
+               JOIN tasks t ON t.id = w.task_id
+               WHERE t.project_id = $1
+               ORDER BY w.created_at DESC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Load workspace with full validation - ensures workspace belongs to task and task belongs to project
     pub async fn load_context(
         pool: &SqlitePool,
@@ -322,12 +347,15 @@ impl Workspace {
         .await
     }
 
-    pub async fn create(
-        pool: &SqlitePool,
+    pub async fn create<'e, E>(
+        executor: E,
         data: &CreateWorkspace,
         id: Uuid,
         task_id: Uuid,
-    ) -> Result<Self, WorkspaceError> {
+    ) -> Result<Self, WorkspaceError>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         Ok(sqlx::query_as!(
             Workspace,
             r#"INSERT INTO workspaces (id, task_id, container_ref, branch, agent_working_dir, setup_completed_at)
@@ -340,10 +368,21 @@ impl Workspace {
             data.agent_working_dir,
             Option::<DateTime<Utc>>::None
         )
-        .fetch_one(pool)
+        .fetch_one(executor)
         .await?)
     }
 
+    /// Delete a workspace along with its sessions and repos
+    pub async fn delete<'e, E>(executor: E, id: Uuid) -> Result<u64, sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query!("DELETE FROM workspaces WHERE id = $1", id)
+            .execute(executor)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
     pub async fn update_branch_name(
         pool: &SqlitePool,
         workspace_id: Uuid,
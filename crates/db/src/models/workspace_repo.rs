@@ -2,7 +2,7 @@ use std::path::PathBuf;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool};
+use sqlx::{Acquire, FromRow, Sqlite, SqlitePool};
 use ts_rs::TS;
 use uuid::Uuid;
 
@@ -20,7 +20,7 @@ pub struct WorkspaceRepo {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct CreateWorkspaceRepo {
     pub repo_id: Uuid,
     pub target_branch: String,
@@ -44,11 +44,15 @@ pub struct RepoWithCopyFiles {
 }
 
 impl WorkspaceRepo {
-    pub async fn create_many(
-        pool: &SqlitePool,
+    pub async fn create_many<'a, A>(
+        conn: A,
         workspace_id: Uuid,
         repos: &[CreateWorkspaceRepo],
-    ) -> Result<Vec<Self>, sqlx::Error> {
+    ) -> Result<Vec<Self>, sqlx::Error>
+    where
+        A: Acquire<'a, Database = Sqlite>,
+    {
+        let mut conn = conn.acquire().await?;
         let mut results = Vec::with_capacity(repos.len());
 
         for repo in repos {
@@ -68,7 +72,7 @@ impl WorkspaceRepo {
                 repo.repo_id,
                 repo.target_branch
             )
-            .fetch_one(pool)
+            .fetch_one(&mut *conn)
             .await?;
             results.push(workspace_repo);
         }
@@ -241,6 +245,27 @@ impl WorkspaceRepo {
         .await
     }
 
+    /// Repositories and target branches of the task's most recent workspace
+    pub async fn find_for_latest_workspace(
+        pool: &SqlitePool,
+        task_id: Uuid,
+    ) -> Result<Vec<CreateWorkspaceRepo>, sqlx::Error> {
+        sqlx::query_as!(
+            CreateWorkspaceRepo,
+            r#"SELECT repo_id as "repo_id!: Uuid", target_branch
+               FROM workspace_repos
+               WHERE workspace_id = (
+                   SELECT id FROM workspaces
+                   WHERE task_id = $1
+                   ORDER BY created_at DESC
+                   LIMIT 1
+               )"#,
+            task_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Find repos for a workspace with their copy_files configuration.
     /// Uses LEFT JOIN so repos without project_repo entries still appear (with NULL copy_files).
     pub async fn find_repos_with_copy_files(
@@ -0,0 +1,16 @@
+use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+
+/// A fresh in-memory database with every migration applied. The pool holds a single
+/// connection, since each connection to `sqlite::memory:` opens a separate database.
+pub async fn test_pool() -> SqlitePool {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .expect("in-memory database should open");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("migrations should apply");
+    pool
+}
@@ -19,6 +19,7 @@ use services::services::{
     analytics::{AnalyticsContext, AnalyticsService},
     approvals::Approvals,
     auth::AuthContext,
+    backup::BackupService,
     config::{Config, ConfigError},
     container::{ContainerError, ContainerService},
     events::{EventError, EventService},
@@ -27,11 +28,13 @@ use services::services::{
     filesystem_watcher::FilesystemWatcherError,
     git::{GitService, GitServiceError},
     image::{ImageError, ImageService},
+    plan_sync::PlanSyncService,
     pr_monitor::PrMonitorService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
     share::SharePublisher,
+    webhooks::WebhookService,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -113,6 +116,12 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn share_publisher(&self) -> Result<SharePublisher, RemoteClientNotConfigured>;
 
+    fn webhooks(&self) -> &WebhookService;
+
+    fn plan_sync(&self) -> &PlanSyncService;
+
+    fn backups(&self) -> &BackupService;
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
@@ -133,7 +142,15 @@ pub trait Deployment: Clone + Send + Sync + 'static {
                 analytics_service: analytics_service.clone(),
             });
         let publisher = self.share_publisher().ok();
-        PrMonitorService::spawn(db, analytics, publisher).await
+        let notifications = self.container().notification_service().clone();
+        PrMonitorService::spawn(
+            db,
+            analytics,
+            publisher,
+            self.webhooks().clone(),
+            notifications,
+        )
+        .await
     }
 
     async fn track_if_analytics_allowed(&self, event_name: &str, properties: Value) {
@@ -1,9 +1,11 @@
 use std::fmt;
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
+use ts_rs::TS;
 use workspace_utils::approvals::ApprovalStatus;
 
 /// Errors emitted by executor approval services.
@@ -23,6 +25,17 @@ impl ExecutorApprovalError {
     }
 }
 
+/// What happens to a pending tool approval when its timeout elapses: deny the tool call,
+/// let it run, or start another timeout period and keep waiting for a response.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalTimeoutAction {
+    #[default]
+    Deny,
+    Approve,
+    KeepWaiting,
+}
+
 /// Abstraction for executor approval backends.
 #[async_trait]
 pub trait ExecutorApprovalService: Send + Sync {
@@ -6,7 +6,7 @@ use thiserror::Error;
 use ts_rs::TS;
 use workspace_utils::shell::resolve_executable_path;
 
-use crate::executors::ExecutorError;
+use crate::{approvals::ApprovalTimeoutAction, executors::ExecutorError};
 
 #[derive(Debug, Error)]
 pub enum CommandBuildError {
@@ -58,6 +58,18 @@ pub struct CmdOverrides {
     )]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub env: Option<HashMap<String, String>>,
+    #[schemars(
+        title = "Approval Timeout (seconds)",
+        description = "How long to wait for a tool approval before the timeout action is applied. Defaults to one hour."
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_timeout_seconds: Option<u32>,
+    #[schemars(
+        title = "Approval Timeout Action",
+        description = "What to do when a tool approval times out: deny the call, approve it, or keep waiting"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_timeout_action: Option<ApprovalTimeoutAction>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS, JsonSchema)]
@@ -2006,6 +2006,8 @@ mod tests {
                 base_command_override: None,
                 additional_params: None,
                 env: None,
+                approval_timeout_seconds: None,
+                approval_timeout_action: None,
             },
             approvals_service: None,
             disable_api_key: None,
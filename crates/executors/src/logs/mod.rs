@@ -1,7 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
-use workspace_utils::approvals::ApprovalStatus;
+use workspace_utils::approvals::{ApprovalRuleRef, ApprovalStatus};
 
 pub mod plain_text_processor;
 pub mod stderr_processor;
@@ -138,6 +138,14 @@ pub enum ToolStatus {
     Failed,
     Denied {
         reason: Option<String>,
+        /// Set when an approval policy rule denied the call
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional)]
+        rule: Option<ApprovalRuleRef>,
+    },
+    /// Approved by an approval policy rule without asking the user
+    AutoApproved {
+        rule: ApprovalRuleRef,
     },
     PendingApproval {
         approval_id: String,
@@ -153,6 +161,7 @@ impl ToolStatus {
             ApprovalStatus::Approved => Some(ToolStatus::Created),
             ApprovalStatus::Denied { reason } => Some(ToolStatus::Denied {
                 reason: reason.clone(),
+                rule: None,
             }),
             ApprovalStatus::TimedOut => Some(ToolStatus::TimedOut),
             ApprovalStatus::Pending => None, // this should not happen
@@ -8,6 +8,7 @@ use std::{
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use chrono::Utc;
 use command_group::AsyncGroupChild;
 use db::{
     DBService,
@@ -16,10 +17,12 @@ use db::{
         execution_process::{
             ExecutionContext, ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus,
         },
+        execution_process_logs::ExecutionProcessLogs,
         execution_process_repo_state::ExecutionProcessRepoState,
         project_repo::ProjectRepo,
         repo::Repo,
         scratch::{DraftFollowUpData, Scratch, ScratchType},
+        search::SearchIndex,
         task::{Task, TaskStatus},
         workspace::Workspace,
         workspace_repo::WorkspaceRepo,
@@ -35,14 +38,17 @@ use executors::{
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::ExecutionEnv,
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
-    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
-    profile::ExecutorProfileId,
+    logs::{
+        ActionType, NormalizedEntryType, ToolStatus,
+        utils::patch::extract_normalized_entry_from_patch,
+    },
+    profile::{ExecutorConfigs, ExecutorProfileId},
 };
-use futures::{FutureExt, TryStreamExt, stream::select};
+use futures::{FutureExt, StreamExt, TryStreamExt, stream::select};
 use serde_json::json;
 use services::services::{
     analytics::AnalyticsContext,
-    approvals::{Approvals, executor_approvals::ExecutorApprovalBridge},
+    approvals::{ApprovalTimeout, Approvals, executor_approvals::ExecutorApprovalBridge},
     config::Config,
     container::{ContainerError, ContainerRef, ContainerService},
     diff_stream::{self, DiffStreamHandle},
@@ -51,19 +57,25 @@ use services::services::{
     notification::NotificationService,
     queued_message::QueuedMessageService,
     share::SharePublisher,
+    webhooks::WebhookService,
     workspace_manager::{RepoWorkspaceInput, WorkspaceManager},
 };
 use tokio::{sync::RwLock, task::JoinHandle};
 use tokio_util::io::ReaderStream;
+use tracing::{Instrument, field};
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
+    telemetry,
     text::{git_branch_id, short_uuid, truncate_to_char_boundary},
 };
 use uuid::Uuid;
 
 use crate::{command, copy};
 
+/// Free space the database file must hold before log retention rewrites it with `VACUUM`
+const VACUUM_THRESHOLD_BYTES: i64 = 64 * 1024 * 1024;
+
 #[derive(Clone)]
 pub struct LocalContainerService {
     db: DBService,
@@ -78,6 +90,7 @@ pub struct LocalContainerService {
     queued_message_service: QueuedMessageService,
     publisher: Result<SharePublisher, RemoteClientNotConfigured>,
     notification_service: NotificationService,
+    webhooks: WebhookService,
 }
 
 impl LocalContainerService {
@@ -92,6 +105,7 @@ impl LocalContainerService {
         approvals: Approvals,
         queued_message_service: QueuedMessageService,
         publisher: Result<SharePublisher, RemoteClientNotConfigured>,
+        webhooks: WebhookService,
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
@@ -110,9 +124,12 @@ impl LocalContainerService {
             queued_message_service,
             publisher,
             notification_service,
+            webhooks,
         };
 
         container.spawn_workspace_cleanup().await;
+        container.spawn_log_retention();
+        container.spawn_search_backfill();
 
         container
     }
@@ -211,6 +228,101 @@ impl LocalContainerService {
         });
     }
 
+    /// Compress finished execution logs and, when a retention window is configured, replace
+    /// the raw stdout/stderr of old executions on Done tasks with their normalized entries.
+    /// Returns the number of bytes reclaimed.
+    pub async fn run_log_retention(&self) -> Result<i64, ContainerError> {
+        let mut reclaimed = 0;
+
+        for execution_id in
+            ExecutionProcessLogs::find_uncompacted_execution_ids(&self.db.pool).await?
+        {
+            // Skip processes still streaming into the DB
+            if self.get_msg_store_by_id(&execution_id).await.is_some() {
+                continue;
+            }
+            match ExecutionProcessLogs::compact(&self.db.pool, execution_id).await {
+                Ok(bytes) => reclaimed += bytes,
+                Err(e) => tracing::warn!(
+                    "Failed to compact logs for execution {}: {}",
+                    execution_id,
+                    e
+                ),
+            }
+        }
+
+        let retention_days = self
+            .config
+            .read()
+            .await
+            .log_retention
+            .raw_log_retention_days;
+        if let Some(days) = retention_days {
+            let cutoff = Utc::now() - chrono::Duration::days(days as i64);
+            for prunable in ExecutionProcessLogs::find_prunable(&self.db.pool, cutoff).await? {
+                match self.prune_raw_logs(prunable.execution_id).await {
+                    Ok(bytes) => reclaimed += bytes,
+                    Err(e) => tracing::warn!(
+                        "Failed to prune raw logs for execution {}: {}",
+                        prunable.execution_id,
+                        e
+                    ),
+                }
+            }
+        }
+
+        Ok(reclaimed)
+    }
+
+    pub fn spawn_log_retention(&self) {
+        let container = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tracing::info!("Starting log retention...");
+                match container.run_log_retention().await {
+                    Ok(reclaimed) if reclaimed > 0 => {
+                        tracing::info!("Log retention reclaimed {} bytes of log data", reclaimed);
+                        // A full VACUUM rewrites the whole file, so only run it once enough
+                        // free pages have built up, possibly across several runs
+                        let free = match container.db.free_bytes().await {
+                            Ok(free) => free,
+                            Err(e) => {
+                                tracing::error!("Failed to read database free space: {}", e);
+                                0
+                            }
+                        };
+                        if free < VACUUM_THRESHOLD_BYTES {
+                            tracing::debug!(
+                                "Skipping vacuum: {} bytes free, threshold is {} bytes",
+                                free,
+                                VACUUM_THRESHOLD_BYTES
+                            );
+                        } else {
+                            let size_before = container.db.size_bytes().await.ok();
+                            if let Err(e) = container.db.vacuum().await {
+                                tracing::error!("Failed to vacuum database: {}", e);
+                            } else if let (Some(before), Ok(after)) =
+                                (size_before, container.db.size_bytes().await)
+                            {
+                                tracing::info!(
+                                    "Database vacuumed: {} -> {} bytes ({} bytes freed)",
+                                    before,
+                                    after,
+                                    before - after
+                                );
+                            }
+                        }
+                    }
+                    Ok(_) => tracing::debug!("Log retention found nothing to reclaim"),
+                    Err(e) => tracing::error!("Failed to run log retention: {}", e),
+                }
+
+                let interval_hours = container.config.read().await.log_retention.interval_hours;
+                tokio::time::sleep(Duration::from_secs(interval_hours.max(1) as u64 * 3600)).await;
+            }
+        });
+    }
+
     /// Record the current HEAD commit for each repository as the "after" state.
     /// Errors are silently ignored since this runs after the main execution completes
     /// and failure should not block process finalization.
@@ -311,6 +423,12 @@ impl LocalContainerService {
     }
 
     /// Commit changes to each repo. Logs failures but continues with other repos.
+    #[tracing::instrument(
+        target = "vk::lifecycle",
+        name = "git.commit",
+        skip_all,
+        fields(repo_count = repos_with_changes.len(), committed)
+    )]
     fn commit_repos(&self, repos_with_changes: Vec<(Repo, PathBuf)>, message: &str) -> bool {
         let mut any_committed = false;
 
@@ -335,6 +453,7 @@ impl LocalContainerService {
             }
         }
 
+        tracing::Span::current().record("committed", any_committed);
         any_committed
     }
 
@@ -344,6 +463,7 @@ impl LocalContainerService {
         &self,
         exec_id: &Uuid,
         exit_signal: Option<ExecutorExitSignal>,
+        span: tracing::Span,
     ) -> JoinHandle<()> {
         let exec_id = *exec_id;
         let child_store = self.child_store.clone();
@@ -355,6 +475,7 @@ impl LocalContainerService {
         let publisher = self.publisher.clone();
 
         let mut process_exit_rx = self.spawn_os_exit_watcher(exec_id);
+        let monitor_span = span.clone();
 
         tokio::spawn(async move {
             let mut exit_signal_future = exit_signal
@@ -402,6 +523,10 @@ impl LocalContainerService {
                 }
                 Err(_) => (None, ExecutionProcessStatus::Failed),
             };
+            if let Some(code) = exit_code {
+                span.record("exit_code", code);
+            }
+            span.record("status", field::debug(&status));
 
             if !ExecutionProcess::was_stopped(&db.pool, exec_id).await
                 && let Err(e) =
@@ -416,6 +541,14 @@ impl LocalContainerService {
                     tracing::warn!("Failed to update executor session summary: {}", e);
                 }
 
+                if matches!(
+                    ctx.execution_process.run_reason,
+                    ExecutionProcessRunReason::CodingAgent
+                ) && let Err(e) = container.index_conversation(&exec_id).await
+                {
+                    tracing::warn!("Failed to index conversation for search: {}", e);
+                }
+
                 let success = matches!(
                     ctx.execution_process.status,
                     ExecutionProcessStatus::Completed
@@ -558,7 +691,8 @@ impl LocalContainerService {
 
             // Cleanup child handle
             child_store.write().await.remove(&exec_id);
-        })
+        }
+        .instrument(monitor_span))
     }
 
     pub fn spawn_os_exit_watcher(
@@ -697,8 +831,85 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Add the assistant messages, commands and edited files of a finished coding agent run
+    /// to the search index. Runs that are no longer in memory are read from their stored logs.
+    async fn index_conversation(&self, exec_id: &Uuid) -> Result<(), anyhow::Error> {
+        let entries = self.normalized_entries(exec_id).await?;
+
+        let mut assistant_messages = Vec::new();
+        let mut commands = Vec::new();
+        let mut file_edits: Vec<String> = Vec::new();
+        for entry in entries {
+            match entry.entry_type {
+                NormalizedEntryType::AssistantMessage if !entry.content.trim().is_empty() => {
+                    assistant_messages.push(entry.content);
+                }
+                NormalizedEntryType::ToolUse {
+                    action_type: ActionType::CommandRun { command, .. },
+                    ..
+                } => commands.push(command),
+                NormalizedEntryType::ToolUse {
+                    action_type: ActionType::FileEdit { path, .. },
+                    ..
+                } if !file_edits.contains(&path) => file_edits.push(path),
+                _ => {}
+            }
+        }
+
+        SearchIndex::index_conversation(
+            &self.db.pool,
+            *exec_id,
+            &assistant_messages,
+            &commands,
+            &file_edits,
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Index the conversations of executions that finished before conversations were
+    /// indexed. Progress is kept in the database, so a restart picks up where it stopped.
+    pub fn spawn_search_backfill(&self) {
+        const BATCH_SIZE: i64 = 50;
+        let container = self.clone();
+        tokio::spawn(async move {
+            let mut indexed = 0;
+            loop {
+                let pending =
+                    match SearchIndex::pending_backfill(&container.db.pool, BATCH_SIZE).await {
+                        Ok(pending) => pending,
+                        Err(e) => {
+                            tracing::error!("Failed to load conversations to index: {}", e);
+                            return;
+                        }
+                    };
+                if pending.is_empty() {
+                    break;
+                }
+                for exec_id in pending {
+                    if let Err(e) = container.index_conversation(&exec_id).await {
+                        tracing::warn!(
+                            "Failed to index conversation of execution {} for search: {}",
+                            exec_id,
+                            e
+                        );
+                    }
+                    // Failed executions are dropped too; retrying them on every start won't help
+                    if let Err(e) = SearchIndex::finish_backfill(&container.db.pool, exec_id).await
+                    {
+                        tracing::error!("Failed to record search backfill progress: {}", e);
+                        return;
+                    }
+                    indexed += 1;
+                }
+                tracing::info!("Indexed {} past conversations for search", indexed);
+            }
+        });
+    }
+
     /// Copy project files and images to the workspace.
     /// Skips files/images that already exist (fast no-op if all exist).
+    #[tracing::instrument(target = "vk::lifecycle", name = "workspace.copy_files", skip_all)]
     async fn copy_files_and_images(
         &self,
         workspace_dir: &Path,
@@ -889,6 +1100,10 @@ impl ContainerService for LocalContainerService {
         &self.notification_service
     }
 
+    fn webhooks(&self) -> &WebhookService {
+        &self.webhooks
+    }
+
     async fn git_branch_prefix(&self) -> String {
         self.config.read().await.git_branch_prefix.clone()
     }
@@ -931,17 +1146,29 @@ impl ContainerService for LocalContainerService {
             })
             .collect();
 
-        let created_workspace = WorkspaceManager::create_workspace(
-            &workspace_dir,
-            &workspace_inputs,
-            &workspace.branch,
-        )
-        .await?;
-
-        // Copy project files and images to workspace
-        self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
+        let setup_span = tracing::info_span!(
+            target: "vk::lifecycle",
+            "workspace.setup",
+            workspace_id = %workspace.id,
+            task_id = %task.id,
+        );
+        telemetry::attach_to_attempt(&setup_span, workspace.id);
+        let created_workspace = async {
+            let created_workspace = WorkspaceManager::create_workspace(
+                &workspace_dir,
+                &workspace_inputs,
+                &workspace.branch,
+            )
             .await?;
 
+            // Copy project files and images to workspace
+            self.copy_files_and_images(&created_workspace.workspace_dir, workspace)
+                .await?;
+            Ok::<_, ContainerError>(created_workspace)
+        }
+        .instrument(setup_span)
+        .await?;
+
         Self::create_workspace_config_files(&created_workspace.workspace_dir, &repositories)
             .await?;
 
@@ -1061,6 +1288,16 @@ impl ContainerService for LocalContainerService {
                     self.db.clone(),
                     self.notification_service.clone(),
                     execution_process.id,
+                    executor_action
+                        .executor_profile_id()
+                        .map(|profile_id| {
+                            ApprovalTimeout::from_overrides(
+                                ExecutorConfigs::get_cached()
+                                    .get_coding_agent_or_default(&profile_id)
+                                    .cmd_overrides(),
+                            )
+                        })
+                        .unwrap_or_default(),
                 ),
                 _ => Arc::new(NoopExecutorApprovalService {}),
             };
@@ -1086,11 +1323,25 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
 
+        let span = tracing::info_span!(
+            target: "vk::lifecycle",
+            "execution",
+            otel.name = execution_span_name(&execution_process.run_reason),
+            execution_process_id = %execution_process.id,
+            workspace_id = %workspace.id,
+            task_id = %task.id,
+            executor = executor_action.base_executor().map(field::display),
+            exit_code = field::Empty,
+            status = field::Empty,
+        );
+        telemetry::attach_to_attempt(&span, workspace.id);
+
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
             Duration::from_secs(30),
             executor_action.spawn(&current_dir, approvals_service, &env),
         )
+        .instrument(span.clone())
         .await
         .map_err(|_| {
             ContainerError::Other(anyhow!(
@@ -1110,8 +1361,18 @@ impl ContainerService for LocalContainerService {
                 .await;
         }
 
+        if telemetry::is_enabled()
+            && matches!(
+                execution_process.run_reason,
+                ExecutionProcessRunReason::CodingAgent
+            )
+            && let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await
+        {
+            spawn_tool_call_tracer(msg_store, span.clone());
+        }
+
         // Spawn unified exit monitor: watches OS exit and optional executor signal
-        let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal);
+        let _hn = self.spawn_exit_monitor(&execution_process.id, spawned.exit_signal, span);
 
         Ok(())
     }
@@ -1370,3 +1631,61 @@ fn success_exit_status() -> std::process::ExitStatus {
         ExitStatusExt::from_raw(0)
     }
 }
+
+fn execution_span_name(run_reason: &ExecutionProcessRunReason) -> &'static str {
+    match run_reason {
+        ExecutionProcessRunReason::SetupScript => "execution.setup_script",
+        ExecutionProcessRunReason::CodingAgent => "execution.agent_run",
+        ExecutionProcessRunReason::CleanupScript => "execution.cleanup_script",
+        ExecutionProcessRunReason::DevServer => "execution.dev_server",
+    }
+}
+
+/// Follow the normalized log of a coding agent run and emit one span per tool call, open
+/// from the first entry for the call until it reaches a final status
+fn spawn_tool_call_tracer(msg_store: Arc<MsgStore>, parent: tracing::Span) {
+    tokio::spawn(async move {
+        let mut open: HashMap<usize, tracing::Span> = HashMap::new();
+        let mut stream = msg_store.history_plus_stream();
+
+        while let Some(Ok(msg)) = stream.next().await {
+            let patch = match msg {
+                LogMsg::JsonPatch(patch) => patch,
+                LogMsg::Finished => break,
+                _ => continue,
+            };
+            let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) else {
+                continue;
+            };
+            let NormalizedEntryType::ToolUse {
+                tool_name, status, ..
+            } = entry.entry_type
+            else {
+                continue;
+            };
+
+            let span = open.entry(index).or_insert_with(|| {
+                tracing::info_span!(
+                    target: "vk::lifecycle",
+                    parent: &parent,
+                    "tool_call",
+                    otel.name = %format!("tool.{tool_name}"),
+                    tool_name = %tool_name,
+                    status = field::Empty,
+                )
+            });
+            match status {
+                ToolStatus::Created
+                | ToolStatus::PendingApproval { .. }
+                | ToolStatus::AutoApproved { .. } => {}
+                ToolStatus::Success
+                | ToolStatus::Failed
+                | ToolStatus::Denied { .. }
+                | ToolStatus::TimedOut => {
+                    span.record("status", field::debug(&status));
+                    open.remove(&index);
+                }
+            }
+        }
+    });
+}
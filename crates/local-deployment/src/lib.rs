@@ -8,19 +8,23 @@ use services::services::{
     analytics::{AnalyticsConfig, AnalyticsContext, AnalyticsService, generate_user_id},
     approvals::Approvals,
     auth::AuthContext,
+    backup::BackupService,
     config::{Config, load_config_from_file, save_config_to_file},
     container::ContainerService,
-    events::EventService,
+    events::{EventService, MutedTasks},
     file_search_cache::FileSearchCache,
     filesystem::FilesystemService,
     git::GitService,
     image::ImageService,
     oauth_credentials::OAuthCredentials,
+    plan_sync::PlanSyncService,
     project::ProjectService,
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
     share::{ShareConfig, SharePublisher},
+    task_schedule,
+    webhooks::WebhookService,
 };
 use tokio::sync::RwLock;
 use utils::{
@@ -55,6 +59,9 @@ pub struct LocalDeployment {
     share_config: Option<ShareConfig>,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
     auth_context: AuthContext,
+    webhooks: WebhookService,
+    plan_sync: PlanSyncService,
+    backups: BackupService,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
 }
 
@@ -103,6 +110,7 @@ impl Deployment for LocalDeployment {
         // Create shared components for EventService
         let events_msg_store = Arc::new(MsgStore::new());
         let events_entry_count = Arc::new(RwLock::new(0));
+        let events_muted_tasks = MutedTasks::default();
 
         // Create DB with event hooks
         let db = {
@@ -110,6 +118,7 @@ impl Deployment for LocalDeployment {
                 events_msg_store.clone(),
                 events_entry_count.clone(),
                 DBService::new().await?, // Temporary DB service for the hook
+                events_muted_tasks.clone(),
             );
             DBService::new_with_after_connect(hook).await?
         };
@@ -125,7 +134,12 @@ impl Deployment for LocalDeployment {
             });
         }
 
-        let approvals = Approvals::new(msg_stores.clone());
+        let approvals = Approvals::new(db.pool.clone(), config.clone(), msg_stores.clone());
+        match approvals.expire_stale().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Marked {} stale approval requests as timed out", count),
+            Err(e) => tracing::warn!("Failed to expire stale approval requests: {}", e),
+        }
         let queued_message_service = QueuedMessageService::new();
 
         let share_config = ShareConfig::from_env();
@@ -165,6 +179,7 @@ impl Deployment for LocalDeployment {
             .map_err(|e| *e);
 
         let oauth_handoffs = Arc::new(RwLock::new(HashMap::new()));
+        let webhooks = WebhookService::new(db.clone());
 
         // We need to make analytics accessible to the ContainerService
         // TODO: Handle this more gracefully
@@ -182,10 +197,22 @@ impl Deployment for LocalDeployment {
             approvals.clone(),
             queued_message_service.clone(),
             share_publisher.clone(),
+            webhooks.clone(),
         )
         .await;
+        task_schedule::spawn_scheduler(container.clone());
 
-        let events = EventService::new(db.clone(), events_msg_store, events_entry_count);
+        let events = EventService::new(
+            db.clone(),
+            events_msg_store,
+            events_entry_count,
+            events_muted_tasks,
+        );
+        webhooks.spawn_task_status_listener(events.msg_store().clone());
+        let plan_sync = PlanSyncService::new(db.clone());
+        plan_sync.spawn(events.msg_store().clone());
+        let backups = BackupService::new(db.clone(), config.clone());
+        backups.spawn();
 
         let file_search_cache = Arc::new(FileSearchCache::new());
 
@@ -208,6 +235,9 @@ impl Deployment for LocalDeployment {
             share_config: share_config.clone(),
             remote_client,
             auth_context,
+            webhooks,
+            plan_sync,
+            backups,
             oauth_handoffs,
         };
 
@@ -277,6 +307,18 @@ impl Deployment for LocalDeployment {
     fn auth_context(&self) -> &AuthContext {
         &self.auth_context
     }
+
+    fn webhooks(&self) -> &WebhookService {
+        &self.webhooks
+    }
+
+    fn plan_sync(&self) -> &PlanSyncService {
+        &self.plan_sync
+    }
+
+    fn backups(&self) -> &BackupService {
+        &self.backups
+    }
 }
 
 impl LocalDeployment {
@@ -0,0 +1,13 @@
+DO $$
+BEGIN
+    CREATE TYPE task_priority AS ENUM ('low', 'medium', 'high', 'urgent');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END
+$$;
+
+ALTER TABLE shared_tasks
+    ADD COLUMN IF NOT EXISTS priority         task_priority,
+    ADD COLUMN IF NOT EXISTS due_date         TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS estimate_minutes INTEGER,
+    ADD COLUMN IF NOT EXISTS labels           TEXT[] NOT NULL DEFAULT '{}';
@@ -25,6 +25,27 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, TS)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "task_priority", rename_all = "lowercase")]
+#[ts(export)]
+pub enum TaskPriority {
+    Low,
+    Medium,
+    High,
+    Urgent,
+}
+
+/// Planning fields mirrored from the local task
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SharedTaskPlanning {
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i32>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SharedTaskWithUser {
     pub task: SharedTask,
@@ -49,6 +70,10 @@ pub struct SharedTask {
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    pub priority: Option<TaskPriority>,
+    pub due_date: Option<DateTime<Utc>>,
+    pub estimate_minutes: Option<i32>,
+    pub labels: Vec<String>,
     pub deleted_at: Option<DateTime<Utc>>,
     pub shared_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -62,6 +87,7 @@ pub struct CreateSharedTaskData {
     pub description: Option<String>,
     pub creator_user_id: Uuid,
     pub assignee_user_id: Option<Uuid>,
+    pub planning: SharedTaskPlanning,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -69,6 +95,8 @@ pub struct UpdateSharedTaskData {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    /// Replaces all planning fields when present
+    pub planning: Option<SharedTaskPlanning>,
     pub acting_user_id: Uuid,
 }
 
@@ -126,6 +154,10 @@ impl<'a> SharedTaskRepository<'a> {
                 title               AS "title!",
                 description         AS "description?",
                 status              AS "status!: TaskStatus",
+                priority            AS "priority?: TaskPriority",
+                due_date            AS "due_date?",
+                estimate_minutes    AS "estimate_minutes?",
+                labels              AS "labels!",
                 deleted_at          AS "deleted_at?",
                 shared_at           AS "shared_at?",
                 created_at          AS "created_at!",
@@ -154,6 +186,7 @@ impl<'a> SharedTaskRepository<'a> {
             description,
             creator_user_id,
             assignee_user_id,
+            planning,
         } = data;
 
         ensure_text_size(&title, description.as_deref())?;
@@ -177,9 +210,13 @@ impl<'a> SharedTaskRepository<'a> {
                 assignee_user_id,
                 title,
                 description,
+                priority,
+                due_date,
+                estimate_minutes,
+                labels,
                 shared_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())
             RETURNING id                 AS "id!",
                       organization_id    AS "organization_id!: Uuid",
                       project_id         AS "project_id!",
@@ -189,6 +226,10 @@ impl<'a> SharedTaskRepository<'a> {
                       title              AS "title!",
                       description        AS "description?",
                       status             AS "status!: TaskStatus",
+                      priority           AS "priority?: TaskPriority",
+                      due_date           AS "due_date?",
+                      estimate_minutes   AS "estimate_minutes?",
+                      labels             AS "labels!",
                       deleted_at         AS "deleted_at?",
                       shared_at          AS "shared_at?",
                       created_at         AS "created_at!",
@@ -199,7 +240,11 @@ impl<'a> SharedTaskRepository<'a> {
             creator_user_id,
             assignee_user_id,
             title,
-            description
+            description,
+            planning.priority as Option<TaskPriority>,
+            planning.due_date,
+            planning.estimate_minutes,
+            &planning.labels
         )
         .fetch_one(&mut *tx)
         .await?;
@@ -219,6 +264,8 @@ impl<'a> SharedTaskRepository<'a> {
         data: UpdateSharedTaskData,
     ) -> Result<SharedTaskWithUser, SharedTaskError> {
         let mut tx = self.pool.begin().await.map_err(SharedTaskError::from)?;
+        let replace_planning = data.planning.is_some();
+        let planning = data.planning.unwrap_or_default();
 
         let task = sqlx::query_as!(
             SharedTask,
@@ -227,6 +274,10 @@ impl<'a> SharedTaskRepository<'a> {
         SET title       = COALESCE($2, t.title),
             description = COALESCE($3, t.description),
             status      = COALESCE($4, t.status),
+            priority    = CASE WHEN $6 THEN $7 ELSE t.priority END,
+            due_date    = CASE WHEN $6 THEN $8 ELSE t.due_date END,
+            estimate_minutes = CASE WHEN $6 THEN $9 ELSE t.estimate_minutes END,
+            labels      = CASE WHEN $6 THEN $10 ELSE t.labels END,
             updated_at  = NOW()
         WHERE t.id = $1
           AND t.assignee_user_id = $5
@@ -241,6 +292,10 @@ impl<'a> SharedTaskRepository<'a> {
             t.title             AS "title!",
             t.description       AS "description?",
             t.status            AS "status!: TaskStatus",
+            t.priority          AS "priority?: TaskPriority",
+            t.due_date          AS "due_date?",
+            t.estimate_minutes  AS "estimate_minutes?",
+            t.labels            AS "labels!",
             t.deleted_at        AS "deleted_at?",
             t.shared_at         AS "shared_at?",
             t.created_at        AS "created_at!",
@@ -250,7 +305,12 @@ impl<'a> SharedTaskRepository<'a> {
             data.title,
             data.description,
             data.status as Option<TaskStatus>,
-            data.acting_user_id
+            data.acting_user_id,
+            replace_planning,
+            planning.priority as Option<TaskPriority>,
+            planning.due_date,
+            planning.estimate_minutes,
+            &planning.labels
         )
         .fetch_optional(&mut *tx)
         .await?
@@ -292,6 +352,10 @@ impl<'a> SharedTaskRepository<'a> {
             t.title             AS "title!",
             t.description       AS "description?",
             t.status            AS "status!: TaskStatus",
+            t.priority          AS "priority?: TaskPriority",
+            t.due_date          AS "due_date?",
+            t.estimate_minutes  AS "estimate_minutes?",
+            t.labels            AS "labels!",
             t.deleted_at        AS "deleted_at?",
             t.shared_at         AS "shared_at?",
             t.created_at        AS "created_at!",
@@ -340,6 +404,10 @@ impl<'a> SharedTaskRepository<'a> {
             t.title             AS "title!",
             t.description       AS "description?",
             t.status            AS "status!: TaskStatus",
+            t.priority          AS "priority?: TaskPriority",
+            t.due_date          AS "due_date?",
+            t.estimate_minutes  AS "estimate_minutes?",
+            t.labels            AS "labels!",
             t.deleted_at        AS "deleted_at?",
             t.shared_at         AS "shared_at?",
             t.created_at        AS "created_at!",
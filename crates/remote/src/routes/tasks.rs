@@ -22,8 +22,8 @@ use crate::{
         organization_members,
         tasks::{
             AssignTaskData, CreateSharedTaskData, DeleteTaskData, SharedTask, SharedTaskError,
-            SharedTaskRepository, SharedTaskWithUser, TaskStatus, UpdateSharedTaskData,
-            ensure_text_size,
+            SharedTaskPlanning, SharedTaskRepository, SharedTaskWithUser, TaskStatus,
+            UpdateSharedTaskData, ensure_text_size,
         },
         users::{UserData, UserRepository},
     },
@@ -99,6 +99,7 @@ pub async fn create_shared_task(
         title,
         description,
         assignee_user_id,
+        planning,
     } = payload;
 
     if let Err(error) = ensure_text_size(&title, description.as_deref()) {
@@ -130,6 +131,7 @@ pub async fn create_shared_task(
         description,
         creator_user_id: ctx.user.id,
         assignee_user_id,
+        planning,
     };
 
     match repo.create(data).await {
@@ -180,6 +182,7 @@ pub async fn update_shared_task(
         title,
         description,
         status,
+        planning,
     } = payload;
 
     let next_title = title.as_deref().unwrap_or(existing.title.as_str());
@@ -193,6 +196,7 @@ pub async fn update_shared_task(
         title,
         description,
         status,
+        planning,
         acting_user_id: ctx.user.id,
     };
 
@@ -342,6 +346,8 @@ pub struct CreateSharedTaskRequest {
     pub title: String,
     pub description: Option<String>,
     pub assignee_user_id: Option<Uuid>,
+    #[serde(default)]
+    pub planning: SharedTaskPlanning,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -349,6 +355,9 @@ pub struct UpdateSharedTaskRequest {
     pub title: Option<String>,
     pub description: Option<String>,
     pub status: Option<TaskStatus>,
+    /// Omitted by older clients, in which case the planning fields are left unchanged
+    #[serde(default)]
+    pub planning: Option<SharedTaskPlanning>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
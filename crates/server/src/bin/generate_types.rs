@@ -30,18 +30,56 @@ fn generate_types_content() -> String {
         db::models::tag::Tag::decl(),
         db::models::tag::CreateTag::decl(),
         db::models::tag::UpdateTag::decl(),
+        db::models::label::Label::decl(),
+        db::models::label::CreateLabel::decl(),
+        db::models::label::UpdateLabel::decl(),
+        db::models::approval_policy::ApprovalPolicyAction::decl(),
+        db::models::approval_policy::ApprovalPatternKind::decl(),
+        db::models::approval_policy::ApprovalPolicyRule::decl(),
+        db::models::approval_policy::CreateApprovalPolicyRule::decl(),
+        db::models::approval_policy::UpdateApprovalPolicyRule::decl(),
+        db::models::approval_policy::ReorderApprovalPolicyRules::decl(),
+        db::models::approval::ApprovalOutcome::decl(),
+        db::models::approval::ApprovalDecisionSource::decl(),
+        db::models::approval::ApprovalRecord::decl(),
+        db::models::webhook::WebhookEventType::decl(),
+        db::models::webhook::WebhookSubscription::decl(),
+        db::models::webhook::CreateWebhookSubscription::decl(),
+        db::models::webhook::UpdateWebhookSubscription::decl(),
+        db::models::webhook::WebhookDeliveryStatus::decl(),
+        db::models::webhook::WebhookDelivery::decl(),
+        db::models::task_schedule::TaskScheduleAction::decl(),
+        db::models::task_schedule::TaskScheduleRunStatus::decl(),
+        db::models::task_schedule::TaskSchedule::decl(),
+        db::models::task_schedule::CreateTaskSchedule::decl(),
+        db::models::task_schedule::UpdateTaskSchedule::decl(),
+        db::models::task_schedule::TaskScheduleRun::decl(),
+        db::models::access_token::AccessToken::decl(),
+        db::models::access_token::CreateAccessToken::decl(),
         db::models::task::TaskStatus::decl(),
+        db::models::task::TaskPriority::decl(),
         db::models::task::Task::decl(),
         db::models::task::TaskWithAttemptStatus::decl(),
         db::models::task::TaskRelationships::decl(),
         db::models::task::CreateTask::decl(),
         db::models::task::UpdateTask::decl(),
+        db::models::task::TaskFieldsToClear::decl(),
+        db::models::task::TaskSortField::decl(),
+        db::models::task::SortOrder::decl(),
+        db::models::task::TaskFilter::decl(),
+        db::models::task_activity::TaskActivityKind::decl(),
+        db::models::task_activity::TaskActivityDetails::decl(),
+        db::models::task_activity::TaskActivity::decl(),
+        db::models::task_activity::CreateTaskComment::decl(),
+        db::models::task_activity::UpdateTaskComment::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::ScratchPayload::decl(),
         db::models::scratch::ScratchType::decl(),
         db::models::scratch::Scratch::decl(),
         db::models::scratch::CreateScratch::decl(),
         db::models::scratch::UpdateScratch::decl(),
+        db::models::search::SearchHitKind::decl(),
+        db::models::search::SearchHit::decl(),
         db::models::image::Image::decl(),
         db::models::image::CreateImage::decl(),
         db::models::workspace::Workspace::decl(),
@@ -56,8 +94,10 @@ fn generate_types_content() -> String {
         db::models::merge::MergeStatus::decl(),
         db::models::merge::PullRequestInfo::decl(),
         utils::approvals::ApprovalStatus::decl(),
+        utils::approvals::ApprovalRequest::decl(),
         utils::approvals::CreateApprovalRequest::decl(),
         utils::approvals::ApprovalResponse::decl(),
+        utils::approvals::ApprovalRuleRef::decl(),
         utils::diff::Diff::decl(),
         utils::diff::DiffChangeKind::decl(),
         utils::response::ApiResponse::<()>::decl(),
@@ -94,6 +134,19 @@ fn generate_types_content() -> String {
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
         server::routes::tags::TagSearchParams::decl(),
+        server::routes::labels::LabelQuery::decl(),
+        server::routes::search::SearchQuery::decl(),
+        server::routes::approvals::ApprovalHistoryQuery::decl(),
+        server::routes::approvals::ApprovalLinkQuery::decl(),
+        services::services::approvals::webhook::ApprovalLinkDecision::decl(),
+        services::services::approvals::webhook::ApprovalWebhookPayload::decl(),
+        server::routes::webhooks::WebhookDeliveriesQuery::decl(),
+        server::routes::task_schedules::TaskScheduleQuery::decl(),
+        server::routes::task_schedules::TaskScheduleRunsQuery::decl(),
+        services::services::webhooks::WebhookEnvelope::decl(),
+        server::routes::auth::AuthStatus::decl(),
+        server::routes::auth::LoginRequest::decl(),
+        server::routes::auth::CreatedAccessToken::decl(),
         server::routes::oauth::TokenResponse::decl(),
         server::routes::config::UserSystemInfo::decl(),
         server::routes::config::Environment::decl(),
@@ -119,13 +172,41 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::CreateGitHubPrRequest::decl(),
         server::routes::images::ImageResponse::decl(),
         server::routes::images::ImageMetadata::decl(),
-        server::routes::plans::PlanPhaseProgress::decl(),
-        server::routes::plans::PlanPhaseDetail::decl(),
-        server::routes::plans::PlanMetadata::decl(),
+        services::services::plans::PlanPhaseProgress::decl(),
+        services::services::plans::PlanPhaseDetail::decl(),
+        services::services::plans::PlanMetadata::decl(),
         server::routes::plans::PlanPhaseSelection::decl(),
         server::routes::plans::ImportPlansRequest::decl(),
         server::routes::plans::ImportPlansResponse::decl(),
         server::routes::plans::PlanFileContent::decl(),
+        services::services::github::GitHubIssue::decl(),
+        services::services::github::GitHubIssueLabel::decl(),
+        services::services::github::GitHubIssueMilestone::decl(),
+        db::models::task_github_issue::TaskGitHubIssue::decl(),
+        server::routes::github_issues::GitHubIssueQuery::decl(),
+        server::routes::github_issues::GitHubIssueCandidate::decl(),
+        server::routes::github_issues::ImportGitHubIssuesRequest::decl(),
+        server::routes::github_issues::ImportGitHubIssuesResponse::decl(),
+        services::services::project_archive::ProjectExportFormat::decl(),
+        services::services::project_archive::ProjectExportOptions::decl(),
+        services::services::project_archive::ProjectArchive::decl(),
+        services::services::project_archive::ArchivedProject::decl(),
+        services::services::project_archive::ArchivedRepo::decl(),
+        services::services::project_archive::ArchivedTag::decl(),
+        services::services::project_archive::ArchivedTask::decl(),
+        services::services::project_archive::ArchivedAttempt::decl(),
+        services::services::project_archive::ArchivedAttemptRepo::decl(),
+        services::services::project_archive::ArchivedSession::decl(),
+        services::services::project_archive::ArchivedExecutionProcess::decl(),
+        services::services::project_archive::ProjectImportSummary::decl(),
+        server::routes::projects::ImportProjectRequest::decl(),
+        server::routes::projects::ImportProjectResponse::decl(),
+        services::services::backup::BackupInfo::decl(),
+        services::services::archival::PurgeArchivedResult::decl(),
+        server::routes::archive::PurgeArchivedRequest::decl(),
+        services::services::task_bulk::BulkTaskOperation::decl(),
+        services::services::task_bulk::BulkTaskRequest::decl(),
+        services::services::task_bulk::BulkTaskResult::decl(),
         server::routes::task_attempts::CreateTaskAttemptBody::decl(),
         server::routes::task_attempts::WorkspaceRepoInput::decl(),
         server::routes::task_attempts::RunAgentSetupRequest::decl(),
@@ -145,8 +226,11 @@ fn generate_types_content() -> String {
         server::routes::task_attempts::pr::GetPrCommentsQuery::decl(),
         services::services::github::UnifiedPrComment::decl(),
         server::routes::task_attempts::RepoBranchStatus::decl(),
+        server::routes::task_attempts::RepoDiff::decl(),
         services::services::filesystem::DirectoryEntry::decl(),
         services::services::filesystem::DirectoryListResponse::decl(),
+        services::services::transcript::TranscriptFormat::decl(),
+        services::services::transcript::TranscriptOptions::decl(),
         services::services::config::Config::decl(),
         services::services::config::NotificationConfig::decl(),
         services::services::config::ThemeMode::decl(),
@@ -157,6 +241,13 @@ fn generate_types_content() -> String {
         services::services::config::SoundFile::decl(),
         services::services::config::UiLanguage::decl(),
         services::services::config::ShowcaseState::decl(),
+        services::services::config::LogRetentionConfig::decl(),
+        services::services::config::BackupConfig::decl(),
+        services::services::config::ApprovalWebhookConfig::decl(),
+        services::services::config::NotificationEvent::decl(),
+        services::services::config::SmtpSecurity::decl(),
+        services::services::config::NotificationChannelKind::decl(),
+        services::services::config::NotificationChannelConfig::decl(),
         services::services::git::GitBranch::decl(),
         services::services::share::SharedTaskDetails::decl(),
         services::services::queued_message::QueuedMessage::decl(),
@@ -176,6 +267,7 @@ fn generate_types_content() -> String {
         executors::profile::ExecutorConfig::decl(),
         executors::profile::ExecutorConfigs::decl(),
         executors::executors::BaseAgentCapability::decl(),
+        executors::approvals::ApprovalTimeoutAction::decl(),
         executors::executors::claude::ClaudeCode::decl(),
         executors::executors::gemini::Gemini::decl(),
         executors::executors::amp::Amp::decl(),
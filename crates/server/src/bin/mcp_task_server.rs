@@ -52,7 +52,14 @@ fn main() -> anyhow::Result<()> {
                 url
             };
 
-            let service = TaskServer::new(&base_url)
+            let access_token = std::env::var("VK_ACCESS_TOKEN")
+                .ok()
+                .filter(|token| !token.trim().is_empty());
+            if access_token.is_some() {
+                tracing::info!("[MCP] Authenticating with VK_ACCESS_TOKEN");
+            }
+
+            let service = TaskServer::new(&base_url, access_token.as_deref())
                 .init()
                 .await
                 .serve(stdio())
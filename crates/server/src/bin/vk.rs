@@ -0,0 +1,856 @@
+//! `vk`: command-line client for a running Vibe Kanban server.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, anyhow, bail};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use db::models::{
+    approval::{ApprovalOutcome, ApprovalRecord},
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    project::{CreateProject, Project},
+    project_repo::CreateProjectRepo,
+    task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus},
+    workspace::Workspace,
+};
+use executors::{
+    executors::BaseCodingAgent,
+    logs::{
+        NormalizedEntry, NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch,
+    },
+    profile::ExecutorProfileId,
+};
+use futures_util::StreamExt;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use server::routes::task_attempts::{
+    CreateTaskAttemptBody, MergeTaskAttemptRequest, WorkspaceRepoInput, pr::CreateGitHubPrRequest,
+};
+use services::services::backup::{BackupInfo, RestoreOptions, restore_backup};
+use tokio_tungstenite::tungstenite::{Message, client::IntoClientRequest, http::HeaderValue};
+use utils::{
+    approvals::{ApprovalResponse, ApprovalStatus},
+    log_msg::LogMsg,
+    port_file::read_port_file,
+    response::ApiResponse,
+};
+use uuid::Uuid;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "vk",
+    about = "Command-line client for a running Vibe Kanban server"
+)]
+#[command(version)]
+struct Cli {
+    /// Server URL; discovered from the port file when omitted
+    #[arg(long, global = true, env = "VIBE_BACKEND_URL")]
+    url: Option<String>,
+
+    /// Access token for servers running in remote access mode
+    #[arg(long, global = true, env = "VK_ACCESS_TOKEN", hide_env_values = true)]
+    token: Option<String>,
+
+    #[arg(long, short, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List and create projects
+    #[command(subcommand)]
+    Projects(ProjectsCommand),
+    /// List and create tasks
+    #[command(subcommand)]
+    Tasks(TasksCommand),
+    /// List and start task attempts
+    #[command(subcommand)]
+    Attempts(AttemptsCommand),
+    /// Follow the normalized log of an execution process
+    Logs(LogsArgs),
+    /// List and answer tool approvals
+    #[command(subcommand)]
+    Approvals(ApprovalsCommand),
+    /// Open a GitHub pull request for an attempt
+    Pr(PrArgs),
+    /// Merge an attempt's branch into its target branch
+    Merge(MergeArgs),
+    /// List, take and restore local backups
+    #[command(subcommand)]
+    Backups(BackupsCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum ProjectsCommand {
+    List,
+    Create {
+        name: String,
+        /// Path of a git repository to add; repeat for several
+        #[arg(long = "repo", required = true)]
+        repos: Vec<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum TasksCommand {
+    List {
+        #[arg(long)]
+        project: Uuid,
+        #[arg(long, value_parser = TaskStatus::from_str)]
+        status: Option<TaskStatus>,
+    },
+    Create {
+        #[arg(long)]
+        project: Uuid,
+        title: String,
+        #[arg(long)]
+        description: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum AttemptsCommand {
+    List {
+        #[arg(long)]
+        task: Uuid,
+    },
+    Start {
+        #[arg(long)]
+        task: Uuid,
+        /// Coding agent, e.g. CLAUDE_CODE or codex
+        #[arg(long)]
+        executor: String,
+        /// Profile variant of the executor, e.g. PLAN
+        #[arg(long)]
+        variant: Option<String>,
+        /// Repository and target branch as REPO_ID=BRANCH; repeat for several
+        #[arg(long = "repo", required = true, value_parser = parse_repo_input)]
+        repos: Vec<WorkspaceRepoInput>,
+        /// Prompt to use instead of the task description
+        #[arg(long)]
+        prompt: Option<String>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct LogsArgs {
+    /// Execution process to follow
+    #[arg(required_unless_present = "attempt", conflicts_with = "attempt")]
+    execution_process: Option<Uuid>,
+    /// Follow the latest coding agent run of this attempt instead
+    #[arg(long)]
+    attempt: Option<Uuid>,
+}
+
+#[derive(Subcommand, Debug)]
+enum ApprovalsCommand {
+    List {
+        #[arg(long)]
+        task: Uuid,
+        /// Only show approvals still waiting for an answer
+        #[arg(long)]
+        pending: bool,
+    },
+    Approve {
+        id: String,
+        #[arg(long)]
+        task: Uuid,
+    },
+    Deny {
+        id: String,
+        #[arg(long)]
+        task: Uuid,
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BackupsCommand {
+    List,
+    Create,
+    /// Replace the local database and settings with a backup; the server must be stopped
+    Restore {
+        archive: PathBuf,
+        /// Also overwrite the coding agents' MCP config files
+        #[arg(long)]
+        mcp_configs: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+struct PrArgs {
+    attempt: Uuid,
+    #[arg(long)]
+    repo: Uuid,
+    #[arg(long)]
+    title: String,
+    #[arg(long)]
+    body: Option<String>,
+    /// Base branch; defaults to the attempt's target branch
+    #[arg(long)]
+    base: Option<String>,
+    #[arg(long)]
+    draft: bool,
+}
+
+#[derive(Args, Debug)]
+struct MergeArgs {
+    attempt: Uuid,
+    #[arg(long)]
+    repo: Uuid,
+}
+
+fn parse_repo_input(value: &str) -> Result<WorkspaceRepoInput, String> {
+    let (repo_id, branch) = value
+        .split_once('=')
+        .ok_or_else(|| "expected REPO_ID=BRANCH".to_string())?;
+    Ok(WorkspaceRepoInput {
+        repo_id: Uuid::parse_str(repo_id.trim()).map_err(|e| e.to_string())?,
+        target_branch: branch.trim().to_string(),
+    })
+}
+
+fn parse_executor(executor: &str, variant: Option<String>) -> anyhow::Result<ExecutorProfileId> {
+    let normalized = executor.trim().replace('-', "_").to_ascii_uppercase();
+    let executor = BaseCodingAgent::from_str(&normalized)
+        .map_err(|_| anyhow!("Unknown executor '{}'", executor.trim()))?;
+    Ok(ExecutorProfileId {
+        executor,
+        variant: variant.filter(|v| !v.trim().is_empty()),
+    })
+}
+
+struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl Client {
+    async fn connect(url: Option<String>, token: Option<String>) -> anyhow::Result<Self> {
+        let base_url = match url {
+            Some(url) => url,
+            None => {
+                let port = read_port_file("vibe-kanban")
+                    .await
+                    .context("No running server found; start vibe-kanban or pass --url")?;
+                format!("http://127.0.0.1:{port}")
+            }
+        };
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.filter(|t| !t.trim().is_empty()),
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path));
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> anyhow::Result<T> {
+        let response = request.send().await.context("Failed to reach server")?;
+        let status = response.status();
+        let body: ApiResponse<T, Value> = response
+            .json()
+            .await
+            .with_context(|| format!("Unexpected response from server ({status})"))?;
+        if !body.is_success() {
+            let detail = body
+                .message()
+                .map(str::to_string)
+                .or_else(|| body.error_data().map(Value::to_string))
+                .unwrap_or_else(|| status.to_string());
+            bail!("{detail}");
+        }
+        // Endpoints returning `()` send `data: null`
+        match body.into_data() {
+            Some(data) => Ok(data),
+            None => serde_json::from_value(Value::Null)
+                .map_err(|_| anyhow!("Server response is missing data")),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        self.send(self.request(reqwest::Method::GET, path)).await
+    }
+
+    async fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> anyhow::Result<T> {
+        self.send(self.request(reqwest::Method::POST, path).json(body))
+            .await
+    }
+
+    /// Open a websocket and yield its text frames
+    async fn websocket(
+        &self,
+        path: &str,
+    ) -> anyhow::Result<impl futures_util::Stream<Item = anyhow::Result<String>>> {
+        let ws_base = self
+            .base_url
+            .replacen("https://", "wss://", 1)
+            .replacen("http://", "ws://", 1);
+        let mut request = format!("{ws_base}{path}").into_client_request()?;
+        if let Some(token) = &self.token {
+            request.headers_mut().insert(
+                "Authorization",
+                HeaderValue::from_str(&format!("Bearer {token}"))?,
+            );
+        }
+        let (socket, _) = tokio_tungstenite::connect_async(request)
+            .await
+            .context("Failed to open log stream")?;
+        Ok(socket.filter_map(|message| async move {
+            match message {
+                Ok(Message::Text(text)) => Some(Ok(text.to_string())),
+                Ok(_) => None,
+                Err(e) => Some(Err(e.into())),
+            }
+        }))
+    }
+}
+
+/// Print `rows` as JSON, or as a table built by `columns`
+fn print_rows<T: Serialize>(
+    output: OutputFormat,
+    rows: &[T],
+    headers: &[&str],
+    columns: impl Fn(&T) -> Vec<String>,
+) -> anyhow::Result<()> {
+    println!("{}", format_rows(output, rows, headers, columns)?);
+    Ok(())
+}
+
+fn format_rows<T: Serialize>(
+    output: OutputFormat,
+    rows: &[T],
+    headers: &[&str],
+    columns: impl Fn(&T) -> Vec<String>,
+) -> anyhow::Result<String> {
+    Ok(match output {
+        OutputFormat::Json => serde_json::to_string_pretty(rows)?,
+        OutputFormat::Table => {
+            let cells: Vec<Vec<String>> = rows.iter().map(columns).collect();
+            let mut widths: Vec<usize> = headers.iter().map(|h| h.len()).collect();
+            for row in &cells {
+                for (width, cell) in widths.iter_mut().zip(row) {
+                    *width = (*width).max(cell.chars().count());
+                }
+            }
+            let line = |row: Vec<String>| {
+                row.iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{cell:<width$}"))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            };
+            std::iter::once(line(headers.iter().map(|h| h.to_string()).collect()))
+                .chain(cells.into_iter().map(&line))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+    })
+}
+
+fn print_one<T: Serialize>(output: OutputFormat, value: &T, summary: String) -> anyhow::Result<()> {
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Table => println!("{summary}"),
+    }
+    Ok(())
+}
+
+async fn run(cli: Cli) -> anyhow::Result<()> {
+    // Restoring works on the files directly, so it must not need (or race) a server
+    if let Command::Backups(BackupsCommand::Restore {
+        archive,
+        mcp_configs,
+    }) = &cli.command
+    {
+        return restore(cli.output, archive, *mcp_configs).await;
+    }
+
+    let client = Client::connect(cli.url, cli.token).await?;
+    let output = cli.output;
+
+    match cli.command {
+        Command::Projects(ProjectsCommand::List) => {
+            let projects: Vec<Project> = client.get("/api/projects").await?;
+            print_rows(output, &projects, &["ID", "NAME", "CREATED"], |p| {
+                vec![
+                    p.id.to_string(),
+                    p.name.clone(),
+                    p.created_at.format("%Y-%m-%d").to_string(),
+                ]
+            })
+        }
+        Command::Projects(ProjectsCommand::Create { name, repos }) => {
+            let repositories = repos
+                .into_iter()
+                .map(|path| {
+                    let path = std::fs::canonicalize(&path)
+                        .with_context(|| format!("Repository not found: {}", path.display()))?;
+                    Ok(CreateProjectRepo {
+                        display_name: path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().to_string())
+                            .unwrap_or_else(|| path.display().to_string()),
+                        git_repo_path: path.display().to_string(),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let project: Project = client
+                .post("/api/projects", &CreateProject { name, repositories })
+                .await?;
+            print_one(output, &project, format!("Created project {}", project.id))
+        }
+        Command::Tasks(TasksCommand::List { project, status }) => {
+            let mut tasks: Vec<TaskWithAttemptStatus> = client
+                .get(&format!("/api/tasks?project_id={project}"))
+                .await?;
+            if let Some(status) = status {
+                tasks.retain(|t| t.status == status);
+            }
+            print_rows(output, &tasks, &["ID", "STATUS", "ATTEMPT", "TITLE"], |t| {
+                let attempt = if t.has_in_progress_attempt {
+                    "running"
+                } else if t.last_attempt_failed {
+                    "failed"
+                } else {
+                    ""
+                };
+                vec![
+                    t.id.to_string(),
+                    t.status.to_string(),
+                    attempt.to_string(),
+                    t.title.clone(),
+                ]
+            })
+        }
+        Command::Tasks(TasksCommand::Create {
+            project,
+            title,
+            description,
+        }) => {
+            let task: Task = client
+                .post(
+                    "/api/tasks",
+                    &CreateTask::from_title_description(project, title, description),
+                )
+                .await?;
+            print_one(output, &task, format!("Created task {}", task.id))
+        }
+        Command::Attempts(AttemptsCommand::List { task }) => {
+            let attempts: Vec<Workspace> = client
+                .get(&format!("/api/task-attempts?task_id={task}"))
+                .await?;
+            print_rows(output, &attempts, &["ID", "BRANCH", "CREATED"], |w| {
+                vec![
+                    w.id.to_string(),
+                    w.branch.clone(),
+                    w.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+        }
+        Command::Attempts(AttemptsCommand::Start {
+            task,
+            executor,
+            variant,
+            repos,
+            prompt,
+        }) => {
+            let body = CreateTaskAttemptBody {
+                task_id: task,
+                executor_profile_id: parse_executor(&executor, variant)?,
+                repos,
+                custom_prompt: prompt,
+            };
+            let workspace: Workspace = client.post("/api/task-attempts", &body).await?;
+            print_one(
+                output,
+                &workspace,
+                format!(
+                    "Started attempt {} on branch {}",
+                    workspace.id, workspace.branch
+                ),
+            )
+        }
+        Command::Logs(args) => {
+            let exec_id = match (args.execution_process, args.attempt) {
+                (Some(id), _) => id,
+                (None, Some(attempt)) => latest_agent_run(&client, attempt).await?,
+                (None, None) => unreachable!("clap requires one of them"),
+            };
+            follow_logs(&client, exec_id, output).await
+        }
+        Command::Approvals(ApprovalsCommand::List { task, pending }) => {
+            let mut approvals: Vec<ApprovalRecord> = client
+                .get(&format!("/api/approvals?task_id={task}"))
+                .await?;
+            if pending {
+                approvals.retain(|a| a.status == ApprovalOutcome::Pending);
+            }
+            print_rows(
+                output,
+                &approvals,
+                &["ID", "STATUS", "TOOL", "REQUESTED"],
+                |a| {
+                    vec![
+                        a.id.clone(),
+                        a.status.to_string(),
+                        a.tool_name.clone(),
+                        a.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    ]
+                },
+            )
+        }
+        Command::Approvals(ApprovalsCommand::Approve { id, task }) => {
+            respond(&client, output, &id, task, ApprovalStatus::Approved).await
+        }
+        Command::Approvals(ApprovalsCommand::Deny { id, task, reason }) => {
+            respond(
+                &client,
+                output,
+                &id,
+                task,
+                ApprovalStatus::Denied { reason },
+            )
+            .await
+        }
+        Command::Pr(args) => {
+            let body = CreateGitHubPrRequest {
+                title: args.title,
+                body: args.body,
+                target_branch: args.base,
+                draft: Some(args.draft),
+                repo_id: args.repo,
+                auto_generate_description: false,
+            };
+            let url: String = client
+                .post(&format!("/api/task-attempts/{}/pr", args.attempt), &body)
+                .await?;
+            print_one(output, &url, format!("Opened {url}"))
+        }
+        Command::Merge(args) => {
+            let _: () = client
+                .post(
+                    &format!("/api/task-attempts/{}/merge", args.attempt),
+                    &MergeTaskAttemptRequest { repo_id: args.repo },
+                )
+                .await?;
+            print_one(
+                output,
+                &args.attempt,
+                format!("Merged attempt {}", args.attempt),
+            )
+        }
+        Command::Backups(BackupsCommand::List) => {
+            let backups: Vec<BackupInfo> = client.get("/api/backups").await?;
+            print_rows(output, &backups, &["FILE", "SIZE", "CREATED"], |b| {
+                vec![
+                    b.path.clone(),
+                    format!("{:.1} MB", b.size_bytes as f64 / 1_000_000.0),
+                    b.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                ]
+            })
+        }
+        Command::Backups(BackupsCommand::Create) => {
+            let backup: BackupInfo = client.post("/api/backups", &()).await?;
+            print_one(output, &backup, format!("Wrote backup {}", backup.path))
+        }
+        Command::Backups(BackupsCommand::Restore { .. }) => unreachable!("handled above"),
+    }
+}
+
+async fn restore(output: OutputFormat, archive: &Path, mcp_configs: bool) -> anyhow::Result<()> {
+    if let Ok(port) = read_port_file("vibe-kanban").await
+        && tokio::net::TcpStream::connect(("127.0.0.1", port))
+            .await
+            .is_ok()
+    {
+        bail!("Vibe Kanban is running on port {port}; stop the server before restoring a backup");
+    }
+
+    let summary = restore_backup(
+        archive,
+        &RestoreOptions {
+            include_mcp_configs: mcp_configs,
+        },
+    )
+    .await
+    .with_context(|| format!("Failed to restore {}", archive.display()))?;
+    print_one(
+        output,
+        &summary,
+        format!(
+            "Restored {} items from the backup taken {} (v{}); replaced files were moved to {}",
+            summary.restored.len(),
+            summary.manifest.created_at.format("%Y-%m-%d %H:%M"),
+            summary.manifest.app_version,
+            summary.previous_files_dir.display()
+        ),
+    )
+}
+
+async fn respond(
+    client: &Client,
+    output: OutputFormat,
+    id: &str,
+    task: Uuid,
+    status: ApprovalStatus,
+) -> anyhow::Result<()> {
+    let approvals: Vec<ApprovalRecord> = client
+        .get(&format!("/api/approvals?task_id={task}"))
+        .await?;
+    let approval = approvals
+        .into_iter()
+        .find(|a| a.id == id)
+        .ok_or_else(|| anyhow!("Approval {id} not found for task {task}"))?;
+
+    // This endpoint answers with the bare status rather than the usual envelope
+    let response = client
+        .request(
+            reqwest::Method::POST,
+            &format!("/api/approvals/{id}/respond"),
+        )
+        .json(&ApprovalResponse {
+            execution_process_id: approval.execution_process_id,
+            status,
+        })
+        .send()
+        .await
+        .context("Failed to reach server")?;
+    if !response.status().is_success() {
+        bail!("Failed to answer approval: {}", response.status());
+    }
+    let status: ApprovalStatus = response.json().await?;
+    print_one(output, &status, format!("Approval {id}: {status:?}"))
+}
+
+/// The most recent coding agent execution of an attempt, taken from the snapshot the
+/// execution process stream starts with
+async fn latest_agent_run(client: &Client, attempt: Uuid) -> anyhow::Result<Uuid> {
+    let mut stream = client
+        .websocket(&format!(
+            "/api/execution-processes/stream/ws?workspace_id={attempt}"
+        ))
+        .await?;
+    let first = stream
+        .next()
+        .await
+        .ok_or_else(|| anyhow!("Execution process stream closed"))??;
+    let LogMsg::JsonPatch(patch) = serde_json::from_str::<LogMsg>(&first)? else {
+        bail!("Unexpected message on execution process stream");
+    };
+    let snapshot = serde_json::to_value(&patch)?;
+    let processes: HashMap<String, ExecutionProcess> =
+        serde_json::from_value(snapshot.pointer("/0/value").cloned().unwrap_or(Value::Null))
+            .context("Unexpected execution process snapshot")?;
+
+    processes
+        .into_values()
+        .filter(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+        .max_by_key(|p| p.created_at)
+        .map(|p| p.id)
+        .ok_or_else(|| anyhow!("Attempt {attempt} has no coding agent runs"))
+}
+
+async fn follow_logs(client: &Client, exec_id: Uuid, output: OutputFormat) -> anyhow::Result<()> {
+    let mut stream = client
+        .websocket(&format!(
+            "/api/execution-processes/{exec_id}/normalized-logs/ws"
+        ))
+        .await?;
+    // Entries are patched in place as they progress; print each rendering once
+    let mut printed: HashMap<usize, String> = HashMap::new();
+
+    while let Some(text) = stream.next().await {
+        let text = text?;
+        if serde_json::from_str::<Value>(&text)
+            .is_ok_and(|v| v.get("finished") == Some(&Value::Bool(true)))
+        {
+            break;
+        }
+        let Ok(LogMsg::JsonPatch(patch)) = serde_json::from_str::<LogMsg>(&text) else {
+            continue;
+        };
+        let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) else {
+            continue;
+        };
+        let rendered = match output {
+            OutputFormat::Json => serde_json::to_string(&entry)?,
+            OutputFormat::Table => render_entry(&entry),
+        };
+        if !rendered.is_empty() && printed.get(&index) != Some(&rendered) {
+            println!("{rendered}");
+            printed.insert(index, rendered);
+        }
+    }
+    Ok(())
+}
+
+fn render_entry(entry: &NormalizedEntry) -> String {
+    let content = entry.content.trim();
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => format!("> {content}"),
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            format!("[feedback on {denied_tool}] {content}")
+        }
+        NormalizedEntryType::AssistantMessage => content.to_string(),
+        NormalizedEntryType::ToolUse {
+            tool_name, status, ..
+        } => format!("[{tool_name}] {content} ({status:?})"),
+        NormalizedEntryType::SystemMessage => format!("[system] {content}"),
+        NormalizedEntryType::ErrorMessage { .. } => format!("[error] {content}"),
+        NormalizedEntryType::Thinking => format!("[thinking] {content}"),
+        NormalizedEntryType::Loading => String::new(),
+        NormalizedEntryType::NextAction { failed, .. } => {
+            if *failed {
+                "[finished with errors]".to_string()
+            } else {
+                "[finished]".to_string()
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli).await {
+        eprintln!("error: {e:#}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_attempts_start() {
+        let repo = Uuid::new_v4();
+        let task = Uuid::new_v4();
+        let cli = Cli::try_parse_from([
+            "vk",
+            "attempts",
+            "start",
+            "--task",
+            &task.to_string(),
+            "--executor",
+            "claude-code",
+            "--repo",
+            &format!("{repo}=main"),
+            "-o",
+            "json",
+        ])
+        .unwrap();
+        assert_eq!(cli.output, OutputFormat::Json);
+        let Command::Attempts(AttemptsCommand::Start {
+            task: parsed_task,
+            executor,
+            repos,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected attempts start");
+        };
+        assert_eq!(parsed_task, task);
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].repo_id, repo);
+        assert_eq!(repos[0].target_branch, "main");
+        assert_eq!(
+            parse_executor(&executor, None).unwrap().executor,
+            BaseCodingAgent::ClaudeCode
+        );
+        assert!(parse_executor("nope", None).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_arguments() {
+        // Repos need REPO_ID=BRANCH
+        assert!(
+            Cli::try_parse_from([
+                "vk",
+                "attempts",
+                "start",
+                "--task",
+                &Uuid::new_v4().to_string(),
+                "--executor",
+                "codex",
+                "--repo",
+                "main",
+            ])
+            .is_err()
+        );
+        // Logs takes either an execution process or --attempt, not both
+        let id = Uuid::new_v4().to_string();
+        assert!(Cli::try_parse_from(["vk", "logs"]).is_err());
+        assert!(Cli::try_parse_from(["vk", "logs", &id, "--attempt", &id]).is_err());
+        assert!(Cli::try_parse_from(["vk", "logs", "--attempt", &id]).is_ok());
+        // Output defaults to a table
+        let cli = Cli::try_parse_from(["vk", "projects", "list"]).unwrap();
+        assert_eq!(cli.output, OutputFormat::Table);
+    }
+
+    #[test]
+    fn test_format_rows() {
+        let rows = vec![("a".to_string(), 1), ("longer".to_string(), 22)];
+        let columns = |r: &(String, i32)| vec![r.0.clone(), r.1.to_string()];
+
+        let table = format_rows(OutputFormat::Table, &rows, &["NAME", "N"], columns).unwrap();
+        assert_eq!(table, "NAME    N\na       1\nlonger  22");
+
+        let json = format_rows(OutputFormat::Json, &rows, &["NAME", "N"], columns).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&json).unwrap(),
+            serde_json::json!([["a", 1], ["longer", 22]])
+        );
+    }
+
+    #[test]
+    fn test_render_entry() {
+        let entry = |entry_type, content: &str| NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        };
+        assert_eq!(
+            render_entry(&entry(NormalizedEntryType::UserMessage, " fix it \n")),
+            "> fix it"
+        );
+        assert_eq!(
+            render_entry(&entry(NormalizedEntryType::Thinking, "hmm")),
+            "[thinking] hmm"
+        );
+        assert_eq!(render_entry(&entry(NormalizedEntryType::Loading, "")), "");
+    }
+}
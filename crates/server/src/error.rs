@@ -13,15 +13,22 @@ use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::executors::ExecutorError;
 use git2::Error as Git2Error;
 use services::services::{
+    approvals::ApprovalError,
+    archival::ArchivalError,
+    backup::BackupError,
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
     git::GitServiceError,
     github::GitHubServiceError,
     image::ImageError,
     project::ProjectServiceError,
+    project_archive::ProjectArchiveError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
     share::ShareError,
+    task_bulk::TaskBulkError,
+    task_schedule::TaskScheduleError,
+    webhooks::WebhookError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
@@ -260,6 +267,106 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl From<ApprovalError> for ApiError {
+    fn from(err: ApprovalError) -> Self {
+        match err {
+            ApprovalError::Sqlx(db_err) => ApiError::Database(db_err),
+            ApprovalError::InvalidSignature => {
+                ApiError::Forbidden("Invalid approval link".to_string())
+            }
+            ApprovalError::AlreadyCompleted => {
+                ApiError::Conflict("Approval has already been answered".to_string())
+            }
+            ApprovalError::NotFound => {
+                ApiError::Conflict("Approval not found or no longer pending".to_string())
+            }
+            other => {
+                tracing::error!(?other, "approval response failed");
+                ApiError::Conflict("Failed to respond to approval".to_string())
+            }
+        }
+    }
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(err: WebhookError) -> Self {
+        match err {
+            WebhookError::Database(db_err) => ApiError::Database(db_err),
+            WebhookError::DeliveryNotFound => {
+                ApiError::BadRequest("Webhook delivery not found".to_string())
+            }
+            WebhookError::SubscriptionNotFound => {
+                ApiError::BadRequest("Webhook for this delivery no longer exists".to_string())
+            }
+        }
+    }
+}
+
+impl From<ProjectArchiveError> for ApiError {
+    fn from(err: ProjectArchiveError) -> Self {
+        match err {
+            ProjectArchiveError::Database(db_err) => ApiError::Database(db_err),
+            ProjectArchiveError::ProjectRepo(repo_err) => ApiError::from(repo_err),
+            ProjectArchiveError::Workspace(workspace_err) => ApiError::Workspace(workspace_err),
+            ProjectArchiveError::Session(session_err) => ApiError::Session(session_err),
+            ProjectArchiveError::Container(container_err) => ApiError::Container(container_err),
+            ProjectArchiveError::Serde(serde_err) => {
+                ApiError::BadRequest(format!("Invalid project archive: {}", serde_err))
+            }
+            e @ (ProjectArchiveError::UnsupportedVersion(_)
+            | ProjectArchiveError::MissingRepository(_)) => ApiError::BadRequest(e.to_string()),
+        }
+    }
+}
+
+impl From<ArchivalError> for ApiError {
+    fn from(err: ArchivalError) -> Self {
+        match err {
+            ArchivalError::Database(db_err) => ApiError::Database(db_err),
+            ArchivalError::Workspace(workspace_err) => ApiError::Workspace(workspace_err),
+            ArchivalError::Container(container_err) => ApiError::Container(container_err),
+            e @ ArchivalError::RunningProcesses(_) => ApiError::Conflict(e.to_string()),
+        }
+    }
+}
+
+impl From<TaskBulkError> for ApiError {
+    fn from(err: TaskBulkError) -> Self {
+        match err {
+            TaskBulkError::Database(db_err) => ApiError::Database(db_err),
+            TaskBulkError::Workspace(workspace_err) => ApiError::Workspace(workspace_err),
+            TaskBulkError::Container(container_err) => ApiError::Container(container_err),
+            e @ TaskBulkError::Invalid(_) => ApiError::BadRequest(e.to_string()),
+        }
+    }
+}
+
+impl From<BackupError> for ApiError {
+    fn from(err: BackupError) -> Self {
+        match err {
+            BackupError::Io(io_err) => ApiError::Io(io_err),
+            BackupError::Database(db_err) => ApiError::Database(db_err),
+            BackupError::Join(join_err) => ApiError::Io(std::io::Error::other(join_err)),
+            e @ (BackupError::Json(_)
+            | BackupError::InvalidBackup(_)
+            | BackupError::NewerSchema { .. }) => ApiError::BadRequest(e.to_string()),
+        }
+    }
+}
+
+impl From<TaskScheduleError> for ApiError {
+    fn from(err: TaskScheduleError) -> Self {
+        match err {
+            TaskScheduleError::Database(db_err) => ApiError::Database(db_err),
+            TaskScheduleError::Workspace(workspace_err) => ApiError::Workspace(workspace_err),
+            TaskScheduleError::Container(container_err) => ApiError::Container(container_err),
+            e @ (TaskScheduleError::InvalidCron(..)
+            | TaskScheduleError::InvalidTimezone(_)
+            | TaskScheduleError::Invalid(_)) => ApiError::BadRequest(e.to_string()),
+        }
+    }
+}
+
 impl From<ShareError> for ApiError {
     fn from(err: ShareError) -> Self {
         match err {
@@ -1,6 +1,11 @@
 use anyhow::{self, Error as AnyhowError};
+use axum_server::tls_rustls::RustlsConfig;
 use deployment::{Deployment, DeploymentError};
-use server::{DeploymentImpl, routes};
+use server::{
+    DeploymentImpl,
+    middleware::auth::{AuthSettings, ensure_bootstrap_token},
+    routes,
+};
 use services::services::container::ContainerService;
 use sqlx::Error as SqlxError;
 use strip_ansi_escapes::strip;
@@ -11,6 +16,7 @@ use utils::{
     browser::open_browser,
     port_file::write_port_file,
     sentry::{self as sentry_utils, SentrySource, sentry_layer},
+    telemetry::{self, TelemetryConfig},
 };
 
 #[derive(Debug, Error)]
@@ -35,9 +41,13 @@ async fn main() -> Result<(), VibeKanbanError> {
         level = log_level
     );
     let env_filter = EnvFilter::try_new(filter_string).expect("Failed to create tracing filter");
+    // Kept alive until exit so buffered spans are flushed
+    let (otel_layer, _otel_guard) =
+        telemetry::layer(&TelemetryConfig::from_env(), "vibe-kanban").unzip();
     tracing_subscriber::registry()
         .with(tracing_subscriber::fmt::layer().with_filter(env_filter))
         .with(sentry_layer())
+        .with(otel_layer)
         .init();
 
     // Create asset directory if it doesn't exist
@@ -88,7 +98,27 @@ async fn main() -> Result<(), VibeKanbanError> {
         }
     });
 
-    let app_router = routes::router(deployment.clone());
+    // Serve over TLS when both a certificate and its key are provided
+    let tls_config = match (std::env::var("VK_TLS_CERT"), std::env::var("VK_TLS_KEY")) {
+        (Ok(cert), Ok(key)) => Some(RustlsConfig::from_pem_file(cert, key).await?),
+        (Ok(_), Err(_)) | (Err(_), Ok(_)) => {
+            return Err(VibeKanbanError::Other(anyhow::anyhow!(
+                "VK_TLS_CERT and VK_TLS_KEY must be set together"
+            )));
+        }
+        (Err(_), Err(_)) => None,
+    };
+    let auth_settings = AuthSettings::from_env(tls_config.is_some());
+    if auth_settings.required
+        && let Some(token) = ensure_bootstrap_token(&deployment.db().pool).await?
+    {
+        tracing::warn!(
+            "Remote access is enabled and no access tokens existed, so one was created. \
+             Sign in at /api/auth/login with this token; it will not be shown again: {token}"
+        );
+    }
+
+    let app_router = routes::router(deployment.clone(), auth_settings);
 
     let port = std::env::var("BACKEND_PORT")
         .or_else(|_| std::env::var("PORT"))
@@ -113,30 +143,61 @@ async fn main() -> Result<(), VibeKanbanError> {
         tracing::warn!("Failed to write port file: {}", e);
     }
 
-    tracing::info!("Server running on http://{host}:{actual_port}");
+    let scheme = if tls_config.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    tracing::info!("Server running on {scheme}://{host}:{actual_port}");
+    if !auth_settings.required && !is_loopback_host(&host) {
+        tracing::warn!(
+            "Listening on {host} without authentication; set VK_AUTH_REQUIRED=true to require access tokens"
+        );
+    }
 
     if !cfg!(debug_assertions) {
         tracing::info!("Opening browser...");
         tokio::spawn(async move {
-            if let Err(e) = open_browser(&format!("http://127.0.0.1:{actual_port}")).await {
+            if let Err(e) = open_browser(&format!("{scheme}://127.0.0.1:{actual_port}")).await {
                 tracing::warn!(
-                    "Failed to open browser automatically: {}. Please open http://127.0.0.1:{} manually.",
+                    "Failed to open browser automatically: {}. Please open {}://127.0.0.1:{} manually.",
                     e,
+                    scheme,
                     actual_port
                 );
             }
         });
     }
 
-    axum::serve(listener, app_router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    if let Some(tls_config) = tls_config {
+        let handle = axum_server::Handle::new();
+        let shutdown_handle = handle.clone();
+        tokio::spawn(async move {
+            shutdown_signal().await;
+            shutdown_handle.graceful_shutdown(None);
+        });
+        axum_server::from_tcp_rustls(listener.into_std()?, tls_config)
+            .handle(handle)
+            .serve(app_router)
+            .await?;
+    } else {
+        axum::serve(listener, app_router)
+            .with_graceful_shutdown(shutdown_signal())
+            .await?;
+    }
 
     perform_cleanup_actions(&deployment).await;
 
     Ok(())
 }
 
+fn is_loopback_host(host: &str) -> bool {
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
 pub async fn shutdown_signal() {
     // Always wait for Ctrl+C
     let ctrl_c = async {
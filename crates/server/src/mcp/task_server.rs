@@ -1,29 +1,62 @@
+mod resources;
+
 use std::{future::Future, str::FromStr};
 
+use axum::body::{Body, Bytes};
 use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus},
+    merge::{Merge, MergeStatus},
     project::Project,
     repo::Repo,
+    search::SearchHit,
+    session::Session,
     tag::Tag,
-    task::{CreateTask, Task, TaskStatus, TaskWithAttemptStatus, UpdateTask},
+    task::{
+        CreateTask, SortOrder, Task, TaskFieldsToClear, TaskFilter, TaskPriority, TaskSortField,
+        TaskStatus, TaskWithAttemptStatus, UpdateTask,
+    },
     workspace::{Workspace, WorkspaceContext},
+    workspace_repo::CreateWorkspaceRepo,
+};
+use executors::{
+    executors::BaseCodingAgent,
+    logs::{NormalizedEntry, NormalizedEntryType},
+    profile::ExecutorProfileId,
 };
-use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use regex::Regex;
 use rmcp::{
-    ErrorData, ServerHandler,
+    ErrorData, RoleServer, ServerHandler,
     handler::server::tool::{Parameters, ToolRouter},
     model::{
-        CallToolResult, Content, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo,
+        CallToolResult, Content, GetPromptRequestParam, GetPromptResult, Implementation,
+        ListPromptsResult, ListResourceTemplatesResult, ListResourcesResult, PaginatedRequestParam,
+        ProtocolVersion, ReadResourceRequestParam, ReadResourceResult, ServerCapabilities,
+        ServerInfo, SubscribeRequestParam, UnsubscribeRequestParam,
     },
-    schemars, tool, tool_handler, tool_router,
+    schemars,
+    service::RequestContext,
+    tool, tool_handler, tool_router,
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use services::services::{
+    events::EventService,
+    git::GitService,
+    task_bulk::{BulkTaskOperation, BulkTaskRequest, BulkTaskResult},
+};
+use tower::ServiceExt;
+use utils::diff::{compute_line_change_counts, create_unified_diff};
 use uuid::Uuid;
 
+use self::resources::Subscriptions;
 use crate::routes::{
     containers::ContainerQuery,
-    task_attempts::{CreateTaskAttemptBody, WorkspaceRepoInput},
+    sessions::CreateFollowUpAttempt,
+    task_attempts::{
+        CreateTaskAttemptBody, MergeTaskAttemptRequest, RepoBranchStatus, RepoDiff,
+        WorkspaceRepoInput, pr::CreateGitHubPrRequest,
+    },
+    tasks::TaskQuery,
 };
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -34,6 +67,14 @@ pub struct CreateTaskRequest {
     pub title: String,
     #[schemars(description = "Optional description of the task")]
     pub description: Option<String>,
+    #[schemars(description = "Optional priority: 'low', 'medium', 'high', 'urgent'")]
+    pub priority: Option<String>,
+    #[schemars(description = "Optional due date as an RFC 3339 timestamp")]
+    pub due_date: Option<String>,
+    #[schemars(description = "Optional estimated effort in minutes")]
+    pub estimate_minutes: Option<i64>,
+    #[schemars(description = "Optional label names; missing labels are created")]
+    pub labels: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -99,6 +140,22 @@ pub struct ListTasksRequest {
         description = "Optional status filter: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
     )]
     pub status: Option<String>,
+    #[schemars(description = "Optional priority filter: 'low', 'medium', 'high', 'urgent'")]
+    pub priority: Option<String>,
+    #[schemars(description = "Optional label filter (label name, case-insensitive)")]
+    pub label: Option<String>,
+    #[schemars(
+        description = "Optional filter for tasks due before this RFC 3339 timestamp, e.g. '2025-01-31T00:00:00Z'"
+    )]
+    pub due_before: Option<String>,
+    #[schemars(
+        description = "Optional sort field: 'created_at', 'updated_at', 'priority', 'due_date', 'title'"
+    )]
+    pub sort: Option<String>,
+    #[schemars(
+        description = "Optional sort order: 'asc' or 'desc'. Defaults to most urgent, soonest due, alphabetical or newest first"
+    )]
+    pub order: Option<String>,
     #[schemars(description = "Maximum number of tasks to return (default: 50)")]
     pub limit: Option<i32>,
 }
@@ -111,6 +168,12 @@ pub struct TaskSummary {
     pub title: String,
     #[schemars(description = "Current status of the task")]
     pub status: String,
+    #[schemars(description = "Priority of the task, if set")]
+    pub priority: Option<String>,
+    #[schemars(description = "When the task is due, if set")]
+    pub due_date: Option<String>,
+    #[schemars(description = "Names of the task's labels")]
+    pub labels: Vec<String>,
     #[schemars(description = "When the task was created")]
     pub created_at: String,
     #[schemars(description = "When the task was last updated")]
@@ -127,6 +190,9 @@ impl TaskSummary {
             id: task.id.to_string(),
             title: task.title.to_string(),
             status: task.status.to_string(),
+            priority: task.priority.map(|p| p.to_string()),
+            due_date: task.due_date.map(|d| d.to_rfc3339()),
+            labels: task.labels.iter().map(|l| l.name.clone()).collect(),
             created_at: task.created_at.to_rfc3339(),
             updated_at: task.updated_at.to_rfc3339(),
             has_in_progress_attempt: Some(task.has_in_progress_attempt),
@@ -145,6 +211,12 @@ pub struct TaskDetails {
     pub description: Option<String>,
     #[schemars(description = "Current status of the task")]
     pub status: String,
+    #[schemars(description = "Priority of the task, if set")]
+    pub priority: Option<String>,
+    #[schemars(description = "When the task is due, if set")]
+    pub due_date: Option<String>,
+    #[schemars(description = "Estimated effort in minutes, if set")]
+    pub estimate_minutes: Option<i64>,
     #[schemars(description = "When the task was created")]
     pub created_at: String,
     #[schemars(description = "When the task was last updated")]
@@ -162,6 +234,9 @@ impl TaskDetails {
             title: task.title,
             description: task.description,
             status: task.status.to_string(),
+            priority: task.priority.map(|p| p.to_string()),
+            due_date: task.due_date.map(|d| d.to_rfc3339()),
+            estimate_minutes: task.estimate_minutes,
             created_at: task.created_at.to_rfc3339(),
             updated_at: task.updated_at.to_rfc3339(),
             has_in_progress_attempt: None,
@@ -181,6 +256,11 @@ pub struct ListTasksResponse {
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct ListTasksFilters {
     pub status: Option<String>,
+    pub priority: Option<String>,
+    pub label: Option<String>,
+    pub due_before: Option<String>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
     pub limit: i32,
 }
 
@@ -194,6 +274,18 @@ pub struct UpdateTaskRequest {
     pub description: Option<String>,
     #[schemars(description = "New status: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'")]
     pub status: Option<String>,
+    #[schemars(
+        description = "New priority: 'low', 'medium', 'high', 'urgent', or 'none' to clear it"
+    )]
+    pub priority: Option<String>,
+    #[schemars(description = "New due date as an RFC 3339 timestamp, or 'none' to clear it")]
+    pub due_date: Option<String>,
+    #[schemars(description = "New estimated effort in minutes")]
+    pub estimate_minutes: Option<i64>,
+    #[schemars(
+        description = "Label names replacing the task's labels; an empty list removes them"
+    )]
+    pub labels: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -240,6 +332,47 @@ pub struct DeleteTaskResponse {
     pub deleted_task_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct BulkUpdateTasksRequest {
+    #[schemars(description = "The IDs of the tasks to change")]
+    pub task_ids: Vec<Uuid>,
+    #[schemars(
+        description = "The operation: 'set_status', 'delete', 'add_labels', 'remove_labels' or 'start_attempt'"
+    )]
+    pub operation: String,
+    #[schemars(
+        description = "New status for 'set_status': 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
+    )]
+    pub status: Option<String>,
+    #[schemars(description = "Label names for 'add_labels' and 'remove_labels'")]
+    pub labels: Option<Vec<String>>,
+    #[schemars(description = "The coding agent executor to run for 'start_attempt'")]
+    pub executor: Option<String>,
+    #[schemars(description = "Optional executor variant for 'start_attempt'")]
+    pub variant: Option<String>,
+    #[schemars(
+        description = "Base branch for each repository for 'start_attempt'. Defaults to the repositories of each task's latest attempt"
+    )]
+    pub repos: Option<Vec<McpWorkspaceRepoInput>>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct BulkTaskResultSummary {
+    pub task_id: String,
+    pub success: bool,
+    #[schemars(description = "Why the task was skipped, or why its attempt failed to start")]
+    pub error: Option<String>,
+    #[schemars(description = "The attempt started on the task by 'start_attempt'")]
+    pub workspace_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct BulkUpdateTasksResponse {
+    pub results: Vec<BulkTaskResultSummary>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct GetTaskRequest {
     #[schemars(description = "The ID of the task to retrieve")]
@@ -251,10 +384,450 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchRequest {
+    #[schemars(
+        description = "Free text to search for in task titles/descriptions, prompts, agent messages and commands"
+    )]
+    pub query: String,
+    #[schemars(description = "Optional project ID to restrict the search to")]
+    pub project_id: Option<Uuid>,
+    #[schemars(
+        description = "Optional RFC 3339 timestamp; only return hits created at or after it"
+    )]
+    pub since: Option<String>,
+    #[schemars(description = "Maximum number of hits to return (default: 20)")]
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SearchHitSummary {
+    #[schemars(
+        description = "Where the match was found: 'task', 'prompt', 'summary', 'assistant_message', 'command' or 'file_edit'"
+    )]
+    pub kind: String,
+    pub project_id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub task_status: String,
+    pub workspace_id: Option<String>,
+    #[schemars(description = "Session of the matching conversation entry, if any")]
+    pub session_id: Option<String>,
+    #[schemars(description = "Execution process of the matching conversation entry, if any")]
+    pub execution_process_id: Option<String>,
+    #[schemars(description = "Matching excerpt with hits wrapped in `**`")]
+    pub snippet: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SearchResponse {
+    pub hits: Vec<SearchHitSummary>,
+    pub count: usize,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AttemptRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpProcessSummary {
+    pub id: String,
+    #[schemars(
+        description = "Why the process ran: 'codingagent', 'setupscript', 'cleanupscript' or 'devserver'"
+    )]
+    pub run_reason: String,
+    #[schemars(description = "'running', 'completed', 'failed' or 'killed'")]
+    pub status: String,
+    pub exit_code: Option<i64>,
+    pub started_at: String,
+    pub completed_at: Option<String>,
+}
+
+impl McpProcessSummary {
+    fn from_process(process: &ExecutionProcess) -> Self {
+        Self {
+            id: process.id.to_string(),
+            run_reason: serde_label(&process.run_reason),
+            status: serde_label(&process.status),
+            exit_code: process.exit_code,
+            started_at: process.started_at.to_rfc3339(),
+            completed_at: process.completed_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpBranchStatus {
+    pub repo_id: String,
+    pub repo_name: String,
+    pub target_branch: String,
+    pub commits_ahead: Option<usize>,
+    pub commits_behind: Option<usize>,
+    pub has_uncommitted_changes: Option<bool>,
+    pub conflicted_files: Vec<String>,
+    pub is_rebase_in_progress: bool,
+    #[schemars(description = "True once the branch was merged directly or via a merged PR")]
+    pub merged: bool,
+    pub pull_request_url: Option<String>,
+    #[schemars(description = "'open', 'merged', 'closed' or 'unknown'")]
+    pub pull_request_status: Option<String>,
+}
+
+impl McpBranchStatus {
+    fn from_repo_status(repo: RepoBranchStatus) -> Self {
+        let status = repo.status;
+        let mut merged = false;
+        let mut pull_request = None;
+        for merge in &status.merges {
+            match merge {
+                Merge::Direct(_) => merged = true,
+                Merge::Pr(pr) => {
+                    merged |= matches!(pr.pr_info.status, MergeStatus::Merged);
+                    pull_request.get_or_insert(&pr.pr_info);
+                }
+            }
+        }
+        Self {
+            repo_id: repo.repo_id.to_string(),
+            repo_name: repo.repo_name,
+            target_branch: status.target_branch_name,
+            commits_ahead: status.commits_ahead,
+            commits_behind: status.commits_behind,
+            has_uncommitted_changes: status.has_uncommitted_changes,
+            conflicted_files: status.conflicted_files,
+            is_rebase_in_progress: status.is_rebase_in_progress,
+            merged,
+            pull_request_url: pull_request.map(|pr| pr.url.clone()),
+            pull_request_status: pull_request.map(|pr| serde_label(&pr.status)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetAttemptStatusResponse {
+    pub attempt_id: String,
+    pub task_id: String,
+    pub task_title: String,
+    pub task_status: String,
+    pub branch: String,
+    #[schemars(description = "True while any execution process of the attempt is running")]
+    pub is_running: bool,
+    pub latest_process: Option<McpProcessSummary>,
+    pub process_count: usize,
+    pub branches: Vec<McpBranchStatus>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptLogsRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(
+        description = "Optional execution process ID; defaults to the attempt's latest coding agent run"
+    )]
+    pub execution_process_id: Option<Uuid>,
+    #[schemars(description = "Number of trailing log entries to include (default: 20)")]
+    pub max_entries: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpLogEntry {
+    #[schemars(
+        description = "'user_message', 'assistant_message', 'tool_use', 'error_message', 'system_message' or 'user_feedback'"
+    )]
+    pub kind: String,
+    pub tool_name: Option<String>,
+    pub content: String,
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetAttemptLogsResponse {
+    pub process: McpProcessSummary,
+    pub total_entries: usize,
+    pub tool_calls: usize,
+    #[schemars(description = "Error messages reported during the run")]
+    pub errors: Vec<String>,
+    #[schemars(description = "The last assistant message, usually the agent's own summary")]
+    pub final_message: Option<String>,
+    #[schemars(description = "The trailing entries, oldest first, with long content truncated")]
+    pub entries: Vec<McpLogEntry>,
+}
+
+impl GetAttemptLogsResponse {
+    /// Summarize a run's entries, keeping the last `max_entries` of them
+    fn summarize(
+        process: McpProcessSummary,
+        entries: &[NormalizedEntry],
+        max_entries: usize,
+    ) -> Self {
+        let mut tool_calls = 0;
+        let mut errors = Vec::new();
+        let mut final_message = None;
+        let mut summarized = Vec::new();
+        for entry in entries {
+            let (kind, tool_name) = match &entry.entry_type {
+                NormalizedEntryType::UserMessage => ("user_message", None),
+                NormalizedEntryType::UserFeedback { .. } => ("user_feedback", None),
+                NormalizedEntryType::AssistantMessage => {
+                    final_message = Some(&entry.content);
+                    ("assistant_message", None)
+                }
+                NormalizedEntryType::ToolUse { tool_name, .. } => {
+                    tool_calls += 1;
+                    ("tool_use", Some(tool_name.clone()))
+                }
+                NormalizedEntryType::SystemMessage => ("system_message", None),
+                NormalizedEntryType::ErrorMessage { .. } => {
+                    errors.push(truncate_chars(&entry.content, 500));
+                    ("error_message", None)
+                }
+                NormalizedEntryType::Thinking
+                | NormalizedEntryType::Loading
+                | NormalizedEntryType::NextAction { .. } => continue,
+            };
+            summarized.push(McpLogEntry {
+                kind: kind.to_string(),
+                tool_name,
+                content: truncate_chars(&entry.content, 1000),
+                timestamp: entry.timestamp.clone(),
+            });
+        }
+
+        let entries_tail = summarized.split_off(summarized.len().saturating_sub(max_entries));
+
+        Self {
+            process,
+            total_entries: entries.len(),
+            tool_calls,
+            errors,
+            final_message: final_message.map(|m| truncate_chars(m, 4000)),
+            entries: entries_tail,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetAttemptDiffRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "Only return per-file line counts, without patches")]
+    pub stats_only: Option<bool>,
+    #[schemars(
+        description = "Maximum total size of the returned patches in bytes (default: 50000); later files are listed without a patch"
+    )]
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpFileDiff {
+    pub path: String,
+    #[schemars(
+        description = "'added', 'deleted', 'modified', 'renamed', 'copied' or 'permissionChange'"
+    )]
+    pub change: String,
+    pub additions: usize,
+    pub deletions: usize,
+    #[schemars(description = "Unified diff, omitted for stats-only requests and large files")]
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct McpRepoDiff {
+    pub repo_id: String,
+    pub repo_name: String,
+    pub files: Vec<McpFileDiff>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetAttemptDiffResponse {
+    pub repos: Vec<McpRepoDiff>,
+    pub files_changed: usize,
+    pub additions: usize,
+    pub deletions: usize,
+    #[schemars(description = "True when some patches were dropped to stay within `max_bytes`")]
+    pub truncated: bool,
+}
+
+impl GetAttemptDiffResponse {
+    /// Per-file stats for every repo, with patches until `max_bytes` is used up
+    fn from_repo_diffs(repo_diffs: Vec<RepoDiff>, stats_only: bool, max_bytes: usize) -> Self {
+        let mut budget = max_bytes;
+        let mut truncated = false;
+        let (mut total_additions, mut total_deletions, mut files_changed) = (0, 0, 0);
+
+        let mut repos = Vec::with_capacity(repo_diffs.len());
+        for repo_diff in repo_diffs {
+            let mut files = Vec::with_capacity(repo_diff.diffs.len());
+            for diff in repo_diff.diffs {
+                let path = GitService::diff_path(&diff);
+                let old = diff.old_content.as_deref().unwrap_or("");
+                let new = diff.new_content.as_deref().unwrap_or("");
+                let (additions, deletions) = match (diff.additions, diff.deletions) {
+                    (Some(additions), Some(deletions)) => (additions, deletions),
+                    _ => compute_line_change_counts(old, new),
+                };
+
+                let patch = if stats_only || diff.content_omitted {
+                    None
+                } else {
+                    let patch = create_unified_diff(&path, old, new);
+                    if patch.len() <= budget {
+                        budget -= patch.len();
+                        Some(patch)
+                    } else {
+                        truncated = true;
+                        None
+                    }
+                };
+
+                files_changed += 1;
+                total_additions += additions;
+                total_deletions += deletions;
+                files.push(McpFileDiff {
+                    path,
+                    change: serde_label(&diff.change),
+                    additions,
+                    deletions,
+                    patch,
+                });
+            }
+            repos.push(McpRepoDiff {
+                repo_id: repo_diff.repo_id.to_string(),
+                repo_name: repo_diff.repo_name,
+                files,
+            });
+        }
+
+        Self {
+            repos,
+            files_changed,
+            additions: total_additions,
+            deletions: total_deletions,
+            truncated,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SendFollowUpRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "The follow-up prompt for the coding agent")]
+    pub prompt: String,
+    #[schemars(description = "Optional session ID; defaults to the attempt's latest session")]
+    pub session_id: Option<Uuid>,
+    #[schemars(description = "Optional executor variant, if needed")]
+    pub variant: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct SendFollowUpResponse {
+    pub session_id: String,
+    pub execution_process_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct StopExecutionRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(
+        description = "Optional execution process ID to stop; by default every running process of the attempt is stopped"
+    )]
+    pub execution_process_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StopExecutionResponse {
+    pub attempt_id: String,
+    pub execution_process_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MergeAttemptRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "The repository whose branch should be merged")]
+    pub repo_id: Uuid,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct MergeAttemptResponse {
+    pub attempt_id: String,
+    pub repo_id: String,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreatePullRequestRequest {
+    #[schemars(description = "The ID of the project the attempt's task belongs to")]
+    pub project_id: Uuid,
+    #[schemars(description = "The ID of the task attempt (workspace)")]
+    pub attempt_id: Uuid,
+    #[schemars(description = "The repository to open the pull request for")]
+    pub repo_id: Uuid,
+    #[schemars(description = "The pull request title")]
+    pub title: String,
+    #[schemars(description = "Optional pull request body")]
+    pub body: Option<String>,
+    #[schemars(description = "Optional base branch; defaults to the attempt's target branch")]
+    pub target_branch: Option<String>,
+    #[schemars(description = "Open the pull request as a draft")]
+    pub draft: Option<bool>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct CreatePullRequestResponse {
+    pub attempt_id: String,
+    pub pr_url: String,
+}
+
+/// The string a unit-variant enum serializes to, for enums without `Display`
+const PRIORITY_VALUES: &str = "'low', 'medium', 'high' or 'urgent'";
+const TIMESTAMP_FORMAT: &str = "an RFC 3339 timestamp, e.g. '2025-01-31T17:00:00Z'";
+
+/// `none` clears an optional task field in `update_task`
+fn is_none_arg(value: &str) -> bool {
+    value.trim().eq_ignore_ascii_case("none")
+}
+
+fn serde_label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn truncate_chars(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}…", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[derive(Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
     base_url: String,
+    /// Set when served from inside the backend; requests are dispatched straight into the
+    /// API router instead of over HTTP
+    router: Option<axum::Router>,
+    /// Backs resource subscriptions; only available in-process
+    events: Option<EventService>,
+    subscriptions: Subscriptions,
     tool_router: ToolRouter<TaskServer>,
     context: Option<McpContext>,
 }
@@ -283,10 +856,41 @@ pub struct McpContext {
 }
 
 impl TaskServer {
-    pub fn new(base_url: &str) -> Self {
+    /// `access_token` authenticates against a server running in remote access mode
+    pub fn new(base_url: &str, access_token: Option<&str>) -> Self {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(token) = access_token
+            && let Ok(mut value) =
+                reqwest::header::HeaderValue::from_str(&format!("Bearer {token}"))
+        {
+            value.set_sensitive(true);
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+        let client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()
+            .unwrap_or_default();
+
         Self {
-            client: reqwest::Client::new(),
+            client,
             base_url: base_url.to_string(),
+            router: None,
+            events: None,
+            subscriptions: Subscriptions::default(),
+            tool_router: Self::tool_router(),
+            context: None,
+        }
+    }
+
+    /// A server for the backend's own MCP endpoint. `router` serves `/api` without the auth
+    /// layer, since callers already authenticated against the MCP endpoint.
+    pub fn in_process(router: axum::Router, events: EventService) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: "http://localhost".to_string(),
+            router: Some(router),
+            events: Some(events),
+            subscriptions: Subscriptions::default(),
             tool_router: Self::tool_router(),
             context: None,
         }
@@ -363,6 +967,7 @@ struct ApiResponseEnvelope<T> {
     success: bool,
     data: Option<T>,
     message: Option<String>,
+    error_data: Option<serde_json::Value>,
 }
 
 impl TaskServer {
@@ -388,34 +993,136 @@ impl TaskServer {
         Self::err_value(v)
     }
 
+    /// Parse an optional tool argument, or return the error result describing `expected`
+    fn parse_arg<T: FromStr>(
+        value: Option<&str>,
+        name: &str,
+        expected: &str,
+    ) -> Result<Option<T>, Result<CallToolResult, ErrorData>> {
+        value
+            .map(|raw| {
+                T::from_str(raw.trim()).map_err(|_| {
+                    Self::err(
+                        format!("Invalid {name}. Expected {expected}"),
+                        Some(raw.to_string()),
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Parse an executor name (case-insensitive, hyphens or underscores) and optional variant
+    fn parse_executor_profile(
+        executor: &str,
+        variant: Option<String>,
+    ) -> Result<ExecutorProfileId, Result<CallToolResult, ErrorData>> {
+        let executor_trimmed = executor.trim();
+        if executor_trimmed.is_empty() {
+            return Err(Self::err(
+                "Executor must not be empty.".to_string(),
+                None::<String>,
+            ));
+        }
+
+        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
+        let base_executor = BaseCodingAgent::from_str(&normalized_executor).map_err(|_| {
+            Self::err(
+                format!("Unknown executor '{executor_trimmed}'."),
+                None::<String>,
+            )
+        })?;
+
+        let variant = variant.and_then(|v| {
+            let trimmed = v.trim();
+            if trimmed.is_empty() {
+                None
+            } else {
+                Some(trimmed.to_string())
+            }
+        });
+
+        Ok(ExecutorProfileId {
+            executor: base_executor,
+            variant,
+        })
+    }
+
+    /// Send a request to the VK API, returning the status and body
+    async fn execute(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<(reqwest::StatusCode, Bytes), String> {
+        let Some(router) = &self.router else {
+            let resp = rb.send().await.map_err(|e| e.to_string())?;
+            let status = resp.status();
+            let body = resp.bytes().await.map_err(|e| e.to_string())?;
+            return Ok((status, body));
+        };
+
+        let request = rb.build().map_err(|e| e.to_string())?;
+        let uri = match request.url().query() {
+            Some(query) => format!("{}?{}", request.url().path(), query),
+            None => request.url().path().to_string(),
+        };
+        let mut builder = axum::http::Request::builder()
+            .method(request.method().clone())
+            .uri(uri);
+        for (name, value) in request.headers() {
+            builder = builder.header(name, value);
+        }
+        let body = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .map(|bytes| Body::from(bytes.to_vec()))
+            .unwrap_or_else(Body::empty);
+        let request = builder.body(body).map_err(|e| e.to_string())?;
+
+        let response = router
+            .clone()
+            .oneshot(request)
+            .await
+            .map_err(|e| e.to_string())?;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok((status, body))
+    }
+
     async fn send_json<T: DeserializeOwned>(
         &self,
         rb: reqwest::RequestBuilder,
     ) -> Result<T, CallToolResult> {
-        let resp = rb
-            .send()
+        let (status, body) = self
+            .execute(rb)
             .await
-            .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
+            .map_err(|e| Self::err("Failed to connect to VK API", Some(&e)).unwrap())?;
 
-        if !resp.status().is_success() {
-            let status = resp.status();
+        if !status.is_success() {
             return Err(
                 Self::err(format!("VK API returned error status: {}", status), None).unwrap(),
             );
         }
 
-        let api_response = resp.json::<ApiResponseEnvelope<T>>().await.map_err(|e| {
-            Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
-        })?;
+        let api_response =
+            serde_json::from_slice::<ApiResponseEnvelope<T>>(&body).map_err(|e| {
+                Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
+            })?;
 
         if !api_response.success {
-            let msg = api_response.message.as_deref().unwrap_or("Unknown error");
-            return Err(Self::err("VK API returned error", Some(msg)).unwrap());
+            let msg = api_response
+                .message
+                .or_else(|| api_response.error_data.map(|data| data.to_string()))
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(Self::err("VK API returned error".to_string(), Some(msg)).unwrap());
         }
 
-        api_response
-            .data
-            .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
+        match api_response.data {
+            Some(data) => Ok(data),
+            // Unit responses serialize their data as null
+            None => serde_json::from_value(serde_json::Value::Null)
+                .map_err(|_| Self::err("VK API response missing data field", None).unwrap()),
+        }
     }
 
     fn url(&self, path: &str) -> String {
@@ -426,6 +1133,44 @@ impl TaskServer {
         )
     }
 
+    /// Fetch an attempt and check that its task belongs to `project_id`
+    async fn attempt_in_project(
+        &self,
+        project_id: Uuid,
+        attempt_id: Uuid,
+    ) -> Result<(Workspace, Task), CallToolResult> {
+        let url = self.url(&format!("/api/task-attempts/{}", attempt_id));
+        let workspace: Workspace = self.send_json(self.client.get(&url)).await?;
+
+        let url = self.url(&format!("/api/tasks/{}", workspace.task_id));
+        let task: Task = self.send_json(self.client.get(&url)).await?;
+
+        if task.project_id != project_id {
+            return Err(Self::err(
+                "Attempt does not belong to the requested project".to_string(),
+                Some(format!(
+                    "attempt {attempt_id} is not in project {project_id}"
+                )),
+            )
+            .unwrap());
+        }
+        Ok((workspace, task))
+    }
+
+    /// Execution processes of an attempt, oldest first
+    async fn attempt_processes(
+        &self,
+        attempt_id: Uuid,
+    ) -> Result<Vec<ExecutionProcess>, CallToolResult> {
+        let url = self.url("/api/execution-processes");
+        self.send_json(
+            self.client
+                .get(&url)
+                .query(&[("workspace_id", attempt_id.to_string())]),
+        )
+        .await
+    }
+
     /// Expands @tagname references in text by replacing them with tag content.
     /// Returns the original text if expansion fails (e.g., network error).
     /// Unknown tags are left as-is (not expanded, not an error).
@@ -450,9 +1195,9 @@ impl TaskServer {
 
         // Fetch all tags from the API
         let url = self.url("/api/tags");
-        let tags: Vec<Tag> = match self.client.get(&url).send().await {
-            Ok(resp) if resp.status().is_success() => {
-                match resp.json::<ApiResponseEnvelope<Vec<Tag>>>().await {
+        let tags: Vec<Tag> = match self.execute(self.client.get(&url)).await {
+            Ok((status, body)) if status.is_success() => {
+                match serde_json::from_slice::<ApiResponseEnvelope<Vec<Tag>>>(&body) {
                     Ok(envelope) if envelope.success => envelope.data.unwrap_or_default(),
                     _ => return text.to_string(),
                 }
@@ -500,8 +1245,21 @@ impl TaskServer {
             project_id,
             title,
             description,
+            priority,
+            due_date,
+            estimate_minutes,
+            labels,
         }): Parameters<CreateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
+        let priority = match Self::parse_arg(priority.as_deref(), "priority", PRIORITY_VALUES) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+        let due_date = match Self::parse_arg(due_date.as_deref(), "due_date", TIMESTAMP_FORMAT) {
+            Ok(d) => d,
+            Err(e) => return e,
+        };
+
         // Expand @tagname references in description
         let expanded_description = match description {
             Some(desc) => Some(self.expand_tags(&desc).await),
@@ -509,19 +1267,15 @@ impl TaskServer {
         };
 
         let url = self.url("/api/tasks");
+        let payload = CreateTask {
+            priority,
+            due_date,
+            estimate_minutes,
+            labels,
+            ..CreateTask::from_title_description(project_id, title, expanded_description)
+        };
 
-        let task: Task = match self
-            .send_json(
-                self.client
-                    .post(&url)
-                    .json(&CreateTask::from_title_description(
-                        project_id,
-                        title,
-                        expanded_description,
-                    )),
-            )
-            .await
-        {
+        let task: Task = match self.send_json(self.client.post(&url).json(&payload)).await {
             Ok(t) => t,
             Err(e) => return Ok(e),
         };
@@ -581,13 +1335,18 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "List all the task/tickets in a project with optional filtering and execution status. `project_id` is required!"
+        description = "List all the task/tickets in a project with execution status, optionally filtered by status, priority, label or due date and sorted. `project_id` is required!"
     )]
     async fn list_tasks(
         &self,
         Parameters(ListTasksRequest {
             project_id,
             status,
+            priority,
+            label,
+            due_before,
+            sort,
+            order,
             limit,
         }): Parameters<ListTasksRequest>,
     ) -> Result<CallToolResult, ErrorData> {
@@ -604,23 +1363,47 @@ impl TaskServer {
         } else {
             None
         };
-
-        let url = self.url(&format!("/api/tasks?project_id={}", project_id));
-        let all_tasks: Vec<TaskWithAttemptStatus> =
-            match self.send_json(self.client.get(&url)).await {
+        let filter = TaskFilter {
+            status: status_filter,
+            priority: match Self::parse_arg::<TaskPriority>(
+                priority.as_deref(),
+                "priority filter",
+                PRIORITY_VALUES,
+            ) {
+                Ok(p) => p,
+                Err(e) => return e,
+            },
+            label: label.clone(),
+            due_before: match Self::parse_arg(due_before.as_deref(), "due_before", TIMESTAMP_FORMAT)
+            {
+                Ok(d) => d,
+                Err(e) => return e,
+            },
+            sort: match Self::parse_arg::<TaskSortField>(
+                sort.as_deref(),
+                "sort",
+                "'created_at', 'updated_at', 'priority', 'due_date' or 'title'",
+            ) {
+                Ok(s) => s,
+                Err(e) => return e,
+            },
+            order: match Self::parse_arg::<SortOrder>(order.as_deref(), "order", "'asc' or 'desc'")
+            {
+                Ok(o) => o,
+                Err(e) => return e,
+            },
+        };
+
+        let url = self.url("/api/tasks");
+        let query = TaskQuery { project_id, filter };
+        let all_tasks: Vec<TaskWithAttemptStatus> =
+            match self.send_json(self.client.get(&url).query(&query)).await {
                 Ok(t) => t,
                 Err(e) => return Ok(e),
             };
 
         let task_limit = limit.unwrap_or(50).max(0) as usize;
-        let filtered = all_tasks.into_iter().filter(|t| {
-            if let Some(ref want) = status_filter {
-                &t.status == want
-            } else {
-                true
-            }
-        });
-        let limited: Vec<TaskWithAttemptStatus> = filtered.take(task_limit).collect();
+        let limited: Vec<TaskWithAttemptStatus> = all_tasks.into_iter().take(task_limit).collect();
 
         let task_summaries: Vec<TaskSummary> = limited
             .into_iter()
@@ -633,6 +1416,11 @@ impl TaskServer {
             project_id: project_id.to_string(),
             applied_filters: ListTasksFilters {
                 status: status.clone(),
+                priority,
+                label,
+                due_before,
+                sort,
+                order,
                 limit: task_limit as i32,
             },
         };
@@ -659,34 +1447,9 @@ impl TaskServer {
             );
         }
 
-        let executor_trimmed = executor.trim();
-        if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
-        }
-
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
-        };
-
-        let variant = variant.and_then(|v| {
-            let trimmed = v.trim();
-            if trimmed.is_empty() {
-                None
-            } else {
-                Some(trimmed.to_string())
-            }
-        });
-
-        let executor_profile_id = ExecutorProfileId {
-            executor: base_executor,
-            variant,
+        let executor_profile_id = match Self::parse_executor_profile(&executor, variant) {
+            Ok(profile) => profile,
+            Err(e) => return e,
         };
 
         let workspace_repos: Vec<WorkspaceRepoInput> = repos
@@ -720,7 +1483,7 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "Update an existing task/ticket's title, description, or status. `project_id` and `task_id` are required! `title`, `description`, and `status` are optional."
+        description = "Update an existing task/ticket's title, description, status, priority, due date, estimate or labels. `task_id` is required! All other fields are optional."
     )]
     async fn update_task(
         &self,
@@ -729,6 +1492,10 @@ impl TaskServer {
             title,
             description,
             status,
+            priority,
+            due_date,
+            estimate_minutes,
+            labels,
         }): Parameters<UpdateTaskRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         let status = if let Some(ref status_str) = status {
@@ -751,10 +1518,37 @@ impl TaskServer {
             None => None,
         };
 
+        let clear = TaskFieldsToClear {
+            priority: priority.as_deref().is_some_and(is_none_arg),
+            due_date: due_date.as_deref().is_some_and(is_none_arg),
+            estimate_minutes: false,
+        };
+        let priority = match Self::parse_arg(
+            priority.as_deref().filter(|p| !is_none_arg(p)),
+            "priority",
+            PRIORITY_VALUES,
+        ) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+        let due_date = match Self::parse_arg(
+            due_date.as_deref().filter(|d| !is_none_arg(d)),
+            "due_date",
+            TIMESTAMP_FORMAT,
+        ) {
+            Ok(d) => d,
+            Err(e) => return e,
+        };
+
         let payload = UpdateTask {
             title,
             description: expanded_description,
             status,
+            priority,
+            due_date,
+            estimate_minutes,
+            clear,
+            labels,
             parent_workspace_id: None,
             image_ids: None,
         };
@@ -791,6 +1585,113 @@ impl TaskServer {
         TaskServer::success(&repsonse)
     }
 
+    #[tool(
+        description = "Apply one operation to many tasks at once: change their status, delete them, add or remove labels, or start an attempt on each with a coding agent. Tasks that can't take the operation are reported per task and skipped."
+    )]
+    async fn bulk_update_tasks(
+        &self,
+        Parameters(BulkUpdateTasksRequest {
+            task_ids,
+            operation,
+            status,
+            labels,
+            executor,
+            variant,
+            repos,
+        }): Parameters<BulkUpdateTasksRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let operation = match operation.trim() {
+            "set_status" => {
+                let status = match Self::parse_arg::<TaskStatus>(
+                    status.as_deref(),
+                    "status",
+                    "'todo', 'inprogress', 'inreview', 'done' or 'cancelled'",
+                ) {
+                    Ok(Some(status)) => status,
+                    Ok(None) => {
+                        return Self::err(
+                            "'set_status' requires a status.".to_string(),
+                            None::<String>,
+                        );
+                    }
+                    Err(e) => return e,
+                };
+                BulkTaskOperation::SetStatus { status }
+            }
+            "delete" => BulkTaskOperation::Delete,
+            "add_labels" | "remove_labels" => {
+                let Some(labels) = labels.filter(|labels| !labels.is_empty()) else {
+                    return Self::err(
+                        format!("'{}' requires labels.", operation.trim()),
+                        None::<String>,
+                    );
+                };
+                if operation.trim() == "add_labels" {
+                    BulkTaskOperation::AddLabels { labels }
+                } else {
+                    BulkTaskOperation::RemoveLabels { labels }
+                }
+            }
+            "start_attempt" => {
+                let Some(executor) = executor else {
+                    return Self::err(
+                        "'start_attempt' requires an executor.".to_string(),
+                        None::<String>,
+                    );
+                };
+                let executor_profile_id = match Self::parse_executor_profile(&executor, variant) {
+                    Ok(profile) => profile,
+                    Err(e) => return e,
+                };
+                BulkTaskOperation::StartAttempt {
+                    executor_profile_id,
+                    repos: repos
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|r| CreateWorkspaceRepo {
+                            repo_id: r.repo_id,
+                            target_branch: r.base_branch,
+                        })
+                        .collect(),
+                }
+            }
+            other => {
+                return Self::err(
+                    "Invalid operation. Expected 'set_status', 'delete', 'add_labels', 'remove_labels' or 'start_attempt'".to_string(),
+                    Some(other.to_string()),
+                );
+            }
+        };
+
+        let payload = BulkTaskRequest {
+            task_ids,
+            operation,
+        };
+        let url = self.url("/api/tasks/bulk");
+        let results: Vec<BulkTaskResult> =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(results) => results,
+                Err(e) => return Ok(e),
+            };
+
+        let succeeded = results.iter().filter(|r| r.success).count();
+        let response = BulkUpdateTasksResponse {
+            failed: results.len() - succeeded,
+            succeeded,
+            results: results
+                .into_iter()
+                .map(|r| BulkTaskResultSummary {
+                    task_id: r.task_id.to_string(),
+                    success: r.success,
+                    error: r.error,
+                    workspace_id: r.workspace_id.map(|id| id.to_string()),
+                })
+                .collect(),
+        };
+
+        TaskServer::success(&response)
+    }
+
     #[tool(
         description = "Get detailed information (like task description) about a specific task/ticket. You can use `list_tasks` to find the `task_ids` of all tasks in a project. `project_id` and `task_id` are required!"
     )]
@@ -809,20 +1710,408 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Full-text search across task titles/descriptions and past agent conversations (prompts, summaries, assistant messages and commands). Use it to find prior work on a topic. `query` is required!"
+    )]
+    async fn search(
+        &self,
+        Parameters(SearchRequest {
+            query,
+            project_id,
+            since,
+            limit,
+        }): Parameters<SearchRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if query.trim().is_empty() {
+            return Self::err(
+                "Search query must not be empty.".to_string(),
+                None::<String>,
+            );
+        }
+
+        let mut params = vec![("q", query), ("limit", limit.unwrap_or(20).to_string())];
+        if let Some(project_id) = project_id {
+            params.push(("project_id", project_id.to_string()));
+        }
+        if let Some(since) = since {
+            params.push(("since", since));
+        }
+
+        let url = self.url("/api/search");
+        let hits: Vec<SearchHit> = match self.send_json(self.client.get(&url).query(&params)).await
+        {
+            Ok(hits) => hits,
+            Err(e) => return Ok(e),
+        };
+
+        let hits: Vec<SearchHitSummary> = hits
+            .into_iter()
+            .map(|hit| SearchHitSummary {
+                kind: hit.kind.to_string(),
+                project_id: hit.project_id.to_string(),
+                task_id: hit.task_id.to_string(),
+                task_title: hit.task_title,
+                task_status: hit.task_status.to_string(),
+                workspace_id: hit.workspace_id.map(|id| id.to_string()),
+                session_id: hit.session_id.map(|id| id.to_string()),
+                execution_process_id: hit.execution_process_id.map(|id| id.to_string()),
+                snippet: hit.snippet,
+                created_at: hit.created_at.to_rfc3339(),
+            })
+            .collect();
+
+        let response = SearchResponse {
+            count: hits.len(),
+            hits,
+        };
+
+        TaskServer::success(&response)
+    }
+    #[tool(
+        description = "Get the status of a task attempt: its latest execution process and, per repository, how far its branch is ahead/behind the target branch, uncommitted changes, conflicts and merge/PR state. `project_id` and `attempt_id` are required!"
+    )]
+    async fn get_attempt_status(
+        &self,
+        Parameters(AttemptRequest {
+            project_id,
+            attempt_id,
+        }): Parameters<AttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let (workspace, task) = match self.attempt_in_project(project_id, attempt_id).await {
+            Ok(found) => found,
+            Err(e) => return Ok(e),
+        };
+
+        let processes = match self.attempt_processes(attempt_id).await {
+            Ok(processes) => processes,
+            Err(e) => return Ok(e),
+        };
+
+        let url = self.url(&format!("/api/task-attempts/{}/branch-status", attempt_id));
+        let branches: Vec<RepoBranchStatus> = match self.send_json(self.client.get(&url)).await {
+            Ok(branches) => branches,
+            Err(e) => return Ok(e),
+        };
+
+        let response = GetAttemptStatusResponse {
+            attempt_id: workspace.id.to_string(),
+            task_id: task.id.to_string(),
+            task_title: task.title,
+            task_status: task.status.to_string(),
+            branch: workspace.branch,
+            is_running: processes
+                .iter()
+                .any(|p| p.status == ExecutionProcessStatus::Running),
+            latest_process: processes.last().map(McpProcessSummary::from_process),
+            process_count: processes.len(),
+            branches: branches
+                .into_iter()
+                .map(McpBranchStatus::from_repo_status)
+                .collect(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Summarize the normalized logs of a task attempt's coding agent run: tool call count, errors, the agent's final message and the trailing log entries. Defaults to the latest coding agent run. `project_id` and `attempt_id` are required!"
+    )]
+    async fn get_attempt_logs(
+        &self,
+        Parameters(GetAttemptLogsRequest {
+            project_id,
+            attempt_id,
+            execution_process_id,
+            max_entries,
+        }): Parameters<GetAttemptLogsRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let processes = match self.attempt_processes(attempt_id).await {
+            Ok(processes) => processes,
+            Err(e) => return Ok(e),
+        };
+        let process = match execution_process_id {
+            Some(id) => processes.iter().find(|p| p.id == id),
+            None => processes
+                .iter()
+                .rev()
+                .find(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent),
+        };
+        let Some(process) = process else {
+            return Self::err(
+                "No matching execution process found for this attempt".to_string(),
+                execution_process_id.map(|id| id.to_string()),
+            );
+        };
+
+        let url = self.url(&format!(
+            "/api/execution-processes/{}/normalized-logs",
+            process.id
+        ));
+        let entries: Vec<NormalizedEntry> = match self.send_json(self.client.get(&url)).await {
+            Ok(entries) => entries,
+            Err(e) => return Ok(e),
+        };
+
+        let response = GetAttemptLogsResponse::summarize(
+            McpProcessSummary::from_process(process),
+            &entries,
+            max_entries.unwrap_or(20),
+        );
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Get the diff of a task attempt's worktree against its target branch, per repository, as unified patches. Use `stats_only` for line counts only. `project_id` and `attempt_id` are required!"
+    )]
+    async fn get_attempt_diff(
+        &self,
+        Parameters(GetAttemptDiffRequest {
+            project_id,
+            attempt_id,
+            stats_only,
+            max_bytes,
+        }): Parameters<GetAttemptDiffRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let url = self.url(&format!("/api/task-attempts/{}/diff", attempt_id));
+        let repo_diffs: Vec<RepoDiff> = match self.send_json(self.client.get(&url)).await {
+            Ok(diffs) => diffs,
+            Err(e) => return Ok(e),
+        };
+
+        let response = GetAttemptDiffResponse::from_repo_diffs(
+            repo_diffs,
+            stats_only.unwrap_or(false),
+            max_bytes.unwrap_or(50_000),
+        );
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Send a follow-up prompt to a task attempt's coding agent session, starting a new agent run. Defaults to the attempt's latest session. `project_id`, `attempt_id` and `prompt` are required!"
+    )]
+    async fn send_follow_up(
+        &self,
+        Parameters(SendFollowUpRequest {
+            project_id,
+            attempt_id,
+            prompt,
+            session_id,
+            variant,
+        }): Parameters<SendFollowUpRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if prompt.trim().is_empty() {
+            return Self::err("Prompt must not be empty.".to_string(), None::<String>);
+        }
+
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let url = self.url("/api/sessions");
+        let sessions: Vec<Session> = match self
+            .send_json(
+                self.client
+                    .get(&url)
+                    .query(&[("workspace_id", attempt_id.to_string())]),
+            )
+            .await
+        {
+            Ok(sessions) => sessions,
+            Err(e) => return Ok(e),
+        };
+
+        // Sessions come back newest first
+        let session = match session_id {
+            Some(id) => sessions.into_iter().find(|s| s.id == id),
+            None => sessions.into_iter().next(),
+        };
+        let Some(session) = session else {
+            return Self::err(
+                "No matching session found for this attempt".to_string(),
+                session_id.map(|id| id.to_string()),
+            );
+        };
+
+        let payload = CreateFollowUpAttempt {
+            prompt: self.expand_tags(&prompt).await,
+            variant: variant.filter(|v| !v.trim().is_empty()),
+            retry_process_id: None,
+            force_when_dirty: None,
+            perform_git_reset: None,
+        };
+        let url = self.url(&format!("/api/sessions/{}/follow-up", session.id));
+        let process: ExecutionProcess =
+            match self.send_json(self.client.post(&url).json(&payload)).await {
+                Ok(process) => process,
+                Err(e) => return Ok(e),
+            };
+
+        let response = SendFollowUpResponse {
+            session_id: session.id.to_string(),
+            execution_process_id: process.id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Stop a task attempt's running execution. Stops every running process of the attempt unless `execution_process_id` is given. `project_id` and `attempt_id` are required!"
+    )]
+    async fn stop_execution(
+        &self,
+        Parameters(StopExecutionRequest {
+            project_id,
+            attempt_id,
+            execution_process_id,
+        }): Parameters<StopExecutionRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let url = match execution_process_id {
+            Some(process_id) => {
+                let processes = match self.attempt_processes(attempt_id).await {
+                    Ok(processes) => processes,
+                    Err(e) => return Ok(e),
+                };
+                if !processes.iter().any(|p| p.id == process_id) {
+                    return Self::err(
+                        "Execution process does not belong to this attempt".to_string(),
+                        Some(process_id.to_string()),
+                    );
+                }
+                self.url(&format!("/api/execution-processes/{}/stop", process_id))
+            }
+            None => self.url(&format!("/api/task-attempts/{}/stop", attempt_id)),
+        };
+
+        if let Err(e) = self.send_json::<()>(self.client.post(&url)).await {
+            return Ok(e);
+        }
+
+        let response = StopExecutionResponse {
+            attempt_id: attempt_id.to_string(),
+            execution_process_id: execution_process_id.map(|id| id.to_string()),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Merge a task attempt's branch for one repository into its target branch. `project_id`, `attempt_id` and `repo_id` are required!"
+    )]
+    async fn merge_attempt(
+        &self,
+        Parameters(MergeAttemptRequest {
+            project_id,
+            attempt_id,
+            repo_id,
+        }): Parameters<MergeAttemptRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let payload = MergeTaskAttemptRequest { repo_id };
+        let url = self.url(&format!("/api/task-attempts/{}/merge", attempt_id));
+        if let Err(e) = self
+            .send_json::<()>(self.client.post(&url).json(&payload))
+            .await
+        {
+            return Ok(e);
+        }
+
+        let response = MergeAttemptResponse {
+            attempt_id: attempt_id.to_string(),
+            repo_id: repo_id.to_string(),
+        };
+
+        TaskServer::success(&response)
+    }
+
+    #[tool(
+        description = "Push a task attempt's branch and open a GitHub pull request for one repository. `project_id`, `attempt_id`, `repo_id` and `title` are required!"
+    )]
+    async fn create_pull_request(
+        &self,
+        Parameters(CreatePullRequestRequest {
+            project_id,
+            attempt_id,
+            repo_id,
+            title,
+            body,
+            target_branch,
+            draft,
+        }): Parameters<CreatePullRequestRequest>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if title.trim().is_empty() {
+            return Self::err("Title must not be empty.".to_string(), None::<String>);
+        }
+
+        if let Err(e) = self.attempt_in_project(project_id, attempt_id).await {
+            return Ok(e);
+        }
+
+        let payload = CreateGitHubPrRequest {
+            title,
+            body,
+            target_branch,
+            draft,
+            repo_id,
+            auto_generate_description: false,
+        };
+        let url = self.url(&format!("/api/task-attempts/{}/pr", attempt_id));
+        let pr_url: String = match self.send_json(self.client.post(&url).json(&payload)).await {
+            Ok(pr_url) => pr_url,
+            Err(e) => return Ok(e),
+        };
+
+        let response = CreatePullRequestResponse {
+            attempt_id: attempt_id.to_string(),
+            pr_url,
+        };
+
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos'. Make sure to pass `project_id` or `task_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project`.. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'bulk_update_tasks', 'list_repos', 'search', 'get_attempt_status', 'get_attempt_logs', 'get_attempt_diff', 'send_follow_up', 'stop_execution', 'merge_attempt', 'create_pull_request'. Make sure to pass `project_id` or `task_id` where required. Attempt tools take the `project_id` together with an `attempt_id` (the workspace id returned by `start_workspace_session`) and reject attempts from other projects. Projects, tasks and plan files are also exposed as `vibe-kanban://projects/...` resources, and tag snippets as prompts. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
         }
 
+        let capabilities = ServerCapabilities::builder()
+            .enable_tools()
+            .enable_prompts()
+            .enable_resources();
+        let capabilities = if self.events.is_some() {
+            capabilities
+                .enable_resources_subscribe()
+                .enable_resources_list_changed()
+                .build()
+        } else {
+            capabilities.build()
+        };
+
         ServerInfo {
             protocol_version: ProtocolVersion::V_2025_03_26,
-            capabilities: ServerCapabilities::builder().enable_tools().build(),
+            capabilities,
             server_info: Implementation {
                 name: "vibe-kanban".to_string(),
                 version: "1.0.0".to_string(),
@@ -830,4 +2119,272 @@ impl ServerHandler for TaskServer {
             instructions: Some(instruction),
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, ErrorData> {
+        Ok(ListResourcesResult {
+            resources: self.resources().await?,
+            next_cursor: None,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, ErrorData> {
+        Ok(ListResourceTemplatesResult {
+            resource_templates: Self::resource_templates(),
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        self.read_resource_uri(&uri).await
+    }
+
+    async fn subscribe(
+        &self,
+        SubscribeRequestParam { uri }: SubscribeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.subscribe_uri(uri, context.peer)
+    }
+
+    async fn unsubscribe(
+        &self,
+        UnsubscribeRequestParam { uri }: UnsubscribeRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        self.unsubscribe_uri(&uri);
+        Ok(())
+    }
+
+    async fn list_prompts(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListPromptsResult, ErrorData> {
+        Ok(ListPromptsResult {
+            prompts: self.tag_prompts().await?,
+            next_cursor: None,
+        })
+    }
+
+    async fn get_prompt(
+        &self,
+        GetPromptRequestParam { name, arguments }: GetPromptRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let input = arguments
+            .as_ref()
+            .and_then(|args| args.get("input"))
+            .and_then(|value| value.as_str());
+        self.tag_prompt(&name, input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, routing::get};
+    use chrono::Utc;
+    use executors::logs::{ActionType, NormalizedEntryError, ToolStatus};
+    use utils::{
+        diff::{Diff, DiffChangeKind},
+        response::ApiResponse,
+    };
+
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn process_summary() -> McpProcessSummary {
+        McpProcessSummary {
+            id: Uuid::new_v4().to_string(),
+            run_reason: "codingagent".to_string(),
+            status: "completed".to_string(),
+            exit_code: Some(0),
+            started_at: Utc::now().to_rfc3339(),
+            completed_at: None,
+        }
+    }
+
+    fn modified(path: &str, old: &str, new: &str) -> Diff {
+        Diff {
+            change: DiffChangeKind::Modified,
+            old_path: Some(path.to_string()),
+            new_path: Some(path.to_string()),
+            old_content: Some(old.to_string()),
+            new_content: Some(new.to_string()),
+            content_omitted: false,
+            additions: None,
+            deletions: None,
+        }
+    }
+
+    #[test]
+    fn test_truncate_chars() {
+        assert_eq!(truncate_chars("short", 10), "short");
+        assert_eq!(truncate_chars("exact", 5), "exact");
+        assert_eq!(truncate_chars("abcdef", 3), "abc…");
+        // Cuts on character boundaries, not bytes
+        assert_eq!(truncate_chars("héllo wörld", 7), "héllo w…");
+        assert_eq!(truncate_chars("日本語テキスト", 2), "日本…");
+    }
+
+    #[test]
+    fn test_log_summary() {
+        let tool_use = || NormalizedEntryType::ToolUse {
+            tool_name: "Bash".to_string(),
+            action_type: ActionType::Other {
+                description: "ls".to_string(),
+            },
+            status: ToolStatus::Success,
+        };
+        let entries = vec![
+            entry(NormalizedEntryType::UserMessage, "do it"),
+            entry(NormalizedEntryType::Thinking, "hmm"),
+            entry(tool_use(), "ls"),
+            entry(
+                NormalizedEntryType::ErrorMessage {
+                    error_type: NormalizedEntryError::Other,
+                },
+                &"x".repeat(600),
+            ),
+            entry(NormalizedEntryType::AssistantMessage, "first"),
+            entry(tool_use(), "ls -la"),
+            entry(NormalizedEntryType::AssistantMessage, "done"),
+            entry(NormalizedEntryType::Loading, ""),
+        ];
+
+        let summary = GetAttemptLogsResponse::summarize(process_summary(), &entries, 2);
+        assert_eq!(summary.total_entries, entries.len());
+        assert_eq!(summary.tool_calls, 2);
+        assert_eq!(summary.errors.len(), 1);
+        assert_eq!(summary.errors[0].chars().count(), 501);
+        assert_eq!(summary.final_message.as_deref(), Some("done"));
+        // Thinking and loading entries are skipped; the tail keeps the newest entries
+        let kinds: Vec<_> = summary.entries.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, ["tool_use", "assistant_message"]);
+        assert_eq!(summary.entries[0].tool_name.as_deref(), Some("Bash"));
+
+        let all = GetAttemptLogsResponse::summarize(process_summary(), &entries, 100);
+        assert_eq!(all.entries.len(), 6);
+    }
+
+    #[test]
+    fn test_diff_budget() {
+        let repo_diff = || RepoDiff {
+            repo_id: Uuid::new_v4(),
+            repo_name: "app".to_string(),
+            diffs: vec![
+                modified("a.txt", "one\n", "two\n"),
+                modified("b.txt", "one\n", "two\nthree\n"),
+            ],
+        };
+        let first_patch = create_unified_diff("a.txt", "one\n", "two\n");
+
+        let full = GetAttemptDiffResponse::from_repo_diffs(vec![repo_diff()], false, 50_000);
+        assert!(!full.truncated);
+        assert_eq!(full.files_changed, 2);
+        assert_eq!((full.additions, full.deletions), (3, 2));
+        assert!(full.repos[0].files.iter().all(|f| f.patch.is_some()));
+
+        // Only the first patch fits; the second file is still listed with its counts
+        let limited =
+            GetAttemptDiffResponse::from_repo_diffs(vec![repo_diff()], false, first_patch.len());
+        assert!(limited.truncated);
+        let files = &limited.repos[0].files;
+        assert_eq!(files[0].patch.as_deref(), Some(first_patch.as_str()));
+        assert_eq!(files[1].patch, None);
+        assert_eq!((files[1].additions, files[1].deletions), (2, 1));
+
+        let stats = GetAttemptDiffResponse::from_repo_diffs(vec![repo_diff()], true, 50_000);
+        assert!(!stats.truncated);
+        assert!(stats.repos[0].files.iter().all(|f| f.patch.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_attempt_in_other_project_is_rejected() {
+        let project_id = Uuid::new_v4();
+        let now = Utc::now();
+        let task = Task {
+            id: Uuid::new_v4(),
+            project_id,
+            title: "Task".to_string(),
+            description: None,
+            status: TaskStatus::Todo,
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            parent_workspace_id: None,
+            shared_task_id: None,
+            archived_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let workspace = Workspace {
+            id: Uuid::new_v4(),
+            task_id: task.id,
+            container_ref: None,
+            branch: "vk/task".to_string(),
+            agent_working_dir: None,
+            setup_completed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let (attempt_id, task_json, workspace_json) = (
+            workspace.id,
+            serde_json::to_value(&task).unwrap(),
+            serde_json::to_value(&workspace).unwrap(),
+        );
+        let router = axum::Router::new()
+            .route(
+                "/api/task-attempts/{id}",
+                get(move || {
+                    std::future::ready(Json(ApiResponse::<_, ()>::success(workspace_json.clone())))
+                }),
+            )
+            .route(
+                "/api/tasks/{id}",
+                get(move || {
+                    std::future::ready(Json(ApiResponse::<_, ()>::success(task_json.clone())))
+                }),
+            );
+        let server = TaskServer {
+            client: reqwest::Client::new(),
+            base_url: "http://localhost".to_string(),
+            router: Some(router),
+            events: None,
+            subscriptions: Subscriptions::default(),
+            tool_router: TaskServer::tool_router(),
+            context: None,
+        };
+
+        let (found, _) = server
+            .attempt_in_project(project_id, attempt_id)
+            .await
+            .unwrap();
+        assert_eq!(found.id, attempt_id);
+
+        let rejected = server
+            .attempt_in_project(Uuid::new_v4(), attempt_id)
+            .await
+            .unwrap_err();
+        assert_eq!(rejected.is_error, Some(true));
+    }
 }
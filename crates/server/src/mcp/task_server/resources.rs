@@ -0,0 +1,629 @@
+//! MCP resources (projects, tasks and plan files) and prompts (tag snippets) for
+//! [`TaskServer`]. Resource reads go through the same VK API as the tools.
+
+use std::{
+    collections::HashSet,
+    fmt::Write as _,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessRunReason},
+    project::Project,
+    tag::Tag,
+    task::{Task, TaskWithAttemptStatus},
+    workspace::Workspace,
+};
+use executors::logs::{NormalizedEntry, NormalizedEntryType};
+use rmcp::{
+    ErrorData, Peer, RoleServer,
+    model::{
+        AnnotateAble, GetPromptResult, Prompt, PromptArgument, PromptMessage, PromptMessageRole,
+        RawResource, RawResourceTemplate, ReadResourceResult, Resource, ResourceContents,
+        ResourceTemplate, ResourceUpdatedNotificationParam,
+    },
+};
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use services::services::{events::EventService, plans::PlanMetadata};
+use tokio::sync::broadcast::error::RecvError;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+use super::{
+    ApiResponseEnvelope, McpProcessSummary, ProjectSummary, TaskServer, TaskSummary, truncate_chars,
+};
+use crate::routes::plans::PlanFileContent;
+
+const PROJECTS_URI: &str = "vibe-kanban://projects";
+
+/// Optional prompt argument appended after the tag's snippet
+const PROMPT_INPUT_ARG: &str = "input";
+
+/// Parsed `vibe-kanban://` resource URI
+#[derive(Debug, PartialEq, Eq)]
+enum ResourceUri {
+    Projects,
+    Project(Uuid),
+    Task { project_id: Uuid, task_id: Uuid },
+    Plan { project_id: Uuid, plan_id: String },
+}
+
+impl ResourceUri {
+    fn parse(uri: &str) -> Option<Self> {
+        let rest = uri.strip_prefix(PROJECTS_URI)?;
+        if rest.is_empty() {
+            return Some(Self::Projects);
+        }
+        let mut segments = rest.strip_prefix('/')?.splitn(3, '/');
+        let project_id = Uuid::parse_str(segments.next()?).ok()?;
+        match (segments.next(), segments.next()) {
+            (None, _) => Some(Self::Project(project_id)),
+            (Some("tasks"), Some(task_id)) => Some(Self::Task {
+                project_id,
+                task_id: Uuid::parse_str(task_id).ok()?,
+            }),
+            (Some("plans"), Some(plan_id)) if !plan_id.is_empty() => Some(Self::Plan {
+                project_id,
+                plan_id: plan_id.to_string(),
+            }),
+            _ => None,
+        }
+    }
+
+    fn project(project_id: Uuid) -> String {
+        format!("{PROJECTS_URI}/{project_id}")
+    }
+
+    fn task(project_id: Uuid, task_id: Uuid) -> String {
+        format!("{PROJECTS_URI}/{project_id}/tasks/{task_id}")
+    }
+
+    fn plan(project_id: Uuid, plan_id: &str) -> String {
+        format!("{PROJECTS_URI}/{project_id}/plans/{plan_id}")
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct ProjectResource {
+    project: ProjectSummary,
+    tasks: Vec<TaskSummary>,
+}
+
+/// Resource subscriptions of one MCP session, notified from [`EventService`] task and project
+/// patches
+#[derive(Clone, Default)]
+pub(super) struct Subscriptions {
+    uris: Arc<Mutex<HashSet<String>>>,
+    watching: Arc<AtomicBool>,
+}
+
+impl Subscriptions {
+    fn insert(&self, uri: String) {
+        self.uris.lock().unwrap().insert(uri);
+    }
+
+    fn remove(&self, uri: &str) {
+        self.uris.lock().unwrap().remove(uri);
+    }
+
+    fn contains(&self, uri: &str) -> bool {
+        self.uris.lock().unwrap().contains(uri)
+    }
+
+    /// Subscribed URIs ending with `suffix`, for records whose project isn't known (removals)
+    fn matching_suffix(&self, suffix: &str) -> Vec<String> {
+        self.uris
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|uri| uri.ends_with(suffix))
+            .cloned()
+            .collect()
+    }
+
+    /// Start forwarding changes to `peer`, once per session
+    fn watch(&self, events: &EventService, peer: Peer<RoleServer>) {
+        if self.watching.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let subscriptions = self.clone();
+        let mut receiver = events.msg_store().get_receiver();
+        tokio::spawn(async move {
+            loop {
+                let patch = match receiver.recv().await {
+                    Ok(LogMsg::JsonPatch(patch)) => patch,
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(Value::Array(ops)) = serde_json::to_value(&patch) else {
+                    continue;
+                };
+                let (updated, list_changed) = subscriptions.affected_uris(&ops);
+                for uri in updated {
+                    if peer
+                        .notify_resource_updated(ResourceUpdatedNotificationParam { uri })
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+                if list_changed && peer.notify_resource_list_changed().await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Subscribed URIs touched by a patch, and whether resources were added or removed
+    fn affected_uris(&self, ops: &[Value]) -> (Vec<String>, bool) {
+        let mut updated = Vec::new();
+        let mut list_changed = false;
+        for op in ops {
+            let path = op["path"].as_str().unwrap_or_default();
+            let kind = op["op"].as_str().unwrap_or_default();
+            let project_id = op["value"]["project_id"]
+                .as_str()
+                .and_then(|id| Uuid::parse_str(id).ok());
+
+            if let Some(task_id) = path.strip_prefix("/tasks/") {
+                list_changed |= kind != "replace";
+                updated.extend(self.matching_suffix(&format!("/tasks/{task_id}")));
+                if let Some(project_id) = project_id {
+                    updated.push(ResourceUri::project(project_id));
+                }
+            } else if let Some(project_id) = path.strip_prefix("/projects/") {
+                list_changed |= kind != "replace";
+                updated.push(PROJECTS_URI.to_string());
+                updated.push(format!("{PROJECTS_URI}/{project_id}"));
+            }
+        }
+        updated.retain(|uri| self.contains(uri));
+        updated.sort();
+        updated.dedup();
+        (updated, list_changed)
+    }
+}
+
+impl TaskServer {
+    /// [`TaskServer::send_json`] for resource and prompt handlers, which report protocol errors
+    async fn fetch<T: DeserializeOwned>(
+        &self,
+        rb: reqwest::RequestBuilder,
+    ) -> Result<T, ErrorData> {
+        let (status, body) = self.execute(rb).await.map_err(|e| {
+            ErrorData::internal_error(format!("Failed to connect to VK API: {e}"), None)
+        })?;
+        if !status.is_success() {
+            return Err(ErrorData::internal_error(
+                format!("VK API returned error status: {status}"),
+                None,
+            ));
+        }
+        let envelope = serde_json::from_slice::<ApiResponseEnvelope<T>>(&body).map_err(|e| {
+            ErrorData::internal_error(format!("Failed to parse VK API response: {e}"), None)
+        })?;
+        if !envelope.success {
+            let msg = envelope
+                .message
+                .unwrap_or_else(|| "Unknown error".to_string());
+            return Err(ErrorData::internal_error(
+                format!("VK API returned error: {msg}"),
+                None,
+            ));
+        }
+        envelope
+            .data
+            .ok_or_else(|| ErrorData::internal_error("VK API response missing data field", None))
+    }
+
+    async fn fetch_projects(&self) -> Result<Vec<Project>, ErrorData> {
+        self.fetch(self.client.get(self.url("/api/projects"))).await
+    }
+
+    async fn fetch_tasks(&self, project_id: Uuid) -> Result<Vec<TaskWithAttemptStatus>, ErrorData> {
+        let url = self.url(&format!("/api/tasks?project_id={}", project_id));
+        self.fetch(self.client.get(url)).await
+    }
+
+    async fn fetch_plans(&self, project_id: Uuid) -> Result<Vec<PlanMetadata>, ErrorData> {
+        let url = self.url(&format!("/api/plans?project_id={}", project_id));
+        self.fetch(self.client.get(url)).await
+    }
+
+    pub(super) async fn resources(&self) -> Result<Vec<Resource>, ErrorData> {
+        let mut resources = vec![resource(
+            PROJECTS_URI.to_string(),
+            "Projects".to_string(),
+            "All Vibe Kanban projects",
+            "application/json",
+        )];
+
+        for project in self.fetch_projects().await? {
+            resources.push(resource(
+                ResourceUri::project(project.id),
+                project.name.clone(),
+                "Project summary and its tasks",
+                "application/json",
+            ));
+
+            for task in self.fetch_tasks(project.id).await? {
+                resources.push(resource(
+                    ResourceUri::task(project.id, task.id),
+                    task.title.clone(),
+                    &format!("Task in {} ({})", project.name, task.status),
+                    "text/markdown",
+                ));
+            }
+
+            // Plans need a scanner and a configured repo; projects without them have none
+            let plans = match self.fetch_plans(project.id).await {
+                Ok(plans) => plans,
+                Err(e) => {
+                    tracing::debug!("Skipping plans for project {}: {}", project.id, e.message);
+                    continue;
+                }
+            };
+            for plan in plans {
+                resources.push(resource(
+                    ResourceUri::plan(project.id, &plan.id),
+                    plan.title.clone().unwrap_or_else(|| plan.name.clone()),
+                    &format!("Plan in {} ({}% complete)", project.name, plan.progress),
+                    "text/markdown",
+                ));
+            }
+        }
+
+        Ok(resources)
+    }
+
+    pub(super) fn resource_templates() -> Vec<ResourceTemplate> {
+        [
+            (
+                format!("{PROJECTS_URI}/{{project_id}}"),
+                "project",
+                "Project summary and its tasks",
+                "application/json",
+            ),
+            (
+                format!("{PROJECTS_URI}/{{project_id}}/tasks/{{task_id}}"),
+                "task",
+                "Task description and latest attempt summary",
+                "text/markdown",
+            ),
+            (
+                format!("{PROJECTS_URI}/{{project_id}}/plans/{{plan_id}}"),
+                "plan",
+                "Plan markdown file",
+                "text/markdown",
+            ),
+        ]
+        .into_iter()
+        .map(|(uri_template, name, description, mime_type)| {
+            RawResourceTemplate {
+                uri_template,
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                mime_type: Some(mime_type.to_string()),
+            }
+            .no_annotation()
+        })
+        .collect()
+    }
+
+    pub(super) async fn read_resource_uri(
+        &self,
+        uri: &str,
+    ) -> Result<ReadResourceResult, ErrorData> {
+        let (text, mime_type) = match ResourceUri::parse(uri) {
+            Some(ResourceUri::Projects) => {
+                let projects: Vec<ProjectSummary> = self
+                    .fetch_projects()
+                    .await?
+                    .into_iter()
+                    .map(ProjectSummary::from_project)
+                    .collect();
+                (to_json(&projects)?, "application/json")
+            }
+            Some(ResourceUri::Project(project_id)) => {
+                let project: Project = self
+                    .fetch(
+                        self.client
+                            .get(self.url(&format!("/api/projects/{}", project_id))),
+                    )
+                    .await?;
+                let tasks = self
+                    .fetch_tasks(project_id)
+                    .await?
+                    .into_iter()
+                    .map(TaskSummary::from_task_with_status)
+                    .collect();
+                let resource = ProjectResource {
+                    project: ProjectSummary::from_project(project),
+                    tasks,
+                };
+                (to_json(&resource)?, "application/json")
+            }
+            Some(ResourceUri::Task {
+                project_id,
+                task_id,
+            }) => {
+                let task: Task = self
+                    .fetch(
+                        self.client
+                            .get(self.url(&format!("/api/tasks/{}", task_id))),
+                    )
+                    .await?;
+                if task.project_id != project_id {
+                    return Err(not_found(uri));
+                }
+                (self.render_task(task).await?, "text/markdown")
+            }
+            Some(ResourceUri::Plan {
+                project_id,
+                plan_id,
+            }) => {
+                let plan = self
+                    .fetch_plans(project_id)
+                    .await?
+                    .into_iter()
+                    .find(|plan| plan.id == plan_id)
+                    .ok_or_else(|| not_found(uri))?;
+                let file: PlanFileContent = self
+                    .fetch(self.client.get(self.url("/api/plans/file")).query(&[
+                        ("project_id", project_id.to_string()),
+                        ("file_path", plan.path),
+                    ]))
+                    .await?;
+                (file.content, "text/markdown")
+            }
+            None => return Err(not_found(uri)),
+        };
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some(mime_type.to_string()),
+                text,
+            }],
+        })
+    }
+
+    /// Task description followed by the latest attempt's state and the agent's last message
+    async fn render_task(&self, task: Task) -> Result<String, ErrorData> {
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}\n", task.title);
+        let _ = writeln!(out, "- Task ID: {}", task.id);
+        let _ = writeln!(out, "- Project ID: {}", task.project_id);
+        let _ = writeln!(out, "- Status: {}", task.status);
+        let _ = writeln!(out, "- Updated: {}\n", task.updated_at.to_rfc3339());
+        let _ = writeln!(out, "## Description\n");
+        match task.description.as_deref().filter(|d| !d.trim().is_empty()) {
+            Some(description) => {
+                let _ = writeln!(out, "{}\n", description.trim_end());
+            }
+            None => {
+                let _ = writeln!(out, "_No description_\n");
+            }
+        }
+
+        let _ = writeln!(out, "## Latest attempt\n");
+        let attempts: Vec<Workspace> = self
+            .fetch(
+                self.client
+                    .get(self.url("/api/task-attempts"))
+                    .query(&[("task_id", task.id.to_string())]),
+            )
+            .await?;
+        // Attempts come back newest first
+        let Some(attempt) = attempts.into_iter().next() else {
+            let _ = writeln!(out, "_No attempts yet_");
+            return Ok(out);
+        };
+        let _ = writeln!(out, "- Attempt ID: {}", attempt.id);
+        let _ = writeln!(out, "- Branch: {}", attempt.branch);
+        let _ = writeln!(out, "- Started: {}", attempt.created_at.to_rfc3339());
+
+        let processes: Vec<ExecutionProcess> = self
+            .fetch(
+                self.client
+                    .get(self.url("/api/execution-processes"))
+                    .query(&[("workspace_id", attempt.id.to_string())]),
+            )
+            .await?;
+        if let Some(process) = processes.last() {
+            let summary = McpProcessSummary::from_process(process);
+            let _ = write!(
+                out,
+                "- Latest process: {} ({})",
+                summary.run_reason, summary.status
+            );
+            match summary.exit_code {
+                Some(code) => {
+                    let _ = writeln!(out, ", exit code {code}");
+                }
+                None => out.push('\n'),
+            }
+        }
+
+        let Some(agent_run) = processes
+            .iter()
+            .rev()
+            .find(|p| p.run_reason == ExecutionProcessRunReason::CodingAgent)
+        else {
+            return Ok(out);
+        };
+        let url = self.url(&format!(
+            "/api/execution-processes/{}/normalized-logs",
+            agent_run.id
+        ));
+        let entries: Vec<NormalizedEntry> = self.fetch(self.client.get(url)).await?;
+        if let Some(message) = entries
+            .iter()
+            .rev()
+            .find(|entry| matches!(entry.entry_type, NormalizedEntryType::AssistantMessage))
+        {
+            let _ = writeln!(out, "\n### Agent summary\n");
+            let _ = writeln!(out, "{}", truncate_chars(message.content.trim_end(), 4000));
+        }
+
+        Ok(out)
+    }
+
+    pub(super) async fn tag_prompts(&self) -> Result<Vec<Prompt>, ErrorData> {
+        let tags: Vec<Tag> = self.fetch(self.client.get(self.url("/api/tags"))).await?;
+        Ok(tags
+            .into_iter()
+            .map(|tag| {
+                let description = tag
+                    .content
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| truncate_chars(line.trim(), 120));
+                Prompt::new(
+                    tag.tag_name,
+                    description,
+                    Some(vec![PromptArgument {
+                        name: PROMPT_INPUT_ARG.to_string(),
+                        description: Some("Text to append after the snippet".to_string()),
+                        required: Some(false),
+                    }]),
+                )
+            })
+            .collect())
+    }
+
+    pub(super) async fn tag_prompt(
+        &self,
+        name: &str,
+        input: Option<&str>,
+    ) -> Result<GetPromptResult, ErrorData> {
+        let tags: Vec<Tag> = self.fetch(self.client.get(self.url("/api/tags"))).await?;
+        let tag = tags
+            .into_iter()
+            .find(|tag| tag.tag_name == name)
+            .ok_or_else(|| ErrorData::invalid_params(format!("Unknown prompt '{name}'"), None))?;
+
+        let mut text = tag.content;
+        if let Some(input) = input.filter(|input| !input.trim().is_empty()) {
+            text.push_str("\n\n");
+            text.push_str(input);
+        }
+
+        Ok(GetPromptResult {
+            description: Some(format!("Prompt snippet @{}", tag.tag_name)),
+            messages: vec![PromptMessage::new_text(PromptMessageRole::User, text)],
+        })
+    }
+
+    pub(super) fn subscribe_uri(
+        &self,
+        uri: String,
+        peer: Peer<RoleServer>,
+    ) -> Result<(), ErrorData> {
+        let Some(events) = &self.events else {
+            return Err(ErrorData::invalid_request(
+                "Resource subscriptions are only available on the backend's HTTP endpoint",
+                None,
+            ));
+        };
+        if ResourceUri::parse(&uri).is_none() {
+            return Err(not_found(&uri));
+        }
+        self.subscriptions.insert(uri);
+        self.subscriptions.watch(events, peer);
+        Ok(())
+    }
+
+    pub(super) fn unsubscribe_uri(&self, uri: &str) {
+        self.subscriptions.remove(uri);
+    }
+}
+
+fn resource(uri: String, name: String, description: &str, mime_type: &str) -> Resource {
+    let mut raw = RawResource::new(uri, name);
+    raw.description = Some(description.to_string());
+    raw.mime_type = Some(mime_type.to_string());
+    raw.no_annotation()
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String, ErrorData> {
+    serde_json::to_string_pretty(value)
+        .map_err(|e| ErrorData::internal_error(format!("Failed to serialize resource: {e}"), None))
+}
+
+fn not_found(uri: &str) -> ErrorData {
+    ErrorData::resource_not_found(format!("Unknown resource '{uri}'"), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        let project_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+
+        assert_eq!(
+            ResourceUri::parse(PROJECTS_URI),
+            Some(ResourceUri::Projects)
+        );
+        assert_eq!(
+            ResourceUri::parse(&ResourceUri::project(project_id)),
+            Some(ResourceUri::Project(project_id))
+        );
+        assert_eq!(
+            ResourceUri::parse(&ResourceUri::task(project_id, task_id)),
+            Some(ResourceUri::Task {
+                project_id,
+                task_id
+            })
+        );
+        assert_eq!(
+            ResourceUri::parse(&ResourceUri::plan(project_id, "2025-01-auth/plan")),
+            Some(ResourceUri::Plan {
+                project_id,
+                plan_id: "2025-01-auth/plan".to_string()
+            })
+        );
+
+        assert_eq!(ResourceUri::parse("vibe-kanban://projectsx"), None);
+        assert_eq!(
+            ResourceUri::parse("vibe-kanban://projects/not-a-uuid"),
+            None
+        );
+        assert_eq!(
+            ResourceUri::parse(&format!("{}/tasks/", ResourceUri::project(project_id))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_affected_uris_only_includes_subscriptions() {
+        let project_id = Uuid::new_v4();
+        let task_id = Uuid::new_v4();
+        let subscriptions = Subscriptions::default();
+        subscriptions.insert(ResourceUri::task(project_id, task_id));
+
+        let update = json!([{
+            "op": "replace",
+            "path": format!("/tasks/{task_id}"),
+            "value": { "id": task_id, "project_id": project_id }
+        }]);
+        let (updated, list_changed) = subscriptions.affected_uris(update.as_array().unwrap());
+        assert_eq!(updated, vec![ResourceUri::task(project_id, task_id)]);
+        assert!(!list_changed);
+
+        let removal = json!([{ "op": "remove", "path": format!("/tasks/{}", Uuid::new_v4()) }]);
+        let (updated, list_changed) = subscriptions.affected_uris(removal.as_array().unwrap());
+        assert!(updated.is_empty());
+        assert!(list_changed);
+    }
+}
@@ -0,0 +1,233 @@
+//! Token authentication for remote access mode. Requests authenticate either with an
+//! access token (`Authorization: Bearer vk_...`, used by the MCP server and scripts) or with
+//! the session cookie issued by exchanging a token at `/api/auth/login`.
+
+use std::fmt::Write as _;
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{Duration, Utc};
+use db::models::access_token::{AccessToken, AuthSession};
+use rand::{Rng, distributions::Alphanumeric};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+
+pub const SESSION_COOKIE: &str = "vk_session";
+const SESSION_TTL: Duration = Duration::days(30);
+const TOKEN_PREFIX: &str = "vk_";
+const TOKEN_DISPLAY_LEN: usize = 10;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AuthSettings {
+    /// Reject unauthenticated `/api` requests
+    pub required: bool,
+    /// Mark the session cookie `Secure`, set when serving over TLS
+    pub secure_cookies: bool,
+}
+
+impl AuthSettings {
+    /// `VK_AUTH_REQUIRED=true` turns on remote access mode
+    pub fn from_env(tls: bool) -> Self {
+        let required = std::env::var("VK_AUTH_REQUIRED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        Self {
+            required,
+            secure_cookies: tls,
+        }
+    }
+
+    pub fn session_cookie(&self, value: &str) -> HeaderValue {
+        self.cookie(value, SESSION_TTL.num_seconds())
+    }
+
+    pub fn cleared_session_cookie(&self) -> HeaderValue {
+        self.cookie("", 0)
+    }
+
+    fn cookie(&self, value: &str, max_age: i64) -> HeaderValue {
+        let mut cookie =
+            format!("{SESSION_COOKIE}={value}; Path=/; HttpOnly; SameSite=Lax; Max-Age={max_age}");
+        if self.secure_cookies {
+            cookie.push_str("; Secure");
+        }
+        HeaderValue::from_str(&cookie).expect("cookie is valid header value")
+    }
+}
+
+/// A new access token; the caller shows it once and stores only [`hash_secret`]
+pub fn generate_token() -> String {
+    format!("{TOKEN_PREFIX}{}", random_alphanumeric(40))
+}
+
+pub fn generate_session_secret() -> String {
+    random_alphanumeric(48)
+}
+
+pub fn token_prefix(token: &str) -> &str {
+    &token[..token.len().min(TOKEN_DISPLAY_LEN)]
+}
+
+pub fn hash_secret(secret: &str) -> String {
+    let mut output = String::with_capacity(64);
+    for byte in Sha256::digest(secret.as_bytes()) {
+        let _ = write!(output, "{:02x}", byte);
+    }
+    output
+}
+
+fn random_alphanumeric(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+pub fn session_cookie(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            (name == SESSION_COOKIE && !value.is_empty()).then_some(value)
+        })
+}
+
+/// Validate an access token and return its id
+pub async fn verify_token(pool: &SqlitePool, token: &str) -> Result<Option<Uuid>, sqlx::Error> {
+    let Some(access_token) = AccessToken::find_by_hash(pool, &hash_secret(token)).await? else {
+        return Ok(None);
+    };
+    AccessToken::touch(pool, access_token.id).await?;
+    Ok(Some(access_token.id))
+}
+
+/// Id of the access token the request authenticates with, from the bearer token or the
+/// session cookie
+pub async fn authenticate(
+    pool: &SqlitePool,
+    headers: &HeaderMap,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    if let Some(token) = bearer_token(headers) {
+        return verify_token(pool, token).await;
+    }
+    if let Some(secret) = session_cookie(headers) {
+        return Ok(AuthSession::find_valid_by_hash(pool, &hash_secret(secret))
+            .await?
+            .map(|session| session.token_id));
+    }
+    Ok(None)
+}
+
+/// Start a browser session for `token_id`, returning the cookie value. Expired sessions are
+/// pruned on the way.
+pub async fn issue_session(pool: &SqlitePool, token_id: Uuid) -> Result<String, sqlx::Error> {
+    AuthSession::delete_expired(pool).await?;
+    let secret = generate_session_secret();
+    AuthSession::create(
+        pool,
+        token_id,
+        &hash_secret(&secret),
+        Utc::now() + SESSION_TTL,
+    )
+    .await?;
+    Ok(secret)
+}
+
+/// Create a first token when remote access is on and none exist yet, so the operator can log
+/// in. Returns the new token.
+pub async fn ensure_bootstrap_token(pool: &SqlitePool) -> Result<Option<String>, sqlx::Error> {
+    if AccessToken::count(pool).await? > 0 {
+        return Ok(None);
+    }
+    let token = generate_token();
+    AccessToken::create(
+        pool,
+        "bootstrap",
+        &hash_secret(&token),
+        token_prefix(&token),
+    )
+    .await?;
+    Ok(Some(token))
+}
+
+pub async fn require_auth(
+    State(pool): State<SqlitePool>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    match authenticate(&pool, request.headers()).await? {
+        Some(_) => Ok(next.run(request).await),
+        None => Err(ApiError::Unauthorized),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_cookie_parsing() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("theme=dark; vk_session=abc123; other=1"),
+        );
+        assert_eq!(session_cookie(&headers), Some("abc123"));
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("vk_session="));
+        assert_eq!(session_cookie(&headers), None);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(bearer_token(&headers), None);
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer vk_secret"),
+        );
+        assert_eq!(bearer_token(&headers), Some("vk_secret"));
+    }
+
+    #[test]
+    fn test_generated_tokens() {
+        let token = generate_token();
+        assert!(token.starts_with(TOKEN_PREFIX));
+        assert_eq!(token.len(), TOKEN_PREFIX.len() + 40);
+        assert_eq!(token_prefix(&token).len(), TOKEN_DISPLAY_LEN);
+        assert_ne!(hash_secret(&token), token);
+        assert_eq!(hash_secret(&token), hash_secret(&token));
+    }
+
+    #[test]
+    fn test_secure_cookie_flag() {
+        let plain = AuthSettings::default().session_cookie("x");
+        assert!(!plain.to_str().unwrap().contains("Secure"));
+        let secure = AuthSettings {
+            required: true,
+            secure_cookies: true,
+        }
+        .session_cookie("x");
+        assert!(secure.to_str().unwrap().ends_with("; Secure"));
+    }
+}
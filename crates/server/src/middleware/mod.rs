@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod model_loaders;
 
 pub use model_loaders::*;
@@ -1,22 +1,68 @@
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    http::StatusCode,
-    routing::post,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{Html, IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post},
 };
+use db::models::{access_token::AccessToken, approval::ApprovalRecord};
 use deployment::Deployment;
-use utils::approvals::{ApprovalResponse, ApprovalStatus};
+use serde::Deserialize;
+use services::services::approvals::{UI_RESPONDER, webhook::ApprovalLinkDecision};
+use ts_rs::TS;
+use utils::{
+    approvals::{ApprovalResponse, ApprovalStatus},
+    response::ApiResponse,
+};
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError, middleware::auth};
 
-use crate::DeploymentImpl;
+#[derive(Debug, Deserialize, TS)]
+pub struct ApprovalHistoryQuery {
+    #[serde(default)]
+    pub task_id: Option<Uuid>,
+    #[serde(default)]
+    pub session_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ApprovalLinkQuery {
+    pub decision: ApprovalLinkDecision,
+    pub token: String,
+    /// Passed to the agent when denying
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Who answered through the API: the access token the request authenticated with, or the
+/// local UI
+async fn api_responder(deployment: &DeploymentImpl, headers: &HeaderMap) -> String {
+    let pool = &deployment.db().pool;
+    let token = match auth::authenticate(pool, headers).await {
+        Ok(Some(token_id)) => AccessToken::find_by_id(pool, token_id).await.ok().flatten(),
+        _ => None,
+    };
+    match token {
+        Some(token) => format!("token '{}'", token.name),
+        None => UI_RESPONDER.to_string(),
+    }
+}
 
 pub async fn respond_to_approval(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<String>,
+    headers: HeaderMap,
     Json(request): Json<ApprovalResponse>,
 ) -> Result<Json<ApprovalStatus>, StatusCode> {
     let service = deployment.approvals();
+    let responder = Some(api_responder(&deployment, &headers).await);
 
-    match service.respond(&deployment.db().pool, &id, request).await {
+    match service
+        .respond(&deployment.db().pool, &id, request, responder)
+        .await
+    {
         Ok((status, context)) => {
             deployment
                 .track_if_analytics_allowed(
@@ -39,6 +85,155 @@ pub async fn respond_to_approval(
     }
 }
 
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn link_page(title: &str, body: &str) -> Html<String> {
+    Html(format!(
+        r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Vibe Kanban - {title}</title>
+<style>
+  body {{ font-family: system-ui, sans-serif; display: flex; justify-content: center; margin-top: 15vh; }}
+  main, form {{ display: flex; flex-direction: column; gap: 0.75rem; width: 22rem; }}
+  textarea, button {{ font-size: 1rem; padding: 0.5rem; }}
+</style>
+</head>
+<body>
+<main>
+  <h2>{title}</h2>
+  {body}
+</main>
+</body>
+</html>
+"#
+    ))
+}
+
+/// Confirmation page for a signed approve/deny link. Opening a link never answers the
+/// approval, so link previews and prefetchers can't approve tool calls; the page's form
+/// POSTs back to the same URL.
+pub async fn confirm_link_response(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Query(query): Query<ApprovalLinkQuery>,
+) -> Result<Html<String>, ApiError> {
+    let context = deployment
+        .approvals()
+        .check_link(&id, query.decision, &query.token)?;
+    let tool_name = escape_html(&context.tool_name);
+
+    Ok(match query.decision {
+        ApprovalLinkDecision::Approve => link_page(
+            "Approve tool call",
+            &format!(
+                "<p>Allow the agent to run <code>{tool_name}</code>?</p>\n  <form method=\"post\"><button type=\"submit\">Approve</button></form>"
+            ),
+        ),
+        ApprovalLinkDecision::Deny => link_page(
+            "Deny tool call",
+            &format!(
+                "<p>Deny the agent's <code>{tool_name}</code> call?</p>\n  <form method=\"post\">\n    <label for=\"reason\">Reason (optional)</label>\n    <textarea id=\"reason\" name=\"reason\" rows=\"3\">{}</textarea>\n    <button type=\"submit\">Deny</button>\n  </form>",
+                escape_html(query.reason.as_deref().unwrap_or_default())
+            ),
+        ),
+    })
+}
+
+/// `reason` field of a form-encoded body, as sent by the confirmation page
+fn form_reason(headers: &HeaderMap, body: &Bytes) -> Option<String> {
+    let is_form = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/x-www-form-urlencoded"));
+    if !is_form {
+        return None;
+    }
+    url::form_urlencoded::parse(body)
+        .find(|(key, _)| key == "reason")
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|reason| !reason.is_empty())
+}
+
+/// Answer an approval through a signed one-time link, e.g. from a webhook consumer or the
+/// confirmation page. The link's token authenticates the request; it only works while the
+/// approval is pending.
+pub async fn respond_via_link(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<String>,
+    Query(query): Query<ApprovalLinkQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let wants_html = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("text/html"));
+    let reason = form_reason(&headers, &body).or(query.reason);
+    let (status, context) = deployment
+        .approvals()
+        .respond_via_link(&id, query.decision, &query.token, reason)
+        .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "approval_responded",
+            serde_json::json!({
+                "approval_id": &id,
+                "status": format!("{:?}", status),
+                "tool_name": context.tool_name,
+                "execution_process_id": context.execution_process_id.to_string(),
+                "via": "signed_link",
+            }),
+        )
+        .await;
+
+    if wants_html {
+        let title = match status {
+            ApprovalStatus::Approved => "Tool call approved",
+            _ => "Tool call denied",
+        };
+        return Ok(link_page(title, "<p>You can close this page.</p>").into_response());
+    }
+    Ok(ResponseJson(ApiResponse::success(status)).into_response())
+}
+
+/// Approval audit trail for a task or a session, newest first
+pub async fn get_approval_history(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ApprovalHistoryQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalRecord>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let records = match (query.task_id, query.session_id) {
+        (Some(task_id), None) => ApprovalRecord::find_by_task_id(pool, task_id).await?,
+        (None, Some(session_id)) => ApprovalRecord::find_by_session_id(pool, session_id).await?,
+        _ => {
+            return Err(ApiError::BadRequest(
+                "Exactly one of task_id or session_id is required".to_string(),
+            ));
+        }
+    };
+    Ok(ResponseJson(ApiResponse::success(records)))
+}
+
 pub fn router() -> Router<DeploymentImpl> {
-    Router::new().route("/approvals/{id}/respond", post(respond_to_approval))
+    Router::new()
+        .route("/approvals", get(get_approval_history))
+        .route("/approvals/{id}/respond", post(respond_to_approval))
+}
+
+/// Signed links authenticate with their own token, so they stay reachable in remote
+/// access mode
+pub fn public_router() -> Router<DeploymentImpl> {
+    Router::new().route(
+        "/approvals/{id}/respond/link",
+        get(confirm_link_response).post(respond_via_link),
+    )
 }
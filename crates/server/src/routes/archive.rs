@@ -0,0 +1,45 @@
+use axum::{Json, Router, extract::State, response::Json as ResponseJson, routing::post};
+use deployment::Deployment;
+use serde::Deserialize;
+use services::services::archival::{self, DEFAULT_ARCHIVE_RETENTION_DAYS, PurgeArchivedResult};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct PurgeArchivedRequest {
+    /// Only purge items archived at least this many days ago (default 30); 0 purges everything
+    /// archived
+    pub retention_days: Option<u32>,
+}
+
+/// Permanently delete archived tasks and projects past the retention window
+pub async fn purge_archived(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<PurgeArchivedRequest>,
+) -> Result<ResponseJson<ApiResponse<PurgeArchivedResult>>, ApiError> {
+    let retention_days = payload
+        .retention_days
+        .unwrap_or(DEFAULT_ARCHIVE_RETENTION_DAYS);
+    let publisher = deployment.share_publisher().ok();
+    let result =
+        archival::purge_archived(&deployment.db().pool, publisher.as_ref(), retention_days).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "archive_purged",
+            serde_json::json!({
+                "retention_days": retention_days,
+                "task_count": result.purged_task_ids.len(),
+                "project_count": result.purged_project_ids.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(result)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/archive/purge", post(purge_archived))
+}
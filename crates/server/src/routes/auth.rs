@@ -0,0 +1,198 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{Path, State},
+    http::{HeaderMap, header},
+    response::{Html, IntoResponse, Json as ResponseJson, Response},
+    routing::{delete, get, post},
+};
+use db::models::access_token::{AccessToken, AuthSession, CreateAccessToken};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::auth::{
+        AuthSettings, authenticate, generate_token, hash_secret, issue_session, session_cookie,
+        token_prefix, verify_token,
+    },
+};
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct AuthStatus {
+    /// Whether the server requires authentication
+    pub required: bool,
+    pub authenticated: bool,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct LoginRequest {
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct CreatedAccessToken {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub access_token: AccessToken,
+    /// The token itself; it is only returned once
+    pub token: String,
+}
+
+const LOGIN_PAGE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Vibe Kanban - Sign in</title>
+<style>
+  body { font-family: system-ui, sans-serif; display: flex; justify-content: center; margin-top: 15vh; }
+  form { display: flex; flex-direction: column; gap: 0.75rem; width: 22rem; }
+  input, button { font-size: 1rem; padding: 0.5rem; }
+  #error { color: #b91c1c; min-height: 1.25rem; }
+</style>
+</head>
+<body>
+<form id="login">
+  <h2>Vibe Kanban</h2>
+  <label for="token">Access token</label>
+  <input id="token" type="password" autocomplete="current-password" autofocus required>
+  <button type="submit">Sign in</button>
+  <div id="error"></div>
+</form>
+<script>
+  document.getElementById('login').addEventListener('submit', async (e) => {
+    e.preventDefault();
+    const res = await fetch('/api/auth/login', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ token: document.getElementById('token').value.trim() }),
+    });
+    if (res.ok) {
+      window.location.assign('/');
+    } else {
+      document.getElementById('error').textContent = 'Invalid access token';
+    }
+  });
+</script>
+</body>
+</html>
+"#;
+
+pub async fn login_page() -> Html<&'static str> {
+    Html(LOGIN_PAGE)
+}
+
+pub async fn get_auth_status(
+    State(deployment): State<DeploymentImpl>,
+    Extension(settings): Extension<AuthSettings>,
+    headers: HeaderMap,
+) -> Result<ResponseJson<ApiResponse<AuthStatus>>, ApiError> {
+    let authenticated = !settings.required
+        || authenticate(&deployment.db().pool, &headers)
+            .await?
+            .is_some();
+    Ok(ResponseJson(ApiResponse::success(AuthStatus {
+        required: settings.required,
+        authenticated,
+    })))
+}
+
+/// Exchange an access token for a session cookie
+pub async fn login(
+    State(deployment): State<DeploymentImpl>,
+    Extension(settings): Extension<AuthSettings>,
+    Json(payload): Json<LoginRequest>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+    let token_id = verify_token(pool, payload.token.trim())
+        .await?
+        .ok_or(ApiError::Unauthorized)?;
+    let secret = issue_session(pool, token_id).await?;
+
+    Ok((
+        [(header::SET_COOKIE, settings.session_cookie(&secret))],
+        ResponseJson(ApiResponse::<()>::success(())),
+    )
+        .into_response())
+}
+
+pub async fn logout(
+    State(deployment): State<DeploymentImpl>,
+    Extension(settings): Extension<AuthSettings>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    if let Some(secret) = session_cookie(&headers) {
+        AuthSession::delete_by_hash(&deployment.db().pool, &hash_secret(secret)).await?;
+    }
+    Ok((
+        [(header::SET_COOKIE, settings.cleared_session_cookie())],
+        ResponseJson(ApiResponse::<()>::success(())),
+    )
+        .into_response())
+}
+
+pub async fn get_access_tokens(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<AccessToken>>>, ApiError> {
+    let tokens = AccessToken::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(tokens)))
+}
+
+pub async fn create_access_token(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateAccessToken>,
+) -> Result<ResponseJson<ApiResponse<CreatedAccessToken>>, ApiError> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest(
+            "Token name must not be empty".to_string(),
+        ));
+    }
+
+    let token = generate_token();
+    let access_token = AccessToken::create(
+        &deployment.db().pool,
+        name,
+        &hash_secret(&token),
+        token_prefix(&token),
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(CreatedAccessToken {
+        access_token,
+        token,
+    })))
+}
+
+pub async fn delete_access_token(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = AccessToken::delete(&deployment.db().pool, id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+/// Routes reachable without authentication
+pub fn public_router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/auth/status", get(get_auth_status))
+        .route("/auth/login", get(login_page).post(login))
+        .route("/auth/logout", post(logout))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/auth/tokens",
+            get(get_access_tokens).post(create_access_token),
+        )
+        .route("/auth/tokens/{id}", delete(delete_access_token))
+}
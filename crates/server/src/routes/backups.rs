@@ -0,0 +1,35 @@
+use axum::{Router, extract::State, response::Json as ResponseJson, routing::get};
+use deployment::Deployment;
+use services::services::backup::BackupInfo;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_backups(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<BackupInfo>>>, ApiError> {
+    let backups = deployment.backups().list_backups().await?;
+    Ok(ResponseJson(ApiResponse::success(backups)))
+}
+
+/// Take a backup now. Restoring is done offline with `vk backups restore`.
+pub async fn create_backup(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<BackupInfo>>, ApiError> {
+    let backup = deployment.backups().create_backup().await?;
+    let keep = deployment.config().read().await.backup.keep.max(1) as usize;
+    deployment.backups().rotate(keep).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "backup_created",
+            serde_json::json!({ "size_bytes": backup.size_bytes }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(backup)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/backups", get(get_backups).post(create_backup))
+}
@@ -12,8 +12,10 @@ use axum::{
 use db::models::{
     execution_process::{ExecutionProcess, ExecutionProcessError, ExecutionProcessStatus},
     execution_process_repo_state::ExecutionProcessRepoState,
+    session::Session,
 };
 use deployment::Deployment;
+use executors::logs::NormalizedEntry;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::Deserialize;
 use services::services::container::ContainerService;
@@ -37,6 +39,38 @@ pub async fn get_execution_process_by_id(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+/// All execution processes of a workspace across its sessions, oldest first
+pub async fn get_execution_processes(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ExecutionProcessQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<ExecutionProcess>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let show_soft_deleted = query.show_soft_deleted.unwrap_or(false);
+
+    let mut processes = Vec::new();
+    for session in Session::find_by_workspace_id(pool, query.workspace_id).await? {
+        processes.extend(
+            ExecutionProcess::find_by_session_id(pool, session.id, show_soft_deleted).await?,
+        );
+    }
+    processes.sort_by_key(|process| process.created_at);
+
+    Ok(ResponseJson(ApiResponse::success(processes)))
+}
+
+/// Normalized log entries produced so far, for clients that can't follow
+/// `/normalized-logs/ws`
+pub async fn get_normalized_logs(
+    Extension(execution_process): Extension<ExecutionProcess>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<NormalizedEntry>>>, ApiError> {
+    let entries = deployment
+        .container()
+        .normalized_entries(&execution_process.id)
+        .await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
 pub async fn stream_raw_logs_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -249,6 +283,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/stop", post(stop_execution_process))
         .route("/repo-states", get(get_execution_process_repo_states))
         .route("/raw-logs/ws", get(stream_raw_logs_ws))
+        .route("/normalized-logs", get(get_normalized_logs))
         .route("/normalized-logs/ws", get(stream_normalized_logs_ws))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -256,6 +291,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         ));
 
     let workspaces_router = Router::new()
+        .route("/", get(get_execution_processes))
         .route("/stream/ws", get(stream_execution_processes_ws))
         .nest("/{id}", workspace_id_router);
 
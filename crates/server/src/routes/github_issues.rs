@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use axum::{Json, Router, extract::State, response::Json as ResponseJson, routing::post};
+use db::models::{
+    project_repo::ProjectRepo,
+    repo::Repo,
+    task::{CreateTask, Task},
+    task_github_issue::{CreateTaskGitHubIssue, TaskGitHubIssue},
+};
+use deployment::Deployment;
+use serde::{Deserialize, Serialize};
+use services::services::github::{GitHubIssue, GitHubRepoInfo, GitHubService, IssueFilter};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+/// Which open issues of a project's repository to list or import
+#[derive(Debug, Deserialize, TS)]
+pub struct GitHubIssueQuery {
+    pub project_id: Uuid,
+    /// Repository to read issues from; defaults to the project's first repository
+    pub repo_id: Option<Uuid>,
+    /// Issues must have every one of these labels
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Milestone title
+    pub milestone: Option<String>,
+    /// GitHub search query, e.g. `no:assignee sort:created-asc`
+    pub search: Option<String>,
+    /// Maximum number of issues to fetch (default 100)
+    pub limit: Option<u32>,
+}
+
+/// An open issue, with the task it was imported as if any
+#[derive(Debug, Serialize, TS)]
+pub struct GitHubIssueCandidate {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub issue: GitHubIssue,
+    pub task_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportGitHubIssuesRequest {
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub query: GitHubIssueQuery,
+    /// Only import these issue numbers from the matching issues
+    pub issue_numbers: Option<Vec<i64>>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportGitHubIssuesResponse {
+    pub imported: Vec<TaskGitHubIssue>,
+    /// Matching issues that were already imported into the project
+    pub skipped_count: u32,
+    pub errors: Vec<String>,
+}
+
+/// The repository and its GitHub coordinates that `query` reads issues from
+async fn resolve_repo(
+    deployment: &DeploymentImpl,
+    query: &GitHubIssueQuery,
+) -> Result<(Repo, GitHubRepoInfo), ApiError> {
+    let repos =
+        ProjectRepo::find_repos_for_project(&deployment.db().pool, query.project_id).await?;
+    let repo = match query.repo_id {
+        Some(repo_id) => repos.into_iter().find(|r| r.id == repo_id).ok_or_else(|| {
+            ApiError::BadRequest("Repository does not belong to the project".to_string())
+        })?,
+        None => repos.into_iter().next().ok_or_else(|| {
+            ApiError::BadRequest("Project has no repositories configured".to_string())
+        })?,
+    };
+    let repo_info = deployment.git().get_github_repo_info(&repo.path)?;
+    Ok((repo, repo_info))
+}
+
+async fn fetch_issues(
+    repo_info: &GitHubRepoInfo,
+    query: &GitHubIssueQuery,
+) -> Result<Vec<GitHubIssue>, ApiError> {
+    let filter = IssueFilter {
+        labels: query.labels.clone(),
+        milestone: query.milestone.clone(),
+        search: query.search.clone(),
+        limit: query.limit.unwrap_or(IssueFilter::default().limit),
+        closed: false,
+    };
+    Ok(GitHubService::new()?
+        .list_issues(repo_info, &filter)
+        .await?)
+}
+
+/// Tasks already imported from `repo_info`'s issues, by issue number
+async fn imported_issues(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    repo_info: &GitHubRepoInfo,
+) -> Result<Vec<TaskGitHubIssue>, ApiError> {
+    Ok(
+        TaskGitHubIssue::find_by_project_id(&deployment.db().pool, project_id)
+            .await?
+            .into_iter()
+            .filter(|link| {
+                link.owner.eq_ignore_ascii_case(&repo_info.owner)
+                    && link.repo_name.eq_ignore_ascii_case(&repo_info.repo_name)
+            })
+            .collect(),
+    )
+}
+
+/// List open issues matching the query, marking those already imported
+pub async fn search_github_issues(
+    State(deployment): State<DeploymentImpl>,
+    Json(query): Json<GitHubIssueQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<GitHubIssueCandidate>>>, ApiError> {
+    let (_, repo_info) = resolve_repo(&deployment, &query).await?;
+    let issues = fetch_issues(&repo_info, &query).await?;
+    let imported = imported_issues(&deployment, query.project_id, &repo_info).await?;
+
+    let candidates = issues
+        .into_iter()
+        .map(|issue| GitHubIssueCandidate {
+            task_id: imported
+                .iter()
+                .find(|link| link.issue_number == issue.number)
+                .map(|link| link.task_id),
+            issue,
+        })
+        .collect();
+    Ok(ResponseJson(ApiResponse::success(candidates)))
+}
+
+/// Create a task for each matching issue that isn't imported yet
+pub async fn import_github_issues(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportGitHubIssuesRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportGitHubIssuesResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let query = &payload.query;
+    let (repo, repo_info) = resolve_repo(&deployment, query).await?;
+    let issues = fetch_issues(&repo_info, query).await?;
+    let already_imported: HashSet<i64> = imported_issues(&deployment, query.project_id, &repo_info)
+        .await?
+        .into_iter()
+        .map(|link| link.issue_number)
+        .collect();
+
+    let mut imported = Vec::new();
+    let mut skipped_count = 0;
+    let mut errors = Vec::new();
+
+    for issue in issues {
+        if let Some(numbers) = &payload.issue_numbers
+            && !numbers.contains(&issue.number)
+        {
+            continue;
+        }
+        if already_imported.contains(&issue.number) {
+            skipped_count += 1;
+            continue;
+        }
+
+        let body = issue.body.trim();
+        let description = if body.is_empty() {
+            format!("GitHub issue: {}", issue.url)
+        } else {
+            format!("{body}\n\nGitHub issue: {}", issue.url)
+        };
+        let create_task = CreateTask {
+            project_id: query.project_id,
+            title: issue.title.clone(),
+            description: Some(description),
+            status: None,
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            labels: None,
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+        };
+
+        let result = async {
+            let task = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+            TaskGitHubIssue::create(
+                pool,
+                &CreateTaskGitHubIssue {
+                    task_id: task.id,
+                    repo_id: repo.id,
+                    owner: repo_info.owner.clone(),
+                    repo_name: repo_info.repo_name.clone(),
+                    issue_number: issue.number,
+                    issue_url: issue.url.clone(),
+                },
+            )
+            .await
+        }
+        .await;
+
+        match result {
+            Ok(link) => {
+                tracing::info!(
+                    "Imported issue {}/{}#{} as task {}",
+                    repo_info.owner,
+                    repo_info.repo_name,
+                    issue.number,
+                    link.task_id
+                );
+                imported.push(link);
+            }
+            Err(e) => {
+                let err_msg = format!("Failed to import issue #{}: {}", issue.number, e);
+                tracing::error!("{}", err_msg);
+                errors.push(err_msg);
+            }
+        }
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "github_issues_imported",
+            serde_json::json!({
+                "project_id": query.project_id.to_string(),
+                "imported_count": imported.len(),
+                "skipped_count": skipped_count,
+                "error_count": errors.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        ImportGitHubIssuesResponse {
+            imported,
+            skipped_count,
+            errors,
+        },
+    )))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/search", post(search_github_issues))
+        .route("/import", post(import_github_issues))
+}
@@ -0,0 +1,91 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, put},
+};
+use db::models::label::{CreateLabel, Label, UpdateLabel};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+#[derive(Debug, Deserialize, TS)]
+pub struct LabelQuery {
+    pub project_id: Uuid,
+}
+
+/// Reject names that are empty or already used by another label of the project
+async fn ensure_name_available(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    name: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ApiError::BadRequest("Label name is required".to_string()));
+    }
+    let labels = Label::find_by_project_id(&deployment.db().pool, project_id).await?;
+    if labels
+        .iter()
+        .any(|label| Some(label.id) != except && label.name.eq_ignore_ascii_case(name))
+    {
+        return Err(ApiError::Conflict(format!("Label '{name}' already exists")));
+    }
+    Ok(())
+}
+
+pub async fn get_labels(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<LabelQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Label>>>, ApiError> {
+    let labels = Label::find_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(labels)))
+}
+
+pub async fn create_label(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    ensure_name_available(&deployment, payload.project_id, &payload.name, None).await?;
+    let label = Label::create(&deployment.db().pool, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+pub async fn update_label(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateLabel>,
+) -> Result<ResponseJson<ApiResponse<Label>>, ApiError> {
+    let label = Label::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Label not found".to_string()))?;
+    if let Some(name) = &payload.name {
+        ensure_name_available(&deployment, label.project_id, name, Some(label.id)).await?;
+    }
+    let label = Label::update(&deployment.db().pool, id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(label)))
+}
+
+/// Delete a label and remove it from every task carrying it
+pub async fn delete_label(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = Label::delete(&deployment.db().pool, id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/labels", get(get_labels).post(create_label))
+        .route("/labels/{id}", put(update_label).delete(delete_label))
+}
@@ -0,0 +1,24 @@
+//! The MCP task server over streamable HTTP, so remote agents and IDEs can connect with a URL
+//! instead of spawning the stdio binary.
+
+use std::sync::Arc;
+
+use axum::Router;
+use rmcp::transport::streamable_http_server::{
+    StreamableHttpServerConfig, StreamableHttpService, session::local::LocalSessionManager,
+};
+use services::services::events::EventService;
+
+use crate::mcp::task_server::TaskServer;
+
+/// `api` serves `/api` without the auth layer; each MCP session's task server dispatches its
+/// tool calls into it directly and watches `events` for resource subscriptions
+pub fn router<S: Clone + Send + Sync + 'static>(api: Router, events: EventService) -> Router<S> {
+    let service = StreamableHttpService::new(
+        move || Ok(TaskServer::in_process(api.clone(), events.clone())),
+        Arc::new(LocalSessionManager::default()),
+        StreamableHttpServerConfig::default(),
+    );
+
+    Router::new().nest_service("/mcp", service)
+}
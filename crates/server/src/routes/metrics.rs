@@ -0,0 +1,30 @@
+use axum::{
+    Router,
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use deployment::Deployment;
+use services::services::{
+    container::ContainerService,
+    metrics::{self, MetricsSources},
+};
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_metrics(State(deployment): State<DeploymentImpl>) -> Result<Response, ApiError> {
+    let body = metrics::render(MetricsSources {
+        db: deployment.db(),
+        execution_msg_stores: deployment.container().msg_stores(),
+        events_msg_store: deployment.events().msg_store(),
+        queued_messages: deployment.queued_message_service(),
+    })
+    .await?;
+
+    Ok(([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], body).into_response())
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/metrics", get(get_metrics))
+}
@@ -1,11 +1,20 @@
 use axum::{
-    Router,
+    Extension, Router,
+    middleware::from_fn_with_state,
     routing::{IntoMakeService, get},
 };
+use deployment::Deployment;
+use sqlx::SqlitePool;
 
-use crate::DeploymentImpl;
+use crate::{
+    DeploymentImpl,
+    middleware::auth::{AuthSettings, require_auth},
+};
 
 pub mod approvals;
+pub mod archive;
+pub mod auth;
+pub mod backups;
 pub mod config;
 pub mod containers;
 pub mod filesystem;
@@ -13,47 +22,158 @@ pub mod filesystem;
 pub mod events;
 pub mod execution_processes;
 pub mod frontend;
+pub mod github_issues;
 pub mod health;
 pub mod images;
+pub mod labels;
+pub mod mcp;
+pub mod metrics;
 pub mod oauth;
 pub mod organizations;
 pub mod plans;
 pub mod projects;
 pub mod repo;
 pub mod scratch;
+pub mod search;
 pub mod sessions;
 pub mod shared_tasks;
 pub mod tags;
+pub mod task_activity;
 pub mod task_attempts;
+pub mod task_schedules;
 pub mod tasks;
+pub mod webhooks;
+
+pub fn router(deployment: DeploymentImpl, auth_settings: AuthSettings) -> IntoMakeService<Router> {
+    // The MCP endpoint authenticates once; its tool calls then go straight to the API routes
+    let internal_api = Router::new().nest(
+        "/api",
+        api_routes(&deployment)
+            .layer(Extension(auth_settings))
+            .with_state(deployment.clone()),
+    );
+
+    let protected_routes = protected_routes(
+        api_routes(&deployment),
+        mcp::router(internal_api, deployment.events().clone()),
+        auth_settings.required.then(|| deployment.db().pool.clone()),
+    );
 
-pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
-    // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
+        .merge(auth::public_router())
+        .merge(approvals::public_router())
+        .merge(protected_routes)
+        .layer(Extension(auth_settings))
+        .with_state(deployment);
+
+    Router::new()
+        .route("/", get(frontend::serve_frontend_root))
+        .route("/{*path}", get(frontend::serve_frontend))
+        .nest("/api", base_routes)
+        .into_make_service()
+}
+
+/// The API and the MCP endpoint, behind `require_auth` when `auth_pool` is set. `/api/mcp` must
+/// stay in here: its tool calls go to `internal_api`, which has no auth layer of its own.
+fn protected_routes<S: Clone + Send + Sync + 'static>(
+    api: Router<S>,
+    mcp: Router<S>,
+    auth_pool: Option<SqlitePool>,
+) -> Router<S> {
+    let routes = api.merge(mcp);
+    match auth_pool {
+        Some(pool) => routes.layer(from_fn_with_state(pool, require_auth)),
+        None => routes,
+    }
+}
+
+/// Every `/api` route that sits behind authentication in remote access mode
+fn api_routes(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+    Router::new()
+        .merge(auth::router())
         .merge(config::router())
-        .merge(containers::router(&deployment))
-        .merge(projects::router(&deployment))
-        .merge(tasks::router(&deployment))
+        .merge(containers::router(deployment))
+        .merge(projects::router(deployment))
+        .merge(tasks::router(deployment))
         .merge(shared_tasks::router())
-        .merge(task_attempts::router(&deployment))
-        .merge(execution_processes::router(&deployment))
-        .merge(tags::router(&deployment))
+        .merge(task_attempts::router(deployment))
+        .merge(execution_processes::router(deployment))
+        .merge(tags::router(deployment))
+        .merge(labels::router())
         .merge(oauth::router())
         .merge(organizations::router())
         .merge(filesystem::router())
         .merge(repo::router())
-        .merge(events::router(&deployment))
+        .merge(events::router(deployment))
         .merge(approvals::router())
-        .merge(scratch::router(&deployment))
-        .merge(sessions::router(&deployment))
+        .merge(scratch::router(deployment))
+        .merge(search::router())
+        .merge(sessions::router(deployment))
+        .merge(webhooks::router())
+        .merge(metrics::router())
+        .merge(backups::router())
+        .merge(archive::router())
+        .merge(task_schedules::router())
         .nest("/images", images::routes())
-        .nest("/plans", plans::router(&deployment))
-        .with_state(deployment);
+        .nest("/plans", plans::router(deployment))
+        .nest("/github-issues", github_issues::router())
+}
 
-    Router::new()
-        .route("/", get(frontend::serve_frontend_root))
-        .route("/{*path}", get(frontend::serve_frontend))
-        .nest("/api", base_routes)
-        .into_make_service()
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode, header},
+    };
+    use db::{DBService, models::access_token::AccessToken, test_utils::test_pool};
+    use services::services::events::{EventService, MutedTasks};
+    use tower::ServiceExt;
+    use utils::msg_store::MsgStore;
+
+    use super::*;
+    use crate::middleware::auth::{generate_token, hash_secret, token_prefix};
+
+    #[tokio::test]
+    async fn test_mcp_endpoint_requires_auth() {
+        let pool = test_pool().await;
+        let events = EventService::new(
+            DBService { pool: pool.clone() },
+            Arc::new(MsgStore::new()),
+            Default::default(),
+            MutedTasks::default(),
+        );
+        let routes: Router = protected_routes(
+            Router::new(),
+            mcp::router(Router::new(), events),
+            Some(pool.clone()),
+        );
+        let request = |token: Option<&str>| {
+            let mut request = Request::post("/mcp")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::ACCEPT, "application/json, text/event-stream");
+            if let Some(token) = token {
+                request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+            }
+            request.body(Body::from("{}")).unwrap()
+        };
+
+        let response = routes.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = routes
+            .clone()
+            .oneshot(request(Some("vk_wrong")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let token = generate_token();
+        AccessToken::create(&pool, "test", &hash_secret(&token), token_prefix(&token))
+            .await
+            .unwrap();
+        let response = routes.oneshot(request(Some(token.as_str()))).await.unwrap();
+        assert_ne!(response.status(), StatusCode::UNAUTHORIZED);
+    }
 }
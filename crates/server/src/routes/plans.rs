@@ -1,6 +1,4 @@
-use std::path::Path;
-use std::process::Command;
-use std::time::Duration;
+use std::{collections::HashSet, path::Path};
 
 use axum::{
     Json, Router,
@@ -8,65 +6,19 @@ use axum::{
     response::Json as ResponseJson,
     routing::{get, post},
 };
-use db::models::{
-    project_repo::ProjectRepo,
-    task::{CreateTask, Task, TaskStatus},
-};
+use db::models::{plan_task_link::PlanTaskLink, project_repo::ProjectRepo};
 use deployment::Deployment;
 use serde::{Deserialize, Serialize};
+use services::services::{
+    plan_sync::PlanSyncService,
+    plans::{PlanMetadata, scan_plans},
+};
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
 
-/// Plan phase progress from scanner
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[ts(export)]
-pub struct PlanPhaseProgress {
-    pub total: u32,
-    pub completed: u32,
-    #[serde(rename = "inProgress")]
-    pub in_progress: u32,
-    pub pending: u32,
-    pub percentage: u32,
-}
-
-/// Individual phase detail from plan
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[ts(export)]
-pub struct PlanPhaseDetail {
-    pub phase: u32,
-    pub name: String,
-    pub status: String,
-    pub file: String,
-    #[serde(rename = "linkText")]
-    pub link_text: Option<String>,
-}
-
-/// Plan metadata from JS scanner output
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[ts(export)]
-pub struct PlanMetadata {
-    pub id: String,
-    pub name: String,
-    pub path: String,
-    pub directory: String,
-    pub phases: PlanPhaseProgress,
-    #[serde(rename = "phaseDetails", default)]
-    pub phase_details: Vec<PlanPhaseDetail>,
-    pub progress: u32,
-    #[serde(rename = "lastModified")]
-    pub last_modified: String,
-    pub status: String,
-    pub description: Option<String>,
-    pub priority: Option<String>,
-    pub branch: Option<String>,
-    #[serde(default)]
-    pub tags: Vec<String>,
-    pub title: Option<String>,
-}
-
 /// Selected phases for a plan during import
 #[derive(Debug, Clone, Deserialize, TS)]
 #[ts(export)]
@@ -99,116 +51,6 @@ pub struct ImportPlansResponse {
     pub errors: Vec<String>,
 }
 
-/// Map plan status string to TaskStatus
-fn map_plan_status_to_task(plan_status: &str) -> TaskStatus {
-    match plan_status.to_lowercase().as_str() {
-        "in-progress" | "inprogress" | "in_progress" => TaskStatus::InProgress,
-        "in-review" | "inreview" | "in_review" => TaskStatus::InReview,
-        "completed" | "done" => TaskStatus::Done,
-        "cancelled" | "canceled" => TaskStatus::Cancelled,
-        other => {
-            if !other.is_empty() && other != "pending" {
-                tracing::debug!("Unknown plan status '{}', defaulting to Todo", other);
-            }
-            TaskStatus::Todo
-        }
-    }
-}
-
-/// Validate project path for safety
-fn validate_project_path(project_root: &str) -> Result<(), String> {
-    let path = Path::new(project_root);
-
-    // Must be absolute path
-    if !path.is_absolute() {
-        return Err("Project path must be absolute".to_string());
-    }
-
-    // Must exist and be a directory
-    if !path.is_dir() {
-        return Err("Project path does not exist or is not a directory".to_string());
-    }
-
-    // Check for shell metacharacters that could be dangerous
-    let dangerous_chars = [';', '|', '&', '\n', '\r', '\0', '`', '$', '(', ')'];
-    if project_root.chars().any(|c| dangerous_chars.contains(&c)) {
-        return Err("Project path contains invalid characters".to_string());
-    }
-
-    Ok(())
-}
-
-const SCANNER_TIMEOUT_SECS: u64 = 30;
-
-/// Execute plan scanner and parse output
-fn scan_plans_via_node(project_root: &str) -> Result<Vec<PlanMetadata>, String> {
-    // Validate path first
-    validate_project_path(project_root)?;
-
-    // Check if node is available
-    let node_check = Command::new("which")
-        .arg("node")
-        .output()
-        .map_err(|e| format!("Failed to check node availability: {}", e))?;
-
-    if !node_check.status.success() {
-        return Err("Node.js is not installed or not in PATH".to_string());
-    }
-
-    let plans_dir = format!("{}/plans", project_root);
-    let script_path = format!("{}/scripts/plan-scanner-json.cjs", project_root);
-
-    // Check if script exists
-    if !Path::new(&script_path).exists() {
-        return Err(format!("Plan scanner script not found at {}", script_path));
-    }
-
-    // Execute with timeout using spawn and wait_with_output
-    let mut child = Command::new("node")
-        .arg(&script_path)
-        .arg(&plans_dir)
-        .current_dir(project_root)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| format!("Failed to spawn plan scanner: {}", e))?;
-
-    // Wait with timeout
-    let timeout = Duration::from_secs(SCANNER_TIMEOUT_SECS);
-    let start = std::time::Instant::now();
-
-    loop {
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                let output = child.wait_with_output()
-                    .map_err(|e| format!("Failed to get scanner output: {}", e))?;
-
-                if !status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    return Err(format!("Plan scanner failed: {}", stderr));
-                }
-
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                return serde_json::from_str(&stdout)
-                    .map_err(|e| format!("Failed to parse scanner output: {} - output: {}", e, stdout));
-            }
-            Ok(None) => {
-                if start.elapsed() > timeout {
-                    let _ = child.kill();
-                    return Err(format!(
-                        "Plan scanner timed out after {}s",
-                        SCANNER_TIMEOUT_SECS
-                    ));
-                }
-                std::thread::sleep(Duration::from_millis(50));
-            }
-            Err(e) => {
-                return Err(format!("Failed to check scanner status: {}", e));
-            }
-        }
-    }
-}
-
 /// Query params for listing plans
 #[derive(Debug, Deserialize)]
 pub struct ListPlansQuery {
@@ -228,10 +70,7 @@ pub async fn list_plans(
         "Project has no repositories configured".to_string(),
     ))?;
 
-    let project_root = first_repo.path.to_string_lossy().to_string();
-
-    // Scan plans using Node.js script
-    let plans = scan_plans_via_node(&project_root)
+    let plans = scan_plans(&first_repo.path)
         .map_err(|e| ApiError::BadRequest(format!("Failed to scan plans: {}", e)))?;
 
     Ok(ResponseJson(ApiResponse::success(plans)))
@@ -250,10 +89,7 @@ pub async fn import_plans(
         "Project has no repositories configured".to_string(),
     ))?;
 
-    let project_root = first_repo.path.to_string_lossy().to_string();
-
-    // Scan plans using Node.js script
-    let plans = scan_plans_via_node(&project_root)
+    let plans = scan_plans(&first_repo.path)
         .map_err(|e| ApiError::BadRequest(format!("Failed to scan plans: {}", e)))?;
 
     // Build a map of plan_id -> selected phases for quick lookup
@@ -279,7 +115,13 @@ pub async fn import_plans(
     let mut errors = Vec::new();
 
     for plan in plans_to_import {
-        let plan_title = plan.title.clone().unwrap_or_else(|| plan.name.clone());
+        // Phases imported earlier are already linked to a task and kept in sync with it
+        let linked_files: HashSet<String> =
+            PlanTaskLink::find_by_plan(pool, payload.project_id, &plan.id)
+                .await?
+                .into_iter()
+                .map(|link| link.phase_file.unwrap_or_default())
+                .collect();
 
         // Check if specific phases are selected for this plan
         let selected_phases = phase_selections.get(&plan.id);
@@ -293,26 +135,18 @@ pub async fn import_plans(
                         continue;
                     }
                 }
-                let title = format!("{} - Phase {}: {}", plan_title, phase.phase, phase.name);
-                let status = map_plan_status_to_task(&phase.status);
-                let description = Some(format!(
-                    "Plan: {}\nPhase file: {}",
-                    plan.name,
-                    phase.file
-                ));
-
-                let create_task = CreateTask {
-                    project_id: payload.project_id,
-                    title,
-                    description,
-                    status: Some(status),
-                    parent_workspace_id: None,
-                    image_ids: None,
-                    shared_task_id: None,
-                };
-
-                let task_id = Uuid::new_v4();
-                match Task::create(pool, &create_task, task_id).await {
+                if linked_files.contains(&phase.file) {
+                    tracing::debug!(
+                        "Phase {} of plan '{}' is already imported",
+                        phase.phase,
+                        plan.name
+                    );
+                    continue;
+                }
+
+                match PlanSyncService::import_task(pool, payload.project_id, plan, Some(phase))
+                    .await
+                {
                     Ok(task) => {
                         tracing::info!(
                             "Imported phase {} of plan '{}' as task {}",
@@ -332,22 +166,9 @@ pub async fn import_plans(
                     }
                 }
             }
-        } else {
+        } else if linked_files.is_empty() {
             // No phases, create single task for the plan
-            let status = map_plan_status_to_task(&plan.status);
-
-            let create_task = CreateTask {
-                project_id: payload.project_id,
-                title: plan_title.clone(),
-                description: plan.description.clone(),
-                status: Some(status),
-                parent_workspace_id: None,
-                image_ids: None,
-                shared_task_id: None,
-            };
-
-            let task_id = Uuid::new_v4();
-            match Task::create(pool, &create_task, task_id).await {
+            match PlanSyncService::import_task(pool, payload.project_id, plan, None).await {
                 Ok(task) => {
                     tracing::info!("Imported plan '{}' as task {}", plan.name, task.id);
                     task_ids.push(task.id);
@@ -361,6 +182,12 @@ pub async fn import_plans(
         }
     }
 
+    if !task_ids.is_empty() {
+        deployment
+            .plan_sync()
+            .watch_project(payload.project_id, &first_repo.path);
+    }
+
     let response = ImportPlansResponse {
         imported_count: task_ids.len() as u32,
         task_ids,
@@ -389,7 +216,7 @@ pub struct ReadPlanFileQuery {
 }
 
 /// Response for plan file content
-#[derive(Debug, Serialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct PlanFileContent {
     pub content: String,
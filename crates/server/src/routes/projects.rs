@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow;
 use axum::{
@@ -7,21 +7,32 @@ use axum::{
         Path, Query, State,
         ws::{WebSocket, WebSocketUpgrade},
     },
-    http::StatusCode,
+    http::{StatusCode, header},
     middleware::from_fn_with_state,
-    response::{IntoResponse, Json as ResponseJson},
-    routing::{get, post},
+    response::{IntoResponse, Json as ResponseJson, Response},
+    routing::{get, post, put},
 };
 use db::models::{
+    approval_policy::{
+        ApprovalPatternKind, ApprovalPolicyRule, CreateApprovalPolicyRule,
+        ReorderApprovalPolicyRules, UpdateApprovalPolicyRule,
+    },
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo, UpdateProjectRepo},
     repo::Repo,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::{
-    file_search_cache::SearchQuery, project::ProjectServiceError,
+    approvals::policy,
+    archival,
+    file_search_cache::SearchQuery,
+    project::ProjectServiceError,
+    project_archive::{
+        self, ProjectArchive, ProjectArchiveError, ProjectExportFormat, ProjectExportOptions,
+        ProjectImportSummary,
+    },
     remote_client::CreateRemoteProjectPayload,
 };
 use ts_rs::TS;
@@ -51,6 +62,63 @@ pub async fn get_projects(
     Ok(ResponseJson(ApiResponse::success(projects)))
 }
 
+pub async fn get_archived_projects(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<Project>>>, ApiError> {
+    let projects = Project::find_archived(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(projects)))
+}
+
+pub async fn archive_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    if project.archived_at.is_some() {
+        return Err(ApiError::Conflict(
+            "Project is already archived".to_string(),
+        ));
+    }
+    archival::archive_project(deployment.container(), &project).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_archived",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    let project = Project::find_by_id(&deployment.db().pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
+pub async fn restore_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Project::restore(pool, project.id).await? == 0 {
+        return Err(ApiError::Conflict("Project is not archived".to_string()));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_restored",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+            }),
+        )
+        .await;
+
+    let project = Project::find_by_id(pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(project)))
+}
+
 pub async fn stream_projects_ws(
     ws: WebSocketUpgrade,
     State(deployment): State<DeploymentImpl>,
@@ -311,6 +379,139 @@ pub async fn delete_project(
     }
 }
 
+/// Download the project board as a JSON archive or a CSV of its tasks
+pub async fn export_project(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Query(options): Query<ProjectExportOptions>,
+) -> Result<Response, ApiError> {
+    let include_history = options.include_history && options.format == ProjectExportFormat::Json;
+    let archive =
+        project_archive::export_project(deployment.container(), &project, include_history).await?;
+    let document = match options.format {
+        ProjectExportFormat::Json => {
+            serde_json::to_string_pretty(&archive).map_err(ProjectArchiveError::from)?
+        }
+        ProjectExportFormat::Csv => project_archive::tasks_csv(&archive),
+    };
+    let disposition = format!(
+        "attachment; filename=\"project-{}.{}\"",
+        project.id,
+        options.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                options.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        document,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct ImportProjectRequest {
+    pub archive: ProjectArchive,
+    /// Name of the new project; defaults to the archived name
+    pub name: Option<String>,
+    /// Path on this machine of each archived repository, by archived repository ID.
+    /// Repositories left out are expected at their archived path.
+    #[serde(default)]
+    pub repo_paths: HashMap<Uuid, String>,
+}
+
+#[derive(Debug, Serialize, TS)]
+pub struct ImportProjectResponse {
+    pub project: Project,
+    #[serde(flatten)]
+    #[ts(flatten)]
+    pub summary: ProjectImportSummary,
+}
+
+/// Create a new project from an exported archive
+pub async fn import_project(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ImportProjectRequest>,
+) -> Result<ResponseJson<ApiResponse<ImportProjectResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let archive = &payload.archive;
+    project_archive::validate_archive(archive)?;
+
+    let mut repositories = Vec::with_capacity(archive.repositories.len());
+    let mut archived_paths = Vec::with_capacity(archive.repositories.len());
+    for repo in &archive.repositories {
+        let path = payload.repo_paths.get(&repo.id).unwrap_or(&repo.path);
+        archived_paths.push((repo.id, deployment.repo().normalize_path(path)?));
+        repositories.push(CreateProjectRepo {
+            display_name: repo.display_name.clone(),
+            git_repo_path: path.clone(),
+        });
+    }
+
+    let project = deployment
+        .project()
+        .create_project(
+            pool,
+            deployment.repo(),
+            CreateProject {
+                name: payload
+                    .name
+                    .clone()
+                    .unwrap_or_else(|| archive.project.name.clone()),
+                repositories,
+            },
+        )
+        .await?;
+
+    // Existing repositories are reused by path, so match on that rather than on names
+    let repos = ProjectRepo::find_repos_for_project(pool, project.id).await?;
+    let repo_ids: HashMap<Uuid, Uuid> = archived_paths
+        .into_iter()
+        .filter_map(|(archived_id, path)| {
+            let repo = repos.iter().find(|repo| repo.path == path)?;
+            Some((archived_id, repo.id))
+        })
+        .collect();
+
+    let summary = match project_archive::import_project(pool, &project, archive, &repo_ids).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            if let Err(delete_err) = deployment.project().delete_project(pool, project.id).await {
+                tracing::error!(
+                    "Failed to remove partially imported project {}: {}",
+                    project.id,
+                    delete_err
+                );
+            }
+            return Err(e.into());
+        }
+    };
+    let project = Project::find_by_id(pool, project.id)
+        .await?
+        .ok_or(ProjectError::ProjectNotFound)?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_imported",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "archive_version": archive.version,
+                "task_count": summary.task_count,
+                "attempt_count": summary.attempt_count,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(ImportProjectResponse {
+        project,
+        summary,
+    })))
+}
+
 #[derive(serde::Deserialize)]
 pub struct OpenEditorRequest {
     editor_type: Option<String>,
@@ -582,6 +783,111 @@ pub async fn update_project_repository(
     }
 }
 
+fn validate_approval_policy_rule(
+    tool_name: Option<&str>,
+    pattern: Option<&str>,
+    pattern_kind: ApprovalPatternKind,
+) -> Result<(), ApiError> {
+    policy::validate_rule(tool_name, pattern, pattern_kind)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid approval rule pattern: {e}")))
+}
+
+async fn find_project_approval_policy_rule(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    rule_id: Uuid,
+) -> Result<ApprovalPolicyRule, ApiError> {
+    ApprovalPolicyRule::find_by_id(&deployment.db().pool, rule_id)
+        .await?
+        .filter(|rule| rule.project_id == project_id)
+        .ok_or_else(|| ApiError::BadRequest("Approval rule not found in project".to_string()))
+}
+
+pub async fn get_approval_policy_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalPolicyRule>>>, ApiError> {
+    let rules = ApprovalPolicyRule::find_by_project_id(&deployment.db().pool, project.id).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn create_approval_policy_rule(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(mut payload): Json<CreateApprovalPolicyRule>,
+) -> Result<ResponseJson<ApiResponse<ApprovalPolicyRule>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Approval rule name must not be empty".to_string(),
+        ));
+    }
+    payload.tool_name = payload.tool_name.filter(|t| !t.trim().is_empty());
+    payload.pattern = payload.pattern.filter(|p| !p.is_empty());
+    validate_approval_policy_rule(
+        payload.tool_name.as_deref(),
+        payload.pattern.as_deref(),
+        payload.pattern_kind,
+    )?;
+
+    let rule = ApprovalPolicyRule::create(&deployment.db().pool, project.id, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "approval_policy_rule_created",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "action": rule.action.to_string(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn reorder_approval_policy_rules(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<ReorderApprovalPolicyRules>,
+) -> Result<ResponseJson<ApiResponse<Vec<ApprovalPolicyRule>>>, ApiError> {
+    let rules =
+        ApprovalPolicyRule::reorder(&deployment.db().pool, project.id, &payload.rule_ids).await?;
+    Ok(ResponseJson(ApiResponse::success(rules)))
+}
+
+pub async fn update_approval_policy_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateApprovalPolicyRule>,
+) -> Result<ResponseJson<ApiResponse<ApprovalPolicyRule>>, ApiError> {
+    let existing = find_project_approval_policy_rule(&deployment, project_id, rule_id).await?;
+
+    let tool_name = match &payload.tool_name {
+        Some(t) => Some(t.as_str()).filter(|t| !t.trim().is_empty()),
+        None => existing.tool_name.as_deref(),
+    };
+    let pattern = match &payload.pattern {
+        Some(p) => Some(p.as_str()).filter(|p| !p.is_empty()),
+        None => existing.pattern.as_deref(),
+    };
+    validate_approval_policy_rule(
+        tool_name,
+        pattern,
+        payload.pattern_kind.unwrap_or(existing.pattern_kind),
+    )?;
+
+    let rule = ApprovalPolicyRule::update(&deployment.db().pool, rule_id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(rule)))
+}
+
+pub async fn delete_approval_policy_rule(
+    State(deployment): State<DeploymentImpl>,
+    Path((project_id, rule_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    find_project_approval_policy_rule(&deployment, project_id, rule_id).await?;
+    ApprovalPolicyRule::delete(&deployment.db().pool, rule_id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let project_id_router = Router::new()
         .route(
@@ -590,6 +896,9 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         )
         .route("/remote/members", get(get_project_remote_members))
         .route("/search", get(search_project_files))
+        .route("/export", get(export_project))
+        .route("/archive", post(archive_project))
+        .route("/restore", post(restore_project))
         .route("/open-editor", post(open_project_in_editor))
         .route(
             "/link",
@@ -600,6 +909,14 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
         )
+        .route(
+            "/approval-policies",
+            get(get_approval_policy_rules).post(create_approval_policy_rule),
+        )
+        .route(
+            "/approval-policies/order",
+            put(reorder_approval_policy_rules),
+        )
         .layer(from_fn_with_state(
             deployment.clone(),
             load_project_middleware,
@@ -607,12 +924,18 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     let projects_router = Router::new()
         .route("/", get(get_projects).post(create_project))
+        .route("/import", post(import_project))
+        .route("/archived", get(get_archived_projects))
         .route(
             "/{project_id}/repositories/{repo_id}",
             get(get_project_repository)
                 .put(update_project_repository)
                 .delete(delete_project_repository),
         )
+        .route(
+            "/{project_id}/approval-policies/{rule_id}",
+            put(update_approval_policy_rule).delete(delete_approval_policy_rule),
+        )
         .route("/stream/ws", get(stream_projects_ws))
         .nest("/{id}", project_id_router);
 
@@ -1,10 +1,13 @@
 pub mod queue;
 
+use std::path::PathBuf;
+
 use axum::{
     Extension, Json, Router,
     extract::{Query, State},
+    http::header,
     middleware::from_fn_with_state,
-    response::Json as ResponseJson,
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post},
 };
 use db::models::{
@@ -12,6 +15,7 @@ use db::models::{
     project_repo::ProjectRepo,
     scratch::{Scratch, ScratchType},
     session::{CreateSession, Session},
+    task_activity::TaskActivity,
     workspace::{Workspace, WorkspaceError},
 };
 use deployment::Deployment;
@@ -21,8 +25,11 @@ use executors::{
     },
     profile::ExecutorProfileId,
 };
-use serde::Deserialize;
-use services::services::container::ContainerService;
+use serde::{Deserialize, Serialize};
+use services::services::{
+    container::ContainerService,
+    transcript::{Transcript, TranscriptOptions, TranscriptSection},
+};
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
 use utils::response::ApiResponse;
@@ -59,6 +66,59 @@ pub async fn get_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
+pub async fn export_transcript(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+    Query(options): Query<TranscriptOptions>,
+) -> Result<Response, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+    let title = match workspace.parent_task(pool).await? {
+        Some(task) => task.title,
+        None => format!("Session {}", session.id),
+    };
+
+    let mut sections = Vec::new();
+    for process in ExecutionProcess::find_by_session_id(pool, session.id, false).await? {
+        if process.run_reason != ExecutionProcessRunReason::CodingAgent {
+            continue;
+        }
+        let entries = deployment
+            .container()
+            .normalized_entries(&process.id)
+            .await?;
+        sections.push(TranscriptSection {
+            executor: session.executor.clone(),
+            started_at: process.started_at,
+            entries,
+        });
+    }
+
+    let document = Transcript { title, sections }.render(&options);
+    let disposition = format!(
+        "attachment; filename=\"transcript-{}.{}\"",
+        session.id,
+        options.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                options.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        document,
+    )
+        .into_response())
+}
+
 pub async fn create_session(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<CreateSessionRequest>,
@@ -85,7 +145,7 @@ pub async fn create_session(
     Ok(ResponseJson(ApiResponse::success(session)))
 }
 
-#[derive(Debug, Deserialize, TS)]
+#[derive(Debug, Serialize, Deserialize, TS)]
 pub struct CreateFollowUpAttempt {
     pub prompt: String,
     pub variant: Option<String>,
@@ -110,7 +170,25 @@ pub async fn follow_up(
 
     tracing::info!("{:?}", workspace);
 
-    deployment
+    // Get parent task
+    let task = workspace
+        .parent_task(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    // Get parent project
+    let project = task
+        .parent_project(pool)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+
+    if task.archived_at.is_some() || project.archived_at.is_some() {
+        return Err(ApiError::Conflict(
+            "Cannot send a follow-up to an archived task".to_string(),
+        ));
+    }
+
+    let container_ref = deployment
         .container()
         .ensure_container_exists(&workspace)
         .await?;
@@ -124,18 +202,6 @@ pub async fn follow_up(
         variant: payload.variant,
     };
 
-    // Get parent task
-    let task = workspace
-        .parent_task(pool)
-        .await?
-        .ok_or(SqlxError::RowNotFound)?;
-
-    // Get parent project
-    let project = task
-        .parent_project(pool)
-        .await?
-        .ok_or(SqlxError::RowNotFound)?;
-
     // If retry settings provided, perform replace-logic before proceeding
     if let Some(proc_id) = payload.retry_process_id {
         // Validate process belongs to this session
@@ -174,7 +240,28 @@ pub async fn follow_up(
     let latest_agent_session_id =
         ExecutionProcess::find_latest_coding_agent_turn_session_id(pool, session.id).await?;
 
-    let prompt = payload.prompt;
+    // Forward comments posted since the agent last ran, and make their images available
+    let mut prompt = payload.prompt;
+    if let Some(last_run) = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+        pool,
+        workspace.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    {
+        let comments =
+            TaskActivity::find_comments_since(pool, task.id, last_run.created_at).await?;
+        if let Some(section) = TaskActivity::comments_prompt(&comments) {
+            prompt = format!("{prompt}\n\n{section}");
+            if let Err(e) = deployment
+                .image()
+                .copy_images_by_task_to_worktree(&PathBuf::from(&container_ref), task.id)
+                .await
+            {
+                tracing::warn!("Failed to copy comment images to worktree: {}", e);
+            }
+        }
+    }
 
     let project_repos = ProjectRepo::find_by_project_id_with_names(pool, project.id).await?;
     let cleanup_action = deployment
@@ -234,6 +321,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
+        .route("/transcript", get(export_transcript))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
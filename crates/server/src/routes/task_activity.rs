@@ -0,0 +1,153 @@
+use axum::{
+    Extension, Json, Router,
+    extract::{
+        Path, State,
+        ws::{WebSocket, WebSocketUpgrade},
+    },
+    response::{IntoResponse, Json as ResponseJson},
+    routing::{get, post},
+};
+use db::models::{
+    image::Image,
+    task::Task,
+    task_activity::{CreateTaskComment, TaskActivity, TaskActivityKind, UpdateTaskComment},
+};
+use deployment::Deployment;
+use futures_util::{SinkExt, StreamExt, TryStreamExt};
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_task_activity(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskActivity>>>, ApiError> {
+    let entries = TaskActivity::find_by_task_id(&deployment.db().pool, task.id).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
+
+pub async fn stream_task_activity_ws(
+    ws: WebSocketUpgrade,
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| async move {
+        if let Err(e) = handle_task_activity_ws(socket, deployment, task.id).await {
+            tracing::warn!("task activity WS closed: {}", e);
+        }
+    })
+}
+
+async fn handle_task_activity_ws(
+    socket: WebSocket,
+    deployment: DeploymentImpl,
+    task_id: Uuid,
+) -> anyhow::Result<()> {
+    let mut stream = deployment
+        .events()
+        .stream_task_activity_raw(task_id)
+        .await?
+        .map_ok(|msg| msg.to_ws_message_unchecked());
+
+    let (mut sender, mut receiver) = socket.split();
+
+    // Drain (and ignore) any client->server messages so pings/pongs work
+    tokio::spawn(async move { while let Some(Ok(_)) = receiver.next().await {} });
+
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(msg) => {
+                if sender.send(msg).await.is_err() {
+                    break; // client disconnected
+                }
+            }
+            Err(e) => {
+                tracing::error!("stream error: {}", e);
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub async fn create_comment(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskActivity>>, ApiError> {
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest("Comment body is required".to_string()));
+    }
+    let pool = &deployment.db().pool;
+    for image_id in &payload.image_ids {
+        if Image::find_by_id(pool, *image_id).await?.is_none() {
+            return Err(ApiError::BadRequest(format!("Image {image_id} not found")));
+        }
+    }
+
+    let author = deployment.config().read().await.github.username.clone();
+    let comment = TaskActivity::create_comment(pool, task.id, author.as_deref(), &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_comment_created",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "image_count": payload.image_ids.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(comment)))
+}
+
+/// Load a comment of the task, rejecting system entries which can't be edited
+async fn load_comment(
+    deployment: &DeploymentImpl,
+    task_id: Uuid,
+    comment_id: Uuid,
+) -> Result<TaskActivity, ApiError> {
+    match TaskActivity::find_by_id(&deployment.db().pool, comment_id).await? {
+        Some(entry) if entry.task_id == task_id && entry.kind == TaskActivityKind::Comment => {
+            Ok(entry)
+        }
+        Some(entry) if entry.task_id == task_id => Err(ApiError::BadRequest(
+            "Only comments can be edited or deleted".to_string(),
+        )),
+        _ => Err(ApiError::Database(sqlx::Error::RowNotFound)),
+    }
+}
+
+pub async fn update_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateTaskComment>,
+) -> Result<ResponseJson<ApiResponse<TaskActivity>>, ApiError> {
+    if payload.body.trim().is_empty() {
+        return Err(ApiError::BadRequest("Comment body is required".to_string()));
+    }
+    let comment = load_comment(&deployment, task_id, comment_id).await?;
+    let updated =
+        TaskActivity::update_comment(&deployment.db().pool, comment.id, &payload.body).await?;
+    Ok(ResponseJson(ApiResponse::success(updated)))
+}
+
+pub async fn delete_comment(
+    State(deployment): State<DeploymentImpl>,
+    Path((task_id, comment_id)): Path<(Uuid, Uuid)>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let comment = load_comment(&deployment, task_id, comment_id).await?;
+    TaskActivity::delete(&deployment.db().pool, comment.id).await?;
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Routes relative to `/tasks/{task_id}`; the task is loaded by the caller's middleware.
+/// Routes addressing a single comment are mounted by the tasks router, outside the
+/// middleware, since they carry a second path parameter.
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/activity", get(get_task_activity))
+        .route("/activity/stream/ws", get(stream_task_activity_ws))
+        .route("/comments", post(create_comment))
+}
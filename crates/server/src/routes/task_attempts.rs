@@ -44,12 +44,12 @@ use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
-    git::{ConflictOp, GitCliError, GitServiceError},
+    git::{ConflictOp, DiffTarget, GitCliError, GitServiceError},
     github::GitHubService,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{diff::Diff, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
@@ -113,7 +113,7 @@ pub struct CreateTaskAttemptBody {
     pub custom_prompt: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, ts_rs::TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, ts_rs::TS)]
 pub struct WorkspaceRepoInput {
     pub repo_id: Uuid,
     pub target_branch: String,
@@ -150,6 +150,12 @@ pub async fn create_task_attempt(
         .await?
         .ok_or(SqlxError::RowNotFound)?;
 
+    if task.archived_at.is_some() || project.archived_at.is_some() {
+        return Err(ApiError::Conflict(
+            "Cannot start an attempt on an archived task".to_string(),
+        ));
+    }
+
     let agent_working_dir = project
         .default_agent_working_dir
         .as_ref()
@@ -602,7 +608,7 @@ pub struct BranchStatus {
     pub conflicted_files: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
 pub struct RepoBranchStatus {
     pub repo_id: Uuid,
     pub repo_name: String,
@@ -741,6 +747,60 @@ pub async fn get_task_attempt_branch_status(
     Ok(ResponseJson(ApiResponse::success(results)))
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct RepoDiff {
+    pub repo_id: Uuid,
+    pub repo_name: String,
+    pub diffs: Vec<Diff>,
+}
+
+/// Point-in-time diff of each repo's worktree against its merge base with the target branch,
+/// for clients that can't follow `/diff/ws`
+pub async fn get_task_attempt_diff(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<RepoDiff>>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let repositories = WorkspaceRepo::find_repos_for_workspace(pool, workspace.id).await?;
+    let workspace_repos = WorkspaceRepo::find_by_workspace_id(pool, workspace.id).await?;
+    let target_branches: HashMap<_, _> = workspace_repos
+        .iter()
+        .map(|wr| (wr.repo_id, wr.target_branch.clone()))
+        .collect();
+
+    let container_ref = deployment
+        .container()
+        .ensure_container_exists(&workspace)
+        .await?;
+    let workspace_dir = PathBuf::from(&container_ref);
+
+    let mut results = Vec::with_capacity(repositories.len());
+    for repo in repositories {
+        let Some(target_branch) = target_branches.get(&repo.id) else {
+            continue;
+        };
+        let base_commit =
+            deployment
+                .git()
+                .get_base_commit(&repo.path, &workspace.branch, target_branch)?;
+        let diffs = deployment.git().get_diffs(
+            DiffTarget::Worktree {
+                worktree_path: &workspace_dir.join(&repo.name),
+                base_commit: &base_commit,
+            },
+            None,
+        )?;
+        results.push(RepoDiff {
+            repo_id: repo.id,
+            repo_name: repo.name,
+            diffs,
+        });
+    }
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 #[derive(serde::Deserialize, Debug, TS)]
 pub struct ChangeTargetBranchRequest {
     pub repo_id: Uuid,
@@ -1489,6 +1549,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/run-setup-script", post(run_setup_script))
         .route("/run-cleanup-script", post(run_cleanup_script))
         .route("/branch-status", get(get_task_attempt_branch_status))
+        .route("/diff", get(get_task_attempt_diff))
         .route("/diff/ws", get(stream_task_attempt_diff_ws))
         .route("/merge", post(merge_task_attempt))
         .route("/push", post(push_task_attempt_branch))
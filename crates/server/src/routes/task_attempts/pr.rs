@@ -11,6 +11,7 @@ use db::models::{
     repo::{Repo, RepoError},
     session::{CreateSession, Session},
     task::{Task, TaskStatus},
+    task_github_issue::TaskGitHubIssue,
     workspace::{Workspace, WorkspaceError},
     workspace_repo::WorkspaceRepo,
 };
@@ -24,10 +25,13 @@ use serde::{Deserialize, Serialize};
 use services::services::{
     container::ContainerService,
     git::{GitCliError, GitServiceError},
-    github::{CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment},
+    github::{
+        CreatePrRequest, GitHubService, GitHubServiceError, UnifiedPrComment,
+        with_closing_reference,
+    },
 };
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{response::ApiResponse, telemetry};
 use uuid::Uuid;
 
 use crate::{DeploymentImpl, error::ApiError};
@@ -183,11 +187,18 @@ async fn trigger_pr_description_follow_up(
     Ok(())
 }
 
+#[tracing::instrument(
+    target = "vk::lifecycle",
+    name = "pr.create",
+    skip_all,
+    fields(workspace_id = %workspace.id, repo_id = %request.repo_id)
+)]
 pub async fn create_github_pr(
     Extension(workspace): Extension<Workspace>,
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateGitHubPrRequest>,
 ) -> Result<ResponseJson<ApiResponse<String, CreatePrError>>, ApiError> {
+    telemetry::attach_to_attempt(&tracing::Span::current(), workspace.id);
     let pool = &deployment.db().pool;
 
     let workspace_repo =
@@ -278,16 +289,26 @@ pub async fn create_github_pr(
     } else {
         target_branch
     };
+    // Use GitService to get the remote URL, then create GitHubRepoInfo
+    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
+
+    // Tasks imported from a GitHub issue close it when the PR merges
+    let body = match TaskGitHubIssue::find_by_task_id(pool, workspace.task_id).await? {
+        Some(issue) => Some(with_closing_reference(
+            request.body.clone(),
+            &issue.reference_from(&repo_info.owner, &repo_info.repo_name),
+        )),
+        None => request.body.clone(),
+    };
+
     // Create the PR using GitHub service
     let pr_request = CreatePrRequest {
         title: request.title.clone(),
-        body: request.body.clone(),
+        body,
         head_branch: workspace.branch.clone(),
         base_branch: norm_target_branch_name.clone(),
         draft: request.draft,
     };
-    // Use GitService to get the remote URL, then create GitHubRepoInfo
-    let repo_info = deployment.git().get_github_repo_info(&repo_path)?;
 
     // Use GitHubService to create the PR
     let github_service = GitHubService::new()?;
@@ -307,6 +328,13 @@ pub async fn create_github_pr(
                 tracing::error!("Failed to update workspace PR status: {}", e);
             }
 
+            if let Ok(Some(task)) = workspace.parent_task(pool).await {
+                deployment
+                    .webhooks()
+                    .pr_opened(&task, workspace.id, &pr_info)
+                    .await;
+            }
+
             // Auto-open PR in browser
             if let Err(e) = utils::browser::open_browser(&pr_info.url).await {
                 tracing::warn!("Failed to open PR in browser: {}", e);
@@ -0,0 +1,227 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use chrono::Utc;
+use db::models::{
+    project_repo::ProjectRepo,
+    task::Task,
+    task_schedule::{CreateTaskSchedule, TaskSchedule, TaskScheduleRun, UpdateTaskSchedule},
+    workspace_repo::CreateWorkspaceRepo,
+};
+use deployment::Deployment;
+use executors::profile::{ExecutorConfigs, ExecutorProfileId};
+use serde::Deserialize;
+use services::services::task_schedule::{run_schedule, validate_schedule};
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_RUN_LIMIT: i64 = 50;
+const MAX_RUN_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskScheduleQuery {
+    pub project_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, TS)]
+pub struct TaskScheduleRunsQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+async fn find_schedule(deployment: &DeploymentImpl, id: Uuid) -> Result<TaskSchedule, ApiError> {
+    TaskSchedule::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Task schedule not found".to_string()))
+}
+
+/// Check that the task, repositories and executor profile exist and belong to the project
+async fn validate_references(
+    deployment: &DeploymentImpl,
+    project_id: Uuid,
+    task_id: Option<Uuid>,
+    repos: &[CreateWorkspaceRepo],
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<(), ApiError> {
+    let pool = &deployment.db().pool;
+    if let Some(task_id) = task_id {
+        let task = Task::find_by_id(pool, task_id).await?;
+        if task.is_none_or(|task| task.project_id != project_id) {
+            return Err(ApiError::BadRequest(
+                "Task does not belong to the project".to_string(),
+            ));
+        }
+    }
+
+    let project_repos = ProjectRepo::find_repos_for_project(pool, project_id).await?;
+    for repo in repos {
+        if !project_repos.iter().any(|r| r.id == repo.repo_id) {
+            return Err(ApiError::BadRequest(format!(
+                "Repository {} does not belong to the project",
+                repo.repo_id
+            )));
+        }
+    }
+
+    if ExecutorConfigs::get_cached()
+        .get_coding_agent(executor_profile_id)
+        .is_none()
+    {
+        return Err(ApiError::BadRequest(format!(
+            "Unknown executor profile {executor_profile_id}"
+        )));
+    }
+    Ok(())
+}
+
+pub async fn get_task_schedules(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<TaskScheduleQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskSchedule>>>, ApiError> {
+    let schedules = TaskSchedule::find_all(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedules)))
+}
+
+pub async fn get_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let schedule = find_schedule(&deployment, id).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn create_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Schedule name must not be empty".to_string(),
+        ));
+    }
+    let next_run_at = validate_schedule(
+        &payload.cron_expression,
+        payload.timezone.as_deref().unwrap_or("UTC"),
+        payload.action,
+        payload.title.as_deref(),
+        payload.task_id,
+        &payload.repos,
+    )?;
+    validate_references(
+        &deployment,
+        payload.project_id,
+        payload.task_id,
+        &payload.repos,
+        &payload.executor_profile_id,
+    )
+    .await?;
+
+    let schedule = TaskSchedule::create(&deployment.db().pool, &payload, Some(next_run_at)).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_schedule_created",
+            serde_json::json!({
+                "schedule_id": schedule.id.to_string(),
+                "project_id": schedule.project_id.to_string(),
+                "action": schedule.action.to_string(),
+                "executor": schedule.executor_profile_id.executor,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn update_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTaskSchedule>,
+) -> Result<ResponseJson<ApiResponse<TaskSchedule>>, ApiError> {
+    let existing = find_schedule(&deployment, id).await?;
+    let schedule = TaskSchedule::merged(&existing, &payload);
+    if schedule.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Schedule name must not be empty".to_string(),
+        ));
+    }
+    let next_run_at = validate_schedule(
+        &schedule.cron_expression,
+        &schedule.timezone,
+        schedule.action,
+        schedule.title.as_deref(),
+        schedule.task_id,
+        &schedule.repos,
+    )?;
+    validate_references(
+        &deployment,
+        schedule.project_id,
+        schedule.task_id,
+        &schedule.repos,
+        &schedule.executor_profile_id,
+    )
+    .await?;
+
+    let schedule =
+        TaskSchedule::update(&deployment.db().pool, &schedule, Some(next_run_at)).await?;
+    Ok(ResponseJson(ApiResponse::success(schedule)))
+}
+
+pub async fn delete_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = TaskSchedule::delete(&deployment.db().pool, id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn get_task_schedule_runs(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<TaskScheduleRunsQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<TaskScheduleRun>>>, ApiError> {
+    find_schedule(&deployment, id).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_RUN_LIMIT)
+        .clamp(1, MAX_RUN_LIMIT);
+    let runs = TaskScheduleRun::find_by_schedule_id(&deployment.db().pool, id, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(runs)))
+}
+
+/// Run a schedule immediately, outside its cron schedule. Like scheduled runs, this is
+/// skipped while the previous run's attempt is still going.
+pub async fn run_task_schedule(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<TaskScheduleRun>>, ApiError> {
+    let schedule = find_schedule(&deployment, id).await?;
+    let run = run_schedule(deployment.container(), &schedule, Utc::now()).await?;
+    Ok(ResponseJson(ApiResponse::success(run)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route(
+            "/task-schedules",
+            get(get_task_schedules).post(create_task_schedule),
+        )
+        .route(
+            "/task-schedules/{id}",
+            get(get_task_schedule)
+                .put(update_task_schedule)
+                .delete(delete_task_schedule),
+        )
+        .route("/task-schedules/{id}/runs", get(get_task_schedule_runs))
+        .route("/task-schedules/{id}/run", post(run_task_schedule))
+}
@@ -14,9 +14,10 @@ use axum::{
 };
 use db::models::{
     image::TaskImage,
+    label::Label,
     project::{Project, ProjectError},
     repo::Repo,
-    task::{CreateTask, Task, TaskWithAttemptStatus, UpdateTask},
+    task::{CreateTask, Task, TaskFilter, TaskWithAttemptStatus, UpdateTask},
     workspace::{CreateWorkspace, Workspace},
     workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
 };
@@ -25,7 +26,11 @@ use executors::profile::ExecutorProfileId;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use services::services::{
-    container::ContainerService, share::ShareError, workspace_manager::WorkspaceManager,
+    archival,
+    container::ContainerService,
+    share::ShareError,
+    task_bulk::{self, BulkTaskRequest, BulkTaskResult},
+    workspace_manager::WorkspaceManager,
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
@@ -33,13 +38,17 @@ use utils::{api::oauth::LoginStatus, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
-    DeploymentImpl, error::ApiError, middleware::load_task_middleware,
-    routes::task_attempts::WorkspaceRepoInput,
+    DeploymentImpl,
+    error::ApiError,
+    middleware::load_task_middleware,
+    routes::{task_activity, task_attempts::WorkspaceRepoInput},
 };
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TaskQuery {
     pub project_id: Uuid,
+    #[serde(flatten)]
+    pub filter: TaskFilter,
 }
 
 pub async fn get_tasks(
@@ -50,7 +59,9 @@ pub async fn get_tasks(
         Task::find_by_project_id_with_attempt_status(&deployment.db().pool, query.project_id)
             .await?;
 
-    Ok(ResponseJson(ApiResponse::success(tasks)))
+    Ok(ResponseJson(ApiResponse::success(
+        query.filter.apply(tasks),
+    )))
 }
 
 pub async fn stream_tasks_ws(
@@ -125,6 +136,10 @@ pub async fn create_task(
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
     }
 
+    if let Some(labels) = &payload.labels {
+        Label::set_for_task(&deployment.db().pool, task.project_id, task.id, labels).await?;
+    }
+
     deployment
         .track_if_analytics_allowed(
             "task_created",
@@ -166,6 +181,11 @@ pub async fn create_task_and_start(
         TaskImage::associate_many_dedup(pool, task.id, image_ids).await?;
     }
 
+    let labels = match &payload.task.labels {
+        Some(labels) => Label::set_for_task(pool, task.project_id, task.id, labels).await?,
+        None => Vec::new(),
+    };
+
     deployment
         .track_if_analytics_allowed(
             "task_created",
@@ -245,6 +265,7 @@ pub async fn create_task_and_start(
         last_attempt_failed: false,
         executor: payload.executor_profile_id.executor.to_string(),
         suggested_prompt,
+        labels,
     })))
 }
 
@@ -264,18 +285,38 @@ pub async fn update_task(
         None => existing_task.description,      // Field omitted = keep existing
     };
     let status = payload.status.unwrap_or(existing_task.status);
+    // Omitted fields keep their value; `clear` removes it
+    let priority = if payload.clear.priority {
+        None
+    } else {
+        payload.priority.or(existing_task.priority)
+    };
+    let due_date = if payload.clear.due_date {
+        None
+    } else {
+        payload.due_date.or(existing_task.due_date)
+    };
+    let estimate_minutes = if payload.clear.estimate_minutes {
+        None
+    } else {
+        payload.estimate_minutes.or(existing_task.estimate_minutes)
+    };
     let parent_workspace_id = payload
         .parent_workspace_id
         .or(existing_task.parent_workspace_id);
 
-    let task = Task::update(
+    let mut task = Task::update(
         &deployment.db().pool,
-        existing_task.id,
-        existing_task.project_id,
-        title,
-        description,
-        status,
-        parent_workspace_id,
+        &Task {
+            title,
+            description,
+            status,
+            priority,
+            due_date,
+            estimate_minutes,
+            parent_workspace_id,
+            ..existing_task
+        },
     )
     .await?;
 
@@ -284,6 +325,14 @@ pub async fn update_task(
         TaskImage::associate_many_dedup(&deployment.db().pool, task.id, image_ids).await?;
     }
 
+    if let Some(labels) = &payload.labels {
+        Label::set_for_task(&deployment.db().pool, task.project_id, task.id, labels).await?;
+        // Setting labels touches updated_at
+        task = Task::find_by_id(&deployment.db().pool, task.id)
+            .await?
+            .ok_or(ApiError::Database(SqlxError::RowNotFound))?;
+    }
+
     // If task has been shared, broadcast update
     if task.shared_task_id.is_some() {
         let Ok(publisher) = deployment.share_publisher() else {
@@ -430,6 +479,69 @@ pub async fn delete_task(
     Ok((StatusCode::ACCEPTED, ResponseJson(ApiResponse::success(()))))
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ArchivedTasksQuery {
+    pub project_id: Uuid,
+}
+
+pub async fn get_archived_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ArchivedTasksQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<Task>>>, ApiError> {
+    let tasks = Task::find_archived_by_project_id(&deployment.db().pool, query.project_id).await?;
+    Ok(ResponseJson(ApiResponse::success(tasks)))
+}
+
+pub async fn archive_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    if task.archived_at.is_some() {
+        return Err(ApiError::Conflict("Task is already archived".to_string()));
+    }
+    archival::archive_task(deployment.container(), &task).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_archived",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    let task = Task::find_by_id(&deployment.db().pool, task.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
+pub async fn restore_task(
+    Extension(task): Extension<Task>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Task>>, ApiError> {
+    let pool = &deployment.db().pool;
+    if Task::restore(pool, task.id).await? == 0 {
+        return Err(ApiError::Conflict("Task is not archived".to_string()));
+    }
+
+    deployment
+        .track_if_analytics_allowed(
+            "task_restored",
+            serde_json::json!({
+                "task_id": task.id.to_string(),
+                "project_id": task.project_id.to_string(),
+            }),
+        )
+        .await;
+
+    let task = Task::find_by_id(pool, task.id)
+        .await?
+        .ok_or(SqlxError::RowNotFound)?;
+    Ok(ResponseJson(ApiResponse::success(task)))
+}
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 pub struct ShareTaskResponse {
     pub shared_task_id: Uuid,
@@ -462,21 +574,62 @@ pub async fn share_task(
     })))
 }
 
+/// Apply one operation to many tasks. Tasks that can't take it are reported in the results
+/// rather than failing the request.
+pub async fn bulk_update_tasks(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<BulkTaskRequest>,
+) -> Result<ResponseJson<ApiResponse<Vec<BulkTaskResult>>>, ApiError> {
+    let publisher = match deployment.get_login_status().await {
+        LoginStatus::LoggedIn { .. } => deployment.share_publisher().ok(),
+        LoginStatus::LoggedOut => None,
+    };
+    let results = task_bulk::apply(
+        deployment.container(),
+        deployment.events(),
+        publisher.as_ref(),
+        &payload,
+    )
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "tasks_bulk_updated",
+            serde_json::json!({
+                "operation": payload.operation.name(),
+                "task_count": results.len(),
+                "succeeded_count": results.iter().filter(|r| r.success).count(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(results)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_actions_router = Router::new()
         .route("/", put(update_task))
         .route("/", delete(delete_task))
-        .route("/share", post(share_task));
+        .route("/share", post(share_task))
+        .route("/archive", post(archive_task))
+        .route("/restore", post(restore_task));
 
     let task_id_router = Router::new()
         .route("/", get(get_task))
         .merge(task_actions_router)
+        .merge(task_activity::router())
         .layer(from_fn_with_state(deployment.clone(), load_task_middleware));
 
     let inner = Router::new()
         .route("/", get(get_tasks).post(create_task))
         .route("/stream/ws", get(stream_tasks_ws))
+        .route("/archived", get(get_archived_tasks))
+        .route("/bulk", post(bulk_update_tasks))
         .route("/create-and-start", post(create_task_and_start))
+        .route(
+            "/{task_id}/comments/{comment_id}",
+            put(task_activity::update_comment).delete(task_activity::delete_comment),
+        )
         .nest("/{task_id}", task_id_router);
 
     // mount under /projects/:project_id/tasks
@@ -0,0 +1,138 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+    routing::{get, post, put},
+};
+use db::models::webhook::{
+    CreateWebhookSubscription, UpdateWebhookSubscription, WebhookDelivery, WebhookSubscription,
+};
+use deployment::Deployment;
+use serde::Deserialize;
+use ts_rs::TS;
+use utils::response::ApiResponse;
+use uuid::Uuid;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+const DEFAULT_DELIVERY_LIMIT: i64 = 50;
+const MAX_DELIVERY_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize, TS)]
+pub struct WebhookDeliveriesQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+}
+
+fn validate_webhook_url(raw: &str) -> Result<(), ApiError> {
+    let url = url::Url::parse(raw)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid webhook URL: {e}")))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ApiError::BadRequest(
+            "Webhook URL must use http or https".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_subscription(
+    deployment: &DeploymentImpl,
+    id: Uuid,
+) -> Result<WebhookSubscription, ApiError> {
+    WebhookSubscription::find_by_id(&deployment.db().pool, id)
+        .await?
+        .ok_or_else(|| ApiError::BadRequest("Webhook not found".to_string()))
+}
+
+pub async fn get_webhooks(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<WebhookSubscription>>>, ApiError> {
+    let webhooks = WebhookSubscription::find_all(&deployment.db().pool).await?;
+    Ok(ResponseJson(ApiResponse::success(webhooks)))
+}
+
+pub async fn create_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Json(payload): Json<CreateWebhookSubscription>,
+) -> Result<ResponseJson<ApiResponse<WebhookSubscription>>, ApiError> {
+    if payload.name.trim().is_empty() {
+        return Err(ApiError::BadRequest(
+            "Webhook name must not be empty".to_string(),
+        ));
+    }
+    validate_webhook_url(&payload.url)?;
+
+    let webhook = WebhookSubscription::create(&deployment.db().pool, &payload).await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "webhook_created",
+            serde_json::json!({
+                "webhook_id": webhook.id.to_string(),
+                "event_count": webhook.events.len(),
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+pub async fn update_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookSubscription>,
+) -> Result<ResponseJson<ApiResponse<WebhookSubscription>>, ApiError> {
+    find_subscription(&deployment, id).await?;
+    if let Some(url) = &payload.url {
+        validate_webhook_url(url)?;
+    }
+
+    let webhook = WebhookSubscription::update(&deployment.db().pool, id, &payload).await?;
+    Ok(ResponseJson(ApiResponse::success(webhook)))
+}
+
+pub async fn delete_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let rows_affected = WebhookSubscription::delete(&deployment.db().pool, id).await?;
+    if rows_affected == 0 {
+        Err(ApiError::Database(sqlx::Error::RowNotFound))
+    } else {
+        Ok(ResponseJson(ApiResponse::success(())))
+    }
+}
+
+pub async fn get_webhook_deliveries(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<WebhookDeliveriesQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<WebhookDelivery>>>, ApiError> {
+    find_subscription(&deployment, id).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_DELIVERY_LIMIT)
+        .clamp(1, MAX_DELIVERY_LIMIT);
+    let deliveries =
+        WebhookDelivery::find_by_subscription_id(&deployment.db().pool, id, limit).await?;
+    Ok(ResponseJson(ApiResponse::success(deliveries)))
+}
+
+pub async fn redeliver_webhook(
+    State(deployment): State<DeploymentImpl>,
+    Path(delivery_id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<WebhookDelivery>>, ApiError> {
+    let delivery = deployment.webhooks().redeliver(delivery_id).await?;
+    Ok(ResponseJson(ApiResponse::success(delivery)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/webhooks", get(get_webhooks).post(create_webhook))
+        .route("/webhooks/{id}", put(update_webhook).delete(delete_webhook))
+        .route("/webhooks/{id}/deliveries", get(get_webhook_deliveries))
+        .route(
+            "/webhooks/deliveries/{delivery_id}/redeliver",
+            post(redeliver_webhook),
+        )
+}
@@ -1,14 +1,22 @@
 pub mod executor_approvals;
+pub mod policy;
+pub mod webhook;
 
 use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
 
+use chrono::{DateTime, Duration, Utc};
 use dashmap::DashMap;
 use db::models::{
+    approval::{
+        ApprovalDecisionSource, ApprovalOutcome, ApprovalRecord, ApprovalResolution,
+        CreateApprovalRecord,
+    },
     execution_process::ExecutionProcess,
     task::{Task, TaskStatus},
 };
 use executors::{
-    approvals::ToolCallMetadata,
+    approvals::{ApprovalTimeoutAction, ToolCallMetadata},
+    command::CmdOverrides,
     logs::{
         NormalizedEntry, NormalizedEntryType, ToolStatus,
         utils::patch::{ConversationPatch, extract_normalized_entry_from_patch},
@@ -19,18 +27,35 @@ use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
 use tokio::sync::{RwLock, oneshot};
 use utils::{
-    approvals::{ApprovalRequest, ApprovalResponse, ApprovalStatus},
+    approvals::{APPROVAL_TIMEOUT_SECONDS, ApprovalRequest, ApprovalResponse, ApprovalStatus},
     log_msg::LogMsg,
     msg_store::MsgStore,
 };
 use uuid::Uuid;
 
+use crate::services::{
+    approvals::webhook::{
+        ApprovalLinkDecision, ApprovalLinkSigner, ApprovalWebhookPayload, send_approval_webhook,
+    },
+    config::Config,
+};
+
+/// Recorded as the responder for approvals answered through a signed link
+const LINK_RESPONDER: &str = "signed link";
+
+/// Denial reason given to the agent and recorded when an approval times out and is denied
+const TIMEOUT_DENIAL_REASON: &str = "Timed out waiting for approval";
+
+/// Recorded as the responder for approvals answered in the UI without an access token
+pub const UI_RESPONDER: &str = "ui";
+
 #[derive(Debug)]
 struct PendingApproval {
     entry_index: usize,
     entry: NormalizedEntry,
     execution_process_id: Uuid,
     tool_name: String,
+    requested_at: DateTime<Utc>,
     response_tx: oneshot::Sender<ApprovalStatus>,
 }
 
@@ -42,8 +67,41 @@ pub struct ToolContext {
     pub execution_process_id: Uuid,
 }
 
+/// How long approvals wait for a response and what happens when they time out, taken from
+/// the executor profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalTimeout {
+    pub seconds: i64,
+    pub action: ApprovalTimeoutAction,
+}
+
+impl Default for ApprovalTimeout {
+    fn default() -> Self {
+        Self {
+            seconds: APPROVAL_TIMEOUT_SECONDS,
+            action: ApprovalTimeoutAction::default(),
+        }
+    }
+}
+
+impl ApprovalTimeout {
+    pub fn from_overrides(cmd: &CmdOverrides) -> Self {
+        Self {
+            seconds: cmd
+                .approval_timeout_seconds
+                .map(|seconds| i64::from(seconds.max(1)))
+                .unwrap_or(APPROVAL_TIMEOUT_SECONDS),
+            action: cmd.approval_timeout_action.unwrap_or_default(),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Approvals {
+    pool: SqlitePool,
+    config: Arc<RwLock<Config>>,
+    link_signer: ApprovalLinkSigner,
+    http: reqwest::Client,
     pending: Arc<DashMap<String, PendingApproval>>,
     completed: Arc<DashMap<String, ApprovalStatus>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
@@ -59,6 +117,8 @@ pub enum ApprovalError {
     NoExecutorSession(String),
     #[error("corresponding tool use entry not found for approval request")]
     NoToolUseEntry,
+    #[error("invalid approval link signature")]
+    InvalidSignature,
     #[error(transparent)]
     Custom(#[from] anyhow::Error),
     #[error(transparent)]
@@ -66,17 +126,31 @@ pub enum ApprovalError {
 }
 
 impl Approvals {
-    pub fn new(msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>) -> Self {
+    pub fn new(
+        pool: SqlitePool,
+        config: Arc<RwLock<Config>>,
+        msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    ) -> Self {
         Self {
+            pool,
+            config,
+            link_signer: ApprovalLinkSigner::default(),
+            http: reqwest::Client::new(),
             pending: Arc::new(DashMap::new()),
             completed: Arc::new(DashMap::new()),
             msg_stores,
         }
     }
 
+    /// Mark approvals left pending by a previous run as timed out; their agents are gone
+    pub async fn expire_stale(&self) -> Result<u64, ApprovalError> {
+        Ok(ApprovalRecord::expire_pending(&self.pool).await?)
+    }
+
     pub async fn create_with_waiter(
         &self,
         request: ApprovalRequest,
+        timeout: ApprovalTimeout,
     ) -> Result<(ApprovalRequest, ApprovalWaiter), ApprovalError> {
         let (tx, rx) = oneshot::channel();
         let waiter: ApprovalWaiter = rx
@@ -106,6 +180,7 @@ impl Approvals {
                         entry: matching_tool,
                         execution_process_id: request.execution_process_id,
                         tool_name: request.tool_name.clone(),
+                        requested_at: request.created_at,
                         response_tx: tx,
                     },
                 );
@@ -129,21 +204,121 @@ impl Approvals {
             );
         }
 
-        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, waiter.clone());
+        if let Err(e) = self.persist_request(&request).await {
+            tracing::warn!("Failed to record approval request {}: {}", req_id, e);
+        }
+        if self.pending.contains_key(&req_id) {
+            self.spawn_webhook(&request).await;
+        }
+
+        self.spawn_timeout_watcher(req_id.clone(), request.timeout_at, timeout, waiter.clone());
         Ok((request, waiter))
     }
 
+    /// Reflect an approval policy decision on the matching tool use entry and in the audit
+    /// trail, without creating a pending approval
+    pub async fn record_policy_decision(
+        &self,
+        request: &ApprovalRequest,
+        status: ToolStatus,
+        resolution: ApprovalResolution,
+    ) {
+        if let Err(e) = self.persist_request(request).await {
+            tracing::warn!("Failed to record approval request {}: {}", request.id, e);
+        } else if let Err(e) = ApprovalRecord::resolve(&self.pool, &request.id, &resolution).await {
+            tracing::warn!("Failed to record policy decision {}: {}", request.id, e);
+        }
+
+        let Some(store) = self.msg_store_by_id(&request.execution_process_id).await else {
+            tracing::warn!(
+                "No msg_store found for execution_process_id: {}",
+                request.execution_process_id
+            );
+            return;
+        };
+
+        match find_matching_tool_use(store.clone(), &request.tool_call_id)
+            .and_then(|(idx, entry)| Some((idx, entry.with_tool_status(status)?)))
+        {
+            Some((idx, updated_entry)) => {
+                store.push_patch(ConversationPatch::replace(idx, updated_entry));
+            }
+            None => tracing::warn!(
+                "No matching tool use entry found for policy decision: tool='{}', execution_process_id={}",
+                request.tool_name,
+                request.execution_process_id
+            ),
+        }
+    }
+
+    /// Check a signed approve/deny link against a pending approval without answering it
+    pub fn check_link(
+        &self,
+        id: &str,
+        decision: ApprovalLinkDecision,
+        token: &str,
+    ) -> Result<ToolContext, ApprovalError> {
+        if !self.link_signer.verify(id, decision, token) {
+            return Err(ApprovalError::InvalidSignature);
+        }
+        self.pending
+            .get(id)
+            .map(|p| ToolContext {
+                tool_name: p.tool_name.clone(),
+                execution_process_id: p.execution_process_id,
+            })
+            .ok_or_else(|| {
+                if self.completed.contains_key(id) {
+                    ApprovalError::AlreadyCompleted
+                } else {
+                    ApprovalError::NotFound
+                }
+            })
+    }
+
+    /// Answer a pending approval through a signed approve/deny link
+    pub async fn respond_via_link(
+        &self,
+        id: &str,
+        decision: ApprovalLinkDecision,
+        token: &str,
+        reason: Option<String>,
+    ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
+        let context = self.check_link(id, decision, token)?;
+
+        self.respond(
+            &self.pool,
+            id,
+            ApprovalResponse {
+                execution_process_id: context.execution_process_id,
+                status: decision.into_status(reason),
+            },
+            Some(LINK_RESPONDER.to_string()),
+        )
+        .await
+    }
+
+    /// Answer a pending approval; `responder` identifies who answered in the audit trail
     #[tracing::instrument(skip(self, id, req))]
     pub async fn respond(
         &self,
         pool: &SqlitePool,
         id: &str,
         req: ApprovalResponse,
+        responder: Option<String>,
     ) -> Result<(ApprovalStatus, ToolContext), ApprovalError> {
         if let Some((_, p)) = self.pending.remove(id) {
             self.completed.insert(id.to_string(), req.status.clone());
             let _ = p.response_tx.send(req.status.clone());
 
+            if let Some(mut resolution) = resolution_for(&req.status, ApprovalDecisionSource::User)
+            {
+                resolution.responder = responder;
+                if let Err(e) = ApprovalRecord::resolve(pool, id, &resolution).await {
+                    tracing::warn!("Failed to record approval response {}: {}", id, e);
+                }
+            }
+
             if let Some(store) = self.msg_store_by_id(&p.execution_process_id).await {
                 let status = ToolStatus::from_approval_status(&req.status).ok_or(
                     ApprovalError::Custom(anyhow::anyhow!("Invalid approval status")),
@@ -193,61 +368,175 @@ impl Approvals {
     fn spawn_timeout_watcher(
         &self,
         id: String,
-        timeout_at: chrono::DateTime<chrono::Utc>,
+        timeout_at: DateTime<Utc>,
+        timeout: ApprovalTimeout,
         waiter: ApprovalWaiter,
     ) {
+        let pool = self.pool.clone();
         let pending = self.pending.clone();
         let completed = self.completed.clone();
         let msg_stores = self.msg_stores.clone();
 
-        let now = chrono::Utc::now();
-        let to_wait = (timeout_at - now)
+        let to_wait = (timeout_at - Utc::now())
             .to_std()
             .unwrap_or_else(|_| StdDuration::from_secs(0));
-        let deadline = tokio::time::Instant::now() + to_wait;
+        let mut deadline = tokio::time::Instant::now() + to_wait;
 
         tokio::spawn(async move {
-            let status = tokio::select! {
-                biased;
+            loop {
+                let timed_out = tokio::select! {
+                    biased;
 
-                resolved = waiter.clone() => resolved,
-                _ = tokio::time::sleep_until(deadline) => ApprovalStatus::TimedOut,
-            };
-
-            let is_timeout = matches!(&status, ApprovalStatus::TimedOut);
-            completed.insert(id.clone(), status.clone());
+                    resolved = waiter.clone() => {
+                        completed.insert(id.clone(), resolved);
+                        false
+                    }
+                    _ = tokio::time::sleep_until(deadline) => true,
+                };
+                if !timed_out {
+                    return;
+                }
 
-            if is_timeout && let Some((_, pending_approval)) = pending.remove(&id) {
-                if pending_approval.response_tx.send(status.clone()).is_err() {
-                    tracing::debug!("approval '{}' timeout notification receiver dropped", id);
+                if timeout.action == ApprovalTimeoutAction::KeepWaiting {
+                    let Some((idx, entry, execution_process_id, requested_at)) =
+                        pending.get(&id).map(|p| {
+                            (
+                                p.entry_index,
+                                p.entry.clone(),
+                                p.execution_process_id,
+                                p.requested_at,
+                            )
+                        })
+                    else {
+                        return;
+                    };
+
+                    let timeout_at = Utc::now() + Duration::seconds(timeout.seconds);
+                    deadline = tokio::time::Instant::now()
+                        + StdDuration::from_secs(timeout.seconds.unsigned_abs());
+
+                    let store = msg_stores.read().await.get(&execution_process_id).cloned();
+                    if let Some(store) = store
+                        && let Some(updated_entry) =
+                            entry.with_tool_status(ToolStatus::PendingApproval {
+                                approval_id: id.clone(),
+                                requested_at,
+                                timeout_at,
+                            })
+                    {
+                        store.push_patch(ConversationPatch::replace(idx, updated_entry));
+                    }
+                    if let Err(e) = ApprovalRecord::extend_timeout(&pool, &id, timeout_at).await {
+                        tracing::warn!("Failed to extend approval '{}' timeout: {}", id, e);
+                    }
+                    tracing::debug!("Approval '{}' timed out; waiting until {}", id, timeout_at);
+                    continue;
                 }
 
-                let store = {
-                    let map = msg_stores.read().await;
-                    map.get(&pending_approval.execution_process_id).cloned()
-                };
+                let status = timeout_status(timeout.action);
+                completed.insert(id.clone(), status.clone());
 
-                if let Some(store) = store {
-                    if let Some(updated_entry) = pending_approval
-                        .entry
-                        .with_tool_status(ToolStatus::TimedOut)
-                    {
-                        store.push_patch(ConversationPatch::replace(
-                            pending_approval.entry_index,
-                            updated_entry,
-                        ));
+                if let Some((_, pending_approval)) = pending.remove(&id) {
+                    if pending_approval.response_tx.send(status.clone()).is_err() {
+                        tracing::debug!("approval '{}' timeout notification receiver dropped", id);
+                    }
+
+                    let store = {
+                        let map = msg_stores.read().await;
+                        map.get(&pending_approval.execution_process_id).cloned()
+                    };
+
+                    if let Some(store) = store {
+                        if let Some(updated_entry) = ToolStatus::from_approval_status(&status)
+                            .and_then(|tool_status| {
+                                pending_approval.entry.with_tool_status(tool_status)
+                            })
+                        {
+                            store.push_patch(ConversationPatch::replace(
+                                pending_approval.entry_index,
+                                updated_entry,
+                            ));
+                        } else {
+                            tracing::warn!(
+                                "Timed out approval '{}' but couldn't update tool status (no tool-use entry).",
+                                id
+                            );
+                        }
                     } else {
                         tracing::warn!(
-                            "Timed out approval '{}' but couldn't update tool status (no tool-use entry).",
-                            id
+                            "No msg_store found for execution_process_id: {}",
+                            pending_approval.execution_process_id
                         );
                     }
-                } else {
-                    tracing::warn!(
-                        "No msg_store found for execution_process_id: {}",
-                        pending_approval.execution_process_id
-                    );
                 }
+
+                if let Some(resolution) = resolution_for(&status, ApprovalDecisionSource::Timeout) {
+                    if let Err(e) = ApprovalRecord::resolve(&pool, &id, &resolution).await {
+                        tracing::warn!("Failed to record approval timeout {}: {}", id, e);
+                    }
+                }
+                return;
+            }
+        });
+    }
+
+    async fn persist_request(&self, request: &ApprovalRequest) -> Result<(), SqlxError> {
+        ApprovalRecord::create(
+            &self.pool,
+            &CreateApprovalRecord {
+                id: &request.id,
+                execution_process_id: request.execution_process_id,
+                tool_name: &request.tool_name,
+                tool_input: &request.tool_input,
+                tool_call_id: &request.tool_call_id,
+                created_at: request.created_at,
+                timeout_at: request.timeout_at,
+            },
+        )
+        .await
+    }
+
+    /// POST the request and its signed links to the configured approval webhook, if any
+    async fn spawn_webhook(&self, request: &ApprovalRequest) {
+        let webhook = self.config.read().await.approval_webhook.clone();
+        let Some(url) = webhook.url.filter(|url| !url.trim().is_empty()) else {
+            return;
+        };
+
+        let pool = self.pool.clone();
+        let http = self.http.clone();
+        let signer = self.link_signer.clone();
+        let request = request.clone();
+        tokio::spawn(async move {
+            let base_url = match webhook.public_base_url {
+                Some(base_url) => base_url,
+                None => match utils::port_file::read_port_file("vibe-kanban").await {
+                    Ok(port) => format!("http://127.0.0.1:{port}"),
+                    Err(e) => {
+                        tracing::warn!("Cannot build approval links, server port unknown: {}", e);
+                        return;
+                    }
+                },
+            };
+            let task = ExecutionProcess::load_context(&pool, request.execution_process_id)
+                .await
+                .ok()
+                .map(|ctx| ctx.task);
+
+            let payload = ApprovalWebhookPayload {
+                event: "approval.requested".to_string(),
+                approve_url: signer.link(&base_url, &request.id, ApprovalLinkDecision::Approve),
+                deny_url: signer.link(&base_url, &request.id, ApprovalLinkDecision::Deny),
+                task_id: task.as_ref().map(|task| task.id),
+                task_title: task.map(|task| task.title),
+                approval: request,
+            };
+            if let Err(e) = send_approval_webhook(&http, &url, &payload).await {
+                tracing::warn!(
+                    "Failed to deliver approval webhook for {}: {}",
+                    payload.approval.id,
+                    e
+                );
             }
         });
     }
@@ -270,6 +559,38 @@ pub(crate) async fn ensure_task_in_review(pool: &SqlitePool, execution_process_i
     }
 }
 
+/// Audit trail outcome for a final approval status
+/// The final status of an approval whose timeout elapsed; the agent, the tool entry and the
+/// audit trail all see this same status
+fn timeout_status(action: ApprovalTimeoutAction) -> ApprovalStatus {
+    match action {
+        ApprovalTimeoutAction::Approve => ApprovalStatus::Approved,
+        ApprovalTimeoutAction::Deny => ApprovalStatus::Denied {
+            reason: Some(TIMEOUT_DENIAL_REASON.to_string()),
+        },
+        ApprovalTimeoutAction::KeepWaiting => ApprovalStatus::TimedOut,
+    }
+}
+
+pub(crate) fn resolution_for(
+    status: &ApprovalStatus,
+    decided_by: ApprovalDecisionSource,
+) -> Option<ApprovalResolution> {
+    let (status, denial_reason) = match status {
+        ApprovalStatus::Pending => return None,
+        ApprovalStatus::Approved => (ApprovalOutcome::Approved, None),
+        ApprovalStatus::Denied { reason } => (ApprovalOutcome::Denied, reason.clone()),
+        ApprovalStatus::TimedOut => (ApprovalOutcome::TimedOut, None),
+    };
+    Some(ApprovalResolution {
+        status,
+        denial_reason,
+        decided_by,
+        rule_id: None,
+        responder: None,
+    })
+}
+
 /// Find a matching tool use entry that hasn't been assigned to an approval yet
 /// Matches by tool call id from tool metadata
 fn find_matching_tool_use(
@@ -398,4 +719,70 @@ mod tests {
             "Should not match different tool ids"
         );
     }
+
+    #[test]
+    fn test_approval_timeout_from_overrides() {
+        assert_eq!(
+            ApprovalTimeout::from_overrides(&CmdOverrides::default()),
+            ApprovalTimeout::default()
+        );
+
+        let cmd = CmdOverrides {
+            approval_timeout_seconds: Some(0),
+            approval_timeout_action: Some(ApprovalTimeoutAction::KeepWaiting),
+            ..Default::default()
+        };
+        assert_eq!(
+            ApprovalTimeout::from_overrides(&cmd),
+            ApprovalTimeout {
+                seconds: 1,
+                action: ApprovalTimeoutAction::KeepWaiting,
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolution_for_status() {
+        let denied = resolution_for(
+            &ApprovalStatus::Denied {
+                reason: Some("no".to_string()),
+            },
+            ApprovalDecisionSource::User,
+        )
+        .expect("final status");
+        assert_eq!(denied.status, ApprovalOutcome::Denied);
+        assert_eq!(denied.denial_reason.as_deref(), Some("no"));
+        assert!(resolution_for(&ApprovalStatus::Pending, ApprovalDecisionSource::User).is_none());
+    }
+
+    #[test]
+    fn test_timeout_deny_matches_audit_record() {
+        let status = timeout_status(ApprovalTimeoutAction::Deny);
+        let resolution =
+            resolution_for(&status, ApprovalDecisionSource::Timeout).expect("final status");
+
+        // What the agent is told
+        assert!(matches!(
+            &status,
+            ApprovalStatus::Denied { reason } if reason.as_deref() == Some(TIMEOUT_DENIAL_REASON)
+        ));
+        assert!(matches!(
+            ToolStatus::from_approval_status(&status),
+            Some(ToolStatus::Denied { reason, .. }) if reason.as_deref() == Some(TIMEOUT_DENIAL_REASON)
+        ));
+        // What the audit trail records
+        assert_eq!(resolution.status, ApprovalOutcome::Denied);
+        assert_eq!(
+            resolution.denial_reason.as_deref(),
+            Some(TIMEOUT_DENIAL_REASON)
+        );
+        assert_eq!(resolution.decided_by, ApprovalDecisionSource::Timeout);
+
+        let approved = resolution_for(
+            &timeout_status(ApprovalTimeoutAction::Approve),
+            ApprovalDecisionSource::Timeout,
+        )
+        .expect("final status");
+        assert_eq!(approved.status, ApprovalOutcome::Approved);
+    }
 }
@@ -1,19 +1,38 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use async_trait::async_trait;
-use db::{self, DBService};
-use executors::approvals::{ExecutorApprovalError, ExecutorApprovalService};
+use db::{
+    self, DBService,
+    models::{
+        approval::ApprovalDecisionSource,
+        approval_policy::{ApprovalPolicyAction, ApprovalPolicyRule},
+        execution_process::ExecutionProcess,
+    },
+};
+use executors::{
+    approvals::{ExecutorApprovalError, ExecutorApprovalService},
+    logs::ToolStatus,
+};
 use serde_json::Value;
 use utils::approvals::{ApprovalRequest, ApprovalStatus, CreateApprovalRequest};
 use uuid::Uuid;
 
-use crate::services::{approvals::Approvals, notification::NotificationService};
+use crate::services::{
+    approvals::{
+        ApprovalTimeout, Approvals,
+        policy::{self, PolicyDecision, WorktreeRoots},
+        resolution_for,
+    },
+    config::NotificationEvent,
+    notification::NotificationService,
+};
 
 pub struct ExecutorApprovalBridge {
     approvals: Approvals,
     db: DBService,
     notification_service: NotificationService,
     execution_process_id: Uuid,
+    timeout: ApprovalTimeout,
 }
 
 impl ExecutorApprovalBridge {
@@ -22,14 +41,36 @@ impl ExecutorApprovalBridge {
         db: DBService,
         notification_service: NotificationService,
         execution_process_id: Uuid,
+        timeout: ApprovalTimeout,
     ) -> Arc<Self> {
         Arc::new(Self {
             approvals,
             db,
             notification_service,
             execution_process_id,
+            timeout,
         })
     }
+
+    /// Run the project's approval policy against a request
+    async fn evaluate_policy(&self, request: &ApprovalRequest) -> Option<PolicyDecision> {
+        let ctx = ExecutionProcess::load_context(&self.db.pool, self.execution_process_id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load context for approval policy: {}", e))
+            .ok()?;
+        let rules = ApprovalPolicyRule::find_by_project_id(&self.db.pool, ctx.task.project_id)
+            .await
+            .inspect_err(|e| tracing::warn!("Failed to load approval policy rules: {}", e))
+            .ok()?;
+
+        let workspace_dir = PathBuf::from(ctx.workspace.container_ref.as_deref()?);
+        let roots = WorktreeRoots::new(
+            &workspace_dir,
+            ctx.repos.iter().map(|repo| workspace_dir.join(&repo.name)),
+        );
+
+        policy::evaluate(&rules, &request.tool_name, &request.tool_input, &roots)
+    }
 }
 
 #[async_trait]
@@ -40,8 +81,6 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
         tool_input: Value,
         tool_call_id: &str,
     ) -> Result<ApprovalStatus, ExecutorApprovalError> {
-        super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
-
         let request = ApprovalRequest::from_create(
             CreateApprovalRequest {
                 tool_name: tool_name.to_string(),
@@ -49,17 +88,63 @@ impl ExecutorApprovalService for ExecutorApprovalBridge {
                 tool_call_id: tool_call_id.to_string(),
             },
             self.execution_process_id,
-        );
+        )
+        .with_timeout_seconds(self.timeout.seconds);
+
+        if let Some(decision) = self.evaluate_policy(&request).await {
+            tracing::debug!(
+                "Approval rule '{}' decided {} for tool '{}'",
+                decision.rule.rule_name,
+                decision.action,
+                tool_name
+            );
+            let rule_id = decision.rule.rule_id;
+            let responder = format!("policy rule '{}'", decision.rule.rule_name);
+            let (status, tool_status) = match decision.action {
+                ApprovalPolicyAction::Approve => (
+                    ApprovalStatus::Approved,
+                    ToolStatus::AutoApproved {
+                        rule: decision.rule,
+                    },
+                ),
+                ApprovalPolicyAction::Deny => {
+                    let reason = Some(format!(
+                        "Denied by approval rule '{}'",
+                        decision.rule.rule_name
+                    ));
+                    (
+                        ApprovalStatus::Denied {
+                            reason: reason.clone(),
+                        },
+                        ToolStatus::Denied {
+                            reason,
+                            rule: Some(decision.rule),
+                        },
+                    )
+                }
+            };
+            if let Some(mut resolution) = resolution_for(&status, ApprovalDecisionSource::Policy) {
+                resolution.rule_id = Some(rule_id);
+                resolution.responder = Some(responder);
+                self.approvals
+                    .record_policy_decision(&request, tool_status, resolution)
+                    .await;
+            }
+            return Ok(status);
+        }
+
+        super::ensure_task_in_review(&self.db.pool, self.execution_process_id).await;
 
         let (_, waiter) = self
             .approvals
-            .create_with_waiter(request)
+            .create_with_waiter(request, self.timeout)
             .await
             .map_err(ExecutorApprovalError::request_failed)?;
 
         // Play notification sound when approval is needed
         self.notification_service
             .notify(
+                NotificationEvent::ApprovalPending,
                 "Approval Needed",
                 &format!("Tool '{}' requires approval", tool_name),
             )
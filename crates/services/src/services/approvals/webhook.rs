@@ -0,0 +1,223 @@
+//! Outbound approval webhooks. Each pending approval can be announced to an external URL
+//! together with signed approve/deny links, so it can be answered from a chat bot or a phone
+//! shortcut without the UI.
+
+use std::{sync::Arc, time::Duration};
+
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use strum_macros::Display;
+use ts_rs::TS;
+use utils::approvals::{ApprovalRequest, ApprovalStatus};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Decision carried by a signed approval link
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, TS, Display)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ApprovalLinkDecision {
+    Approve,
+    Deny,
+}
+
+impl ApprovalLinkDecision {
+    pub fn into_status(self, reason: Option<String>) -> ApprovalStatus {
+        match self {
+            Self::Approve => ApprovalStatus::Approved,
+            Self::Deny => ApprovalStatus::Denied { reason },
+        }
+    }
+}
+
+/// Body POSTed to the approval webhook
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ApprovalWebhookPayload {
+    /// Always `approval.requested`
+    pub event: String,
+    pub approval: ApprovalRequest,
+    pub task_id: Option<Uuid>,
+    pub task_title: Option<String>,
+    /// POST to approve the tool call, or open (GET) it for a confirmation page; valid until
+    /// the approval is answered
+    pub approve_url: String,
+    /// POST to deny the tool call, or open (GET) it for a confirmation page; an optional
+    /// `reason` query or form parameter is passed to the agent
+    pub deny_url: String,
+}
+
+/// Signs and verifies one-time approval links. The key lives only in memory: approvals do
+/// not survive a restart, so neither do their links.
+#[derive(Clone)]
+pub struct ApprovalLinkSigner {
+    key: Arc<[u8; 32]>,
+}
+
+impl Default for ApprovalLinkSigner {
+    fn default() -> Self {
+        let mut key = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        Self { key: Arc::new(key) }
+    }
+}
+
+impl ApprovalLinkSigner {
+    pub fn token(&self, approval_id: &str, decision: ApprovalLinkDecision) -> String {
+        hex::encode(self.mac(approval_id, decision).finalize().into_bytes())
+    }
+
+    pub fn verify(&self, approval_id: &str, decision: ApprovalLinkDecision, token: &str) -> bool {
+        let Ok(signature) = hex::decode(token) else {
+            return false;
+        };
+        self.mac(approval_id, decision)
+            .verify_slice(&signature)
+            .is_ok()
+    }
+
+    pub fn link(
+        &self,
+        base_url: &str,
+        approval_id: &str,
+        decision: ApprovalLinkDecision,
+    ) -> String {
+        format!(
+            "{}/api/approvals/{}/respond/link?decision={}&token={}",
+            base_url.trim_end_matches('/'),
+            approval_id,
+            decision,
+            self.token(approval_id, decision)
+        )
+    }
+
+    fn mac(&self, approval_id: &str, decision: ApprovalLinkDecision) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(self.key.as_slice()).expect("HMAC accepts any key size");
+        mac.update(format!("{approval_id}:{decision}").as_bytes());
+        mac
+    }
+}
+
+pub async fn send_approval_webhook(
+    client: &reqwest::Client,
+    url: &str,
+    payload: &ApprovalWebhookPayload,
+) -> Result<(), reqwest::Error> {
+    client
+        .post(url)
+        .timeout(WEBHOOK_TIMEOUT)
+        .json(payload)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_link_tokens_are_bound_to_approval_and_decision() {
+        let signer = ApprovalLinkSigner::default();
+        let token = signer.token("approval-1", ApprovalLinkDecision::Approve);
+
+        assert!(signer.verify("approval-1", ApprovalLinkDecision::Approve, &token));
+        assert!(!signer.verify("approval-1", ApprovalLinkDecision::Deny, &token));
+        assert!(!signer.verify("approval-2", ApprovalLinkDecision::Approve, &token));
+        assert!(!signer.verify("approval-1", ApprovalLinkDecision::Approve, "not-hex"));
+        assert!(!ApprovalLinkSigner::default().verify(
+            "approval-1",
+            ApprovalLinkDecision::Approve,
+            &token
+        ));
+    }
+
+    #[test]
+    fn test_link_format() {
+        let signer = ApprovalLinkSigner::default();
+        let link = signer.link("http://localhost:3000/", "abc", ApprovalLinkDecision::Deny);
+        assert!(link.starts_with(
+            "http://localhost:3000/api/approvals/abc/respond/link?decision=deny&token="
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_posts_payload_to_local_listener() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 4096];
+            // Read the headers, then the body up to its content-length
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&received).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    if received.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8(received).unwrap()
+        });
+
+        let signer = ApprovalLinkSigner::default();
+        let now = Utc::now();
+        let payload = ApprovalWebhookPayload {
+            event: "approval.requested".to_string(),
+            approval: ApprovalRequest {
+                id: "approval-1".to_string(),
+                tool_name: "Bash".to_string(),
+                tool_input: serde_json::json!({"command": "cargo test"}),
+                tool_call_id: "call-1".to_string(),
+                execution_process_id: Uuid::new_v4(),
+                created_at: now,
+                timeout_at: now,
+            },
+            task_id: None,
+            task_title: None,
+            approve_url: signer.link("http://vk", "approval-1", ApprovalLinkDecision::Approve),
+            deny_url: signer.link("http://vk", "approval-1", ApprovalLinkDecision::Deny),
+        };
+
+        send_approval_webhook(&reqwest::Client::new(), &url, &payload)
+            .await
+            .expect("webhook delivered");
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert!(request.contains("\"event\":\"approval.requested\""));
+        assert!(request.contains("cargo test"));
+        assert!(request.contains("/api/approvals/approval-1/respond/link?decision=approve"));
+    }
+}
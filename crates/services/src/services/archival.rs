@@ -0,0 +1,258 @@
+//! Archive tasks and projects instead of deleting them. Archiving hides them from the board
+//! and frees their worktrees, but keeps attempts, logs and merges so they can be restored.
+//! Archived items are only deleted for good by [`purge_archived`].
+
+use std::collections::HashSet;
+
+use chrono::{Duration, Utc};
+use db::models::{
+    project::Project,
+    repo::Repo,
+    task::Task,
+    workspace::{Workspace, WorkspaceError},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    share::SharePublisher,
+};
+
+/// How long archived items are kept when a purge doesn't specify a retention window
+pub const DEFAULT_ARCHIVE_RETENTION_DAYS: u32 = 30;
+
+#[derive(Debug, Error)]
+pub enum ArchivalError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error("{0} has running execution processes. Stop them before archiving.")]
+    RunningProcesses(&'static str),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, TS)]
+pub struct PurgeArchivedResult {
+    pub purged_task_ids: Vec<Uuid>,
+    pub purged_project_ids: Vec<Uuid>,
+}
+
+/// Archive a task and remove the worktrees of its attempts
+pub async fn archive_task<C: ContainerService + Sync>(
+    container: &C,
+    task: &Task,
+) -> Result<(), ArchivalError> {
+    if container.has_running_processes(task.id).await? {
+        return Err(ArchivalError::RunningProcesses("Task"));
+    }
+    let pool = &container.db().pool;
+    Task::archive(pool, task.id).await?;
+
+    let workspaces = Workspace::fetch_all(pool, Some(task.id)).await?;
+    cleanup_worktrees(container, &workspaces).await;
+    Ok(())
+}
+
+/// Archive a project and remove the worktrees of all of its tasks' attempts
+pub async fn archive_project<C: ContainerService + Sync>(
+    container: &C,
+    project: &Project,
+) -> Result<(), ArchivalError> {
+    let pool = &container.db().pool;
+    let workspaces = Workspace::find_by_project_id(pool, project.id).await?;
+    let task_ids: HashSet<Uuid> = workspaces.iter().map(|w| w.task_id).collect();
+    for task_id in task_ids {
+        if container.has_running_processes(task_id).await? {
+            return Err(ArchivalError::RunningProcesses("Project"));
+        }
+    }
+
+    Project::archive(pool, project.id).await?;
+    cleanup_worktrees(container, &workspaces).await;
+    Ok(())
+}
+
+async fn cleanup_worktrees<C: ContainerService + Sync>(container: &C, workspaces: &[Workspace]) {
+    for workspace in workspaces.iter().filter(|w| w.container_ref.is_some()) {
+        if let Err(e) = container.delete(workspace).await {
+            tracing::warn!(
+                "Failed to clean up worktree of archived workspace {}: {}",
+                workspace.id,
+                e
+            );
+        }
+    }
+}
+
+/// Permanently delete tasks and projects that were archived more than `retention_days` ago.
+/// Shared tasks are also deleted remotely when `publisher` is available.
+pub async fn purge_archived(
+    pool: &SqlitePool,
+    publisher: Option<&SharePublisher>,
+    retention_days: u32,
+) -> Result<PurgeArchivedResult, ArchivalError> {
+    let cutoff = Utc::now() - Duration::days(i64::from(retention_days));
+    let mut result = PurgeArchivedResult::default();
+
+    for task in Task::find_archived_before(pool, cutoff).await? {
+        if let Some(shared_task_id) = task.shared_task_id
+            && let Some(publisher) = publisher
+            && let Err(e) = publisher.delete_shared_task(shared_task_id).await
+        {
+            tracing::warn!(
+                "Failed to delete shared task {} of purged task {}: {}",
+                shared_task_id,
+                task.id,
+                e
+            );
+        }
+
+        // Detach subtasks created from the task's attempts before the workspaces cascade away
+        let workspaces = Workspace::fetch_all(pool, Some(task.id)).await?;
+        let mut tx = pool.begin().await?;
+        for workspace in &workspaces {
+            Task::nullify_children_by_workspace_id(&mut *tx, workspace.id).await?;
+        }
+        Task::delete(&mut *tx, task.id).await?;
+        tx.commit().await?;
+        result.purged_task_ids.push(task.id);
+    }
+
+    for project in Project::find_archived_before(pool, cutoff).await? {
+        Project::delete(pool, project.id).await?;
+        result.purged_project_ids.push(project.id);
+    }
+
+    if let Err(e) = Repo::delete_orphaned(pool).await {
+        tracing::error!("Failed to delete orphaned repos: {}", e);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        models::{project::CreateProject, task::CreateTask},
+        test_utils::test_pool,
+    };
+
+    use super::*;
+
+    async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+        Task::create(
+            pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn visible_task_ids(pool: &SqlitePool, project_id: Uuid) -> Vec<Uuid> {
+        Task::find_by_project_id_with_attempt_status(pool, project_id)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|task| task.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_restore_task() {
+        let pool = test_pool().await;
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let task = create_task(&pool, project.id, "task").await;
+
+        assert_eq!(Task::archive(&pool, task.id).await.unwrap(), 1);
+        // Archiving twice is a no-op
+        assert_eq!(Task::archive(&pool, task.id).await.unwrap(), 0);
+        assert!(visible_task_ids(&pool, project.id).await.is_empty());
+        let archived = Task::find_archived_by_project_id(&pool, project.id)
+            .await
+            .unwrap();
+        assert_eq!(archived.len(), 1);
+        assert!(archived[0].archived_at.is_some());
+
+        assert_eq!(Task::restore(&pool, task.id).await.unwrap(), 1);
+        assert_eq!(Task::restore(&pool, task.id).await.unwrap(), 0);
+        assert_eq!(visible_task_ids(&pool, project.id).await, vec![task.id]);
+        let restored = Task::find_by_id(&pool, task.id).await.unwrap().unwrap();
+        assert!(restored.archived_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_purge_archived_respects_retention() {
+        let pool = test_pool().await;
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let archived_project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "archived".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        let kept = create_task(&pool, project.id, "kept").await;
+        let archived = create_task(&pool, project.id, "archived").await;
+        Task::archive(&pool, archived.id).await.unwrap();
+        Project::archive(&pool, archived_project.id).await.unwrap();
+
+        // Archived moments ago, so still inside the default retention window
+        let result = purge_archived(&pool, None, DEFAULT_ARCHIVE_RETENTION_DAYS)
+            .await
+            .unwrap();
+        assert!(result.purged_task_ids.is_empty());
+        assert!(result.purged_project_ids.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let result = purge_archived(&pool, None, 0).await.unwrap();
+        assert_eq!(result.purged_task_ids, vec![archived.id]);
+        assert_eq!(result.purged_project_ids, vec![archived_project.id]);
+        assert!(
+            Task::find_by_id(&pool, archived.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(Task::find_by_id(&pool, kept.id).await.unwrap().is_some());
+        assert!(
+            Project::find_by_id(&pool, archived_project.id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            Project::find_by_id(&pool, project.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+}
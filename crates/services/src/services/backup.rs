@@ -0,0 +1,769 @@
+//! Backups of everything Vibe Kanban keeps locally: the database, `config.json`,
+//! `profiles.json`, the coding agents' MCP configs and the image cache, bundled into one
+//! timestamped `.tar.gz`. The database is copied with SQLite's backup API so backups can be
+//! taken while the server runs. Restoring swaps the files in place and must happen while the
+//! server is stopped.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use db::{DBService, backup_database, database_path};
+use executors::{
+    executors::StandardCodingAgentExecutor,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::{sync::RwLock, task::JoinHandle};
+use ts_rs::TS;
+use utils::assets::{asset_dir, config_path, profiles_path};
+
+use crate::services::config::{BackupConfig, Config};
+
+/// Bumped whenever the archive layout changes incompatibly
+pub const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const DATABASE_FILE: &str = "db.sqlite";
+const IMAGES_DIR: &str = "images";
+const FILE_PREFIX: &str = "vibe-kanban-backup-";
+const FILE_EXTENSION: &str = ".tar.gz";
+/// How often the scheduler checks whether a backup is due
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Error)]
+pub enum BackupError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+    #[error("Invalid backup: {0}")]
+    InvalidBackup(String),
+    #[error(
+        "Backup was taken with database migration {backup}, but this version only supports up to {supported}; upgrade Vibe Kanban before restoring it"
+    )]
+    NewerSchema { backup: i64, supported: i64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum BackupFileKind {
+    Database,
+    Config,
+    Profiles,
+    McpConfig,
+    Images,
+}
+
+/// A file or directory in the backup and where it came from
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BackupEntry {
+    pub kind: BackupFileKind,
+    /// Path inside the archive
+    pub archive_path: String,
+    /// Path on the machine the backup was taken on
+    pub original_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub app_version: String,
+    /// Newest database migration applied when the backup was taken
+    pub migration_version: i64,
+    pub entries: Vec<BackupEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct BackupInfo {
+    pub file_name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RestoreOptions {
+    /// Also overwrite the coding agents' MCP config files. These usually hold more than MCP
+    /// servers, so they are left alone unless asked for.
+    pub include_mcp_configs: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreSummary {
+    pub manifest: BackupManifest,
+    pub restored: Vec<BackupEntry>,
+    /// Where the files that were replaced have been moved to
+    pub previous_files_dir: PathBuf,
+}
+
+#[derive(Clone)]
+pub struct BackupService {
+    db: DBService,
+    config: Arc<RwLock<Config>>,
+}
+
+impl BackupService {
+    pub fn new(db: DBService, config: Arc<RwLock<Config>>) -> Self {
+        Self { db, config }
+    }
+
+    async fn backup_dir(&self) -> PathBuf {
+        backup_dir(&self.config.read().await.backup)
+    }
+
+    /// Write a new backup to the backup directory
+    pub async fn create_backup(&self) -> Result<BackupInfo, BackupError> {
+        let dir = self.backup_dir().await;
+        fs::create_dir_all(&dir)?;
+        // Staged next to the backups so the finished archive can be renamed into place
+        let staging = tempfile::tempdir_in(&dir)?;
+
+        let staged_db = staging.path().join(DATABASE_FILE);
+        let source = database_path();
+        let dest = staged_db.clone();
+        tokio::task::spawn_blocking(move || backup_database(&source, &dest)).await??;
+        let migration_version = DBService::applied_migration_version(&staged_db)
+            .await?
+            .unwrap_or_default();
+
+        let created_at = Utc::now();
+        let file_name = format!(
+            "{FILE_PREFIX}{}{FILE_EXTENSION}",
+            created_at.format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(&file_name);
+        let partial = staging.path().join(&file_name);
+
+        let mut sources = vec![(
+            BackupEntry {
+                kind: BackupFileKind::Database,
+                archive_path: DATABASE_FILE.to_string(),
+                original_path: database_path().to_string_lossy().to_string(),
+            },
+            staged_db,
+        )];
+        sources.extend(settings_sources());
+        let manifest = BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at,
+            app_version: utils::version::APP_VERSION.to_string(),
+            migration_version,
+            entries: sources.iter().map(|(entry, _)| entry.clone()).collect(),
+        };
+
+        let archive = partial.clone();
+        tokio::task::spawn_blocking(move || write_archive(&archive, &manifest, &sources)).await??;
+        fs::rename(&partial, &path)?;
+
+        let size_bytes = fs::metadata(&path)?.len();
+        tracing::info!("Wrote backup {} ({} bytes)", path.display(), size_bytes);
+        Ok(BackupInfo {
+            file_name,
+            path: path.to_string_lossy().to_string(),
+            size_bytes,
+            created_at,
+        })
+    }
+
+    /// Backups in the backup directory, newest first
+    pub async fn list_backups(&self) -> Result<Vec<BackupInfo>, BackupError> {
+        list_backups(&self.backup_dir().await)
+    }
+
+    /// Delete all but the newest `keep` backups; returns how many were deleted
+    pub async fn rotate(&self, keep: usize) -> Result<usize, BackupError> {
+        let backups = self.list_backups().await?;
+        let mut deleted = 0;
+        for backup in backups.iter().skip(keep) {
+            match fs::remove_file(&backup.path) {
+                Ok(()) => deleted += 1,
+                Err(e) => tracing::warn!("Failed to delete old backup {}: {}", backup.path, e),
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Take a backup whenever the newest one is older than the configured interval, and
+    /// rotate old ones. Does nothing while scheduled backups are disabled.
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let service = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let config = service.config.read().await.backup.clone();
+                if config.enabled {
+                    match service.backup_if_due(&config).await {
+                        Ok(Some(backup)) => {
+                            tracing::info!("Scheduled backup written to {}", backup.path)
+                        }
+                        Ok(None) => {}
+                        Err(e) => tracing::error!("Scheduled backup failed: {}", e),
+                    }
+                }
+                tokio::time::sleep(SCHEDULE_CHECK_INTERVAL).await;
+            }
+        })
+    }
+
+    async fn backup_if_due(
+        &self,
+        config: &BackupConfig,
+    ) -> Result<Option<BackupInfo>, BackupError> {
+        let interval = chrono::Duration::hours(config.interval_hours.max(1) as i64);
+        let latest = self.list_backups().await?.into_iter().next();
+        if latest.is_some_and(|backup| Utc::now() - backup.created_at < interval) {
+            return Ok(None);
+        }
+
+        let backup = self.create_backup().await?;
+        let deleted = self.rotate(config.keep.max(1) as usize).await?;
+        if deleted > 0 {
+            tracing::info!("Deleted {} old backups", deleted);
+        }
+        Ok(Some(backup))
+    }
+}
+
+/// Where backups are written for the given settings
+pub fn backup_dir(config: &BackupConfig) -> PathBuf {
+    config
+        .directory
+        .as_deref()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| asset_dir().join("backups"))
+}
+
+/// Backups in `dir`, newest first. Files that don't look like backups are ignored.
+pub fn list_backups(dir: &Path) -> Result<Vec<BackupInfo>, BackupError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(created_at) = parse_backup_time(&file_name) else {
+            continue;
+        };
+        backups.push(BackupInfo {
+            path: entry.path().to_string_lossy().to_string(),
+            size_bytes: entry.metadata()?.len(),
+            file_name,
+            created_at,
+        });
+    }
+    backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(backups)
+}
+
+fn parse_backup_time(file_name: &str) -> Option<DateTime<Utc>> {
+    let timestamp = file_name
+        .strip_prefix(FILE_PREFIX)?
+        .strip_suffix(FILE_EXTENSION)?;
+    chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%d-%H%M%S")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+/// Settings files, MCP configs and the image cache that currently exist
+fn settings_sources() -> Vec<(BackupEntry, PathBuf)> {
+    let mut sources = Vec::new();
+    let mut add = |kind, archive_path: String, path: PathBuf| {
+        if path.exists() {
+            sources.push((
+                BackupEntry {
+                    kind,
+                    archive_path,
+                    original_path: path.to_string_lossy().to_string(),
+                },
+                path,
+            ));
+        }
+    };
+
+    add(
+        BackupFileKind::Config,
+        "config.json".to_string(),
+        config_path(),
+    );
+    add(
+        BackupFileKind::Profiles,
+        "profiles.json".to_string(),
+        profiles_path(),
+    );
+
+    for (archive_path, path) in mcp_config_paths() {
+        add(BackupFileKind::McpConfig, archive_path, path);
+    }
+
+    add(
+        BackupFileKind::Images,
+        IMAGES_DIR.to_string(),
+        utils::cache_dir().join(IMAGES_DIR),
+    );
+    sources
+}
+
+/// Archive paths and local locations of the coding agents' MCP configs, from this machine's
+/// executor profiles
+fn mcp_config_paths() -> Vec<(String, PathBuf)> {
+    let profiles = ExecutorConfigs::get_cached();
+    let mut seen = HashSet::new();
+    let mut agents: Vec<_> = profiles.executors.keys().copied().collect();
+    agents.sort_by_key(|agent| agent.to_string());
+
+    let mut paths = Vec::new();
+    for agent in agents {
+        let Some(path) = profiles
+            .get_coding_agent(&ExecutorProfileId::new(agent))
+            .and_then(|coding_agent| coding_agent.default_mcp_config_path())
+        else {
+            continue;
+        };
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "mcp.json".to_string());
+        paths.push((format!("mcp/{agent}/{file_name}"), path));
+    }
+    paths
+}
+
+fn write_archive(
+    path: &Path,
+    manifest: &BackupManifest,
+    sources: &[(BackupEntry, PathBuf)],
+) -> Result<(), BackupError> {
+    let file = fs::File::create(path)?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(manifest.created_at.timestamp().max(0) as u64);
+    header.set_cksum();
+    builder.append_data(&mut header, MANIFEST_FILE, manifest_json.as_slice())?;
+
+    for (entry, source) in sources {
+        if source.is_dir() {
+            builder.append_dir_all(&entry.archive_path, source)?;
+        } else {
+            builder.append_path_with_name(source, &entry.archive_path)?;
+        }
+    }
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+/// Check that a manifest describes a backup this version can restore
+pub fn validate_manifest(manifest: &BackupManifest) -> Result<(), BackupError> {
+    if manifest.format_version == 0 || manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(BackupError::InvalidBackup(format!(
+            "unsupported backup format version {}",
+            manifest.format_version
+        )));
+    }
+    let supported = DBService::latest_migration_version();
+    if manifest.migration_version > supported {
+        return Err(BackupError::NewerSchema {
+            backup: manifest.migration_version,
+            supported,
+        });
+    }
+    if !manifest
+        .entries
+        .iter()
+        .any(|entry| entry.kind == BackupFileKind::Database)
+    {
+        return Err(BackupError::InvalidBackup(
+            "the backup has no database".to_string(),
+        ));
+    }
+    let mut archive_paths = HashSet::new();
+    for entry in &manifest.entries {
+        if !is_plain_relative_path(&entry.archive_path) || entry.archive_path == MANIFEST_FILE {
+            return Err(BackupError::InvalidBackup(format!(
+                "{} is not a valid path inside the archive",
+                entry.archive_path
+            )));
+        }
+        if !archive_paths.insert(entry.archive_path.as_str()) {
+            return Err(BackupError::InvalidBackup(format!(
+                "{} is listed in the manifest more than once",
+                entry.archive_path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// A non-empty relative path made only of normal components, so joining it onto a
+/// directory can't leave that directory
+fn is_plain_relative_path(path: &str) -> bool {
+    let path = Path::new(path);
+    path.components().next().is_some()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// A file or directory from the backup and where it goes
+struct RestoreStep {
+    entry: BackupEntry,
+    staged: PathBuf,
+    target: PathBuf,
+    /// Where the file currently at `target` is moved to
+    previous: PathBuf,
+}
+
+/// Replace the local database and settings with the contents of `archive`. The server must
+/// not be running. Replaced files are moved to a `pre-restore-*` directory in the data
+/// directory rather than deleted. Databases from older versions are migrated on next start.
+pub async fn restore_backup(
+    archive: &Path,
+    options: &RestoreOptions,
+) -> Result<RestoreSummary, BackupError> {
+    let data_dir = asset_dir();
+    let staging = tempfile::tempdir_in(&data_dir)?;
+
+    let archive_path = archive.to_path_buf();
+    let unpack_dir = staging.path().to_path_buf();
+    tokio::task::spawn_blocking(move || -> Result<(), BackupError> {
+        let file = fs::File::open(&archive_path)?;
+        // Refuses entries that would land outside `unpack_dir`
+        tar::Archive::new(GzDecoder::new(file)).unpack(&unpack_dir)?;
+        Ok(())
+    })
+    .await??;
+
+    let manifest_path = staging.path().join(MANIFEST_FILE);
+    if !manifest_path.is_file() {
+        return Err(BackupError::InvalidBackup(
+            "the archive has no manifest".to_string(),
+        ));
+    }
+    let manifest: BackupManifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+    validate_manifest(&manifest)?;
+
+    let staged_db = staging.path().join(DATABASE_FILE);
+    let applied = DBService::applied_migration_version(&staged_db)
+        .await?
+        .unwrap_or_default();
+    if applied != manifest.migration_version {
+        return Err(BackupError::InvalidBackup(format!(
+            "the database is at migration {applied}, but the manifest says {}",
+            manifest.migration_version
+        )));
+    }
+    if !DBService::check_integrity(&staged_db).await? {
+        return Err(BackupError::InvalidBackup(
+            "the database failed SQLite's integrity check".to_string(),
+        ));
+    }
+
+    let previous_files_dir = data_dir.join(format!(
+        "pre-restore-{}",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::create_dir_all(&previous_files_dir)?;
+
+    // MCP configs go where this machine's agents read them, wherever they were taken from
+    let mcp_targets: HashMap<String, PathBuf> = mcp_config_paths().into_iter().collect();
+    let mut targets = HashSet::new();
+    let mut steps = Vec::new();
+    for entry in &manifest.entries {
+        let target = match entry.kind {
+            BackupFileKind::Database => database_path(),
+            BackupFileKind::Config => config_path(),
+            BackupFileKind::Profiles => profiles_path(),
+            BackupFileKind::Images => utils::cache_dir().join(IMAGES_DIR),
+            BackupFileKind::McpConfig if options.include_mcp_configs => {
+                match mcp_targets.get(&entry.archive_path) {
+                    Some(target) => target.clone(),
+                    None => {
+                        tracing::warn!(
+                            "Skipping {}: no local coding agent uses that MCP config",
+                            entry.archive_path
+                        );
+                        continue;
+                    }
+                }
+            }
+            BackupFileKind::McpConfig => continue,
+        };
+        if !targets.insert(target.clone()) {
+            return Err(BackupError::InvalidBackup(format!(
+                "more than one entry restores to {}",
+                target.display()
+            )));
+        }
+        let staged = staging.path().join(&entry.archive_path);
+        if !staged.exists() {
+            return Err(BackupError::InvalidBackup(format!(
+                "{} is listed in the manifest but missing from the archive",
+                entry.archive_path
+            )));
+        }
+        steps.push(RestoreStep {
+            entry: entry.clone(),
+            staged,
+            target,
+            previous: previous_files_dir.join(&entry.archive_path),
+        });
+    }
+
+    swap_in(&steps, &previous_files_dir)?;
+
+    Ok(RestoreSummary {
+        manifest,
+        restored: steps.into_iter().map(|step| step.entry).collect(),
+        previous_files_dir,
+    })
+}
+
+/// Move the current files aside and the staged ones into place. If any move fails, the
+/// moves already made are undone so no half-restored mix of files is left behind.
+fn swap_in(steps: &[RestoreStep], previous_files_dir: &Path) -> Result<(), BackupError> {
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut apply = |from: &Path, to: &Path| -> std::io::Result<()> {
+        move_path(from, to)?;
+        moves.push((from.to_path_buf(), to.to_path_buf()));
+        Ok(())
+    };
+
+    let result = steps.iter().try_for_each(|step| {
+        if step.target.exists() {
+            apply(&step.target, &step.previous)?;
+        }
+        if step.entry.kind == BackupFileKind::Database {
+            // Journal files of the replaced database must not be applied to the restored one
+            for suffix in ["-wal", "-shm"] {
+                let journal = PathBuf::from(format!("{}{suffix}", step.target.display()));
+                if journal.exists() {
+                    apply(
+                        &journal,
+                        &previous_files_dir.join(format!("{DATABASE_FILE}{suffix}")),
+                    )?;
+                }
+            }
+        }
+        apply(&step.staged, &step.target)
+    });
+
+    if let Err(e) = result {
+        for (from, to) in moves.iter().rev() {
+            if let Err(rollback_error) = move_path(to, from) {
+                tracing::error!(
+                    "Failed to move {} back to {} after a failed restore: {}",
+                    to.display(),
+                    from.display(),
+                    rollback_error
+                );
+            }
+        }
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+/// Rename `from` to `to`, copying instead when they are on different filesystems
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_backups_newest_first_and_ignores_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "vibe-kanban-backup-20250101-120000.tar.gz",
+            "vibe-kanban-backup-20250301-080000.tar.gz",
+            "vibe-kanban-backup-20250201-000000.tar.gz",
+            "vibe-kanban-backup-latest.tar.gz",
+            "notes.txt",
+        ] {
+            fs::write(dir.path().join(name), b"x").unwrap();
+        }
+
+        let names: Vec<String> = list_backups(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|backup| backup.file_name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "vibe-kanban-backup-20250301-080000.tar.gz",
+                "vibe-kanban-backup-20250201-000000.tar.gz",
+                "vibe-kanban-backup-20250101-120000.tar.gz",
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_backup_dir_has_no_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            list_backups(&dir.path().join("missing"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn manifest(migration_version: i64) -> BackupManifest {
+        BackupManifest {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at: Utc::now(),
+            app_version: "0.0.0".to_string(),
+            migration_version,
+            entries: vec![BackupEntry {
+                kind: BackupFileKind::Database,
+                archive_path: DATABASE_FILE.to_string(),
+                original_path: "/tmp/db.sqlite".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn rejects_backups_from_newer_schemas() {
+        let latest = DBService::latest_migration_version();
+        assert!(validate_manifest(&manifest(latest)).is_ok());
+        assert!(matches!(
+            validate_manifest(&manifest(latest + 1)),
+            Err(BackupError::NewerSchema { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_archive_paths_outside_the_archive() {
+        for archive_path in [
+            "/etc/passwd",
+            "../db.sqlite",
+            "mcp/../../db.sqlite",
+            "./db.sqlite",
+            "",
+            MANIFEST_FILE,
+        ] {
+            let mut crafted = manifest(1);
+            crafted.entries.push(BackupEntry {
+                kind: BackupFileKind::Config,
+                archive_path: archive_path.to_string(),
+                original_path: "/tmp/config.json".to_string(),
+            });
+            assert!(
+                matches!(
+                    validate_manifest(&crafted),
+                    Err(BackupError::InvalidBackup(_))
+                ),
+                "{archive_path:?} should be rejected"
+            );
+        }
+
+        let mut valid = manifest(1);
+        valid.entries.push(BackupEntry {
+            kind: BackupFileKind::McpConfig,
+            archive_path: "mcp/CLAUDE_CODE/.claude.json".to_string(),
+            original_path: "/home/me/.claude.json".to_string(),
+        });
+        assert!(validate_manifest(&valid).is_ok());
+    }
+
+    #[test]
+    fn failed_restore_moves_replaced_files_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let staging = dir.path().join("staging");
+        let live = dir.path().join("live");
+        let previous = dir.path().join("previous");
+        fs::create_dir_all(&staging).unwrap();
+        fs::create_dir_all(&live).unwrap();
+        fs::write(staging.join("config.json"), "restored config").unwrap();
+        fs::write(live.join("config.json"), "live config").unwrap();
+        fs::write(live.join("profiles.json"), "live profiles").unwrap();
+
+        let step = |kind, name: &str| RestoreStep {
+            entry: BackupEntry {
+                kind,
+                archive_path: name.to_string(),
+                original_path: String::new(),
+            },
+            staged: staging.join(name),
+            target: live.join(name),
+            previous: previous.join(name),
+        };
+        // profiles.json is missing from staging, so the second step fails
+        let steps = [
+            step(BackupFileKind::Config, "config.json"),
+            step(BackupFileKind::Profiles, "profiles.json"),
+        ];
+
+        assert!(swap_in(&steps, &previous).is_err());
+        assert_eq!(
+            fs::read_to_string(live.join("config.json")).unwrap(),
+            "live config"
+        );
+        assert_eq!(
+            fs::read_to_string(live.join("profiles.json")).unwrap(),
+            "live profiles"
+        );
+        assert_eq!(
+            fs::read_to_string(staging.join("config.json")).unwrap(),
+            "restored config"
+        );
+    }
+
+    #[test]
+    fn rejects_backups_without_a_database() {
+        let mut without_db = manifest(1);
+        without_db.entries.clear();
+        assert!(matches!(
+            validate_manifest(&without_db),
+            Err(BackupError::InvalidBackup(_))
+        ));
+    }
+}
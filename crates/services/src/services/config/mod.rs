@@ -26,6 +26,13 @@ pub type EditorType = versions::v8::EditorType;
 pub type GitHubConfig = versions::v8::GitHubConfig;
 pub type UiLanguage = versions::v8::UiLanguage;
 pub type ShowcaseState = versions::v8::ShowcaseState;
+pub type LogRetentionConfig = versions::v8::LogRetentionConfig;
+pub type BackupConfig = versions::v8::BackupConfig;
+pub type ApprovalWebhookConfig = versions::v8::ApprovalWebhookConfig;
+pub type NotificationEvent = versions::v8::NotificationEvent;
+pub type SmtpSecurity = versions::v8::SmtpSecurity;
+pub type NotificationChannelKind = versions::v8::NotificationChannelKind;
+pub type NotificationChannelConfig = versions::v8::NotificationChannelConfig;
 
 /// Will always return config, trying old schemas or eventually returning default
 pub async fn load_config_from_file(config_path: &PathBuf) -> Config {
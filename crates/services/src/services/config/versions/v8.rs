@@ -1,6 +1,7 @@
 use anyhow::Error;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
+use strum_macros::Display;
 use ts_rs::TS;
 pub use v7::{
     EditorConfig, EditorType, GitHubConfig, NotificationConfig, ShowcaseState, SoundFile,
@@ -17,6 +18,166 @@ fn default_pr_auto_description_enabled() -> bool {
     true
 }
 
+fn default_log_retention_interval_hours() -> u32 {
+    24
+}
+
+fn default_backup_interval_hours() -> u32 {
+    24
+}
+
+fn default_backup_keep() -> u32 {
+    7
+}
+
+fn default_notification_channel_enabled() -> bool {
+    true
+}
+
+fn default_notification_events() -> Vec<NotificationEvent> {
+    vec![
+        NotificationEvent::TaskComplete,
+        NotificationEvent::TaskFailed,
+        NotificationEvent::ApprovalPending,
+        NotificationEvent::PrMerged,
+    ]
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct LogRetentionConfig {
+    /// Days after an execution finishes before its raw stdout/stderr is replaced by the
+    /// normalized conversation. Only applies to tasks that are Done. `null` keeps raw logs.
+    #[serde(default)]
+    pub raw_log_retention_days: Option<u32>,
+    #[serde(default = "default_log_retention_interval_hours")]
+    pub interval_hours: u32,
+}
+
+impl Default for LogRetentionConfig {
+    fn default() -> Self {
+        Self {
+            raw_log_retention_days: None,
+            interval_hours: default_log_retention_interval_hours(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct BackupConfig {
+    /// Back up the database and settings every `interval_hours`
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_backup_interval_hours")]
+    pub interval_hours: u32,
+    /// Number of backups to keep; older ones are deleted after each scheduled backup
+    #[serde(default = "default_backup_keep")]
+    pub keep: u32,
+    /// Directory backups are written to. Defaults to `backups` in the data directory.
+    #[serde(default)]
+    pub directory: Option<String>,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_backup_interval_hours(),
+            keep: default_backup_keep(),
+            directory: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, TS)]
+pub struct ApprovalWebhookConfig {
+    /// URL that receives a POST with the approval request and signed approve/deny links
+    /// whenever a tool call waits for approval. `null` disables the webhook.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Base URL the links point at, e.g. when the server is reachable through a tunnel.
+    /// Defaults to the local server address.
+    #[serde(default)]
+    pub public_base_url: Option<String>,
+}
+
+/// Event that can be routed to notification channels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TS, Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum NotificationEvent {
+    TaskComplete,
+    TaskFailed,
+    ApprovalPending,
+    PrMerged,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    #[default]
+    StartTls,
+    /// Implicit TLS (usually port 465)
+    Tls,
+    /// Unencrypted, for local relays only
+    None,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type", rename_all = "snake_case")]
+pub enum NotificationChannelKind {
+    /// POSTs `{ event, title, message, sent_at }` as JSON with the same headers, signature
+    /// and retries as webhook subscriptions
+    Webhook {
+        url: String,
+        /// Key for the `X-Vibe-Kanban-Signature` HMAC
+        #[serde(default)]
+        secret: Option<String>,
+    },
+    /// Slack incoming webhook
+    Slack { webhook_url: String },
+    /// Discord channel webhook
+    Discord { webhook_url: String },
+    /// ntfy server and topic; `token` is sent as a bearer token for protected topics
+    Ntfy {
+        server_url: String,
+        topic: String,
+        #[serde(default)]
+        token: Option<String>,
+    },
+    Email {
+        smtp_host: String,
+        smtp_port: u16,
+        #[serde(default)]
+        security: SmtpSecurity,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+        from: String,
+        to: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+pub struct NotificationChannelConfig {
+    pub name: String,
+    #[serde(default = "default_notification_channel_enabled")]
+    pub enabled: bool,
+    /// Events delivered to this channel
+    #[serde(default = "default_notification_events")]
+    pub events: Vec<NotificationEvent>,
+    #[serde(flatten)]
+    pub channel: NotificationChannelKind,
+}
+
+impl NotificationChannelConfig {
+    pub fn routes(&self, event: NotificationEvent) -> bool {
+        self.enabled && self.events.contains(&event)
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, TS)]
 pub struct Config {
     pub config_version: String,
@@ -41,6 +202,16 @@ pub struct Config {
     pub pr_auto_description_enabled: bool,
     #[serde(default)]
     pub pr_auto_description_prompt: Option<String>,
+    #[serde(default)]
+    pub log_retention: LogRetentionConfig,
+    #[serde(default)]
+    pub approval_webhook: ApprovalWebhookConfig,
+    /// Remote channels (chat, push, email) that receive notifications in addition to the
+    /// local sound and desktop push
+    #[serde(default)]
+    pub notification_channels: Vec<NotificationChannelConfig>,
+    #[serde(default)]
+    pub backup: BackupConfig,
 }
 
 impl Config {
@@ -66,6 +237,10 @@ impl Config {
             showcases: old_config.showcases,
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            log_retention: LogRetentionConfig::default(),
+            approval_webhook: ApprovalWebhookConfig::default(),
+            notification_channels: Vec::new(),
+            backup: BackupConfig::default(),
         }
     }
 
@@ -116,6 +291,10 @@ impl Default for Config {
             showcases: ShowcaseState::default(),
             pr_auto_description_enabled: true,
             pr_auto_description_prompt: None,
+            log_retention: LogRetentionConfig::default(),
+            approval_webhook: ApprovalWebhookConfig::default(),
+            notification_channels: Vec::new(),
+            backup: BackupConfig::default(),
         }
     }
 }
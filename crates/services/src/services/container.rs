@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -34,10 +34,13 @@ use executors::{
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        utils::{ConversationPatch, patch::extract_normalized_entry_from_patch},
+    },
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
-use futures::{StreamExt, future};
+use futures::{StreamExt, TryStreamExt, future};
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 use tokio::{sync::RwLock, task::JoinHandle};
@@ -49,14 +52,19 @@ use utils::{
 use uuid::Uuid;
 
 use crate::services::{
+    config::NotificationEvent,
     git::{GitService, GitServiceError},
     notification::NotificationService,
     share::SharePublisher,
+    webhooks::WebhookService,
     workspace_manager::WorkspaceError as WorkspaceManagerError,
     worktree_manager::WorktreeError,
 };
 pub type ContainerRef = String;
 
+/// Upper bound on re-normalizing stored logs before they are pruned
+const LOG_NORMALIZATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -93,6 +101,8 @@ pub trait ContainerService {
 
     fn notification_service(&self) -> &NotificationService;
 
+    fn webhooks(&self) -> &WebhookService;
+
     fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf;
 
     async fn create(&self, workspace: &Workspace) -> Result<ContainerRef, ContainerError>;
@@ -184,20 +194,28 @@ pub trait ContainerService {
             }
         }
 
+        self.webhooks().execution_finished(ctx).await;
+
         // Skip notification if process was intentionally killed by user
         if matches!(ctx.execution_process.status, ExecutionProcessStatus::Killed) {
             return;
         }
 
         let title = format!("Task Complete: {}", ctx.task.title);
-        let message = match ctx.execution_process.status {
-            ExecutionProcessStatus::Completed => format!(
-                "✅ '{}' completed successfully\nBranch: {:?}\nExecutor: {:?}",
-                ctx.task.title, ctx.workspace.branch, ctx.session.executor
+        let (event, message) = match ctx.execution_process.status {
+            ExecutionProcessStatus::Completed => (
+                NotificationEvent::TaskComplete,
+                format!(
+                    "✅ '{}' completed successfully\nBranch: {:?}\nExecutor: {:?}",
+                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                ),
             ),
-            ExecutionProcessStatus::Failed => format!(
-                "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
-                ctx.task.title, ctx.workspace.branch, ctx.session.executor
+            ExecutionProcessStatus::Failed => (
+                NotificationEvent::TaskFailed,
+                format!(
+                    "❌ '{}' execution failed\nBranch: {:?}\nExecutor: {:?}",
+                    ctx.task.title, ctx.workspace.branch, ctx.session.executor
+                ),
             ),
             _ => {
                 tracing::warn!(
@@ -207,7 +225,9 @@ pub trait ContainerService {
                 return;
             }
         };
-        self.notification_service().notify(&title, &message).await;
+        self.notification_service()
+            .notify(event, &title, &message)
+            .await;
     }
 
     /// Cleanup executions marked as running in the db, call at startup
@@ -688,120 +708,145 @@ pub trait ContainerService {
             )
         } else {
             // Fallback: load from DB and normalize
-            let log_records =
-                match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
-                    Ok(records) if !records.is_empty() => records,
-                    Ok(_) => return None, // No logs exist
-                    Err(e) => {
-                        tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
-                        return None;
-                    }
-                };
+            self.normalize_stored_logs(id, true).await
+        }
+    }
 
-            let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
-                Ok(msgs) => msgs,
+    /// Rebuild the normalized log stream of a finished execution process from its stored logs.
+    /// When `recreate_worktree` is set the workspace is recreated first so paths resolve
+    /// against a real checkout.
+    async fn normalize_stored_logs(
+        &self,
+        id: &Uuid,
+        recreate_worktree: bool,
+    ) -> Option<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>> {
+        let log_records =
+            match ExecutionProcessLogs::find_by_execution_id(&self.db().pool, *id).await {
+                Ok(records) if !records.is_empty() => records,
+                Ok(_) => return None, // No logs exist
                 Err(e) => {
-                    tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                    tracing::error!("Failed to fetch logs for execution {}: {}", id, e);
                     return None;
                 }
             };
 
-            // Create temporary store and populate
-            // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
-            let temp_store = Arc::new(MsgStore::new());
-            for msg in raw_messages {
-                if matches!(
-                    msg,
-                    LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
-                ) {
-                    temp_store.push(msg);
-                }
+        let raw_messages = match ExecutionProcessLogs::parse_logs(&log_records) {
+            Ok(msgs) => msgs,
+            Err(e) => {
+                tracing::error!("Failed to parse logs for execution {}: {}", id, e);
+                return None;
+            }
+        };
+
+        // Pruned logs only contain the already normalized entries
+        if log_records.iter().any(|record| record.raw_pruned) {
+            return Some(
+                futures::stream::iter(
+                    raw_messages
+                        .into_iter()
+                        .filter(|m| matches!(m, LogMsg::JsonPatch(_)))
+                        .chain(std::iter::once(LogMsg::Finished))
+                        .map(Ok::<_, std::io::Error>),
+                )
+                .boxed(),
+            );
+        }
+
+        // Create temporary store and populate
+        // Include JsonPatch messages (already normalized) and Stdout/Stderr (need normalization)
+        let temp_store = Arc::new(MsgStore::new());
+        for msg in raw_messages {
+            if matches!(
+                msg,
+                LogMsg::Stdout(_) | LogMsg::Stderr(_) | LogMsg::JsonPatch(_)
+            ) {
+                temp_store.push(msg);
+            }
+        }
+        temp_store.push_finished();
+
+        let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
+            Ok(Some(process)) => process,
+            Ok(None) => {
+                tracing::error!("No execution process found for ID: {}", id);
+                return None;
+            }
+            Err(e) => {
+                tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                return None;
             }
-            temp_store.push_finished();
+        };
 
-            let process = match ExecutionProcess::find_by_id(&self.db().pool, *id).await {
-                Ok(Some(process)) => process,
+        // Get the workspace to determine correct directory
+        let (workspace, _session) =
+            match process.parent_workspace_and_session(&self.db().pool).await {
+                Ok(Some((workspace, session))) => (workspace, session),
                 Ok(None) => {
-                    tracing::error!("No execution process found for ID: {}", id);
+                    tracing::error!(
+                        "No workspace/session found for session ID: {}",
+                        process.session_id
+                    );
                     return None;
                 }
                 Err(e) => {
-                    tracing::error!("Failed to fetch execution process {}: {}", id, e);
+                    tracing::error!(
+                        "Failed to fetch workspace for session {}: {}",
+                        process.session_id,
+                        e
+                    );
                     return None;
                 }
             };
 
-            // Get the workspace to determine correct directory
-            let (workspace, _session) =
-                match process.parent_workspace_and_session(&self.db().pool).await {
-                    Ok(Some((workspace, session))) => (workspace, session),
-                    Ok(None) => {
-                        tracing::error!(
-                            "No workspace/session found for session ID: {}",
-                            process.session_id
-                        );
-                        return None;
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to fetch workspace for session {}: {}",
-                            process.session_id,
-                            e
-                        );
-                        return None;
-                    }
-                };
+        if recreate_worktree && let Err(err) = self.ensure_container_exists(&workspace).await {
+            tracing::warn!(
+                "Failed to recreate worktree before log normalization for workspace {}: {}",
+                workspace.id,
+                err
+            );
+        }
 
-            if let Err(err) = self.ensure_container_exists(&workspace).await {
-                tracing::warn!(
-                    "Failed to recreate worktree before log normalization for workspace {}: {}",
-                    workspace.id,
-                    err
-                );
-            }
+        let current_dir = self.workspace_to_current_dir(&workspace);
 
-            let current_dir = self.workspace_to_current_dir(&workspace);
+        let executor_action = if let Ok(executor_action) = process.executor_action() {
+            executor_action
+        } else {
+            tracing::error!(
+                "Failed to parse executor action: {:?}",
+                process.executor_action()
+            );
+            return None;
+        };
 
-            let executor_action = if let Ok(executor_action) = process.executor_action() {
-                executor_action
-            } else {
-                tracing::error!(
-                    "Failed to parse executor action: {:?}",
+        // Spawn normalizer on populated store
+        match executor_action.typ() {
+            ExecutorActionType::CodingAgentInitialRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            ExecutorActionType::CodingAgentFollowUpRequest(request) => {
+                let executor = ExecutorConfigs::get_cached()
+                    .get_coding_agent_or_default(&request.executor_profile_id);
+                executor.normalize_logs(temp_store.clone(), &current_dir);
+            }
+            _ => {
+                tracing::debug!(
+                    "Executor action doesn't support log normalization: {:?}",
                     process.executor_action()
                 );
                 return None;
-            };
-
-            // Spawn normalizer on populated store
-            match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => {
-                    let executor = ExecutorConfigs::get_cached()
-                        .get_coding_agent_or_default(&request.executor_profile_id);
-                    executor.normalize_logs(temp_store.clone(), &current_dir);
-                }
-                _ => {
-                    tracing::debug!(
-                        "Executor action doesn't support log normalization: {:?}",
-                        process.executor_action()
-                    );
-                    return None;
-                }
             }
-            Some(
-                temp_store
-                    .history_plus_stream()
-                    .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
-                    .chain(futures::stream::once(async {
-                        Ok::<_, std::io::Error>(LogMsg::Finished)
-                    }))
-                    .boxed(),
-            )
         }
+        Some(
+            temp_store
+                .history_plus_stream()
+                .filter(|msg| future::ready(matches!(msg, Ok(LogMsg::JsonPatch(..)))))
+                .chain(futures::stream::once(async {
+                    Ok::<_, std::io::Error>(LogMsg::Finished)
+                }))
+                .boxed(),
+        )
     }
 
     fn spawn_stream_raw_logs_to_db(&self, execution_id: &Uuid) -> JoinHandle<()> {
@@ -874,10 +919,94 @@ pub trait ContainerService {
                         LogMsg::JsonPatch(_) => continue,
                     }
                 }
+
+                // All lines are written, store them as a single compressed chunk
+                if let Err(e) = ExecutionProcessLogs::compact(&db.pool, execution_id).await {
+                    tracing::warn!(
+                        "Failed to compact logs for execution {}: {}",
+                        execution_id,
+                        e
+                    );
+                }
             }
         })
     }
 
+    /// Replace the raw stdout/stderr of a finished execution process with its normalized
+    /// entries. Returns the number of bytes reclaimed. The raw logs are left untouched
+    /// unless normalization succeeds and yields at least one entry.
+    async fn prune_raw_logs(&self, execution_id: Uuid) -> Result<i64, ContainerError> {
+        let pool = &self.db().pool;
+        let before = ExecutionProcessLogs::stored_size(pool, execution_id).await?;
+
+        let stream = self
+            .normalize_stored_logs(&execution_id, false)
+            .await
+            .ok_or_else(|| {
+                ContainerError::Other(anyhow!(
+                    "Failed to normalize logs for execution {execution_id}"
+                ))
+            })?;
+        let collect = stream
+            .try_filter(|msg| future::ready(matches!(msg, LogMsg::JsonPatch(_))))
+            .try_collect::<Vec<_>>();
+        let normalized = tokio::time::timeout(LOG_NORMALIZATION_TIMEOUT, collect)
+            .await
+            .map_err(|_| {
+                ContainerError::Other(anyhow!(
+                    "Timed out normalizing logs for execution {execution_id}"
+                ))
+            })??;
+        if normalized.is_empty() {
+            return Err(ContainerError::Other(anyhow!(
+                "Normalizing logs for execution {execution_id} produced no entries"
+            )));
+        }
+
+        let mut jsonl = String::new();
+        for msg in &normalized {
+            jsonl.push_str(&serde_json::to_string(msg).map_err(|e| anyhow!(e))?);
+            jsonl.push('\n');
+        }
+        let after = ExecutionProcessLogs::replace(pool, execution_id, &jsonl, true).await?;
+
+        Ok(before - after)
+    }
+
+    /// Snapshot of the normalized conversation of an execution process, with in-place
+    /// updates to entries applied. Running processes return what has been produced so far.
+    async fn normalized_entries(&self, id: &Uuid) -> Result<Vec<NormalizedEntry>, ContainerError> {
+        let patches: Vec<LogMsg> = if let Some(store) = self.get_msg_store_by_id(id).await {
+            store.get_history()
+        } else {
+            match self.normalize_stored_logs(id, false).await {
+                Some(stream) => {
+                    let collect = stream
+                        .try_filter(|msg| future::ready(matches!(msg, LogMsg::JsonPatch(_))))
+                        .try_collect::<Vec<_>>();
+                    tokio::time::timeout(LOG_NORMALIZATION_TIMEOUT, collect)
+                        .await
+                        .map_err(|_| {
+                            ContainerError::Other(anyhow!(
+                                "Timed out normalizing logs for execution {id}"
+                            ))
+                        })??
+                }
+                None => Vec::new(),
+            }
+        };
+
+        let mut entries = BTreeMap::new();
+        for msg in patches {
+            if let LogMsg::JsonPatch(patch) = msg
+                && let Some((index, entry)) = extract_normalized_entry_from_patch(&patch)
+            {
+                entries.insert(index, entry);
+            }
+        }
+        Ok(entries.into_values().collect())
+    }
+
     async fn start_workspace(
         &self,
         workspace: &Workspace,
@@ -29,6 +29,13 @@ pub const MAX_CUMULATIVE_DIFF_BYTES: usize = 200 * 1024 * 1024;
 
 const DIFF_STREAM_CHANNEL_CAPACITY: usize = 1000;
 
+/// Number of live `DiffStreamHandle`s, exported as a metric
+static ACTIVE_STREAMS: AtomicUsize = AtomicUsize::new(0);
+
+pub fn active_streams() -> usize {
+    ACTIVE_STREAMS.load(Ordering::Relaxed)
+}
+
 /// Errors that can occur during diff stream creation and operation
 #[derive(Error, Debug)]
 pub enum DiffStreamError {
@@ -61,6 +68,7 @@ impl futures::Stream for DiffStreamHandle {
 
 impl Drop for DiffStreamHandle {
     fn drop(&mut self) {
+        ACTIVE_STREAMS.fetch_sub(1, Ordering::Relaxed);
         if let Some(handle) = self._watcher_task.take() {
             handle.abort();
         }
@@ -73,6 +81,7 @@ impl DiffStreamHandle {
         stream: futures::stream::BoxStream<'static, Result<LogMsg, io::Error>>,
         watcher_task: Option<JoinHandle<()>>,
     ) -> Self {
+        ACTIVE_STREAMS.fetch_add(1, Ordering::Relaxed);
         Self {
             stream,
             _watcher_task: watcher_task,
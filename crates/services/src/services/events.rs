@@ -1,14 +1,23 @@
-use std::{str::FromStr, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    sync::Arc,
+};
 
 use db::{
     DBService,
     models::{
         execution_process::ExecutionProcess, project::Project, scratch::Scratch, task::Task,
-        workspace::Workspace,
+        task_activity::TaskActivity, workspace::Workspace,
     },
 };
+use json_patch::Patch;
 use serde_json::json;
-use sqlx::{Error as SqlxError, Sqlite, SqlitePool, decode::Decode, sqlite::SqliteOperation};
+use sqlx::{
+    Error as SqlxError, Sqlite, SqlitePool, ValueRef,
+    decode::Decode,
+    sqlite::{PreupdateHookResult, SqliteConnection, SqliteOperation},
+};
 use tokio::sync::RwLock;
 use utils::msg_store::MsgStore;
 use uuid::Uuid;
@@ -21,33 +30,127 @@ mod streams;
 pub mod types;
 
 pub use patches::{
-    execution_process_patch, project_patch, scratch_patch, task_patch, workspace_patch,
+    execution_process_patch, project_patch, scratch_patch, task_activity_patch, task_patch,
+    workspace_patch,
 };
 pub use types::{EventError, EventPatch, EventPatchInner, HookTables, RecordTypes};
 
+#[derive(Default)]
+struct MutedTaskRows {
+    ids: HashSet<Uuid>,
+    rowids: HashSet<i64>,
+}
+
+/// Tasks whose row changes the hooks leave unpublished, because the caller publishes them
+/// itself as one batched patch. Updates are matched by rowid and deletes by task ID.
+#[derive(Clone, Default)]
+pub struct MutedTasks(Arc<std::sync::RwLock<MutedTaskRows>>);
+
+impl MutedTasks {
+    /// Mute `(task_id, rowid)` pairs until the returned guard is dropped
+    pub fn mute(&self, tasks: Vec<(Uuid, i64)>) -> MutedTasksGuard {
+        let mut rows = self.0.write().unwrap();
+        for (id, rowid) in &tasks {
+            rows.ids.insert(*id);
+            rows.rowids.insert(*rowid);
+        }
+        MutedTasksGuard {
+            muted: self.clone(),
+            tasks,
+        }
+    }
+
+    fn is_muted_id(&self, id: Uuid) -> bool {
+        self.0.read().unwrap().ids.contains(&id)
+    }
+
+    fn is_muted_rowid(&self, rowid: i64) -> bool {
+        self.0.read().unwrap().rowids.contains(&rowid)
+    }
+}
+
+pub struct MutedTasksGuard {
+    muted: MutedTasks,
+    tasks: Vec<(Uuid, i64)>,
+}
+
+/// Tables whose rows can be archived, and so reappear to clients when restored
+const ARCHIVABLE_TABLES: [&str; 2] = ["tasks", "projects"];
+
+/// Index of the `archived_at` column of each archivable table, for reading it in the
+/// preupdate hook. Missing before the archive migration has run.
+async fn archived_at_columns(
+    conn: &mut SqliteConnection,
+) -> Result<HashMap<&'static str, i32>, SqlxError> {
+    let mut columns = HashMap::new();
+    for table in ARCHIVABLE_TABLES {
+        let column: Option<i32> =
+            sqlx::query_scalar("SELECT cid FROM pragma_table_info(?1) WHERE name = 'archived_at'")
+                .bind(table)
+                .fetch_optional(&mut *conn)
+                .await?;
+        if let Some(column) = column {
+            columns.insert(table, column);
+        }
+    }
+    Ok(columns)
+}
+
+/// Whether an update clears `archived_at`, i.e. restores the row from the archive
+fn clears_archived_at(preupdate: &PreupdateHookResult<'_>, column: i32) -> bool {
+    matches!(
+        (
+            preupdate.get_old_column_value(column),
+            preupdate.get_new_column_value(column),
+        ),
+        (Ok(old), Ok(new)) if !old.is_null() && new.is_null()
+    )
+}
+
+impl Drop for MutedTasksGuard {
+    fn drop(&mut self) {
+        let mut rows = self.muted.0.write().unwrap();
+        for (id, rowid) in &self.tasks {
+            rows.ids.remove(id);
+            rows.rowids.remove(rowid);
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct EventService {
     msg_store: Arc<MsgStore>,
     db: DBService,
     #[allow(dead_code)]
     entry_count: Arc<RwLock<usize>>,
+    muted_tasks: MutedTasks,
 }
 
 impl EventService {
     /// Creates a new EventService that will work with a DBService configured with hooks
-    pub fn new(db: DBService, msg_store: Arc<MsgStore>, entry_count: Arc<RwLock<usize>>) -> Self {
+    pub fn new(
+        db: DBService,
+        msg_store: Arc<MsgStore>,
+        entry_count: Arc<RwLock<usize>>,
+        muted_tasks: MutedTasks,
+    ) -> Self {
         Self {
             msg_store,
             db,
             entry_count,
+            muted_tasks,
         }
     }
 
     async fn push_task_update_for_task(
         pool: &SqlitePool,
         msg_store: Arc<MsgStore>,
+        muted_tasks: &MutedTasks,
         task_id: Uuid,
     ) -> Result<(), SqlxError> {
+        if muted_tasks.is_muted_id(task_id) {
+            return Ok(());
+        }
         if let Some(task) = Task::find_by_id(pool, task_id).await? {
             let tasks = Task::find_by_project_id_with_attempt_status(pool, task.project_id).await?;
 
@@ -65,13 +168,15 @@ impl EventService {
     async fn push_task_update_for_session(
         pool: &SqlitePool,
         msg_store: Arc<MsgStore>,
+        muted_tasks: &MutedTasks,
         session_id: Uuid,
     ) -> Result<(), SqlxError> {
         use db::models::session::Session;
         if let Some(session) = Session::find_by_id(pool, session_id).await?
             && let Some(workspace) = Workspace::find_by_id(pool, session.workspace_id).await?
         {
-            Self::push_task_update_for_task(pool, msg_store, workspace.task_id).await?;
+            Self::push_task_update_for_task(pool, msg_store, muted_tasks, workspace.task_id)
+                .await?;
         }
 
         Ok(())
@@ -82,6 +187,7 @@ impl EventService {
         msg_store: Arc<MsgStore>,
         entry_count: Arc<RwLock<usize>>,
         db_service: DBService,
+        muted_tasks: MutedTasks,
     ) -> impl for<'a> Fn(
         &'a mut sqlx::sqlite::SqliteConnection,
     ) -> std::pin::Pin<
@@ -93,12 +199,35 @@ impl EventService {
             let msg_store_for_hook = msg_store.clone();
             let entry_count_for_hook = entry_count.clone();
             let db_for_hook = db_service.clone();
+            let muted_tasks = muted_tasks.clone();
             Box::pin(async move {
+                let archived_at_columns = archived_at_columns(conn).await?;
+                // Rows restored from the archive, noted before the update so its patch can
+                // add them back for clients that dropped them
+                let restored_rows: Arc<std::sync::Mutex<HashMap<&'static str, HashSet<i64>>>> =
+                    Default::default();
                 let mut handle = conn.lock_handle().await?;
                 let runtime_handle = tokio::runtime::Handle::current();
                 handle.set_preupdate_hook({
                     let msg_store_for_preupdate = msg_store_for_hook.clone();
+                    let muted_tasks = muted_tasks.clone();
+                    let restored_rows = restored_rows.clone();
                     move |preupdate: sqlx::sqlite::PreupdateHookResult<'_>| {
+                        if preupdate.operation == SqliteOperation::Update {
+                            if let Some((table, column)) =
+                                archived_at_columns.get_key_value(preupdate.table)
+                                && clears_archived_at(&preupdate, *column)
+                                && let Ok(rowid) = preupdate.get_new_row_id()
+                            {
+                                restored_rows
+                                    .lock()
+                                    .unwrap()
+                                    .entry(*table)
+                                    .or_default()
+                                    .insert(rowid);
+                            }
+                            return;
+                        }
                         if preupdate.operation != SqliteOperation::Delete {
                             return;
                         }
@@ -107,6 +236,7 @@ impl EventService {
                             "tasks" => {
                                 if let Ok(value) = preupdate.get_old_column_value(0)
                                     && let Ok(task_id) = <Uuid as Decode<Sqlite>>::decode(value)
+                                    && !muted_tasks.is_muted_id(task_id)
                                 {
                                     let patch = task_patch::remove(task_id);
                                     msg_store_for_preupdate.push_patch(patch);
@@ -137,6 +267,14 @@ impl EventService {
                                     msg_store_for_preupdate.push_patch(patch);
                                 }
                             }
+                            "task_activity" => {
+                                if let Ok(value) = preupdate.get_old_column_value(0)
+                                    && let Ok(activity_id) = <Uuid as Decode<Sqlite>>::decode(value)
+                                {
+                                    let patch = task_activity_patch::remove(activity_id);
+                                    msg_store_for_preupdate.push_patch(patch);
+                                }
+                            }
                             "scratch" => {
                                 // Composite key: need both id (column 0) and scratch_type (column 1)
                                 if let Ok(id_val) = preupdate.get_old_column_value(0)
@@ -159,16 +297,28 @@ impl EventService {
                     let entry_count_for_hook = entry_count_for_hook.clone();
                     let msg_store_for_hook = msg_store_for_hook.clone();
                     let db = db_for_hook.clone();
+                    let muted_tasks = muted_tasks.clone();
 
+                    let restored = matches!(hook.operation, SqliteOperation::Update)
+                        && restored_rows
+                            .lock()
+                            .unwrap()
+                            .get_mut(hook.table)
+                            .is_some_and(|rows| rows.remove(&hook.rowid));
                     if let Ok(table) = HookTables::from_str(hook.table) {
                         let rowid = hook.rowid;
+                        if matches!(table, HookTables::Tasks) && muted_tasks.is_muted_rowid(rowid)
+                        {
+                            return;
+                        }
                         runtime_handle.spawn(async move {
                             let record_type: RecordTypes = match (table, hook.operation.clone()) {
                                 (HookTables::Tasks, SqliteOperation::Delete)
                                 | (HookTables::Projects, SqliteOperation::Delete)
                                 | (HookTables::Workspaces, SqliteOperation::Delete)
                                 | (HookTables::ExecutionProcesses, SqliteOperation::Delete)
-                                | (HookTables::Scratch, SqliteOperation::Delete) => {
+                                | (HookTables::Scratch, SqliteOperation::Delete)
+                                | (HookTables::TaskActivity, SqliteOperation::Delete) => {
                                     // Deletions handled in preupdate hook for reliable data capture
                                     return;
                                 }
@@ -232,6 +382,19 @@ impl EventService {
                                         }
                                     }
                                 }
+                                (HookTables::TaskActivity, _) => {
+                                    match TaskActivity::find_by_rowid(&db.pool, rowid).await {
+                                        Ok(Some(activity)) => RecordTypes::TaskActivity(activity),
+                                        Ok(None) => return,
+                                        Err(e) => {
+                                            tracing::error!(
+                                                "Failed to fetch task activity: {:?}",
+                                                e
+                                            );
+                                            return;
+                                        }
+                                    }
+                                }
                                 (HookTables::Scratch, _) => {
                                     match Scratch::find_by_rowid(&db.pool, rowid).await {
                                         Ok(Some(scratch)) => RecordTypes::Scratch(scratch),
@@ -258,6 +421,11 @@ impl EventService {
                             // Handle task-related operations with direct patches
                             match &record_type {
                                 RecordTypes::Task(task) => {
+                                    // Archived tasks leave the board
+                                    if task.archived_at.is_some() {
+                                        msg_store_for_hook.push_patch(task_patch::remove(task.id));
+                                        return;
+                                    }
                                     // Convert Task to TaskWithAttemptStatus
                                     if let Ok(task_list) =
                                         Task::find_by_project_id_with_attempt_status(
@@ -272,6 +440,10 @@ impl EventService {
                                             SqliteOperation::Insert => {
                                                 task_patch::add(&task_with_status)
                                             }
+                                            // Clients dropped the task when it was archived
+                                            SqliteOperation::Update if restored => {
+                                                task_patch::add(&task_with_status)
+                                            }
                                             SqliteOperation::Update => {
                                                 task_patch::replace(&task_with_status)
                                             }
@@ -291,13 +463,30 @@ impl EventService {
                                 }
                                 RecordTypes::Project(project) => {
                                     let patch = match hook.operation {
+                                        _ if project.archived_at.is_some() => {
+                                            project_patch::remove(project.id)
+                                        }
                                         SqliteOperation::Insert => project_patch::add(project),
+                                        // Clients dropped the project when it was archived
+                                        SqliteOperation::Update if restored => {
+                                            project_patch::add(project)
+                                        }
                                         SqliteOperation::Update => project_patch::replace(project),
                                         _ => project_patch::replace(project),
                                     };
                                     msg_store_for_hook.push_patch(patch);
                                     return;
                                 }
+                                RecordTypes::TaskActivity(activity) => {
+                                    let patch = match hook.operation {
+                                        SqliteOperation::Insert => {
+                                            task_activity_patch::add(activity)
+                                        }
+                                        _ => task_activity_patch::replace(activity),
+                                    };
+                                    msg_store_for_hook.push_patch(patch);
+                                    return;
+                                }
                                 RecordTypes::Scratch(scratch) => {
                                     let patch = match hook.operation {
                                         SqliteOperation::Insert => scratch_patch::add(scratch),
@@ -318,6 +507,9 @@ impl EventService {
                                 }
                                 RecordTypes::Workspace(workspace) => {
                                     // Workspaces should update the parent task with fresh data
+                                    if muted_tasks.is_muted_id(workspace.task_id) {
+                                        return;
+                                    }
                                     if let Ok(Some(task)) =
                                         Task::find_by_id(&db.pool, workspace.task_id).await
                                         && let Ok(task_list) =
@@ -370,6 +562,7 @@ impl EventService {
                                     if let Err(err) = EventService::push_task_update_for_session(
                                         &db.pool,
                                         msg_store_for_hook.clone(),
+                                        &muted_tasks,
                                         process.session_id,
                                     )
                                     .await
@@ -395,6 +588,7 @@ impl EventService {
                                             EventService::push_task_update_for_session(
                                                 &db.pool,
                                                 msg_store_for_hook.clone(),
+                                                &muted_tasks,
                                                 *session_id,
                                             )
                                             .await
@@ -445,4 +639,222 @@ impl EventService {
     pub fn msg_store(&self) -> &Arc<MsgStore> {
         &self.msg_store
     }
+
+    pub fn muted_tasks(&self) -> &MutedTasks {
+        &self.muted_tasks
+    }
+
+    /// Publish changes to many tasks as one patch per project, for callers that muted the
+    /// per-row patches. Deleted tasks are given together with their project.
+    pub async fn push_task_batch(
+        &self,
+        changed: &[Uuid],
+        deleted: &[(Uuid, Uuid)],
+    ) -> Result<(), SqlxError> {
+        let pool = &self.db.pool;
+        let mut patches: HashMap<Uuid, (Vec<_>, Vec<_>)> = HashMap::new();
+
+        let mut changed_by_project: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        for task_id in changed {
+            if let Some(task) = Task::find_by_id(pool, *task_id).await? {
+                changed_by_project
+                    .entry(task.project_id)
+                    .or_default()
+                    .insert(task.id);
+            }
+        }
+        for (project_id, task_ids) in changed_by_project {
+            let replaced = Task::find_by_project_id_with_attempt_status(pool, project_id)
+                .await?
+                .into_iter()
+                .filter(|task| task_ids.contains(&task.id))
+                .flat_map(|task| task_patch::replace(&task).0);
+            patches.entry(project_id).or_default().0.extend(replaced);
+        }
+        for (task_id, project_id) in deleted {
+            patches
+                .entry(*project_id)
+                .or_default()
+                .1
+                .extend(task_patch::remove(*task_id).0);
+        }
+
+        // Replacements go first: task streams route a patch by its first operation
+        for (replaced, removed) in patches.into_values() {
+            self.msg_store
+                .push_patch(Patch(replaced.into_iter().chain(removed).collect()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        models::{project::CreateProject, task::CreateTask},
+        test_utils::test_pool,
+    };
+    use json_patch::PatchOperation;
+    use utils::log_msg::LogMsg;
+
+    use super::*;
+
+    fn describe(op: &PatchOperation) -> String {
+        match op {
+            PatchOperation::Replace(op) => format!("replace {}", op.path.as_str()),
+            PatchOperation::Remove(op) => format!("remove {}", op.path.as_str()),
+            PatchOperation::Add(op) => format!("add {}", op.path.as_str()),
+            _ => panic!("unexpected patch operation"),
+        }
+    }
+
+    async fn create_project(pool: &SqlitePool, name: &str) -> Project {
+        Project::create(
+            pool,
+            &CreateProject {
+                name: name.to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+        Task::create(
+            pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_task_updates_replace_and_restores_add() {
+        let pool = test_pool().await;
+        let msg_store = Arc::new(MsgStore::new());
+        let hook = EventService::create_hook(
+            msg_store.clone(),
+            Default::default(),
+            DBService { pool: pool.clone() },
+            MutedTasks::default(),
+        );
+        let mut conn = pool.acquire().await.unwrap();
+        hook(&mut *conn).await.unwrap();
+        drop(conn);
+
+        // Each change is published from a spawned task; let it run before the next change
+        let settle = || tokio::time::sleep(std::time::Duration::from_millis(50));
+        let project = create_project(&pool, "project").await;
+        let task = create_task(&pool, project.id, "task").await;
+        settle().await;
+        Task::update_status(&pool, task.id, db::models::task::TaskStatus::InProgress)
+            .await
+            .unwrap();
+        settle().await;
+        Task::archive(&pool, task.id).await.unwrap();
+        settle().await;
+        Task::restore(&pool, task.id).await.unwrap();
+        settle().await;
+
+        let task_path = format!("/tasks/{}", task.id);
+        let ops: Vec<String> = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => Some(patch),
+                _ => None,
+            })
+            .flat_map(|patch| patch.0)
+            .map(|op| describe(&op))
+            .filter(|op| op.ends_with(&task_path))
+            .collect();
+        assert_eq!(
+            ops,
+            vec![
+                format!("add {task_path}"),
+                format!("replace {task_path}"),
+                format!("remove {task_path}"),
+                format!("add {task_path}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_muted_tasks_until_guard_dropped() {
+        let muted = MutedTasks::default();
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let guard = muted.mute(vec![(a, 1)]);
+        let other = muted.mute(vec![(b, 2)]);
+        assert!(muted.is_muted_id(a) && muted.is_muted_rowid(1));
+        assert!(muted.is_muted_id(b) && muted.is_muted_rowid(2));
+
+        drop(guard);
+        assert!(!muted.is_muted_id(a) && !muted.is_muted_rowid(1));
+        assert!(muted.is_muted_id(b) && muted.is_muted_rowid(2));
+        drop(other);
+        assert!(!muted.is_muted_id(b) && !muted.is_muted_rowid(2));
+    }
+
+    #[tokio::test]
+    async fn test_push_task_batch_publishes_one_patch_per_project() {
+        let pool = test_pool().await;
+        let msg_store = Arc::new(MsgStore::new());
+        let events = EventService::new(
+            DBService { pool: pool.clone() },
+            msg_store.clone(),
+            Default::default(),
+            MutedTasks::default(),
+        );
+
+        let first = create_project(&pool, "first").await;
+        let second = create_project(&pool, "second").await;
+        let a = create_task(&pool, first.id, "a").await;
+        let b = create_task(&pool, first.id, "b").await;
+        let c = create_task(&pool, second.id, "c").await;
+        let deleted = Uuid::new_v4();
+
+        events
+            .push_task_batch(&[a.id, b.id, c.id], &[(deleted, second.id)])
+            .await
+            .unwrap();
+
+        let patches: Vec<Vec<String>> = msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => Some(patch.0.iter().map(describe).collect()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(patches.len(), 2);
+
+        // Replacements come before removals within a project's patch
+        let second_patch = patches
+            .iter()
+            .find(|ops| ops.iter().any(|op| op.starts_with("remove")))
+            .unwrap();
+        assert_eq!(
+            second_patch,
+            &vec![
+                format!("replace /tasks/{}", c.id),
+                format!("remove /tasks/{deleted}")
+            ]
+        );
+        let mut first_patch = patches
+            .iter()
+            .find(|ops| *ops != second_patch)
+            .unwrap()
+            .clone();
+        first_patch.sort();
+        let mut expected = vec![
+            format!("replace /tasks/{}", a.id),
+            format!("replace /tasks/{}", b.id),
+        ];
+        expected.sort();
+        assert_eq!(first_patch, expected);
+    }
 }
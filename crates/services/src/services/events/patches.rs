@@ -1,6 +1,6 @@
 use db::models::{
     execution_process::ExecutionProcess, project::Project, scratch::Scratch,
-    task::TaskWithAttemptStatus, workspace::Workspace,
+    task::TaskWithAttemptStatus, task_activity::TaskActivity, workspace::Workspace,
 };
 use json_patch::{AddOperation, Patch, PatchOperation, RemoveOperation, ReplaceOperation};
 use uuid::Uuid;
@@ -175,6 +175,47 @@ pub mod workspace_patch {
     }
 }
 
+/// Helper functions for creating task activity-specific patches
+pub mod task_activity_patch {
+    use super::*;
+
+    fn activity_path(activity_id: Uuid) -> String {
+        format!(
+            "/activity/{}",
+            escape_pointer_segment(&activity_id.to_string())
+        )
+    }
+
+    /// Create patch for adding a new activity entry
+    pub fn add(activity: &TaskActivity) -> Patch {
+        Patch(vec![PatchOperation::Add(AddOperation {
+            path: activity_path(activity.id)
+                .try_into()
+                .expect("Activity path should be valid"),
+            value: serde_json::to_value(activity).expect("Activity serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for updating an existing activity entry
+    pub fn replace(activity: &TaskActivity) -> Patch {
+        Patch(vec![PatchOperation::Replace(ReplaceOperation {
+            path: activity_path(activity.id)
+                .try_into()
+                .expect("Activity path should be valid"),
+            value: serde_json::to_value(activity).expect("Activity serialization should not fail"),
+        })])
+    }
+
+    /// Create patch for removing an activity entry
+    pub fn remove(activity_id: Uuid) -> Patch {
+        Patch(vec![PatchOperation::Remove(RemoveOperation {
+            path: activity_path(activity_id)
+                .try_into()
+                .expect("Activity path should be valid"),
+        })])
+    }
+}
+
 /// Helper functions for creating scratch-specific patches.
 /// All patches use path "/scratch" - filtering is done by matching id and payload type in the value.
 pub mod scratch_patch {
@@ -4,6 +4,7 @@ use db::models::{
     scratch::Scratch,
     session::Session,
     task::{Task, TaskWithAttemptStatus},
+    task_activity::TaskActivity,
 };
 use futures::StreamExt;
 use serde_json::json;
@@ -147,6 +148,69 @@ impl EventService {
         Ok(combined_stream)
     }
 
+    /// Stream raw activity entries of a task with initial snapshot
+    pub async fn stream_task_activity_raw(
+        &self,
+        task_id: Uuid,
+    ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, EventError>
+    {
+        let entries = TaskActivity::find_by_task_id(&self.db.pool, task_id).await?;
+
+        // Convert activity array to object keyed by entry ID
+        let entries_map: serde_json::Map<String, serde_json::Value> = entries
+            .into_iter()
+            .map(|entry| (entry.id.to_string(), serde_json::to_value(entry).unwrap()))
+            .collect();
+
+        let initial_patch = json!([
+            {
+                "op": "replace",
+                "path": "/activity",
+                "value": entries_map
+            }
+        ]);
+        let initial_msg = LogMsg::JsonPatch(serde_json::from_value(initial_patch).unwrap());
+
+        let filtered_stream =
+            BroadcastStream::new(self.msg_store.get_receiver()).filter_map(move |msg_result| {
+                async move {
+                    match msg_result {
+                        Ok(LogMsg::JsonPatch(patch)) => {
+                            if let Some(patch_op) = patch.0.first()
+                                && patch_op.path().starts_with("/activity/")
+                            {
+                                let value = match patch_op {
+                                    json_patch::PatchOperation::Add(op) => &op.value,
+                                    json_patch::PatchOperation::Replace(op) => &op.value,
+                                    // Removed entries can't be attributed to a task, so let the
+                                    // client ignore ones it doesn't have
+                                    json_patch::PatchOperation::Remove(_) => {
+                                        return Some(Ok(LogMsg::JsonPatch(patch)));
+                                    }
+                                    _ => return None,
+                                };
+                                if let Ok(entry) =
+                                    serde_json::from_value::<TaskActivity>(value.clone())
+                                    && entry.task_id == task_id
+                                {
+                                    return Some(Ok(LogMsg::JsonPatch(patch)));
+                                }
+                            }
+                            None
+                        }
+                        Ok(other) => Some(Ok(other)), // Pass through non-patch messages
+                        Err(_) => None,               // Filter out broadcast errors
+                    }
+                }
+            });
+
+        // Start with initial snapshot, then live updates
+        let initial_stream = futures::stream::once(async move { Ok(initial_msg) });
+        let combined_stream = initial_stream.chain(filtered_stream).boxed();
+
+        Ok(combined_stream)
+    }
+
     /// Stream raw project messages with initial snapshot
     pub async fn stream_projects_raw(
         &self,
@@ -13,7 +13,14 @@ use ts_rs::TS;
 mod cli;
 
 use cli::{GhCli, GhCliError, PrComment, PrReviewComment};
-pub use cli::{PrCommentAuthor, ReviewCommentUser};
+pub use cli::{
+    GitHubIssue, GitHubIssueLabel, GitHubIssueMilestone, PrCommentAuthor, ReviewCommentUser,
+};
+
+/// Keywords GitHub recognizes for closing an issue from a pull request body
+const CLOSING_KEYWORDS: &[&str] = &[
+    "close", "closes", "closed", "fix", "fixes", "fixed", "resolve", "resolves", "resolved",
+];
 
 /// Unified PR comment that can be either a general comment or review comment
 #[derive(Debug, Clone, Serialize, TS)]
@@ -152,6 +159,53 @@ pub struct CreatePrRequest {
     pub draft: Option<bool>,
 }
 
+/// Which issues to list
+#[derive(Debug, Clone)]
+pub struct IssueFilter {
+    /// Issues must have every one of these labels
+    pub labels: Vec<String>,
+    /// Milestone title
+    pub milestone: Option<String>,
+    /// GitHub search query, e.g. `sort:created-asc no:assignee`
+    pub search: Option<String>,
+    pub limit: u32,
+    /// List closed issues instead of open ones
+    pub closed: bool,
+}
+
+impl Default for IssueFilter {
+    fn default() -> Self {
+        Self {
+            labels: Vec::new(),
+            milestone: None,
+            search: None,
+            limit: 100,
+            closed: false,
+        }
+    }
+}
+
+/// `body` with a "Closes <reference>" line appended, unless it already closes `reference`
+pub fn with_closing_reference(body: Option<String>, reference: &str) -> String {
+    let body = body.unwrap_or_default();
+    let lower = body.to_lowercase();
+    let reference_lower = reference.to_lowercase();
+    let already_closes = CLOSING_KEYWORDS.iter().any(|keyword| {
+        lower.contains(&format!("{keyword} {reference_lower}"))
+            || lower.contains(&format!("{keyword}: {reference_lower}"))
+    });
+    if already_closes {
+        return body;
+    }
+
+    let closing_line = format!("Closes {reference}");
+    if body.trim().is_empty() {
+        closing_line
+    } else {
+        format!("{}\n\n{closing_line}", body.trim_end())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitHubService {
     gh_cli: GhCli,
@@ -450,4 +504,104 @@ impl GitHubService {
         })
         .await
     }
+
+    /// List open (or closed) issues matching `filter`
+    pub async fn list_issues(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let filter = filter.clone();
+            let cli = self.gh_cli.clone();
+            let issues = task::spawn_blocking(move || cli.list_issues(&owner, &repo, &filter))
+                .await
+                .map_err(|err| {
+                    GitHubServiceError::Repository(format!(
+                        "Failed to execute GitHub CLI for listing issues: {err}"
+                    ))
+                })?;
+            issues.map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+
+    /// Get the current state of an issue
+    pub async fn get_issue(
+        &self,
+        repo_info: &GitHubRepoInfo,
+        issue_number: i64,
+    ) -> Result<GitHubIssue, GitHubServiceError> {
+        (|| async {
+            let owner = repo_info.owner.clone();
+            let repo = repo_info.repo_name.clone();
+            let cli = self.gh_cli.clone();
+            let issue = task::spawn_blocking(move || cli.view_issue(&owner, &repo, issue_number))
+                .await
+                .map_err(|err| {
+                    GitHubServiceError::Repository(format!(
+                        "Failed to execute GitHub CLI for viewing issue #{issue_number}: {err}"
+                    ))
+                })?;
+            issue.map_err(GitHubServiceError::from)
+        })
+        .retry(
+            &ExponentialBuilder::default()
+                .with_min_delay(Duration::from_secs(1))
+                .with_max_delay(Duration::from_secs(30))
+                .with_max_times(3)
+                .with_jitter(),
+        )
+        .when(|e: &GitHubServiceError| e.should_retry())
+        .notify(|err: &GitHubServiceError, dur: Duration| {
+            tracing::warn!(
+                "GitHub API call failed, retrying after {:.2}s: {}",
+                dur.as_secs_f64(),
+                err
+            );
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_closing_reference_appends_line() {
+        assert_eq!(
+            with_closing_reference(Some("Adds OAuth.\n".to_string()), "#12"),
+            "Adds OAuth.\n\nCloses #12"
+        );
+        assert_eq!(
+            with_closing_reference(None, "acme/api#3"),
+            "Closes acme/api#3"
+        );
+    }
+
+    #[test]
+    fn test_with_closing_reference_keeps_existing_keyword() {
+        let body = "Adds OAuth.\n\nFixes #12".to_string();
+        assert_eq!(with_closing_reference(Some(body.clone()), "#12"), body);
+        let body = "Resolves: #12".to_string();
+        assert_eq!(with_closing_reference(Some(body.clone()), "#12"), body);
+    }
 }
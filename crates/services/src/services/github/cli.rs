@@ -17,7 +17,10 @@ use thiserror::Error;
 use ts_rs::TS;
 use utils::shell::resolve_executable_path_blocking;
 
-use crate::services::github::{CreatePrRequest, GitHubRepoInfo};
+use crate::services::github::{CreatePrRequest, GitHubRepoInfo, IssueFilter};
+
+/// Fields requested from `gh issue list/view --json`
+const ISSUE_JSON_FIELDS: &str = "number,title,body,url,state,stateReason,labels,milestone";
 
 /// Author information for a PR comment
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -58,6 +61,51 @@ pub struct PrReviewComment {
     pub author_association: String,
 }
 
+/// A label on a GitHub issue
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitHubIssueLabel {
+    pub name: String,
+}
+
+/// The milestone a GitHub issue belongs to
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitHubIssueMilestone {
+    pub title: String,
+}
+
+/// A GitHub issue (from `gh issue list/view --json`)
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct GitHubIssue {
+    pub number: i64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    pub url: String,
+    /// `OPEN` or `CLOSED`
+    pub state: String,
+    /// Why a closed issue was closed: `COMPLETED` or `NOT_PLANNED`
+    #[serde(default, rename = "stateReason")]
+    pub state_reason: Option<String>,
+    #[serde(default)]
+    pub labels: Vec<GitHubIssueLabel>,
+    pub milestone: Option<GitHubIssueMilestone>,
+}
+
+impl GitHubIssue {
+    pub fn is_closed(&self) -> bool {
+        self.state.eq_ignore_ascii_case("closed")
+    }
+
+    /// Closed without being done, e.g. as a duplicate or won't fix
+    pub fn is_not_planned(&self) -> bool {
+        self.is_closed()
+            && self
+                .state_reason
+                .as_deref()
+                .is_some_and(|reason| reason.eq_ignore_ascii_case("not_planned"))
+    }
+}
+
 /// High-level errors originating from the GitHub CLI.
 #[derive(Debug, Error)]
 pub enum GhCliError {
@@ -245,6 +293,65 @@ impl GhCli {
         ])?;
         Self::parse_pr_review_comments(&raw)
     }
+
+    /// List open (or closed) issues matching `filter`.
+    pub fn list_issues(
+        &self,
+        owner: &str,
+        repo: &str,
+        filter: &IssueFilter,
+    ) -> Result<Vec<GitHubIssue>, GhCliError> {
+        let mut args: Vec<OsString> = vec![
+            "issue".into(),
+            "list".into(),
+            "--repo".into(),
+            format!("{owner}/{repo}").into(),
+            "--state".into(),
+            if filter.closed { "closed" } else { "open" }.into(),
+            "--limit".into(),
+            filter.limit.to_string().into(),
+            "--json".into(),
+            ISSUE_JSON_FIELDS.into(),
+        ];
+        for label in &filter.labels {
+            args.push("--label".into());
+            args.push(label.into());
+        }
+        if let Some(milestone) = &filter.milestone {
+            args.push("--milestone".into());
+            args.push(milestone.into());
+        }
+        if let Some(search) = &filter.search {
+            args.push("--search".into());
+            args.push(search.into());
+        }
+
+        let raw = self.run(args)?;
+        Self::parse_issues(&raw)
+    }
+
+    /// Retrieve a single issue.
+    pub fn view_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue_number: i64,
+    ) -> Result<GitHubIssue, GhCliError> {
+        let raw = self.run([
+            "issue",
+            "view",
+            &issue_number.to_string(),
+            "--repo",
+            &format!("{owner}/{repo}"),
+            "--json",
+            ISSUE_JSON_FIELDS,
+        ])?;
+        serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue view response: {err}; raw: {raw}"
+            ))
+        })
+    }
 }
 
 impl GhCli {
@@ -349,6 +456,14 @@ impl GhCli {
             .collect()
     }
 
+    fn parse_issues(raw: &str) -> Result<Vec<GitHubIssue>, GhCliError> {
+        serde_json::from_str(raw.trim()).map_err(|err| {
+            GhCliError::UnexpectedOutput(format!(
+                "Failed to parse gh issue list response: {err}; raw: {raw}"
+            ))
+        })
+    }
+
     fn parse_pr_review_comments(raw: &str) -> Result<Vec<PrReviewComment>, GhCliError> {
         serde_json::from_str(raw.trim()).map_err(|err| {
             GhCliError::UnexpectedOutput(format!(
@@ -162,12 +162,14 @@ impl ImageService {
         Ok(())
     }
 
+    /// Copy the task's images, including those attached to its comments, to the worktree
     pub async fn copy_images_by_task_to_worktree(
         &self,
         worktree_path: &Path,
         task_id: Uuid,
     ) -> Result<(), ImageError> {
-        let images = Image::find_by_task_id(&self.pool, task_id).await?;
+        let mut images = Image::find_by_task_id(&self.pool, task_id).await?;
+        images.extend(Image::find_by_task_comments(&self.pool, task_id).await?);
         self.copy_images(worktree_path, images)
     }
 
@@ -0,0 +1,462 @@
+//! Prometheus metrics for `/api/metrics`. Database-backed values are aggregated at scrape time;
+//! process-local values (PR monitor polls, worktree disk usage) are tracked here.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    path::Path,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicI64, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use db::{
+    DBService,
+    models::{
+        approval::ApprovalRecord, execution_process::ExecutionProcess, merge::MergeStatus,
+        session::Session,
+    },
+};
+use tokio::sync::{Mutex, RwLock};
+use utils::msg_store::MsgStore;
+use uuid::Uuid;
+
+use crate::services::{
+    diff_stream, queued_message::QueuedMessageService, workspace_manager::WorkspaceManager,
+};
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+const PREFIX: &str = "vibe_kanban";
+/// Walking worktrees is expensive (node_modules, target/), so disk usage is refreshed lazily
+const WORKTREE_USAGE_TTL: Duration = Duration::from_secs(300);
+
+/// Counters updated by `PrMonitorService` on every poll
+#[derive(Debug, Default)]
+pub struct PrMonitorMetrics {
+    polls_ok: AtomicU64,
+    polls_failed: AtomicU64,
+    checks_open: AtomicU64,
+    checks_merged: AtomicU64,
+    checks_closed: AtomicU64,
+    checks_unknown: AtomicU64,
+    checks_failed: AtomicU64,
+    open_prs: AtomicI64,
+    last_poll_timestamp: AtomicI64,
+}
+
+impl PrMonitorMetrics {
+    pub fn record_poll(&self, ok: bool, open_prs: usize) {
+        let counter = if ok {
+            &self.polls_ok
+        } else {
+            &self.polls_failed
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.open_prs.store(open_prs as i64, Ordering::Relaxed);
+        self.last_poll_timestamp
+            .store(Utc::now().timestamp(), Ordering::Relaxed);
+    }
+
+    pub fn record_check(&self, status: Option<&MergeStatus>) {
+        let counter = match status {
+            Some(MergeStatus::Open) => &self.checks_open,
+            Some(MergeStatus::Merged) => &self.checks_merged,
+            Some(MergeStatus::Closed) => &self.checks_closed,
+            Some(MergeStatus::Unknown) => &self.checks_unknown,
+            None => &self.checks_failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+pub fn pr_monitor() -> &'static PrMonitorMetrics {
+    static PR_MONITOR: OnceLock<PrMonitorMetrics> = OnceLock::new();
+    PR_MONITOR.get_or_init(PrMonitorMetrics::default)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct WorktreeUsage {
+    workspaces: u64,
+    worktrees: u64,
+    bytes: u64,
+}
+
+/// Inputs for one scrape
+pub struct MetricsSources<'a> {
+    pub db: &'a DBService,
+    pub execution_msg_stores: &'a Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    pub events_msg_store: &'a Arc<MsgStore>,
+    pub queued_messages: &'a QueuedMessageService,
+}
+
+/// Render every metric in the Prometheus text exposition format
+pub async fn render(sources: MetricsSources<'_>) -> Result<String, sqlx::Error> {
+    let pool = &sources.db.pool;
+    let mut out = MetricsWriter::default();
+
+    let execution_stats = ExecutionProcess::stats(pool).await?;
+    out.family(
+        "executions_running",
+        "gauge",
+        "Execution processes currently running",
+    );
+    for stat in execution_stats.iter().filter(|s| s.status == "running") {
+        out.sample(
+            "executions_running",
+            &[
+                ("executor", executor_label(stat.executor.as_deref())),
+                ("run_reason", stat.run_reason.as_str()),
+            ],
+            stat.count as f64,
+        );
+    }
+
+    let mut queued_by_executor: BTreeMap<String, u64> = BTreeMap::new();
+    for session_id in sources.queued_messages.queued_session_ids() {
+        let executor = Session::find_by_id(pool, session_id)
+            .await?
+            .and_then(|s| s.executor);
+        *queued_by_executor
+            .entry(executor_label(executor.as_deref()).to_string())
+            .or_default() += 1;
+    }
+    out.family(
+        "executions_queued",
+        "gauge",
+        "Follow-up messages queued until the current execution finishes",
+    );
+    for (executor, count) in &queued_by_executor {
+        out.sample(
+            "executions_queued",
+            &[("executor", executor)],
+            *count as f64,
+        );
+    }
+
+    out.family(
+        "executions_finished_total",
+        "counter",
+        "Finished execution processes by exit status",
+    );
+    for stat in execution_stats.iter().filter(|s| s.status != "running") {
+        out.sample(
+            "executions_finished_total",
+            &[
+                ("executor", executor_label(stat.executor.as_deref())),
+                ("run_reason", stat.run_reason.as_str()),
+                ("status", stat.status.as_str()),
+            ],
+            stat.count as f64,
+        );
+    }
+
+    out.family(
+        "execution_duration_seconds",
+        "summary",
+        "Run time of finished execution processes",
+    );
+    for stat in execution_stats.iter().filter(|s| s.status != "running") {
+        let labels = [
+            ("executor", executor_label(stat.executor.as_deref())),
+            ("run_reason", stat.run_reason.as_str()),
+            ("status", stat.status.as_str()),
+        ];
+        out.sample(
+            "execution_duration_seconds_sum",
+            &labels,
+            stat.duration_seconds,
+        );
+        out.sample(
+            "execution_duration_seconds_count",
+            &labels,
+            stat.count as f64,
+        );
+    }
+
+    let approval_stats = ApprovalRecord::wait_stats(pool).await?;
+    out.family(
+        "approvals_pending",
+        "gauge",
+        "Tool calls waiting for approval",
+    );
+    out.sample(
+        "approvals_pending",
+        &[],
+        approval_stats
+            .iter()
+            .filter(|s| s.status == "pending")
+            .map(|s| s.count)
+            .sum::<i64>() as f64,
+    );
+    out.family(
+        "approval_wait_seconds",
+        "summary",
+        "Time from approval request to decision",
+    );
+    for stat in approval_stats.iter().filter(|s| s.status != "pending") {
+        let labels = [
+            ("status", stat.status.as_str()),
+            (
+                "decided_by",
+                stat.decided_by.as_deref().unwrap_or("unknown"),
+            ),
+        ];
+        out.sample("approval_wait_seconds_sum", &labels, stat.wait_seconds);
+        out.sample("approval_wait_seconds_count", &labels, stat.count as f64);
+    }
+
+    out.family(
+        "diff_stream_subscribers",
+        "gauge",
+        "Open diff streams watching a worktree",
+    );
+    out.sample(
+        "diff_stream_subscribers",
+        &[],
+        diff_stream::active_streams() as f64,
+    );
+
+    let (execution_stores, execution_bytes) = {
+        let stores = sources.execution_msg_stores.read().await;
+        let bytes: usize = stores.values().map(|s| s.history_bytes()).sum();
+        (stores.len(), bytes)
+    };
+    out.family(
+        "msg_stores",
+        "gauge",
+        "In-memory log stores held for execution processes",
+    );
+    out.sample("msg_stores", &[], execution_stores as f64);
+    out.family(
+        "msg_store_bytes",
+        "gauge",
+        "Bytes of history retained in in-memory log stores",
+    );
+    out.sample(
+        "msg_store_bytes",
+        &[("store", "executions")],
+        execution_bytes as f64,
+    );
+    out.sample(
+        "msg_store_bytes",
+        &[("store", "events")],
+        sources.events_msg_store.history_bytes() as f64,
+    );
+
+    let usage = worktree_usage().await;
+    out.family("workspaces", "gauge", "Workspace directories on disk");
+    out.sample("workspaces", &[], usage.workspaces as f64);
+    out.family("worktrees", "gauge", "Git worktrees on disk");
+    out.sample("worktrees", &[], usage.worktrees as f64);
+    out.family(
+        "worktree_disk_bytes",
+        "gauge",
+        "Disk space used by workspaces, refreshed at most every five minutes",
+    );
+    out.sample("worktree_disk_bytes", &[], usage.bytes as f64);
+
+    let pr = pr_monitor();
+    out.family(
+        "pr_monitor_polls_total",
+        "counter",
+        "PR monitor poll cycles",
+    );
+    out.sample(
+        "pr_monitor_polls_total",
+        &[("result", "ok")],
+        pr.polls_ok.load(Ordering::Relaxed) as f64,
+    );
+    out.sample(
+        "pr_monitor_polls_total",
+        &[("result", "error")],
+        pr.polls_failed.load(Ordering::Relaxed) as f64,
+    );
+    out.family(
+        "pr_monitor_checks_total",
+        "counter",
+        "Individual PR status checks by result",
+    );
+    for (result, counter) in [
+        ("open", &pr.checks_open),
+        ("merged", &pr.checks_merged),
+        ("closed", &pr.checks_closed),
+        ("unknown", &pr.checks_unknown),
+        ("error", &pr.checks_failed),
+    ] {
+        out.sample(
+            "pr_monitor_checks_total",
+            &[("result", result)],
+            counter.load(Ordering::Relaxed) as f64,
+        );
+    }
+    out.family(
+        "pr_monitor_open_prs",
+        "gauge",
+        "Open PRs seen by the last poll",
+    );
+    out.sample(
+        "pr_monitor_open_prs",
+        &[],
+        pr.open_prs.load(Ordering::Relaxed) as f64,
+    );
+    out.family(
+        "pr_monitor_last_poll_timestamp_seconds",
+        "gauge",
+        "Unix time of the last PR monitor poll",
+    );
+    out.sample(
+        "pr_monitor_last_poll_timestamp_seconds",
+        &[],
+        pr.last_poll_timestamp.load(Ordering::Relaxed) as f64,
+    );
+
+    Ok(out.finish())
+}
+
+fn executor_label(executor: Option<&str>) -> &str {
+    executor.filter(|e| !e.is_empty()).unwrap_or("none")
+}
+
+async fn worktree_usage() -> WorktreeUsage {
+    static CACHE: OnceLock<Mutex<Option<(Instant, WorktreeUsage)>>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(|| Mutex::new(None)).lock().await;
+    if let Some((measured_at, usage)) = *cache
+        && measured_at.elapsed() < WORKTREE_USAGE_TTL
+    {
+        return usage;
+    }
+
+    let base_dir = WorkspaceManager::get_workspace_base_dir();
+    let usage = match tokio::task::spawn_blocking(move || measure_worktrees(&base_dir)).await {
+        Ok(usage) => usage,
+        Err(e) => {
+            tracing::warn!("Failed to measure worktree disk usage: {}", e);
+            (*cache).map(|(_, usage)| usage).unwrap_or_default()
+        }
+    };
+    *cache = Some((Instant::now(), usage));
+    usage
+}
+
+/// Workspaces are the directories under the base dir; each holds one worktree per repo
+fn measure_worktrees(base_dir: &Path) -> WorktreeUsage {
+    let mut usage = WorktreeUsage::default();
+    let Ok(entries) = std::fs::read_dir(base_dir) else {
+        return usage;
+    };
+    for workspace in entries.flatten() {
+        let path = workspace.path();
+        if !path.is_dir() {
+            continue;
+        }
+        usage.workspaces += 1;
+        if let Ok(repos) = std::fs::read_dir(&path) {
+            usage.worktrees += repos
+                .flatten()
+                .filter(|repo| repo.path().join(".git").exists())
+                .count() as u64;
+        }
+        usage.bytes += dir_size(&path);
+    }
+    usage
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(ft) if ft.is_dir() => dir_size(&entry.path()),
+            // Symlinks are not followed
+            Ok(ft) if ft.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+#[derive(Default)]
+struct MetricsWriter {
+    out: String,
+}
+
+impl MetricsWriter {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {PREFIX}_{name} {help}");
+        let _ = writeln!(self.out, "# TYPE {PREFIX}_{name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.out, "{PREFIX}_{name}");
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(self.out, "{{{labels}}}");
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_writer_formats_families_and_labels() {
+        let mut out = MetricsWriter::default();
+        out.family("executions_running", "gauge", "Running");
+        out.sample(
+            "executions_running",
+            &[("executor", "CLAUDE_CODE"), ("run_reason", "codingagent")],
+            2.0,
+        );
+        out.sample("executions_running", &[], 0.5);
+
+        assert_eq!(
+            out.finish(),
+            "# HELP vibe_kanban_executions_running Running\n\
+             # TYPE vibe_kanban_executions_running gauge\n\
+             vibe_kanban_executions_running{executor=\"CLAUDE_CODE\",run_reason=\"codingagent\"} 2\n\
+             vibe_kanban_executions_running 0.5\n"
+        );
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\"b\\c\nd"), r#"a\"b\\c\nd"#);
+    }
+
+    #[test]
+    fn test_measure_worktrees() {
+        let base = tempfile::tempdir().unwrap();
+        let worktree = base.path().join("ws-1").join("repo");
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(worktree.join(".git"), "gitdir: /elsewhere").unwrap();
+        std::fs::write(worktree.join("file.txt"), vec![0u8; 100]).unwrap();
+        std::fs::create_dir_all(base.path().join("ws-2")).unwrap();
+
+        let usage = measure_worktrees(base.path());
+        assert_eq!(usage.workspaces, 2);
+        assert_eq!(usage.worktrees, 1);
+        assert_eq!(usage.bytes, 100 + "gitdir: /elsewhere".len() as u64);
+    }
+}
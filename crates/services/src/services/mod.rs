@@ -1,6 +1,8 @@
 pub mod analytics;
 pub mod approvals;
+pub mod archival;
 pub mod auth;
+pub mod backup;
 pub mod config;
 pub mod container;
 pub mod diff_stream;
@@ -12,13 +14,21 @@ pub mod filesystem_watcher;
 pub mod git;
 pub mod github;
 pub mod image;
+pub mod metrics;
 pub mod notification;
 pub mod oauth_credentials;
+pub mod plan_sync;
+pub mod plans;
 pub mod pr_monitor;
 pub mod project;
+pub mod project_archive;
 pub mod queued_message;
 pub mod remote_client;
 pub mod repo;
 pub mod share;
+pub mod task_bulk;
+pub mod task_schedule;
+pub mod transcript;
+pub mod webhooks;
 pub mod workspace_manager;
 pub mod worktree_manager;
@@ -3,12 +3,18 @@ use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 use utils;
 
-use crate::services::config::{Config, NotificationConfig, SoundFile};
+use crate::services::config::{
+    Config, NotificationChannelConfig, NotificationConfig, NotificationEvent, SoundFile,
+};
 
-/// Service for handling cross-platform notifications including sound alerts and push notifications
+pub mod channels;
+
+/// Service for handling cross-platform notifications including sound alerts and push notifications,
+/// plus any remote channels configured in `notification_channels`
 #[derive(Debug, Clone)]
 pub struct NotificationService {
     config: Arc<RwLock<Config>>,
+    http: reqwest::Client,
 }
 
 /// Cache for WSL root path from PowerShell
@@ -16,13 +22,56 @@ static WSL_ROOT_PATH_CACHE: OnceLock<Option<String>> = OnceLock::new();
 
 impl NotificationService {
     pub fn new(config: Arc<RwLock<Config>>) -> Self {
-        Self { config }
+        Self {
+            config,
+            http: reqwest::Client::new(),
+        }
     }
 
-    /// Send both sound and push notifications if enabled
-    pub async fn notify(&self, title: &str, message: &str) {
-        let config = self.config.read().await.notifications.clone();
+    /// Send sound and push notifications if enabled, and forward `event` to its channels
+    pub async fn notify(&self, event: NotificationEvent, title: &str, message: &str) {
+        let (config, channels) = {
+            let config = self.config.read().await;
+            (
+                config.notifications.clone(),
+                config.notification_channels.clone(),
+            )
+        };
         Self::send_notification(&config, title, message).await;
+        self.send_to_channels(channels, event, title, message);
+    }
+
+    /// Forward `event` to its channels only, without a local sound or desktop push
+    pub async fn notify_channels(&self, event: NotificationEvent, title: &str, message: &str) {
+        let channels = self.config.read().await.notification_channels.clone();
+        self.send_to_channels(channels, event, title, message);
+    }
+
+    /// Deliver to every channel routed for `event` in the background
+    fn send_to_channels(
+        &self,
+        channels: Vec<NotificationChannelConfig>,
+        event: NotificationEvent,
+        title: &str,
+        message: &str,
+    ) {
+        for channel in channels.into_iter().filter(|c| c.routes(event)) {
+            let http = self.http.clone();
+            let title = title.to_string();
+            let message = message.to_string();
+            tokio::spawn(async move {
+                if let Err(e) =
+                    channels::send(&http, &channel.channel, event, &title, &message).await
+                {
+                    tracing::warn!(
+                        "Failed to send {} notification to channel '{}': {}",
+                        event,
+                        channel.name,
+                        e
+                    );
+                }
+            });
+        }
     }
 
     /// Internal method to send notifications with a given config
@@ -0,0 +1,271 @@
+//! Remote notification channels. Each configured channel receives the events routed to it,
+//! so notifications still arrive when the server runs headless on another machine.
+
+use std::time::Duration;
+
+use backon::Retryable;
+use chrono::Utc;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    message::{Mailbox, header::ContentType},
+    transport::smtp::authentication::Credentials,
+};
+use serde_json::{Value, json};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::services::{
+    config::{NotificationChannelKind, NotificationEvent, SmtpSecurity},
+    webhooks,
+};
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum NotificationChannelError {
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Webhook(#[from] webhooks::SendError),
+    #[error("invalid email address: {0}")]
+    Address(#[from] lettre::address::AddressError),
+    #[error(transparent)]
+    Email(#[from] lettre::error::Error),
+    #[error(transparent)]
+    Smtp(#[from] lettre::transport::smtp::Error),
+    #[error("email channel has no recipients")]
+    NoRecipients,
+}
+
+/// Deliver one notification to `channel`
+pub async fn send(
+    http: &reqwest::Client,
+    channel: &NotificationChannelKind,
+    event: NotificationEvent,
+    title: &str,
+    message: &str,
+) -> Result<(), NotificationChannelError> {
+    match channel {
+        NotificationChannelKind::Webhook { url, secret } => {
+            let body = webhook_body(event, title, message).to_string();
+            let event = format!("notification.{event}");
+            let delivery_id = Uuid::new_v4();
+            (|| webhooks::send(http, url, secret.as_deref(), &event, delivery_id, &body))
+                .retry(webhooks::retry_policy())
+                .when(webhooks::SendError::should_retry)
+                .await?;
+            Ok(())
+        }
+        NotificationChannelKind::Slack { webhook_url } => {
+            post_json(http, webhook_url, &slack_body(title, message)).await
+        }
+        NotificationChannelKind::Discord { webhook_url } => {
+            post_json(http, webhook_url, &discord_body(title, message)).await
+        }
+        NotificationChannelKind::Ntfy {
+            server_url,
+            topic,
+            token,
+        } => {
+            // JSON publishing keeps non-ASCII titles out of HTTP headers
+            let mut request = http
+                .post(server_url.trim_end_matches('/'))
+                .timeout(SEND_TIMEOUT)
+                .json(&ntfy_body(topic, event, title, message));
+            if let Some(token) = token.as_deref().filter(|t| !t.is_empty()) {
+                request = request.bearer_auth(token);
+            }
+            request.send().await?.error_for_status()?;
+            Ok(())
+        }
+        NotificationChannelKind::Email {
+            smtp_host,
+            smtp_port,
+            security,
+            username,
+            password,
+            from,
+            to,
+        } => {
+            if to.is_empty() {
+                return Err(NotificationChannelError::NoRecipients);
+            }
+            let mut builder = Message::builder()
+                .from(from.parse::<Mailbox>()?)
+                .subject(title);
+            for recipient in to {
+                builder = builder.to(recipient.parse::<Mailbox>()?);
+            }
+            let email = builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.to_string())?;
+
+            let mut transport = match security {
+                SmtpSecurity::StartTls => {
+                    AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(smtp_host)?
+                }
+                SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(smtp_host)?,
+                SmtpSecurity::None => {
+                    AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(smtp_host)
+                }
+            }
+            .port(*smtp_port)
+            .timeout(Some(SEND_TIMEOUT));
+            if let Some(username) = username {
+                transport = transport.credentials(Credentials::new(
+                    username.clone(),
+                    password.clone().unwrap_or_default(),
+                ));
+            }
+
+            transport.build().send(email).await?;
+            Ok(())
+        }
+    }
+}
+
+async fn post_json(
+    http: &reqwest::Client,
+    url: &str,
+    body: &Value,
+) -> Result<(), NotificationChannelError> {
+    http.post(url)
+        .timeout(SEND_TIMEOUT)
+        .json(body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+fn webhook_body(event: NotificationEvent, title: &str, message: &str) -> Value {
+    json!({
+        "event": event,
+        "title": title,
+        "message": message,
+        "sent_at": Utc::now(),
+    })
+}
+
+fn slack_body(title: &str, message: &str) -> Value {
+    json!({ "text": format!("*{title}*\n{message}") })
+}
+
+fn discord_body(title: &str, message: &str) -> Value {
+    json!({ "content": format!("**{title}**\n{message}") })
+}
+
+fn ntfy_body(topic: &str, event: NotificationEvent, title: &str, message: &str) -> Value {
+    let tag = match event {
+        NotificationEvent::TaskComplete => "white_check_mark",
+        NotificationEvent::TaskFailed => "x",
+        NotificationEvent::ApprovalPending => "warning",
+        NotificationEvent::PrMerged => "tada",
+    };
+    json!({
+        "topic": topic,
+        "title": title,
+        "message": message,
+        "tags": [tag],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::services::config::NotificationChannelConfig;
+
+    #[test]
+    fn test_channel_config_round_trip_and_routing() {
+        let channel: NotificationChannelConfig = serde_json::from_value(json!({
+            "name": "phone",
+            "type": "ntfy",
+            "server_url": "https://ntfy.sh",
+            "topic": "vk-builds",
+            "events": ["task_failed", "approval_pending"],
+        }))
+        .unwrap();
+
+        assert!(channel.enabled);
+        assert!(channel.routes(NotificationEvent::TaskFailed));
+        assert!(!channel.routes(NotificationEvent::TaskComplete));
+        assert!(matches!(
+            channel.channel,
+            NotificationChannelKind::Ntfy { ref token, .. } if token.is_none()
+        ));
+
+        let value = serde_json::to_value(&channel).unwrap();
+        assert_eq!(value["type"], "ntfy");
+        assert_eq!(value["topic"], "vk-builds");
+    }
+
+    #[test]
+    fn test_events_default_to_all() {
+        let channel: NotificationChannelConfig = serde_json::from_value(json!({
+            "name": "team",
+            "type": "slack",
+            "webhook_url": "https://hooks.slack.com/services/x",
+        }))
+        .unwrap();
+
+        assert!(channel.routes(NotificationEvent::TaskComplete));
+        assert!(channel.routes(NotificationEvent::PrMerged));
+    }
+
+    #[test]
+    fn test_message_bodies() {
+        assert_eq!(slack_body("Done", "ok")["text"], "*Done*\nok");
+        assert_eq!(discord_body("Done", "ok")["content"], "**Done**\nok");
+        assert_eq!(
+            webhook_body(
+                NotificationEvent::ApprovalPending,
+                "Approval Needed",
+                "Bash"
+            )["event"],
+            "approval_pending"
+        );
+        let ntfy = ntfy_body("topic", NotificationEvent::TaskFailed, "Failed", "boom");
+        assert_eq!(ntfy["topic"], "topic");
+        assert_eq!(ntfy["tags"][0], "x");
+    }
+
+    #[tokio::test]
+    async fn test_webhook_channel_is_signed_like_subscriptions() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 16 * 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 204 No Content\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let channel = NotificationChannelKind::Webhook {
+            url,
+            secret: Some("secret".to_string()),
+        };
+        send(
+            &reqwest::Client::new(),
+            &channel,
+            NotificationEvent::TaskComplete,
+            "Done",
+            "ok",
+        )
+        .await
+        .unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.contains("x-vibe-kanban-event: notification.task_complete"));
+        assert!(request.contains("x-vibe-kanban-delivery: "));
+        assert!(request.contains("x-vibe-kanban-signature: sha256="));
+    }
+}
@@ -0,0 +1,388 @@
+//! Two-way sync between imported plans and their tasks. A task's status change is written back
+//! to its phase in the plan's markdown; edits under a project's `plans/` directory update the
+//! linked tasks and create tasks for phases added to an already imported plan.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use db::{
+    DBService,
+    models::{
+        plan_task_link::{CreatePlanTaskLink, PlanTaskLink},
+        project::Project,
+        project_repo::ProjectRepo,
+        task::{CreateTask, Task, TaskWithAttemptStatus},
+    },
+};
+use futures::StreamExt;
+use json_patch::PatchOperation;
+use sqlx::SqlitePool;
+use thiserror::Error;
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+use crate::services::{
+    filesystem_watcher,
+    plans::{
+        PLANS_DIR, PlanError, PlanMetadata, PlanPhaseDetail, plan_status, scan_plan, task_status,
+        write_frontmatter_status, write_phase_status,
+    },
+};
+
+#[derive(Debug, Error)]
+pub enum PlanSyncError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Plan(#[from] PlanError),
+    #[error(transparent)]
+    Join(#[from] tokio::task::JoinError),
+}
+
+#[derive(Clone)]
+pub struct PlanSyncService {
+    db: DBService,
+    /// Keyed by the watched `plans/` directory
+    watchers: Arc<Mutex<HashMap<PathBuf, JoinHandle<()>>>>,
+}
+
+impl PlanSyncService {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            watchers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Create the task for a plan phase, or for the whole plan when `phase` is `None`, and
+    /// record the link between them
+    pub async fn import_task(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        plan: &PlanMetadata,
+        phase: Option<&PlanPhaseDetail>,
+    ) -> Result<Task, sqlx::Error> {
+        let (description, status) = match phase {
+            Some(phase) => (
+                Some(format!("Plan: {}\nPhase file: {}", plan.name, phase.file)),
+                phase.status.clone(),
+            ),
+            None => (plan.description.clone(), plan.status.clone()),
+        };
+        let title = task_title(plan, phase);
+        let create_task = CreateTask {
+            project_id,
+            title: title.clone(),
+            description,
+            status: Some(task_status(&status)),
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            labels: None,
+            parent_workspace_id: None,
+            image_ids: None,
+            shared_task_id: None,
+        };
+        let task = Task::create(pool, &create_task, Uuid::new_v4()).await?;
+
+        PlanTaskLink::create(
+            pool,
+            &CreatePlanTaskLink {
+                task_id: task.id,
+                project_id,
+                plan_id: plan.id.clone(),
+                plan_path: plan.path.clone(),
+                phase: phase.map(|p| i64::from(p.phase)),
+                phase_file: phase.map(|p| p.file.clone()),
+                synced_status: status,
+                synced_title: title,
+            },
+        )
+        .await?;
+        Ok(task)
+    }
+
+    /// Start syncing: write task status changes back to plans, and watch the plans of every
+    /// repository of each project that has imported one
+    pub fn spawn(&self, events: Arc<MsgStore>) -> JoinHandle<()> {
+        let service = self.clone();
+        tokio::spawn(async move {
+            match PlanTaskLink::find_project_ids(&service.db.pool).await {
+                Ok(project_ids) => {
+                    for project_id in project_ids {
+                        match ProjectRepo::find_repos_for_project(&service.db.pool, project_id)
+                            .await
+                        {
+                            Ok(repos) => {
+                                for repo in repos {
+                                    service.watch_project(project_id, &repo.path);
+                                }
+                            }
+                            Err(e) => tracing::error!(
+                                "Failed to load repositories of project {} for plan sync: {}",
+                                project_id,
+                                e
+                            ),
+                        }
+                    }
+                }
+                Err(e) => tracing::error!("Failed to load projects with imported plans: {}", e),
+            }
+
+            service.listen_for_task_updates(events).await;
+        })
+    }
+
+    /// Watch `<repo_root>/plans` for edits. Does nothing if the directory is already watched.
+    pub fn watch_project(&self, project_id: Uuid, repo_root: &Path) {
+        let plans_dir = repo_root.join(PLANS_DIR);
+        if !plans_dir.is_dir() {
+            return;
+        }
+
+        let mut watchers = self.watchers.lock().unwrap();
+        if watchers
+            .get(&plans_dir)
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            return;
+        }
+        let service = self.clone();
+        let watch_dir = plans_dir.clone();
+        watchers.insert(
+            plans_dir,
+            tokio::spawn(async move { service.watch_plans_dir(project_id, watch_dir).await }),
+        );
+    }
+
+    async fn watch_plans_dir(&self, project_id: Uuid, plans_dir: PathBuf) {
+        let watch_dir = plans_dir.clone();
+        let watcher =
+            tokio::task::spawn_blocking(move || filesystem_watcher::async_watcher(watch_dir)).await;
+        let (debouncer, mut watcher_rx, canonical_plans_dir) = match watcher {
+            Ok(Ok(parts)) => parts,
+            Ok(Err(e)) => {
+                tracing::error!("Failed to watch {}: {}", plans_dir.display(), e);
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Failed to spawn watcher for {}: {}", plans_dir.display(), e);
+                return;
+            }
+        };
+        let _debouncer_guard = debouncer;
+
+        while let Some(result) = watcher_rx.next().await {
+            let events = match result {
+                Ok(events) => events,
+                Err(errors) => {
+                    for e in errors {
+                        tracing::warn!("Plan watcher error in {}: {}", plans_dir.display(), e);
+                    }
+                    continue;
+                }
+            };
+
+            let plan_ids: HashSet<String> = events
+                .iter()
+                .flat_map(|event| event.paths.iter())
+                .filter_map(|path| {
+                    let relative = path.strip_prefix(&canonical_plans_dir).ok()?;
+                    Some(
+                        relative
+                            .components()
+                            .next()?
+                            .as_os_str()
+                            .to_string_lossy()
+                            .to_string(),
+                    )
+                })
+                .collect();
+            for plan_id in plan_ids {
+                if let Err(e) = self.sync_plan(project_id, &plans_dir.join(&plan_id)).await {
+                    tracing::warn!("Failed to sync plan '{}' to tasks: {}", plan_id, e);
+                }
+            }
+        }
+    }
+
+    /// Bring the tasks of an imported plan in line with its markdown. Plans that were never
+    /// imported, and plans of archived projects, are left alone.
+    async fn sync_plan(&self, project_id: Uuid, dir: &Path) -> Result<(), PlanSyncError> {
+        let pool = &self.db.pool;
+        match Project::find_by_id(pool, project_id).await? {
+            Some(project) if project.archived_at.is_none() => {}
+            _ => return Ok(()),
+        }
+        let plan_id = dir
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        // Another repository of the project may hold a plan with the same directory name
+        let links: Vec<_> = PlanTaskLink::find_by_plan(pool, project_id, &plan_id)
+            .await?
+            .into_iter()
+            .filter(|link| Path::new(&link.plan_path).starts_with(dir))
+            .collect();
+        if links.is_empty() {
+            return Ok(());
+        }
+
+        let scan_dir = dir.to_path_buf();
+        let Some(plan) = tokio::task::spawn_blocking(move || scan_plan(&scan_dir)).await?? else {
+            return Ok(());
+        };
+
+        if plan.phase_details.is_empty() {
+            if let Some(link) = links.iter().find(|link| link.phase_file.is_none()) {
+                self.sync_task(link, task_title(&plan, None), &plan.status)
+                    .await?;
+            }
+            return Ok(());
+        }
+
+        for phase in &plan.phase_details {
+            match links
+                .iter()
+                .find(|link| link.phase_file.as_deref() == Some(phase.file.as_str()))
+            {
+                Some(link) => {
+                    self.sync_task(link, task_title(&plan, Some(phase)), &phase.status)
+                        .await?
+                }
+                None => {
+                    let task = Self::import_task(pool, project_id, &plan, Some(phase)).await?;
+                    tracing::info!(
+                        "Created task {} for new phase {} of plan '{}'",
+                        task.id,
+                        phase.phase,
+                        plan.name
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply a plan-side title or status change to the linked task. Only what changed in the
+    /// plan since the last sync is written, so a task renamed on the board keeps its title.
+    async fn sync_task(
+        &self,
+        link: &PlanTaskLink,
+        title: String,
+        status: &str,
+    ) -> Result<(), PlanSyncError> {
+        let pool = &self.db.pool;
+        let status_changed = link.synced_status != status;
+        let title_changed = link.synced_title != title;
+        if !status_changed && !title_changed {
+            return Ok(());
+        }
+        let Some(task) = Task::find_by_id(pool, link.task_id).await? else {
+            return Ok(());
+        };
+        if task.archived_at.is_some() {
+            return Ok(());
+        }
+
+        // Record the change first so the resulting task update isn't written back to the plan
+        PlanTaskLink::set_synced(pool, link.id, status, &title).await?;
+        let new_status = if status_changed {
+            task_status(status)
+        } else {
+            task.status.clone()
+        };
+        let new_title = if title_changed {
+            title
+        } else {
+            task.title.clone()
+        };
+        Task::update(
+            pool,
+            &Task {
+                title: new_title,
+                status: new_status,
+                ..task
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Watch the events stream for task patches and write status changes of linked tasks back
+    /// to their plans
+    async fn listen_for_task_updates(&self, events: Arc<MsgStore>) {
+        let mut receiver = events.get_receiver();
+        loop {
+            let patch = match receiver.recv().await {
+                Ok(LogMsg::JsonPatch(patch)) => patch,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Plan sync task listener skipped {} events", skipped);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            for op in patch.0 {
+                let (path, value) = match op {
+                    PatchOperation::Add(op) => (op.path, op.value),
+                    PatchOperation::Replace(op) => (op.path, op.value),
+                    _ => continue,
+                };
+                if !path.as_str().starts_with("/tasks/") {
+                    continue;
+                }
+                let Ok(task) = serde_json::from_value::<TaskWithAttemptStatus>(value) else {
+                    continue;
+                };
+                if let Err(e) = self.write_task_status(&task.task).await {
+                    tracing::warn!("Failed to write status of task {} to plan: {}", task.id, e);
+                }
+            }
+        }
+    }
+
+    async fn write_task_status(&self, task: &Task) -> Result<(), PlanSyncError> {
+        let pool = &self.db.pool;
+        let Some(link) = PlanTaskLink::find_by_task_id(pool, task.id).await? else {
+            return Ok(());
+        };
+        let status = plan_status(&task.status);
+        if link.synced_status == status {
+            return Ok(());
+        }
+
+        // Record the status first so the plan watcher doesn't echo the edit back to the task
+        PlanTaskLink::set_synced_status(pool, link.id, status).await?;
+        let plan_path = PathBuf::from(&link.plan_path);
+        let phase_file = link.phase_file.map(PathBuf::from);
+        let written = tokio::task::spawn_blocking(move || match phase_file {
+            Some(phase_file) => write_phase_status(&plan_path, &phase_file, status),
+            None => write_frontmatter_status(&plan_path, status),
+        })
+        .await??;
+        if written {
+            tracing::debug!(
+                "Wrote status '{}' of task {} to plan '{}'",
+                status,
+                task.id,
+                link.plan_id
+            );
+        }
+        Ok(())
+    }
+}
+
+/// "Plan title - Phase 2: OAuth", or the plan title for a plan imported as one task
+pub fn task_title(plan: &PlanMetadata, phase: Option<&PlanPhaseDetail>) -> String {
+    let plan_title = plan.title.clone().unwrap_or_else(|| plan.name.clone());
+    match phase {
+        Some(phase) => format!("{} - Phase {}: {}", plan_title, phase.phase, phase.name),
+        None => plan_title,
+    }
+}
@@ -0,0 +1,991 @@
+//! Markdown plan scanner. Every directory under a project's `plans/` that contains a `plan.md`
+//! is a plan; the phase files linked from `plan.md` (or, failing that, the `phase-*.md` files
+//! next to it) are its phases.
+//!
+//! ```markdown
+//! ---
+//! title: Authentication
+//! status: in-progress
+//! priority: P1
+//! branch: feat/auth
+//! tags: [backend, security]
+//! ---
+//! # Authentication
+//!
+//! | Phase | Name  | Status    | File                            |
+//! |-------|-------|-----------|---------------------------------|
+//! | 1     | Setup | Completed | [Setup](./phase-01-setup.md)    |
+//! | 2     | OAuth | Pending   | [OAuth](./phase-02-oauth.md)    |
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::SystemTime,
+};
+
+use chrono::{DateTime, Utc};
+use db::models::task::TaskStatus;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use ts_rs::TS;
+
+pub const PLANS_DIR: &str = "plans";
+pub const PLAN_FILE: &str = "plan.md";
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_IN_PROGRESS: &str = "in-progress";
+pub const STATUS_IN_REVIEW: &str = "in-review";
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_CANCELLED: &str = "cancelled";
+
+static PHASE_LINK: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]*)\]\(([^)\s]+\.md)\)").expect("valid regex"));
+static CHECKBOX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*(?:[-*+]|\d+\.)\s+\[([ xX])\]").expect("valid regex"));
+static PHASE_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)phase[\s\-_]*0*(\d+)").expect("valid regex"));
+static PHASE_PREFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)^phase[\s\-_]*\d+\s*[:.\-–—]?\s*").expect("valid regex"));
+
+#[derive(Debug, Error)]
+pub enum PlanError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Plans directory {0} is not a directory")]
+    NotADirectory(PathBuf),
+}
+
+/// Plan phase progress from scanner
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanPhaseProgress {
+    pub total: u32,
+    pub completed: u32,
+    #[serde(rename = "inProgress")]
+    pub in_progress: u32,
+    pub pending: u32,
+    pub percentage: u32,
+}
+
+/// Individual phase detail from plan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanPhaseDetail {
+    pub phase: u32,
+    pub name: String,
+    pub status: String,
+    pub file: String,
+    #[serde(rename = "linkText")]
+    pub link_text: Option<String>,
+}
+
+/// Plan metadata from scanner output
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PlanMetadata {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub directory: String,
+    pub phases: PlanPhaseProgress,
+    #[serde(rename = "phaseDetails", default)]
+    pub phase_details: Vec<PlanPhaseDetail>,
+    pub progress: u32,
+    #[serde(rename = "lastModified")]
+    pub last_modified: String,
+    pub status: String,
+    pub description: Option<String>,
+    pub priority: Option<String>,
+    pub branch: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub title: Option<String>,
+}
+
+/// Scan `<project_root>/plans`. A project without a plans directory has no plans.
+pub fn scan_plans(project_root: &Path) -> Result<Vec<PlanMetadata>, PlanError> {
+    let plans_dir = project_root.join(PLANS_DIR);
+    if !plans_dir.exists() {
+        return Ok(Vec::new());
+    }
+    if !plans_dir.is_dir() {
+        return Err(PlanError::NotADirectory(plans_dir));
+    }
+
+    let mut plans = Vec::new();
+    for entry in fs::read_dir(&plans_dir)? {
+        let dir = entry?.path();
+        if !dir.is_dir() {
+            continue;
+        }
+        match scan_plan(&dir) {
+            Ok(Some(plan)) => plans.push(plan),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Skipping plan {}: {}", dir.display(), e),
+        }
+    }
+    plans.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(plans)
+}
+
+/// Scan one plan directory; `None` when it has no `plan.md`
+pub fn scan_plan(dir: &Path) -> Result<Option<PlanMetadata>, PlanError> {
+    let plan_path = dir.join(PLAN_FILE);
+    if !plan_path.is_file() {
+        return Ok(None);
+    }
+    let id = dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let content = fs::read_to_string(&plan_path)?;
+    let (frontmatter, body) = Frontmatter::parse(&content);
+
+    let mut phase_details = parse_phase_links(dir, body);
+    if phase_details.is_empty() {
+        phase_details = discover_phase_files(dir)?;
+    }
+
+    // Phases without a status in plan.md fall back to their own frontmatter
+    let mut last_modified = modified_at(&plan_path);
+    for phase in &mut phase_details {
+        let phase_path = Path::new(&phase.file);
+        last_modified = last_modified.max(modified_at(phase_path));
+        if phase.status.is_empty() {
+            phase.status = fs::read_to_string(phase_path)
+                .ok()
+                .and_then(|content| {
+                    Frontmatter::parse(&content)
+                        .0
+                        .scalar("status")
+                        .and_then(normalize_status)
+                })
+                .unwrap_or(STATUS_PENDING)
+                .to_string();
+        }
+    }
+
+    let phases = phase_progress(&phase_details);
+    let status = frontmatter
+        .scalar("status")
+        .and_then(normalize_status)
+        .map(str::to_string)
+        .unwrap_or_else(|| derived_status(&phases).to_string());
+    let title = frontmatter
+        .scalar("title")
+        .map(str::to_string)
+        .or_else(|| first_heading(body));
+    let description = frontmatter
+        .scalar("description")
+        .map(str::to_string)
+        .or_else(|| first_paragraph(body));
+
+    Ok(Some(PlanMetadata {
+        name: id.clone(),
+        id,
+        path: plan_path.to_string_lossy().to_string(),
+        directory: dir.to_string_lossy().to_string(),
+        progress: phases.percentage,
+        phases,
+        phase_details,
+        last_modified: DateTime::<Utc>::from(last_modified).to_rfc3339(),
+        status,
+        description,
+        priority: frontmatter.scalar("priority").map(str::to_string),
+        branch: frontmatter.scalar("branch").map(str::to_string),
+        tags: frontmatter.list("tags"),
+        title,
+    }))
+}
+
+/// Canonical plan status for the spellings plans use, `None` when `value` isn't a status
+pub fn normalize_status(value: &str) -> Option<&'static str> {
+    let normalized = value
+        .trim()
+        .trim_matches(|c: char| c == '*' || c == '_' || c == '`')
+        .to_lowercase()
+        .replace(['_', ' '], "-");
+    let status = match normalized.as_str() {
+        "pending" | "todo" | "to-do" | "not-started" | "planned" | "⏳" => STATUS_PENDING,
+        "in-progress" | "inprogress" | "wip" | "active" | "started" | "🔄" | "🚧" => {
+            STATUS_IN_PROGRESS
+        }
+        "in-review" | "inreview" | "review" => STATUS_IN_REVIEW,
+        "completed" | "complete" | "done" | "✅" => STATUS_COMPLETED,
+        "cancelled" | "canceled" | "skipped" | "❌" => STATUS_CANCELLED,
+        _ => return None,
+    };
+    Some(status)
+}
+
+/// Task status for a plan status
+pub fn task_status(plan_status: &str) -> TaskStatus {
+    match normalize_status(plan_status) {
+        Some(STATUS_IN_PROGRESS) => TaskStatus::InProgress,
+        Some(STATUS_IN_REVIEW) => TaskStatus::InReview,
+        Some(STATUS_COMPLETED) => TaskStatus::Done,
+        Some(STATUS_CANCELLED) => TaskStatus::Cancelled,
+        _ => TaskStatus::Todo,
+    }
+}
+
+/// Plan status for a task status
+pub fn plan_status(task_status: &TaskStatus) -> &'static str {
+    match task_status {
+        TaskStatus::Todo => STATUS_PENDING,
+        TaskStatus::InProgress => STATUS_IN_PROGRESS,
+        TaskStatus::InReview => STATUS_IN_REVIEW,
+        TaskStatus::Done => STATUS_COMPLETED,
+        TaskStatus::Cancelled => STATUS_CANCELLED,
+    }
+}
+
+fn phase_progress(phases: &[PlanPhaseDetail]) -> PlanPhaseProgress {
+    let count = |status: &str| phases.iter().filter(|p| p.status == status).count() as u32;
+    let total = phases.len() as u32;
+    let completed = count(STATUS_COMPLETED);
+    let in_progress = count(STATUS_IN_PROGRESS) + count(STATUS_IN_REVIEW);
+    PlanPhaseProgress {
+        total,
+        completed,
+        in_progress,
+        pending: total - completed - in_progress,
+        percentage: if total == 0 {
+            0
+        } else {
+            completed * 100 / total
+        },
+    }
+}
+
+fn derived_status(progress: &PlanPhaseProgress) -> &'static str {
+    if progress.total > 0 && progress.completed == progress.total {
+        STATUS_COMPLETED
+    } else if progress.in_progress > 0 || progress.completed > 0 {
+        STATUS_IN_PROGRESS
+    } else {
+        STATUS_PENDING
+    }
+}
+
+/// Phases from markdown links to sibling `.md` files in table rows and list items
+fn parse_phase_links(dir: &Path, body: &str) -> Vec<PlanPhaseDetail> {
+    let mut phases: Vec<PlanPhaseDetail> = Vec::new();
+    for line in body.lines() {
+        let Some(link) = PHASE_LINK.captures(line) else {
+            continue;
+        };
+        let link_text = link[1].trim().to_string();
+        let target = link[2].trim();
+        if target.contains("://") || target.ends_with(PLAN_FILE) {
+            continue;
+        }
+        let file = phase_path(dir, target).to_string_lossy().to_string();
+        if phases.iter().any(|p| p.file == file) {
+            continue;
+        }
+
+        let outside_link = line.replace(&link[0], "");
+        let cells: Vec<&str> = if line.trim_start().starts_with('|') {
+            outside_link
+                .split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let number = cells
+            .iter()
+            .find_map(|cell| cell.parse::<u32>().ok().or_else(|| phase_number(cell)))
+            .or_else(|| phase_number(&link_text))
+            .or_else(|| phase_number(target))
+            .unwrap_or(phases.len() as u32 + 1);
+
+        let status = cells
+            .iter()
+            .find_map(|cell| normalize_status(cell))
+            .or_else(|| inline_status(&outside_link))
+            .or_else(|| {
+                CHECKBOX.captures(line).map(|checkbox| match &checkbox[1] {
+                    " " => STATUS_PENDING,
+                    _ => STATUS_COMPLETED,
+                })
+            })
+            .unwrap_or_default();
+
+        let name = cells
+            .iter()
+            .filter(|cell| cell.parse::<u32>().is_err() && normalize_status(cell).is_none())
+            .map(|cell| PHASE_PREFIX.replace(cell, "").trim().to_string())
+            .find(|name| !name.is_empty())
+            .unwrap_or_else(|| phase_name(&link_text, target));
+
+        phases.push(PlanPhaseDetail {
+            phase: number,
+            name,
+            status: status.to_string(),
+            file,
+            link_text: (!link_text.is_empty()).then_some(link_text),
+        });
+    }
+    phases
+}
+
+fn phase_path(dir: &Path, target: &str) -> PathBuf {
+    dir.join(target.trim_start_matches("./"))
+}
+
+/// `phase-*.md` files next to `plan.md`, for plans that don't link their phases
+fn discover_phase_files(dir: &Path) -> Result<Vec<PlanPhaseDetail>, PlanError> {
+    let mut files: Vec<String> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with("phase") && name.ends_with(".md"))
+        .collect();
+    files.sort();
+
+    Ok(files
+        .iter()
+        .enumerate()
+        .map(|(index, file_name)| PlanPhaseDetail {
+            phase: phase_number(file_name).unwrap_or(index as u32 + 1),
+            name: phase_name("", file_name),
+            status: String::new(),
+            file: dir.join(file_name).to_string_lossy().to_string(),
+            link_text: None,
+        })
+        .collect())
+}
+
+/// `Status: done` or a trailing status word outside the link, as in list items
+fn inline_status(text: &str) -> Option<&'static str> {
+    let lower = text.to_lowercase();
+    if let Some(idx) = lower.find("status:") {
+        let value = lower[idx + "status:".len()..]
+            .split(|c: char| c == ',' || c == ')' || c == '|')
+            .next()
+            .unwrap_or_default();
+        if let Some(status) = normalize_status(value) {
+            return Some(status);
+        }
+    }
+    text.split(|c: char| c.is_whitespace() || matches!(c, '-' | '—' | '(' | ')' | '[' | ']'))
+        .rfind(|word| !word.is_empty())
+        .and_then(normalize_status)
+}
+
+/// Phase number from "Phase 2: ..." or "phase-02-oauth.md"
+fn phase_number(text: &str) -> Option<u32> {
+    PHASE_NUMBER.captures(text).and_then(|c| c[1].parse().ok())
+}
+
+/// "Phase 2: OAuth" → "OAuth"; "phase-02-oauth-flow.md" → "Oauth flow"
+fn phase_name(link_text: &str, target: &str) -> String {
+    let from_text = PHASE_PREFIX
+        .replace(link_text.trim(), "")
+        .trim()
+        .to_string();
+    if !from_text.is_empty() && !from_text.ends_with(".md") {
+        return from_text;
+    }
+
+    let stem = Path::new(target)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let words = PHASE_PREFIX.replace(&stem, "").replace(['-', '_'], " ");
+    let words = words.trim();
+    let mut chars = words.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => stem,
+    }
+}
+
+fn first_heading(body: &str) -> Option<String> {
+    body.lines()
+        .find_map(|line| line.strip_prefix("# "))
+        .map(|heading| heading.trim().to_string())
+        .filter(|heading| !heading.is_empty())
+}
+
+/// First prose line of the body, skipping headings, tables and lists
+fn first_paragraph(body: &str) -> Option<String> {
+    body.lines()
+        .map(str::trim)
+        .find(|line| {
+            !line.is_empty()
+                && !line.starts_with(['#', '|', '-', '*', '>'])
+                && !line.starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(str::to_string)
+}
+
+fn modified_at(path: &Path) -> SystemTime {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Write `status` for the phase at `phase_file` back to its plan: into the phase's row or list
+/// item in `plan.md` when that line carries a status, otherwise into the phase file's
+/// frontmatter. Returns whether a file changed.
+pub fn write_phase_status(
+    plan_path: &Path,
+    phase_file: &Path,
+    status: &str,
+) -> Result<bool, PlanError> {
+    let dir = plan_path.parent().unwrap_or(Path::new(""));
+    let content = fs::read_to_string(plan_path)?;
+
+    let mut updated = None;
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if let Some(link) = PHASE_LINK.captures(line)
+            && phase_path(dir, link[2].trim()) == phase_file
+            && let Some(new_line) = set_line_status(line, link.get(0).unwrap().range(), status)
+        {
+            updated = Some((offset..offset + line.len(), new_line));
+            break;
+        }
+        offset += line.len();
+    }
+
+    match updated {
+        Some((range, new_line)) => {
+            if content[range.clone()] == new_line {
+                return Ok(false);
+            }
+            let mut content = content;
+            content.replace_range(range, &new_line);
+            fs::write(plan_path, content)?;
+            Ok(true)
+        }
+        None => write_frontmatter_status(phase_file, status),
+    }
+}
+
+/// Set the `status` key in the frontmatter of `path`, adding frontmatter when there is none.
+/// Returns whether the file changed.
+pub fn write_frontmatter_status(path: &Path, status: &str) -> Result<bool, PlanError> {
+    let content = fs::read_to_string(path)?;
+    let Some((block, _)) = split_frontmatter(&content) else {
+        fs::write(path, format!("---\nstatus: {status}\n---\n{content}"))?;
+        return Ok(true);
+    };
+    let block_start = if content.starts_with("---\r\n") { 5 } else { 4 };
+
+    let mut edit = None;
+    let mut offset = block_start;
+    for line in block.split_inclusive('\n') {
+        if let Some((key, value)) = line.split_once(':')
+            && key.trim().eq_ignore_ascii_case("status")
+        {
+            let value = unquote(value.trim());
+            if normalize_status(value) == Some(status) {
+                return Ok(false);
+            }
+            let eol = &line[line.trim_end_matches(['\r', '\n']).len()..];
+            let new_line = format!("{}: {}{}", key, status_label(status, value), eol);
+            edit = Some((offset..offset + line.len(), new_line));
+            break;
+        }
+        offset += line.len();
+    }
+    let (range, new_line) = edit.unwrap_or_else(|| (offset..offset, format!("status: {status}\n")));
+
+    let mut content = content;
+    content.replace_range(range, &new_line);
+    fs::write(path, content)?;
+    Ok(true)
+}
+
+/// `line` with its status set to `status`, `None` when the line has nowhere to put one.
+/// Table rows update their status cell; list items update a `Status:` note or trailing status
+/// word after the link and their checkbox.
+fn set_line_status(line: &str, link: Range<usize>, status: &str) -> Option<String> {
+    if line.trim_start().starts_with('|') {
+        let mut cell_start = 0;
+        for cell in line.split('|') {
+            let range = cell_start..cell_start + cell.len();
+            cell_start = range.end + 1;
+            let value = cell.trim();
+            let holds_link = range.start <= link.start && link.end <= range.end;
+            if holds_link || normalize_status(value).is_none() {
+                continue;
+            }
+            let value_start = range.start + cell.len() - cell.trim_start().len();
+            let mut updated = line.to_string();
+            updated.replace_range(
+                value_start..value_start + value.len(),
+                &status_label(status, value),
+            );
+            return Some(updated);
+        }
+        return None;
+    }
+
+    let mut updated = line.to_string();
+    let tail = &line[link.end..];
+    let status_span =
+        inline_status_span(tail).map(|span| link.end + span.start..link.end + span.end);
+    if let Some(span) = &status_span {
+        updated.replace_range(span.clone(), &status_label(status, &line[span.clone()]));
+    }
+
+    let checkbox = CHECKBOX
+        .captures(line)
+        .and_then(|c| c.get(1))
+        .map(|m| m.range());
+    if let Some(span) = &checkbox {
+        let mark = if status == STATUS_COMPLETED { "x" } else { " " };
+        updated.replace_range(span.clone(), mark);
+        // A checkbox alone only says pending or completed
+        if status_span.is_none() && status != STATUS_PENDING && status != STATUS_COMPLETED {
+            let content_end = updated.trim_end_matches(['\r', '\n']).len();
+            updated.insert_str(
+                content_end,
+                &format!(" — Status: {}", status.replace('-', " ")),
+            );
+        }
+    }
+
+    (status_span.is_some() || checkbox.is_some()).then_some(updated)
+}
+
+/// Byte range of the status value in `Status: done` or of a trailing status word
+fn inline_status_span(text: &str) -> Option<Range<usize>> {
+    let lower = text.to_ascii_lowercase();
+    if let Some(idx) = lower.find("status:") {
+        let start = idx + "status:".len();
+        let end = text[start..]
+            .find([',', ')', '|', '\r', '\n'])
+            .map_or(text.len(), |end| start + end);
+        let value = &text[start..end];
+        let value_start = start + value.len() - value.trim_start().len();
+        let value = value.trim();
+        if normalize_status(value).is_some() {
+            return Some(value_start..value_start + value.len());
+        }
+    }
+
+    let mut word_end = None;
+    for (idx, c) in text.char_indices().rev() {
+        let is_separator = c.is_whitespace() || matches!(c, '-' | '—' | '(' | ')' | '[' | ']');
+        match (is_separator, word_end) {
+            (false, None) => word_end = Some(idx + c.len_utf8()),
+            (true, Some(end)) => {
+                let span = idx + c.len_utf8()..end;
+                return normalize_status(&text[span.clone()])
+                    .is_some()
+                    .then_some(span);
+            }
+            _ => {}
+        }
+    }
+    word_end
+        .filter(|&end| normalize_status(&text[..end]).is_some())
+        .map(|end| 0..end)
+}
+
+/// `status` written the way `existing` was: "In Progress", "in progress" or "in-progress"
+fn status_label(status: &str, existing: &str) -> String {
+    if existing.chars().any(char::is_uppercase) {
+        status
+            .split('-')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else if existing.contains(' ') {
+        status.replace('-', " ")
+    } else {
+        status.to_string()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum FrontmatterValue {
+    Scalar(String),
+    List(Vec<String>),
+}
+
+/// The flat `key: value` / list subset of YAML that plan frontmatter uses
+#[derive(Debug, Default)]
+pub struct Frontmatter {
+    fields: HashMap<String, FrontmatterValue>,
+}
+
+impl Frontmatter {
+    /// Split `content` into its frontmatter and the markdown body after it
+    pub fn parse(content: &str) -> (Self, &str) {
+        let Some((block, body)) = split_frontmatter(content) else {
+            return (Self::default(), content);
+        };
+
+        let mut fields = HashMap::new();
+        let mut current_list: Option<String> = None;
+        for line in block.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some(item) = trimmed.strip_prefix("- ")
+                && let Some(key) = &current_list
+            {
+                if let Some(FrontmatterValue::List(items)) = fields.get_mut(key) {
+                    items.push(unquote(item).to_string());
+                }
+                continue;
+            }
+            let Some((key, value)) = trimmed.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+            current_list = None;
+            let value = if value.is_empty() {
+                current_list = Some(key.clone());
+                FrontmatterValue::List(Vec::new())
+            } else if let Some(inline) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                FrontmatterValue::List(
+                    inline
+                        .split(',')
+                        .map(|item| unquote(item.trim()).to_string())
+                        .filter(|item| !item.is_empty())
+                        .collect(),
+                )
+            } else {
+                FrontmatterValue::Scalar(unquote(value).to_string())
+            };
+            fields.insert(key, value);
+        }
+        (Self { fields }, body)
+    }
+
+    pub fn scalar(&self, key: &str) -> Option<&str> {
+        match self.fields.get(key)? {
+            FrontmatterValue::Scalar(value) if !value.is_empty() => Some(value),
+            _ => None,
+        }
+    }
+
+    /// A list field; a scalar counts as a comma-separated list
+    pub fn list(&self, key: &str) -> Vec<String> {
+        match self.fields.get(key) {
+            Some(FrontmatterValue::List(items)) => items.clone(),
+            Some(FrontmatterValue::Scalar(value)) => value
+                .split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// `(frontmatter block, body)` when `content` opens with a `---` fence
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixtures() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/plan-project")
+    }
+
+    fn plan(id: &str) -> PlanMetadata {
+        scan_plans(&fixtures())
+            .unwrap()
+            .into_iter()
+            .find(|plan| plan.id == id)
+            .unwrap_or_else(|| panic!("plan {id} not found"))
+    }
+
+    #[test]
+    fn test_scans_only_directories_with_plan_md() {
+        let ids: Vec<String> = scan_plans(&fixtures())
+            .unwrap()
+            .into_iter()
+            .map(|plan| plan.id)
+            .collect();
+        assert_eq!(ids, vec!["auth-table", "docs-list", "search-files"]);
+    }
+
+    #[test]
+    fn test_missing_plans_dir_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(scan_plans(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_table_plan_with_frontmatter() {
+        let plan = plan("auth-table");
+        assert_eq!(plan.title.as_deref(), Some("Authentication overhaul"));
+        assert_eq!(plan.status, STATUS_IN_PROGRESS);
+        assert_eq!(plan.priority.as_deref(), Some("P1"));
+        assert_eq!(plan.branch.as_deref(), Some("feat/auth"));
+        assert_eq!(plan.tags, vec!["backend", "security"]);
+        assert_eq!(
+            plan.description.as_deref(),
+            Some("Replace session auth with OAuth")
+        );
+
+        let phases: Vec<(u32, &str, &str)> = plan
+            .phase_details
+            .iter()
+            .map(|p| (p.phase, p.name.as_str(), p.status.as_str()))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (1, "Setup", STATUS_COMPLETED),
+                (2, "OAuth provider", STATUS_IN_PROGRESS),
+                (3, "Cleanup", STATUS_PENDING),
+            ]
+        );
+        assert!(
+            plan.phase_details[0]
+                .file
+                .ends_with("auth-table/phase-01-setup.md")
+        );
+        assert!(Path::new(&plan.phase_details[0].file).is_absolute());
+        assert_eq!(
+            plan.phases,
+            PlanPhaseProgress {
+                total: 3,
+                completed: 1,
+                in_progress: 1,
+                pending: 1,
+                percentage: 33,
+            }
+        );
+        assert_eq!(plan.progress, 33);
+    }
+
+    #[test]
+    fn test_list_plan_with_checkboxes_and_inline_status() {
+        let plan = plan("docs-list");
+        assert_eq!(plan.title.as_deref(), Some("Docs refresh"));
+        assert_eq!(plan.tags, vec!["docs"]);
+        let phases: Vec<(u32, &str, &str, Option<&str>)> = plan
+            .phase_details
+            .iter()
+            .map(|p| {
+                (
+                    p.phase,
+                    p.name.as_str(),
+                    p.status.as_str(),
+                    p.link_text.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (1, "Outline", STATUS_COMPLETED, Some("Phase 1: Outline")),
+                (
+                    2,
+                    "Write guides",
+                    STATUS_IN_REVIEW,
+                    Some("Phase 2: Write guides")
+                ),
+            ]
+        );
+        // No status in frontmatter: derived from the phases
+        assert_eq!(plan.status, STATUS_IN_PROGRESS);
+    }
+
+    #[test]
+    fn test_unlinked_phase_files_use_their_frontmatter() {
+        let plan = plan("search-files");
+        assert_eq!(plan.title.as_deref(), Some("Search"));
+        let phases: Vec<(u32, &str, &str)> = plan
+            .phase_details
+            .iter()
+            .map(|p| (p.phase, p.name.as_str(), p.status.as_str()))
+            .collect();
+        assert_eq!(
+            phases,
+            vec![
+                (1, "Index schema", STATUS_COMPLETED),
+                (2, "Query api", STATUS_COMPLETED),
+            ]
+        );
+        assert_eq!(plan.status, STATUS_COMPLETED);
+        assert_eq!(plan.progress, 100);
+    }
+
+    #[test]
+    fn test_frontmatter_parsing() {
+        let (frontmatter, body) = Frontmatter::parse(
+            "---\ntitle: \"Quoted: title\"\ntags:\n  - one\n  - 'two'\nbranch: main\n---\n# Body\n",
+        );
+        assert_eq!(frontmatter.scalar("title"), Some("Quoted: title"));
+        assert_eq!(frontmatter.list("tags"), vec!["one", "two"]);
+        assert_eq!(frontmatter.scalar("branch"), Some("main"));
+        assert_eq!(body, "# Body\n");
+
+        let (frontmatter, body) = Frontmatter::parse("# No frontmatter\n");
+        assert_eq!(frontmatter.scalar("title"), None);
+        assert_eq!(body, "# No frontmatter\n");
+    }
+
+    #[test]
+    fn test_normalize_status() {
+        assert_eq!(normalize_status("In Progress"), Some(STATUS_IN_PROGRESS));
+        assert_eq!(normalize_status("**Done**"), Some(STATUS_COMPLETED));
+        assert_eq!(normalize_status("in_review"), Some(STATUS_IN_REVIEW));
+        assert_eq!(normalize_status("✅"), Some(STATUS_COMPLETED));
+        assert_eq!(normalize_status("Setup"), None);
+    }
+
+    /// Copy a fixture plan into a temporary project so it can be rewritten
+    fn copy_plan(id: &str) -> (tempfile::TempDir, PathBuf) {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path().join(PLANS_DIR).join(id);
+        fs::create_dir_all(&dir).unwrap();
+        for entry in fs::read_dir(fixtures().join(PLANS_DIR).join(id)).unwrap() {
+            let entry = entry.unwrap();
+            fs::copy(entry.path(), dir.join(entry.file_name())).unwrap();
+        }
+        (project, dir)
+    }
+
+    fn phase_status(dir: &Path, phase: u32) -> String {
+        scan_plan(dir)
+            .unwrap()
+            .unwrap()
+            .phase_details
+            .into_iter()
+            .find(|p| p.phase == phase)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_write_phase_status_updates_table_row() {
+        let (_project, dir) = copy_plan("auth-table");
+        let plan_path = dir.join(PLAN_FILE);
+        let phase_file = dir.join("phase-02-oauth.md");
+
+        assert!(write_phase_status(&plan_path, &phase_file, STATUS_COMPLETED).unwrap());
+        assert!(!write_phase_status(&plan_path, &phase_file, STATUS_COMPLETED).unwrap());
+
+        let content = fs::read_to_string(&plan_path).unwrap();
+        assert!(content.contains(
+            "| 2 | OAuth provider | Completed | [phase-02-oauth.md](./phase-02-oauth.md) |"
+        ));
+        assert_eq!(phase_status(&dir, 2), STATUS_COMPLETED);
+        assert_eq!(phase_status(&dir, 3), STATUS_PENDING);
+    }
+
+    #[test]
+    fn test_write_phase_status_updates_list_item() {
+        let (_project, dir) = copy_plan("docs-list");
+        let plan_path = dir.join(PLAN_FILE);
+
+        write_phase_status(
+            &plan_path,
+            &dir.join("phase-01-outline.md"),
+            STATUS_IN_PROGRESS,
+        )
+        .unwrap();
+        write_phase_status(
+            &plan_path,
+            &dir.join("phase-02-guides.md"),
+            STATUS_COMPLETED,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(&plan_path).unwrap();
+        assert!(
+            content.contains(
+                "- [ ] [Phase 1: Outline](./phase-01-outline.md) — Status: in progress\n"
+            )
+        );
+        assert!(
+            content.contains(
+                "- [x] [Phase 2: Write guides](./phase-02-guides.md) — Status: completed"
+            )
+        );
+        assert_eq!(phase_status(&dir, 1), STATUS_IN_PROGRESS);
+        assert_eq!(phase_status(&dir, 2), STATUS_COMPLETED);
+    }
+
+    #[test]
+    fn test_write_phase_status_falls_back_to_phase_frontmatter() {
+        let (_project, dir) = copy_plan("search-files");
+        let phase_file = dir.join("phase-01-index-schema.md");
+
+        assert!(write_phase_status(&dir.join(PLAN_FILE), &phase_file, STATUS_IN_REVIEW).unwrap());
+
+        assert_eq!(
+            fs::read_to_string(&phase_file).unwrap(),
+            "---\nstatus: in-review\n---\n# Index schema\n"
+        );
+        assert_eq!(phase_status(&dir, 1), STATUS_IN_REVIEW);
+    }
+
+    #[test]
+    fn test_write_frontmatter_status_adds_frontmatter() {
+        let (_project, dir) = copy_plan("search-files");
+        let plan_path = dir.join(PLAN_FILE);
+
+        assert!(write_frontmatter_status(&plan_path, STATUS_CANCELLED).unwrap());
+
+        let plan = scan_plan(&dir).unwrap().unwrap();
+        assert_eq!(plan.status, STATUS_CANCELLED);
+        assert_eq!(plan.title.as_deref(), Some("Search"));
+    }
+
+    #[test]
+    fn test_task_status_round_trip() {
+        for status in [
+            STATUS_PENDING,
+            STATUS_IN_PROGRESS,
+            STATUS_IN_REVIEW,
+            STATUS_COMPLETED,
+            STATUS_CANCELLED,
+        ] {
+            assert_eq!(plan_status(&task_status(status)), status);
+        }
+        assert_eq!(task_status("Done"), TaskStatus::Done);
+        assert_eq!(task_status("unknown"), TaskStatus::Todo);
+    }
+}
@@ -1,10 +1,11 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use db::{
     DBService,
     models::{
         merge::{Merge, MergeStatus, PrMerge},
         task::{Task, TaskStatus},
+        task_github_issue::{ISSUE_STATE_CLOSED, TaskGitHubIssue},
         workspace::{Workspace, WorkspaceError},
     },
 };
@@ -16,10 +17,17 @@ use tracing::{debug, error, info};
 
 use crate::services::{
     analytics::AnalyticsContext,
-    github::{GitHubRepoInfo, GitHubService, GitHubServiceError},
+    config::NotificationEvent,
+    github::{GitHubIssue, GitHubRepoInfo, GitHubService, GitHubServiceError, IssueFilter},
+    metrics,
+    notification::NotificationService,
     share::SharePublisher,
+    webhooks::WebhookService,
 };
 
+/// Most closed issues fetched per repository and poll
+const CLOSED_ISSUES_LIMIT: u32 = 500;
+
 #[derive(Debug, Error)]
 enum PrMonitorError {
     #[error(transparent)]
@@ -30,12 +38,15 @@ enum PrMonitorError {
     Sqlx(#[from] SqlxError),
 }
 
-/// Service to monitor GitHub PRs and update task status when they are merged
+/// Service to monitor GitHub PRs and imported issues, moving tasks to done when their PR is
+/// merged or their issue is closed
 pub struct PrMonitorService {
     db: DBService,
     poll_interval: Duration,
     analytics: Option<AnalyticsContext>,
     publisher: Option<SharePublisher>,
+    webhooks: WebhookService,
+    notifications: NotificationService,
 }
 
 impl PrMonitorService {
@@ -43,12 +54,16 @@ impl PrMonitorService {
         db: DBService,
         analytics: Option<AnalyticsContext>,
         publisher: Option<SharePublisher>,
+        webhooks: WebhookService,
+        notifications: NotificationService,
     ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             poll_interval: Duration::from_secs(60), // Check every minute
             analytics,
             publisher,
+            webhooks,
+            notifications,
         };
         tokio::spawn(async move {
             service.start().await;
@@ -65,32 +80,125 @@ impl PrMonitorService {
 
         loop {
             interval.tick().await;
-            if let Err(e) = self.check_all_open_prs().await {
-                error!("Error checking open PRs: {}", e);
+            match self.check_all_open_prs().await {
+                Ok(open_prs) => metrics::pr_monitor().record_poll(true, open_prs),
+                Err(e) => {
+                    error!("Error checking open PRs: {}", e);
+                    metrics::pr_monitor().record_poll(false, 0);
+                }
+            }
+            if let Err(e) = self.check_linked_issues().await {
+                error!("Error checking linked GitHub issues: {}", e);
             }
         }
     }
 
-    /// Check all open PRs for updates with the provided GitHub token
-    async fn check_all_open_prs(&self) -> Result<(), PrMonitorError> {
+    /// Move tasks whose imported GitHub issue was closed to done, or to cancelled when the
+    /// issue was closed as not planned. Closed issues are listed once per repository.
+    async fn check_linked_issues(&self) -> Result<(), PrMonitorError> {
+        let issues = TaskGitHubIssue::find_open_with_active_tasks(&self.db.pool).await?;
+        if issues.is_empty() {
+            return Ok(());
+        }
+        debug!("Checking {} linked GitHub issues", issues.len());
+
+        let mut by_repo: HashMap<(String, String), Vec<TaskGitHubIssue>> = HashMap::new();
+        for issue in issues {
+            by_repo
+                .entry((issue.owner.clone(), issue.repo_name.clone()))
+                .or_default()
+                .push(issue);
+        }
+
+        let github_service = GitHubService::new()?;
+        for ((owner, repo_name), issues) in by_repo {
+            // A linked issue can only have been closed after it was imported
+            let linked_since = issues
+                .iter()
+                .map(|issue| issue.created_at)
+                .min()
+                .unwrap_or_default();
+            let filter = IssueFilter {
+                search: Some(format!(
+                    "closed:>={} sort:updated-desc",
+                    linked_since.format("%Y-%m-%d")
+                )),
+                limit: CLOSED_ISSUES_LIMIT,
+                closed: true,
+                ..Default::default()
+            };
+            let repo_info = GitHubRepoInfo {
+                owner: owner.clone(),
+                repo_name: repo_name.clone(),
+            };
+            let closed: HashMap<i64, GitHubIssue> =
+                match github_service.list_issues(&repo_info, &filter).await {
+                    Ok(closed) => closed
+                        .into_iter()
+                        .map(|issue| (issue.number, issue))
+                        .collect(),
+                    Err(e) => {
+                        error!(
+                            "Error listing closed issues of {}/{}: {}",
+                            owner, repo_name, e
+                        );
+                        continue;
+                    }
+                };
+
+            for issue in issues {
+                let Some(remote) = closed.get(&issue.issue_number) else {
+                    continue;
+                };
+                let status = if remote.is_not_planned() {
+                    TaskStatus::Cancelled
+                } else {
+                    TaskStatus::Done
+                };
+                info!(
+                    "Issue {}/{}#{} was closed, updating task {} to {}",
+                    owner, repo_name, issue.issue_number, issue.task_id, status
+                );
+                TaskGitHubIssue::set_state(&self.db.pool, issue.id, ISSUE_STATE_CLOSED).await?;
+                Task::update_status(&self.db.pool, issue.task_id, status).await?;
+
+                if let Some(publisher) = &self.publisher
+                    && let Err(err) = publisher.update_shared_task_by_id(issue.task_id).await
+                {
+                    tracing::warn!(
+                        ?err,
+                        "Failed to propagate shared task update for {}",
+                        issue.task_id
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check all open PRs for updates with the provided GitHub token, returning how many
+    /// were checked
+    async fn check_all_open_prs(&self) -> Result<usize, PrMonitorError> {
         let open_prs = Merge::get_open_prs(&self.db.pool).await?;
 
         if open_prs.is_empty() {
             debug!("No open PRs to check");
-            return Ok(());
+            return Ok(0);
         }
+        let count = open_prs.len();
 
         info!("Checking {} open PRs", open_prs.len());
 
         for pr_merge in open_prs {
             if let Err(e) = self.check_pr_status(&pr_merge).await {
+                metrics::pr_monitor().record_check(None);
                 error!(
                     "Error checking PR #{} for workspace {}: {}",
                     pr_merge.pr_info.number, pr_merge.workspace_id, e
                 );
             }
         }
-        Ok(())
+        Ok(count)
     }
 
     /// Check the status of a specific PR
@@ -107,6 +215,7 @@ impl PrMonitorService {
             "PR #{} status: {:?} (was open)",
             pr_merge.pr_info.number, pr_status.status
         );
+        metrics::pr_monitor().record_check(Some(&pr_status.status));
 
         // Update the PR status in the database
         if !matches!(&pr_status.status, MergeStatus::Open) {
@@ -130,9 +239,26 @@ impl PrMonitorService {
                 );
                 Task::update_status(&self.db.pool, workspace.task_id, TaskStatus::Done).await?;
 
+                let task = Task::find_by_id(&self.db.pool, workspace.task_id).await?;
+                if let Some(task) = &task {
+                    self.webhooks
+                        .pr_merged(task, workspace.id, &pr_status)
+                        .await;
+                    self.notifications
+                        .notify_channels(
+                            NotificationEvent::PrMerged,
+                            &format!("PR Merged: {}", task.title),
+                            &format!(
+                                "🎉 PR #{} for '{}' was merged\n{}",
+                                pr_status.number, task.title, pr_status.url
+                            ),
+                        )
+                        .await;
+                }
+
                 // Track analytics event
                 if let Some(analytics) = &self.analytics
-                    && let Ok(Some(task)) = Task::find_by_id(&self.db.pool, workspace.task_id).await
+                    && let Some(task) = &task
                 {
                     analytics.analytics_service.track_event(
                         &analytics.user_id,
@@ -0,0 +1,749 @@
+//! Export a project board as a versioned JSON archive (or a flat CSV of its tasks) and import
+//! such an archive as a new project, e.g. to move a board to another machine or to seed new
+//! projects from a template.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use db::models::{
+    execution_process::{
+        ExecutionProcess, ExecutionProcessRunReason, ExecutionProcessStatus, ExecutorActionField,
+    },
+    execution_process_logs::ExecutionProcessLogs,
+    label::Label,
+    merge::{Merge, MergeStatus},
+    project::{Project, UpdateProject},
+    project_repo::{ProjectRepo, ProjectRepoError, UpdateProjectRepo},
+    session::{CreateSession, Session, SessionError},
+    tag::{CreateTag, Tag},
+    task::{CreateTask, Task, TaskPriority, TaskStatus},
+    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use executors::logs::{NormalizedEntry, utils::ConversationPatch};
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use utils::log_msg::LogMsg;
+use uuid::Uuid;
+
+use crate::services::container::{ContainerError, ContainerService};
+
+/// Bumped whenever the archive layout changes incompatibly
+pub const PROJECT_ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Error)]
+pub enum ProjectArchiveError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    ProjectRepo(#[from] ProjectRepoError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Session(#[from] SessionError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+    #[error("Unsupported project archive version {0}")]
+    UnsupportedVersion(u32),
+    #[error("No repository given for archived repository '{0}'")]
+    MissingRepository(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProjectExportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+impl ProjectExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ProjectExportFormat::Json => "application/json",
+            ProjectExportFormat::Csv => "text/csv; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProjectExportFormat::Json => "json",
+            ProjectExportFormat::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct ProjectExportOptions {
+    #[serde(default)]
+    pub format: ProjectExportFormat,
+    /// Include sessions, normalized agent logs and merges of every attempt (JSON only)
+    #[serde(default)]
+    pub include_history: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ProjectArchive {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ArchivedProject,
+    pub repositories: Vec<ArchivedRepo>,
+    pub tags: Vec<ArchivedTag>,
+    /// Oldest first
+    pub tasks: Vec<ArchivedTask>,
+}
+
+/// Project settings, including the dev server script
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedProject {
+    pub name: String,
+    pub dev_script: Option<String>,
+    pub dev_script_working_dir: Option<String>,
+    pub default_agent_working_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedRepo {
+    /// Repository ID on the exporting machine, referenced by attempts and merges
+    pub id: Uuid,
+    pub name: String,
+    pub display_name: String,
+    /// Path on the exporting machine
+    pub path: String,
+    pub setup_script: Option<String>,
+    pub cleanup_script: Option<String>,
+    pub copy_files: Option<String>,
+    pub parallel_setup_script: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedTag {
+    pub tag_name: String,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedTask {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TaskStatus,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub estimate_minutes: Option<i64>,
+    /// Label names; labels missing from the target project are created on import
+    #[serde(default)]
+    pub labels: Vec<String>,
+    /// Attempt this task was created from; one of the `attempts` of another archived task
+    pub parent_workspace_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Oldest first. Always present so that `parent_workspace_id` can be resolved.
+    pub attempts: Vec<ArchivedAttempt>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedAttempt {
+    pub id: Uuid,
+    pub branch: String,
+    pub agent_working_dir: Option<String>,
+    pub repos: Vec<ArchivedAttemptRepo>,
+    pub created_at: DateTime<Utc>,
+    /// Empty unless exported with history
+    pub sessions: Vec<ArchivedSession>,
+    /// Empty unless exported with history
+    pub merges: Vec<Merge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedAttemptRepo {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedSession {
+    pub executor: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub processes: Vec<ArchivedExecutionProcess>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ArchivedExecutionProcess {
+    pub run_reason: ExecutionProcessRunReason,
+    #[ts(type = "ExecutorAction")]
+    pub executor_action: ExecutorActionField,
+    pub status: ExecutionProcessStatus,
+    pub exit_code: Option<i64>,
+    pub dropped: bool,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// The normalized conversation; raw process output is not exported
+    pub entries: Vec<NormalizedEntry>,
+}
+
+/// What an import created
+#[derive(Debug, Clone, Default, Serialize, TS)]
+pub struct ProjectImportSummary {
+    pub task_count: u32,
+    pub attempt_count: u32,
+    pub session_count: u32,
+    pub tags_created: u32,
+    /// Archived tags skipped because a tag with the same name exists
+    pub tags_skipped: u32,
+}
+
+/// Collect a project's board into an archive. Attempt history is only read when
+/// `include_history` is set, since normalizing logs of every process can be slow.
+pub async fn export_project<C: ContainerService + Sync>(
+    container: &C,
+    project: &Project,
+    include_history: bool,
+) -> Result<ProjectArchive, ProjectArchiveError> {
+    let pool = &container.db().pool;
+
+    let mut repositories = Vec::new();
+    for repo in ProjectRepo::find_repos_for_project(pool, project.id).await? {
+        let scripts = ProjectRepo::find_by_project_and_repo(pool, project.id, repo.id).await?;
+        repositories.push(ArchivedRepo {
+            id: repo.id,
+            name: repo.name,
+            display_name: repo.display_name,
+            path: repo.path.to_string_lossy().to_string(),
+            setup_script: scripts.as_ref().and_then(|s| s.setup_script.clone()),
+            cleanup_script: scripts.as_ref().and_then(|s| s.cleanup_script.clone()),
+            copy_files: scripts.as_ref().and_then(|s| s.copy_files.clone()),
+            parallel_setup_script: scripts.is_some_and(|s| s.parallel_setup_script),
+        });
+    }
+
+    let tags = Tag::find_all(pool)
+        .await?
+        .into_iter()
+        .map(|tag| ArchivedTag {
+            tag_name: tag.tag_name,
+            content: tag.content,
+        })
+        .collect();
+
+    let mut project_tasks: Vec<(Task, Vec<String>)> =
+        Task::find_by_project_id_with_attempt_status(pool, project.id)
+            .await?
+            .into_iter()
+            .map(|task| {
+                let labels = task.labels.into_iter().map(|label| label.name).collect();
+                (task.task, labels)
+            })
+            .collect();
+    project_tasks.sort_by_key(|(task, _)| task.created_at);
+
+    let mut tasks = Vec::with_capacity(project_tasks.len());
+    for (task, labels) in project_tasks {
+        let mut workspaces = Workspace::fetch_all(pool, Some(task.id)).await?;
+        workspaces.reverse();
+
+        let mut attempts = Vec::with_capacity(workspaces.len());
+        for workspace in workspaces {
+            let repos = WorkspaceRepo::find_by_workspace_id(pool, workspace.id)
+                .await?
+                .into_iter()
+                .map(|repo| ArchivedAttemptRepo {
+                    repo_id: repo.repo_id,
+                    target_branch: repo.target_branch,
+                })
+                .collect();
+            let (sessions, merges) = if include_history {
+                let mut merges = Merge::find_by_workspace_id(pool, workspace.id).await?;
+                merges.reverse();
+                (export_sessions(container, workspace.id).await?, merges)
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            attempts.push(ArchivedAttempt {
+                id: workspace.id,
+                branch: workspace.branch,
+                agent_working_dir: workspace.agent_working_dir,
+                repos,
+                created_at: workspace.created_at,
+                sessions,
+                merges,
+            });
+        }
+
+        tasks.push(ArchivedTask {
+            id: task.id,
+            title: task.title,
+            description: task.description,
+            status: task.status,
+            priority: task.priority,
+            due_date: task.due_date,
+            estimate_minutes: task.estimate_minutes,
+            labels,
+            parent_workspace_id: task.parent_workspace_id,
+            created_at: task.created_at,
+            updated_at: task.updated_at,
+            attempts,
+        });
+    }
+
+    Ok(ProjectArchive {
+        version: PROJECT_ARCHIVE_VERSION,
+        exported_at: Utc::now(),
+        project: ArchivedProject {
+            name: project.name.clone(),
+            dev_script: project.dev_script.clone(),
+            dev_script_working_dir: project.dev_script_working_dir.clone(),
+            default_agent_working_dir: project.default_agent_working_dir.clone(),
+        },
+        repositories,
+        tags,
+        tasks,
+    })
+}
+
+async fn export_sessions<C: ContainerService + Sync>(
+    container: &C,
+    workspace_id: Uuid,
+) -> Result<Vec<ArchivedSession>, ProjectArchiveError> {
+    let pool = &container.db().pool;
+    let mut sessions = Session::find_by_workspace_id(pool, workspace_id).await?;
+    sessions.reverse();
+
+    let mut archived = Vec::with_capacity(sessions.len());
+    for session in sessions {
+        let mut processes = Vec::new();
+        for process in ExecutionProcess::find_by_session_id(pool, session.id, true).await? {
+            let entries = container.normalized_entries(&process.id).await?;
+            processes.push(ArchivedExecutionProcess {
+                run_reason: process.run_reason,
+                executor_action: process.executor_action.0,
+                status: process.status,
+                exit_code: process.exit_code,
+                dropped: process.dropped,
+                started_at: process.started_at,
+                completed_at: process.completed_at,
+                entries,
+            });
+        }
+        archived.push(ArchivedSession {
+            executor: session.executor,
+            created_at: session.created_at,
+            processes,
+        });
+    }
+    Ok(archived)
+}
+
+/// Check that an archive can be imported by this version
+pub fn validate_archive(archive: &ProjectArchive) -> Result<(), ProjectArchiveError> {
+    if archive.version == 0 || archive.version > PROJECT_ARCHIVE_VERSION {
+        return Err(ProjectArchiveError::UnsupportedVersion(archive.version));
+    }
+    Ok(())
+}
+
+/// Fill a freshly created project with the archived board. `repo_ids` maps the archived
+/// repository IDs to the project's repositories on this machine. Every task, attempt, session
+/// and process gets a new ID; references between them are remapped. Runs in one transaction,
+/// so a failed import leaves no tags or tasks behind.
+pub async fn import_project(
+    pool: &SqlitePool,
+    project: &Project,
+    archive: &ProjectArchive,
+    repo_ids: &HashMap<Uuid, Uuid>,
+) -> Result<ProjectImportSummary, ProjectArchiveError> {
+    validate_archive(archive)?;
+    let mut summary = ProjectImportSummary::default();
+    let mut tx = pool.begin().await?;
+
+    Project::update(
+        &mut *tx,
+        project.id,
+        &UpdateProject {
+            name: None,
+            dev_script: archive.project.dev_script.clone(),
+            dev_script_working_dir: archive.project.dev_script_working_dir.clone(),
+            default_agent_working_dir: archive.project.default_agent_working_dir.clone(),
+        },
+    )
+    .await?;
+
+    for repo in &archive.repositories {
+        let repo_id = repo_ids
+            .get(&repo.id)
+            .ok_or_else(|| ProjectArchiveError::MissingRepository(repo.display_name.clone()))?;
+        ProjectRepo::update(
+            &mut *tx,
+            project.id,
+            *repo_id,
+            &UpdateProjectRepo {
+                setup_script: repo.setup_script.clone(),
+                cleanup_script: repo.cleanup_script.clone(),
+                copy_files: repo.copy_files.clone(),
+                parallel_setup_script: Some(repo.parallel_setup_script),
+            },
+        )
+        .await?;
+    }
+
+    let existing_tags: Vec<String> = Tag::find_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|tag| tag.tag_name)
+        .collect();
+    for tag in &archive.tags {
+        if existing_tags.contains(&tag.tag_name) {
+            summary.tags_skipped += 1;
+            continue;
+        }
+        Tag::create(
+            &mut *tx,
+            &CreateTag {
+                tag_name: tag.tag_name.clone(),
+                content: tag.content.clone(),
+            },
+        )
+        .await?;
+        summary.tags_created += 1;
+    }
+
+    // Tasks and attempts reference each other, so parents are linked once both exist
+    let mut workspace_ids = HashMap::new();
+    let mut children = Vec::new();
+    for archived in &archive.tasks {
+        let task = Task::create(
+            &mut *tx,
+            &CreateTask {
+                project_id: project.id,
+                title: archived.title.clone(),
+                description: archived.description.clone(),
+                status: Some(archived.status.clone()),
+                priority: archived.priority,
+                due_date: archived.due_date,
+                estimate_minutes: archived.estimate_minutes,
+                labels: None,
+                parent_workspace_id: None,
+                image_ids: None,
+                shared_task_id: None,
+            },
+            Uuid::new_v4(),
+        )
+        .await?;
+        if !archived.labels.is_empty() {
+            Label::set_for_task(&mut *tx, project.id, task.id, &archived.labels).await?;
+        }
+        summary.task_count += 1;
+        if let Some(parent_workspace_id) = archived.parent_workspace_id {
+            children.push((task.id, parent_workspace_id));
+        }
+
+        for attempt in &archived.attempts {
+            let workspace =
+                import_attempt(&mut tx, task.id, attempt, repo_ids, &mut summary).await?;
+            workspace_ids.insert(attempt.id, workspace.id);
+        }
+    }
+
+    for (task_id, archived_parent) in children {
+        // Parents outside the archive can't be resolved; the task is kept without one
+        if let Some(parent_workspace_id) = workspace_ids.get(&archived_parent) {
+            Task::update_parent_workspace_id(&mut *tx, task_id, Some(*parent_workspace_id)).await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+async fn import_attempt(
+    conn: &mut SqliteConnection,
+    task_id: Uuid,
+    attempt: &ArchivedAttempt,
+    repo_ids: &HashMap<Uuid, Uuid>,
+    summary: &mut ProjectImportSummary,
+) -> Result<Workspace, ProjectArchiveError> {
+    let workspace = Workspace::create(
+        &mut *conn,
+        &CreateWorkspace {
+            branch: attempt.branch.clone(),
+            agent_working_dir: attempt.agent_working_dir.clone(),
+        },
+        Uuid::new_v4(),
+        task_id,
+    )
+    .await?;
+    summary.attempt_count += 1;
+
+    let repos: Vec<CreateWorkspaceRepo> = attempt
+        .repos
+        .iter()
+        .filter_map(|repo| {
+            Some(CreateWorkspaceRepo {
+                repo_id: *repo_ids.get(&repo.repo_id)?,
+                target_branch: repo.target_branch.clone(),
+            })
+        })
+        .collect();
+    WorkspaceRepo::create_many(&mut *conn, workspace.id, &repos).await?;
+
+    for archived in &attempt.sessions {
+        let session = Session::create(
+            &mut *conn,
+            &CreateSession {
+                executor: archived.executor.clone(),
+            },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await?;
+        summary.session_count += 1;
+
+        for process in &archived.processes {
+            import_process(conn, session.id, process).await?;
+        }
+    }
+
+    for merge in &attempt.merges {
+        match merge {
+            Merge::Direct(direct) => {
+                let Some(repo_id) = repo_ids.get(&direct.repo_id) else {
+                    continue;
+                };
+                Merge::create_direct(
+                    &mut *conn,
+                    workspace.id,
+                    *repo_id,
+                    &direct.target_branch_name,
+                    &direct.merge_commit,
+                )
+                .await?;
+            }
+            Merge::Pr(pr) => {
+                let Some(repo_id) = repo_ids.get(&pr.repo_id) else {
+                    continue;
+                };
+                let created = Merge::create_pr(
+                    &mut *conn,
+                    workspace.id,
+                    *repo_id,
+                    &pr.target_branch_name,
+                    pr.pr_info.number,
+                    &pr.pr_info.url,
+                )
+                .await?;
+                if !matches!(pr.pr_info.status, MergeStatus::Open) {
+                    Merge::update_status(
+                        &mut *conn,
+                        created.id,
+                        pr.pr_info.status.clone(),
+                        pr.pr_info.merge_commit_sha.clone(),
+                    )
+                    .await?;
+                }
+            }
+        }
+    }
+
+    Ok(workspace)
+}
+
+/// Recreate a finished process. Its logs are stored as normalized-only, the same way pruned
+/// logs are, so the conversation renders without the raw output.
+async fn import_process(
+    conn: &mut SqliteConnection,
+    session_id: Uuid,
+    archived: &ArchivedExecutionProcess,
+) -> Result<(), ProjectArchiveError> {
+    // Nothing is running on this machine for an imported process
+    let status = match &archived.status {
+        ExecutionProcessStatus::Running => ExecutionProcessStatus::Killed,
+        status => status.clone(),
+    };
+    let process_id = Uuid::new_v4();
+    let completed_at = archived.completed_at.unwrap_or(archived.started_at);
+    ExecutionProcess::create_imported(
+        &mut *conn,
+        &ExecutionProcess {
+            id: process_id,
+            session_id,
+            run_reason: archived.run_reason.clone(),
+            executor_action: sqlx::types::Json(archived.executor_action.clone()),
+            status,
+            exit_code: archived.exit_code,
+            dropped: archived.dropped,
+            started_at: archived.started_at,
+            completed_at: Some(completed_at),
+            created_at: archived.started_at,
+            updated_at: completed_at,
+        },
+    )
+    .await?;
+
+    if !archived.entries.is_empty() {
+        let mut jsonl = String::new();
+        for (index, entry) in archived.entries.iter().enumerate() {
+            let msg = LogMsg::JsonPatch(ConversationPatch::add_normalized_entry(
+                index,
+                entry.clone(),
+            ));
+            jsonl.push_str(&serde_json::to_string(&msg)?);
+            jsonl.push('\n');
+        }
+        ExecutionProcessLogs::replace(&mut *conn, process_id, &jsonl, true).await?;
+    }
+    Ok(())
+}
+
+/// One row per task: `id,title,status,description,parent_task_id,created_at,updated_at,
+/// priority,due_date,estimate_minutes,labels`. `parent_task_id` is the task owning the attempt
+/// the row's task was created from; `labels` are separated by `;`.
+pub fn tasks_csv(archive: &ProjectArchive) -> String {
+    let task_by_workspace: HashMap<Uuid, Uuid> = archive
+        .tasks
+        .iter()
+        .flat_map(|task| task.attempts.iter().map(|attempt| (attempt.id, task.id)))
+        .collect();
+
+    let mut csv = String::from(
+        "id,title,status,description,parent_task_id,created_at,updated_at,priority,due_date,\
+         estimate_minutes,labels\n",
+    );
+    for task in &archive.tasks {
+        let parent_task_id = task
+            .parent_workspace_id
+            .and_then(|id| task_by_workspace.get(&id))
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+        let fields = [
+            task.id.to_string(),
+            task.title.clone(),
+            task.status.to_string(),
+            task.description.clone().unwrap_or_default(),
+            parent_task_id,
+            task.created_at.to_rfc3339(),
+            task.updated_at.to_rfc3339(),
+            task.priority.map(|p| p.to_string()).unwrap_or_default(),
+            task.due_date.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            task.estimate_minutes
+                .map(|m| m.to_string())
+                .unwrap_or_default(),
+            task.labels.join(";"),
+        ];
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quote a field if it contains a delimiter, quote or line break (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn task(id: Uuid, title: &str, parent_workspace_id: Option<Uuid>) -> ArchivedTask {
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+        ArchivedTask {
+            id,
+            title: title.to_string(),
+            description: None,
+            status: TaskStatus::InProgress,
+            priority: None,
+            due_date: None,
+            estimate_minutes: None,
+            labels: Vec::new(),
+            parent_workspace_id,
+            created_at,
+            updated_at: created_at,
+            attempts: Vec::new(),
+        }
+    }
+
+    fn archive(tasks: Vec<ArchivedTask>) -> ProjectArchive {
+        ProjectArchive {
+            version: PROJECT_ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            project: ArchivedProject {
+                name: "board".to_string(),
+                dev_script: None,
+                dev_script_working_dir: None,
+                default_agent_working_dir: None,
+            },
+            repositories: Vec::new(),
+            tags: Vec::new(),
+            tasks,
+        }
+    }
+
+    #[test]
+    fn csv_quotes_fields_and_resolves_parent_tasks() {
+        let parent_id = Uuid::new_v4();
+        let workspace_id = Uuid::new_v4();
+        let child_id = Uuid::new_v4();
+
+        let mut parent = task(parent_id, "Add login", None);
+        parent.attempts.push(ArchivedAttempt {
+            id: workspace_id,
+            branch: "vk/login".to_string(),
+            agent_working_dir: None,
+            repos: Vec::new(),
+            created_at: parent.created_at,
+            sessions: Vec::new(),
+            merges: Vec::new(),
+        });
+        let mut child = task(child_id, "Fix \"remember me\", again", Some(workspace_id));
+        child.description = Some("line one\nline two".to_string());
+        child.priority = Some(TaskPriority::High);
+        child.due_date = Some(Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap());
+        child.estimate_minutes = Some(90);
+        child.labels = vec!["bug".to_string(), "auth, web".to_string()];
+
+        let csv = tasks_csv(&archive(vec![parent, child]));
+        let lines: Vec<&str> = csv.splitn(3, '\n').collect();
+
+        assert_eq!(
+            lines[0],
+            "id,title,status,description,parent_task_id,created_at,updated_at,priority,due_date,estimate_minutes,labels"
+        );
+        assert_eq!(
+            lines[1],
+            format!(
+                "{parent_id},Add login,inprogress,,,2025-01-02T03:04:05+00:00,2025-01-02T03:04:05+00:00,,,,"
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!(
+                "{child_id},\"Fix \"\"remember me\"\", again\",inprogress,\"line one\nline two\",{parent_id},2025-01-02T03:04:05+00:00,2025-01-02T03:04:05+00:00,high,2025-02-01T00:00:00+00:00,90,\"bug;auth, web\"\n"
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_archives_from_newer_versions() {
+        let mut newer = archive(Vec::new());
+        newer.version = PROJECT_ARCHIVE_VERSION + 1;
+        assert!(matches!(
+            validate_archive(&newer),
+            Err(ProjectArchiveError::UnsupportedVersion(_))
+        ));
+        assert!(validate_archive(&archive(Vec::new())).is_ok());
+    }
+}
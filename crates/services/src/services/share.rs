@@ -1,4 +1,5 @@
 mod config;
+mod priority;
 mod publisher;
 mod status;
 
@@ -0,0 +1,11 @@
+use db::models::task::TaskPriority;
+use remote::db::tasks::TaskPriority as RemoteTaskPriority;
+
+pub(super) fn to_remote(priority: TaskPriority) -> RemoteTaskPriority {
+    match priority {
+        TaskPriority::Low => RemoteTaskPriority::Low,
+        TaskPriority::Medium => RemoteTaskPriority::Medium,
+        TaskPriority::High => RemoteTaskPriority::High,
+        TaskPriority::Urgent => RemoteTaskPriority::Urgent,
+    }
+}
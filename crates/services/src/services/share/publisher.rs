@@ -1,16 +1,22 @@
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
+        label::Label,
         project::Project,
-        task::{CreateTask, Task, TaskStatus},
+        task::{CreateTask, Task, TaskPriority, TaskStatus},
     },
 };
-use remote::routes::tasks::{
-    AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTaskResponse, UpdateSharedTaskRequest,
+use remote::{
+    db::tasks::SharedTaskPlanning,
+    routes::tasks::{
+        AssignSharedTaskRequest, CreateSharedTaskRequest, SharedTaskResponse,
+        UpdateSharedTaskRequest,
+    },
 };
 use uuid::Uuid;
 
-use super::{ShareError, status};
+use super::{ShareError, priority, status};
 use crate::services::remote_client::RemoteClient;
 
 #[derive(Clone)]
@@ -26,6 +32,14 @@ pub struct SharedTaskDetails {
     pub title: String,
     pub description: Option<String>,
     pub status: TaskStatus,
+    #[serde(default)]
+    pub priority: Option<TaskPriority>,
+    #[serde(default)]
+    pub due_date: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub estimate_minutes: Option<i32>,
+    #[serde(default)]
+    pub labels: Vec<String>,
 }
 
 impl SharePublisher {
@@ -54,6 +68,7 @@ impl SharePublisher {
             title: task.title.clone(),
             description: task.description.clone(),
             assignee_user_id: Some(user_id),
+            planning: self.planning(&task).await?,
         };
 
         let remote_task = self.client.create_shared_task(&payload).await?;
@@ -72,6 +87,7 @@ impl SharePublisher {
             title: Some(task.title.clone()),
             description: task.description.clone(),
             status: Some(status::to_remote(&task.status)),
+            planning: Some(self.planning(task).await?),
         };
 
         self.client
@@ -81,6 +97,18 @@ impl SharePublisher {
         Ok(())
     }
 
+    async fn planning(&self, task: &Task) -> Result<SharedTaskPlanning, ShareError> {
+        let labels = Label::find_by_task_id(&self.db.pool, task.id).await?;
+        Ok(SharedTaskPlanning {
+            priority: task.priority.map(priority::to_remote),
+            due_date: task.due_date,
+            estimate_minutes: task
+                .estimate_minutes
+                .and_then(|minutes| i32::try_from(minutes).ok()),
+            labels: labels.into_iter().map(|label| label.name).collect(),
+        })
+    }
+
     pub async fn update_shared_task_by_id(&self, task_id: Uuid) -> Result<(), ShareError> {
         let task = Task::find_by_id(&self.db.pool, task_id)
             .await?
@@ -135,16 +163,25 @@ impl SharePublisher {
             return Ok(None);
         }
 
-        let create_task = CreateTask::from_shared_task(
-            shared_task.project_id,
-            shared_task.title,
-            shared_task.description,
-            shared_task.status,
-            shared_task.id,
-        );
+        let create_task = CreateTask {
+            priority: shared_task.priority,
+            due_date: shared_task.due_date,
+            estimate_minutes: shared_task.estimate_minutes.map(i64::from),
+            ..CreateTask::from_shared_task(
+                shared_task.project_id,
+                shared_task.title,
+                shared_task.description,
+                shared_task.status,
+                shared_task.id,
+            )
+        };
 
         let id = Uuid::new_v4();
         let task = Task::create(&self.db.pool, &create_task, id).await?;
+        if !shared_task.labels.is_empty() {
+            Label::set_for_task(&self.db.pool, task.project_id, task.id, &shared_task.labels)
+                .await?;
+        }
 
         Ok(Some(task))
     }
@@ -0,0 +1,864 @@
+//! Apply one operation to many tasks at once. Every task is checked first and the ones that
+//! can't take the operation are reported and skipped; the rest are changed in a single
+//! transaction and published as one batched patch per project instead of a patch per task.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use db::models::{
+    label::Label,
+    project::Project,
+    repo::Repo,
+    task::{Task, TaskStatus},
+    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use executors::profile::ExecutorProfileId;
+use serde::{Deserialize, Serialize};
+use sqlx::{SqliteConnection, SqlitePool};
+use thiserror::Error;
+use ts_rs::TS;
+use uuid::Uuid;
+
+use crate::services::{
+    container::{ContainerError, ContainerService},
+    events::EventService,
+    share::SharePublisher,
+    workspace_manager::WorkspaceManager,
+};
+
+#[derive(Debug, Error)]
+pub enum TaskBulkError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error("Invalid bulk operation: {0}")]
+    Invalid(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BulkTaskOperation {
+    SetStatus {
+        status: TaskStatus,
+    },
+    Delete,
+    /// Add labels by name, creating labels the project doesn't have yet
+    AddLabels {
+        labels: Vec<String>,
+    },
+    /// Remove labels by name, ignoring case
+    RemoveLabels {
+        labels: Vec<String>,
+    },
+    /// Start a new attempt on every task
+    StartAttempt {
+        executor_profile_id: ExecutorProfileId,
+        /// Repositories and target branches of the attempts. Defaults to those of each
+        /// task's latest attempt.
+        #[serde(default)]
+        repos: Vec<CreateWorkspaceRepo>,
+    },
+}
+
+impl BulkTaskOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::SetStatus { .. } => "set_status",
+            Self::Delete => "delete",
+            Self::AddLabels { .. } => "add_labels",
+            Self::RemoveLabels { .. } => "remove_labels",
+            Self::StartAttempt { .. } => "start_attempt",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct BulkTaskRequest {
+    pub task_ids: Vec<Uuid>,
+    pub operation: BulkTaskOperation,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct BulkTaskResult {
+    pub task_id: Uuid,
+    pub success: bool,
+    /// Why the task was skipped, or why its attempt failed to start
+    pub error: Option<String>,
+    /// Attempt created by a `start_attempt` operation
+    pub workspace_id: Option<Uuid>,
+}
+
+impl BulkTaskResult {
+    fn succeeded(task_id: Uuid) -> Self {
+        Self {
+            task_id,
+            success: true,
+            error: None,
+            workspace_id: None,
+        }
+    }
+
+    fn failed(task_id: Uuid, error: impl Into<String>) -> Self {
+        Self {
+            task_id,
+            success: false,
+            error: Some(error.into()),
+            workspace_id: None,
+        }
+    }
+}
+
+/// A task that passed the checks, with what the operation needs to apply to it
+struct Target {
+    task: Task,
+    rowid: i64,
+    repos: Vec<CreateWorkspaceRepo>,
+}
+
+/// Apply `request` to its tasks and return one result per task, in request order.
+/// `publisher` should only be given while signed in; shared tasks are skipped without it.
+pub async fn apply<C: ContainerService + Sync>(
+    container: &C,
+    events: &EventService,
+    publisher: Option<&SharePublisher>,
+    request: &BulkTaskRequest,
+) -> Result<Vec<BulkTaskResult>, TaskBulkError> {
+    let pool = &container.db().pool;
+    let operation = &request.operation;
+
+    let task_ids = unique_task_ids(&request.task_ids);
+    if task_ids.is_empty() {
+        return Err(TaskBulkError::Invalid("no tasks given".to_string()));
+    }
+    if let BulkTaskOperation::AddLabels { labels } | BulkTaskOperation::RemoveLabels { labels } =
+        operation
+        && labels.iter().all(|label| label.trim().is_empty())
+    {
+        return Err(TaskBulkError::Invalid("no labels given".to_string()));
+    }
+
+    let mut results: HashMap<Uuid, BulkTaskResult> = HashMap::new();
+    let mut targets = Vec::new();
+    for &task_id in &task_ids {
+        match check_task(container, publisher, operation, task_id).await? {
+            Ok(target) => targets.push(target),
+            Err(reason) => {
+                results.insert(task_id, BulkTaskResult::failed(task_id, reason));
+            }
+        }
+    }
+
+    // Gather what deleted tasks leave behind before their rows cascade away
+    let mut cleanup: Vec<(Uuid, Vec<PathBuf>, Vec<Repo>)> = Vec::new();
+    let mut workspaces_by_task: HashMap<Uuid, Vec<Workspace>> = HashMap::new();
+    if matches!(operation, BulkTaskOperation::Delete) {
+        for target in &targets {
+            let workspaces = Workspace::fetch_all(pool, Some(target.task.id)).await?;
+            let dirs = workspaces
+                .iter()
+                .filter_map(|w| w.container_ref.as_ref().map(PathBuf::from))
+                .collect();
+            let repos = WorkspaceRepo::find_unique_repos_for_task(pool, target.task.id).await?;
+            cleanup.push((target.task.id, dirs, repos));
+            workspaces_by_task.insert(target.task.id, workspaces);
+        }
+    }
+    let mut agent_working_dirs: HashMap<Uuid, Option<String>> = HashMap::new();
+    if matches!(operation, BulkTaskOperation::StartAttempt { .. }) {
+        for target in &targets {
+            if !agent_working_dirs.contains_key(&target.task.project_id) {
+                let dir = Project::find_by_id(pool, target.task.project_id)
+                    .await?
+                    .and_then(|project| project.default_agent_working_dir)
+                    .filter(|dir| !dir.is_empty());
+                agent_working_dirs.insert(target.task.project_id, dir);
+            }
+        }
+    }
+
+    // Keep the hooks from publishing a patch per task until the batch is published below
+    let muted = events.muted_tasks().mute(
+        targets
+            .iter()
+            .map(|target| (target.task.id, target.rowid))
+            .collect(),
+    );
+
+    let mut workspaces = Vec::new();
+    let mut tx = pool.begin().await?;
+    for target in &targets {
+        let task = &target.task;
+        match operation {
+            BulkTaskOperation::SetStatus { status } => {
+                Task::update_status(&mut *tx, task.id, status.clone()).await?;
+            }
+            BulkTaskOperation::Delete => {
+                for workspace in workspaces_by_task.get(&task.id).into_iter().flatten() {
+                    Task::nullify_children_by_workspace_id(&mut *tx, workspace.id).await?;
+                }
+                Task::delete(&mut *tx, task.id).await?;
+            }
+            BulkTaskOperation::AddLabels { labels } => {
+                let mut names = label_names(&mut tx, task.id).await?;
+                add_label_names(&mut names, labels);
+                Label::set_for_task(&mut *tx, task.project_id, task.id, &names).await?;
+            }
+            BulkTaskOperation::RemoveLabels { labels } => {
+                let mut names = label_names(&mut tx, task.id).await?;
+                remove_label_names(&mut names, labels);
+                Label::set_for_task(&mut *tx, task.project_id, task.id, &names).await?;
+            }
+            BulkTaskOperation::StartAttempt { .. } => {
+                let attempt_id = Uuid::new_v4();
+                let branch = container
+                    .git_branch_from_workspace(&attempt_id, &task.title)
+                    .await;
+                let workspace = Workspace::create(
+                    &mut *tx,
+                    &CreateWorkspace {
+                        branch,
+                        agent_working_dir: agent_working_dirs
+                            .get(&task.project_id)
+                            .cloned()
+                            .flatten(),
+                    },
+                    attempt_id,
+                    task.id,
+                )
+                .await?;
+                WorkspaceRepo::create_many(&mut *tx, workspace.id, &target.repos).await?;
+                workspaces.push(workspace);
+            }
+        }
+    }
+    tx.commit().await?;
+
+    for target in &targets {
+        results.insert(target.task.id, BulkTaskResult::succeeded(target.task.id));
+    }
+
+    let (changed, deleted): (Vec<Uuid>, Vec<(Uuid, Uuid)>) = match operation {
+        BulkTaskOperation::Delete => (
+            Vec::new(),
+            targets
+                .iter()
+                .map(|target| (target.task.id, target.task.project_id))
+                .collect(),
+        ),
+        _ => (
+            targets.iter().map(|target| target.task.id).collect(),
+            Vec::new(),
+        ),
+    };
+    if let Err(e) = events.push_task_batch(&changed, &deleted).await {
+        tracing::error!("Failed to publish bulk task changes: {}", e);
+    }
+    // Later changes, such as attempts moving tasks to in progress, are published as usual
+    drop(muted);
+
+    if let BulkTaskOperation::StartAttempt {
+        executor_profile_id,
+        ..
+    } = operation
+    {
+        for workspace in workspaces {
+            let result = results
+                .get_mut(&workspace.task_id)
+                .expect("every started task has a result");
+            match container
+                .start_workspace(&workspace, executor_profile_id.clone(), None)
+                .await
+            {
+                Ok(_) => result.workspace_id = Some(workspace.id),
+                Err(e) => {
+                    tracing::error!("Failed to start attempt {}: {}", workspace.id, e);
+                    discard_workspace(container, &workspace).await;
+                    result.success = false;
+                    result.error = Some(format!("Failed to start attempt: {e}"));
+                }
+            }
+        }
+    }
+
+    if let Some(publisher) = publisher {
+        sync_shared_tasks(pool, publisher, operation, &targets).await;
+    }
+
+    if !cleanup.is_empty() {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            for (task_id, dirs, repos) in &cleanup {
+                for dir in dirs {
+                    if let Err(e) = WorkspaceManager::cleanup_workspace(dir, repos).await {
+                        tracing::error!(
+                            "Background workspace cleanup failed for task {} at {}: {}",
+                            task_id,
+                            dir.display(),
+                            e
+                        );
+                    }
+                }
+            }
+            if let Err(e) = Repo::delete_orphaned(&pool).await {
+                tracing::error!("Failed to delete orphaned repos: {}", e);
+            }
+        });
+    }
+
+    tracing::info!(
+        "Applied bulk {} to {} of {} tasks",
+        operation.name(),
+        targets.len(),
+        task_ids.len()
+    );
+    Ok(ordered_results(&task_ids, results))
+}
+
+/// Drop repeated task IDs, keeping the first occurrence
+fn unique_task_ids(task_ids: &[Uuid]) -> Vec<Uuid> {
+    let mut seen = HashSet::new();
+    task_ids
+        .iter()
+        .copied()
+        .filter(|id| seen.insert(*id))
+        .collect()
+}
+
+/// One result per task, in request order
+fn ordered_results(
+    task_ids: &[Uuid],
+    mut results: HashMap<Uuid, BulkTaskResult>,
+) -> Vec<BulkTaskResult> {
+    task_ids
+        .iter()
+        .filter_map(|id| results.remove(id))
+        .collect()
+}
+
+/// Append the non-blank `labels` that aren't among `names` yet, ignoring case
+fn add_label_names(names: &mut Vec<String>, labels: &[String]) {
+    for label in labels.iter().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        if !names.iter().any(|name| name.eq_ignore_ascii_case(label)) {
+            names.push(label.to_string());
+        }
+    }
+}
+
+/// Remove `labels` from `names`, ignoring case
+fn remove_label_names(names: &mut Vec<String>, labels: &[String]) {
+    names.retain(|name| {
+        !labels
+            .iter()
+            .any(|label| label.trim().eq_ignore_ascii_case(name))
+    });
+}
+
+/// Remove an attempt that failed to start, so the task isn't left with an attempt that never
+/// ran. Its worktree is removed too if it was already created.
+async fn discard_workspace<C: ContainerService + Sync>(container: &C, workspace: &Workspace) {
+    let pool = &container.db().pool;
+    match Workspace::find_by_id(pool, workspace.id).await {
+        Ok(Some(workspace)) if workspace.container_ref.is_some() => {
+            if let Err(e) = container.delete(&workspace).await {
+                tracing::warn!(
+                    "Failed to clean up worktree of attempt {}: {}",
+                    workspace.id,
+                    e
+                );
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load attempt {}: {}", workspace.id, e),
+    }
+    if let Err(e) = Workspace::delete(pool, workspace.id).await {
+        tracing::error!("Failed to delete attempt {}: {}", workspace.id, e);
+    }
+}
+
+/// Load a task and check it can take the operation; `Ok(Err(reason))` skips it
+async fn check_task<C: ContainerService + Sync>(
+    container: &C,
+    publisher: Option<&SharePublisher>,
+    operation: &BulkTaskOperation,
+    task_id: Uuid,
+) -> Result<Result<Target, String>, TaskBulkError> {
+    let pool = &container.db().pool;
+    let (Some(task), Some(rowid)) = (
+        Task::find_by_id(pool, task_id).await?,
+        Task::find_rowid(pool, task_id).await?,
+    ) else {
+        return Ok(Err("Task not found".to_string()));
+    };
+    if task.archived_at.is_some() {
+        return Ok(Err("Task is archived; restore it first".to_string()));
+    }
+
+    let mut repos = Vec::new();
+    match operation {
+        BulkTaskOperation::StartAttempt {
+            repos: requested, ..
+        } => {
+            repos = if requested.is_empty() {
+                WorkspaceRepo::find_for_latest_workspace(pool, task.id).await?
+            } else {
+                requested.clone()
+            };
+            if repos.is_empty() {
+                return Ok(Err(
+                    "No repositories given and the task has no previous attempt".to_string(),
+                ));
+            }
+        }
+        _ if task.shared_task_id.is_some() && publisher.is_none() => {
+            return Ok(Err(
+                "Shared tasks can only be changed while signed in".to_string()
+            ));
+        }
+        BulkTaskOperation::Delete if container.has_running_processes(task.id).await? => {
+            return Ok(Err("Task has running execution processes".to_string()));
+        }
+        _ => {}
+    }
+
+    Ok(Ok(Target { task, rowid, repos }))
+}
+
+async fn label_names(
+    conn: &mut SqliteConnection,
+    task_id: Uuid,
+) -> Result<Vec<String>, sqlx::Error> {
+    Ok(Label::find_by_task_id(conn, task_id)
+        .await?
+        .into_iter()
+        .map(|label| label.name)
+        .collect())
+}
+
+/// Mirror committed changes of shared tasks to the organization. Failures are logged; the
+/// local changes stand.
+async fn sync_shared_tasks(
+    pool: &SqlitePool,
+    publisher: &SharePublisher,
+    operation: &BulkTaskOperation,
+    targets: &[Target],
+) {
+    for target in targets {
+        let Some(shared_task_id) = target.task.shared_task_id else {
+            continue;
+        };
+        let result = match operation {
+            BulkTaskOperation::Delete => publisher.delete_shared_task(shared_task_id).await,
+            BulkTaskOperation::StartAttempt { .. } => continue,
+            _ => match Task::find_by_id(pool, target.task.id).await {
+                Ok(Some(task)) => publisher.update_shared_task(&task).await,
+                Ok(None) => continue,
+                Err(e) => Err(e.into()),
+            },
+        };
+        if let Err(e) = result {
+            tracing::warn!(
+                "Failed to sync shared task {} of task {}: {}",
+                shared_task_id,
+                target.task.id,
+                e
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::Path, sync::Arc, time::Duration};
+
+    use anyhow::anyhow;
+    use async_trait::async_trait;
+    use db::{
+        DBService,
+        models::{
+            execution_process::{ExecutionContext, ExecutionProcess, ExecutionProcessStatus},
+            project::CreateProject,
+            task::CreateTask,
+        },
+        test_utils::test_pool,
+    };
+    use executors::{actions::ExecutorAction, executors::BaseCodingAgent};
+    use json_patch::PatchOperation;
+    use tokio::sync::RwLock;
+    use utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+    use super::*;
+    use crate::services::{
+        container::ContainerRef, events::MutedTasks, git::GitService,
+        notification::NotificationService, webhooks::WebhookService,
+    };
+
+    /// A container whose worktrees can never be created, so every attempt fails to start
+    struct NoWorktreeContainer {
+        db: DBService,
+        msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+        git: GitService,
+        notifications: NotificationService,
+        webhooks: WebhookService,
+    }
+
+    #[async_trait]
+    impl ContainerService for NoWorktreeContainer {
+        fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>> {
+            &self.msg_stores
+        }
+
+        fn db(&self) -> &DBService {
+            &self.db
+        }
+
+        fn git(&self) -> &GitService {
+            &self.git
+        }
+
+        fn share_publisher(&self) -> Option<&SharePublisher> {
+            None
+        }
+
+        fn notification_service(&self) -> &NotificationService {
+            &self.notifications
+        }
+
+        fn webhooks(&self) -> &WebhookService {
+            &self.webhooks
+        }
+
+        fn workspace_to_current_dir(&self, workspace: &Workspace) -> PathBuf {
+            PathBuf::from(workspace.container_ref.clone().unwrap_or_default())
+        }
+
+        async fn create(&self, _workspace: &Workspace) -> Result<ContainerRef, ContainerError> {
+            Err(ContainerError::Other(anyhow!("no worktrees in tests")))
+        }
+
+        async fn kill_all_running_processes(&self) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn delete(&self, _workspace: &Workspace) -> Result<(), ContainerError> {
+            Ok(())
+        }
+
+        async fn ensure_container_exists(
+            &self,
+            _workspace: &Workspace,
+        ) -> Result<ContainerRef, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn is_container_clean(&self, _workspace: &Workspace) -> Result<bool, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn start_execution_inner(
+            &self,
+            _workspace: &Workspace,
+            _execution_process: &ExecutionProcess,
+            _executor_action: &ExecutorAction,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn stop_execution(
+            &self,
+            _execution_process: &ExecutionProcess,
+            _status: ExecutionProcessStatus,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn try_commit_changes(
+            &self,
+            _ctx: &ExecutionContext,
+        ) -> Result<bool, ContainerError> {
+            unimplemented!()
+        }
+
+        async fn copy_project_files(
+            &self,
+            _source_dir: &Path,
+            _target_dir: &Path,
+            _copy_files: &str,
+        ) -> Result<(), ContainerError> {
+            unimplemented!()
+        }
+
+        async fn stream_diff(
+            &self,
+            _workspace: &Workspace,
+            _stats_only: bool,
+        ) -> Result<
+            futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>,
+            ContainerError,
+        > {
+            unimplemented!()
+        }
+
+        async fn git_branch_prefix(&self) -> String {
+            "vk".to_string()
+        }
+    }
+
+    struct Fixture {
+        pool: SqlitePool,
+        container: NoWorktreeContainer,
+        events: EventService,
+        msg_store: Arc<MsgStore>,
+        project_id: Uuid,
+    }
+
+    /// A project in an in-memory database whose connection publishes row changes like the
+    /// app's does
+    async fn fixture() -> Fixture {
+        let pool = test_pool().await;
+        let db = DBService { pool: pool.clone() };
+        let msg_store = Arc::new(MsgStore::new());
+        let muted = MutedTasks::default();
+        let hook = EventService::create_hook(
+            msg_store.clone(),
+            Default::default(),
+            db.clone(),
+            muted.clone(),
+        );
+        let mut conn = pool.acquire().await.unwrap();
+        hook(&mut *conn).await.unwrap();
+        drop(conn);
+
+        let project = Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap();
+        Fixture {
+            events: EventService::new(db.clone(), msg_store.clone(), Default::default(), muted),
+            container: NoWorktreeContainer {
+                msg_stores: Default::default(),
+                git: GitService::default(),
+                notifications: NotificationService::new(Default::default()),
+                webhooks: WebhookService::new(db.clone()),
+                db,
+            },
+            pool,
+            msg_store,
+            project_id: project.id,
+        }
+    }
+
+    async fn create_task(pool: &SqlitePool, project_id: Uuid, title: &str) -> Task {
+        Task::create(
+            pool,
+            &CreateTask::from_title_description(project_id, title.to_string(), None),
+            Uuid::new_v4(),
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Task paths of every published patch that touches tasks
+    async fn task_patches(msg_store: &MsgStore) -> Vec<Vec<String>> {
+        // Let the update hooks' spawned publishers run
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        msg_store
+            .get_history()
+            .into_iter()
+            .filter_map(|msg| match msg {
+                LogMsg::JsonPatch(patch) => Some(
+                    patch
+                        .0
+                        .iter()
+                        .filter_map(|op| match op {
+                            PatchOperation::Add(op) => Some(op.path.as_str().to_string()),
+                            PatchOperation::Replace(op) => Some(op.path.as_str().to_string()),
+                            PatchOperation::Remove(op) => Some(op.path.as_str().to_string()),
+                            _ => None,
+                        })
+                        .filter(|path| path.starts_with("/tasks/"))
+                        .collect::<Vec<_>>(),
+                ),
+                _ => None,
+            })
+            .filter(|paths| !paths.is_empty())
+            .collect()
+    }
+
+    fn set_status(task_ids: Vec<Uuid>) -> BulkTaskRequest {
+        BulkTaskRequest {
+            task_ids,
+            operation: BulkTaskOperation::SetStatus {
+                status: TaskStatus::Done,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_apply_publishes_one_patch_for_the_batch() {
+        let fixture = fixture().await;
+        let a = create_task(&fixture.pool, fixture.project_id, "a").await;
+        let b = create_task(&fixture.pool, fixture.project_id, "b").await;
+        let before = task_patches(&fixture.msg_store).await.len();
+
+        let missing = Uuid::new_v4();
+        let results = apply(
+            &fixture.container,
+            &fixture.events,
+            None,
+            &set_status(vec![a.id, missing, b.id, a.id]),
+        )
+        .await
+        .unwrap();
+
+        let outcomes: Vec<(Uuid, bool)> = results.iter().map(|r| (r.task_id, r.success)).collect();
+        assert_eq!(outcomes, vec![(a.id, true), (missing, false), (b.id, true)]);
+        for task in [&a, &b] {
+            let task = Task::find_by_id(&fixture.pool, task.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(task.status, TaskStatus::Done);
+        }
+
+        let patches = task_patches(&fixture.msg_store).await;
+        assert_eq!(patches.len(), before + 1, "{patches:?}");
+        let mut batch = patches.last().unwrap().clone();
+        batch.sort();
+        let mut expected = vec![format!("/tasks/{}", a.id), format!("/tasks/{}", b.id)];
+        expected.sort();
+        assert_eq!(batch, expected);
+
+        // Once the batch is published, changes to the tasks are published as usual again
+        Task::update_status(&fixture.pool, a.id, TaskStatus::Todo)
+            .await
+            .unwrap();
+        assert_eq!(task_patches(&fixture.msg_store).await.len(), before + 2);
+    }
+
+    #[tokio::test]
+    async fn test_apply_rolls_back_the_whole_batch_when_a_task_fails() {
+        let fixture = fixture().await;
+        let a = create_task(&fixture.pool, fixture.project_id, "a").await;
+        let b = create_task(&fixture.pool, fixture.project_id, "b").await;
+        sqlx::query(
+            "CREATE TRIGGER fail_b BEFORE UPDATE ON tasks WHEN OLD.title = 'b'
+             BEGIN SELECT RAISE(ABORT, 'cannot update b'); END",
+        )
+        .execute(&fixture.pool)
+        .await
+        .unwrap();
+        let before = task_patches(&fixture.msg_store).await.len();
+
+        let result = apply(
+            &fixture.container,
+            &fixture.events,
+            None,
+            &set_status(vec![a.id, b.id]),
+        )
+        .await;
+
+        assert!(matches!(result, Err(TaskBulkError::Database(_))));
+        let a = Task::find_by_id(&fixture.pool, a.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(a.status, TaskStatus::Todo);
+        assert_eq!(task_patches(&fixture.msg_store).await.len(), before);
+    }
+
+    #[tokio::test]
+    async fn test_start_attempt_failure_discards_the_workspace() {
+        let fixture = fixture().await;
+        let task = create_task(&fixture.pool, fixture.project_id, "a").await;
+        let repo = Repo::find_or_create(&fixture.pool, Path::new("/tmp/repo"), "repo")
+            .await
+            .unwrap();
+
+        let results = apply(
+            &fixture.container,
+            &fixture.events,
+            None,
+            &BulkTaskRequest {
+                task_ids: vec![task.id],
+                operation: BulkTaskOperation::StartAttempt {
+                    executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                    repos: vec![CreateWorkspaceRepo {
+                        repo_id: repo.id,
+                        target_branch: "main".to_string(),
+                    }],
+                },
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].success);
+        assert!(results[0].workspace_id.is_none());
+        assert!(
+            results[0]
+                .error
+                .as_deref()
+                .is_some_and(|error| error.starts_with("Failed to start attempt"))
+        );
+        assert!(
+            Workspace::fetch_all(&fixture.pool, Some(task.id))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_unique_task_ids_keeps_first_occurrence() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(unique_task_ids(&[a, b, a, b, a]), vec![a, b]);
+    }
+
+    #[test]
+    fn test_ordered_results_follow_request_order() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let results = HashMap::from([
+            (c, BulkTaskResult::succeeded(c)),
+            (a, BulkTaskResult::failed(a, "Task not found")),
+            (b, BulkTaskResult::succeeded(b)),
+        ]);
+
+        let results = ordered_results(&[a, b, c], results);
+        let ids: Vec<Uuid> = results.iter().map(|result| result.task_id).collect();
+        assert_eq!(ids, vec![a, b, c]);
+        assert!(!results[0].success);
+        assert_eq!(results[0].error.as_deref(), Some("Task not found"));
+        assert!(results[1].success && results[1].error.is_none());
+    }
+
+    #[test]
+    fn test_add_label_names_ignores_case_and_blanks() {
+        let mut current = names(&["bug"]);
+        add_label_names(&mut current, &names(&["BUG", " ui ", "", "ui"]));
+        assert_eq!(current, names(&["bug", "ui"]));
+    }
+
+    #[test]
+    fn test_remove_label_names_ignores_case() {
+        let mut current = names(&["bug", "ui", "backend"]);
+        remove_label_names(&mut current, &names(&[" UI ", "missing"]));
+        assert_eq!(current, names(&["bug", "backend"]));
+    }
+}
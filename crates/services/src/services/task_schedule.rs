@@ -0,0 +1,334 @@
+//! Runs task schedules: at each time a schedule's cron expression matches, a coding agent is
+//! started either on a fresh task or as a new attempt on an existing one. Every run is
+//! recorded, and a run is skipped while the attempt started by the previous one is still
+//! going.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use croner::Cron;
+use db::models::{
+    execution_process::ExecutionProcess,
+    project::Project,
+    task::{CreateTask, Task},
+    task_schedule::{
+        CreateTaskScheduleRun, TaskSchedule, TaskScheduleAction, TaskScheduleRun,
+        TaskScheduleRunStatus,
+    },
+    workspace::{CreateWorkspace, Workspace, WorkspaceError},
+    workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
+};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::services::container::{ContainerError, ContainerService};
+
+/// How often the scheduler looks for schedules that are due
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Error)]
+pub enum TaskScheduleError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
+    Container(#[from] ContainerError),
+    #[error("Invalid cron expression '{0}': {1}")]
+    InvalidCron(String, String),
+    #[error("Unknown timezone '{0}'")]
+    InvalidTimezone(String),
+    #[error("Invalid schedule: {0}")]
+    Invalid(String),
+}
+
+/// The first time after `after` that `cron_expression` matches in `timezone`
+pub fn next_run_after(
+    cron_expression: &str,
+    timezone: &str,
+    after: DateTime<Utc>,
+) -> Result<DateTime<Utc>, TaskScheduleError> {
+    let cron = Cron::new(cron_expression)
+        .parse()
+        .map_err(|e| TaskScheduleError::InvalidCron(cron_expression.to_string(), e.to_string()))?;
+    let tz: Tz = timezone
+        .parse()
+        .map_err(|_| TaskScheduleError::InvalidTimezone(timezone.to_string()))?;
+    let next = cron
+        .find_next_occurrence(&after.with_timezone(&tz), false)
+        .map_err(|e| TaskScheduleError::InvalidCron(cron_expression.to_string(), e.to_string()))?;
+    Ok(next.with_timezone(&Utc))
+}
+
+/// Check that a schedule has what its action needs; returns its next run time
+pub fn validate_schedule(
+    cron_expression: &str,
+    timezone: &str,
+    action: TaskScheduleAction,
+    title: Option<&str>,
+    task_id: Option<Uuid>,
+    repos: &[CreateWorkspaceRepo],
+) -> Result<DateTime<Utc>, TaskScheduleError> {
+    let next_run_at = next_run_after(cron_expression, timezone, Utc::now())?;
+    match action {
+        TaskScheduleAction::CreateTask => {
+            if title.is_none_or(|title| title.trim().is_empty()) {
+                return Err(TaskScheduleError::Invalid(
+                    "schedules that create tasks need a title".to_string(),
+                ));
+            }
+            if repos.is_empty() {
+                return Err(TaskScheduleError::Invalid(
+                    "schedules that create tasks need at least one repository".to_string(),
+                ));
+            }
+        }
+        TaskScheduleAction::RestartAttempt => {
+            if task_id.is_none() {
+                return Err(TaskScheduleError::Invalid(
+                    "schedules that restart attempts need a task".to_string(),
+                ));
+            }
+        }
+    }
+    Ok(next_run_at)
+}
+
+/// Run `schedule` now, unless the attempt started by its previous run is still going
+pub async fn run_schedule<C: ContainerService + Sync>(
+    container: &C,
+    schedule: &TaskSchedule,
+    scheduled_for: DateTime<Utc>,
+) -> Result<TaskScheduleRun, TaskScheduleError> {
+    let pool = &container.db().pool;
+
+    if let Some(previous) = TaskScheduleRun::find_latest_started(pool, schedule.id).await?
+        && let Some(workspace_id) = previous.workspace_id
+        && ExecutionProcess::has_running_non_dev_server_processes_for_workspace(pool, workspace_id)
+            .await?
+    {
+        tracing::info!(
+            "Skipping run of schedule '{}': attempt {} is still running",
+            schedule.name,
+            workspace_id
+        );
+        return Ok(TaskScheduleRun::create(
+            pool,
+            &CreateTaskScheduleRun {
+                schedule_id: schedule.id,
+                scheduled_for,
+                status: TaskScheduleRunStatus::Skipped,
+                task_id: previous.task_id,
+                workspace_id: Some(workspace_id),
+                error: None,
+            },
+        )
+        .await?);
+    }
+
+    let mut task_id = None;
+    let result = start_attempt(container, schedule, &mut task_id).await;
+    let run = match result {
+        Ok(workspace) => CreateTaskScheduleRun {
+            schedule_id: schedule.id,
+            scheduled_for,
+            status: TaskScheduleRunStatus::Started,
+            task_id,
+            workspace_id: Some(workspace.id),
+            error: None,
+        },
+        Err(e) => {
+            tracing::error!("Scheduled run of '{}' failed: {}", schedule.name, e);
+            CreateTaskScheduleRun {
+                schedule_id: schedule.id,
+                scheduled_for,
+                status: TaskScheduleRunStatus::Failed,
+                task_id,
+                workspace_id: None,
+                error: Some(e.to_string()),
+            }
+        }
+    };
+    Ok(TaskScheduleRun::create(pool, &run).await?)
+}
+
+/// Create the task if needed and start an attempt on it. `task_id` is set as soon as the
+/// task is known so failed runs still point at it.
+async fn start_attempt<C: ContainerService + Sync>(
+    container: &C,
+    schedule: &TaskSchedule,
+    task_id: &mut Option<Uuid>,
+) -> Result<Workspace, TaskScheduleError> {
+    let pool = &container.db().pool;
+
+    let project = Project::find_by_id(pool, schedule.project_id)
+        .await?
+        .ok_or_else(|| TaskScheduleError::Invalid("the project no longer exists".to_string()))?;
+    if project.archived_at.is_some() {
+        return Err(TaskScheduleError::Invalid(
+            "the project is archived".to_string(),
+        ));
+    }
+
+    let task = match schedule.action {
+        TaskScheduleAction::CreateTask => {
+            let title = schedule
+                .title
+                .clone()
+                .ok_or_else(|| TaskScheduleError::Invalid("missing task title".to_string()))?;
+            Task::create(
+                pool,
+                &CreateTask::from_title_description(
+                    schedule.project_id,
+                    title,
+                    schedule.description.clone(),
+                ),
+                Uuid::new_v4(),
+            )
+            .await?
+        }
+        TaskScheduleAction::RestartAttempt => {
+            let id = schedule
+                .task_id
+                .ok_or_else(|| TaskScheduleError::Invalid("missing task".to_string()))?;
+            let task = Task::find_by_id(pool, id).await?.ok_or_else(|| {
+                TaskScheduleError::Invalid("the task no longer exists".to_string())
+            })?;
+            if task.archived_at.is_some() {
+                return Err(TaskScheduleError::Invalid(
+                    "the task is archived".to_string(),
+                ));
+            }
+            task
+        }
+    };
+    *task_id = Some(task.id);
+
+    let repos = if schedule.repos.is_empty() {
+        WorkspaceRepo::find_for_latest_workspace(pool, task.id).await?
+    } else {
+        schedule.repos.0.clone()
+    };
+    if repos.is_empty() {
+        return Err(TaskScheduleError::Invalid(
+            "no repositories to start the attempt on; the task has no previous attempt".to_string(),
+        ));
+    }
+
+    let agent_working_dir = project
+        .default_agent_working_dir
+        .filter(|dir| !dir.is_empty());
+    let attempt_id = Uuid::new_v4();
+    let branch = container
+        .git_branch_from_workspace(&attempt_id, &task.title)
+        .await;
+    let workspace = Workspace::create(
+        pool,
+        &CreateWorkspace {
+            branch,
+            agent_working_dir,
+        },
+        attempt_id,
+        task.id,
+    )
+    .await?;
+    WorkspaceRepo::create_many(pool, workspace.id, &repos).await?;
+
+    container
+        .start_workspace(&workspace, schedule.executor_profile_id.0.clone(), None)
+        .await?;
+    tracing::info!(
+        "Schedule '{}' started attempt {} on task {}",
+        schedule.name,
+        workspace.id,
+        task.id
+    );
+    Ok(workspace)
+}
+
+/// Run every schedule that is due. Occurrences missed while the server was stopped are
+/// collapsed into a single run.
+async fn run_due_schedules<C: ContainerService + Sync>(
+    container: &C,
+) -> Result<(), TaskScheduleError> {
+    let pool = &container.db().pool;
+    let now = Utc::now();
+
+    for schedule in TaskSchedule::find_due(pool, now).await? {
+        let next_run_at = match next_run_after(&schedule.cron_expression, &schedule.timezone, now) {
+            Ok(next) => Some(next),
+            Err(e) => {
+                tracing::error!("Disabling runs of schedule '{}': {}", schedule.name, e);
+                None
+            }
+        };
+        // Advance the schedule before running so a slow start can't run it twice
+        TaskSchedule::set_run_times(pool, schedule.id, now, next_run_at).await?;
+
+        let scheduled_for = schedule.next_run_at.unwrap_or(now);
+        if let Err(e) = run_schedule(container, &schedule, scheduled_for).await {
+            tracing::error!(
+                "Failed to record run of schedule '{}': {}",
+                schedule.name,
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn spawn_scheduler<C>(container: C) -> JoinHandle<()>
+where
+    C: ContainerService + Clone + Send + Sync + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if let Err(e) = run_due_schedules(&container).await {
+                tracing::error!("Task scheduler failed: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn next_run_is_evaluated_in_the_schedule_timezone() {
+        let after = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        // 03:00 in Berlin is 02:00 UTC in winter
+        let next = next_run_after("0 3 * * *", "Europe/Berlin", after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 16, 2, 0, 0).unwrap());
+
+        let next = next_run_after("0 3 * * *", "UTC", after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 16, 3, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn next_run_is_strictly_after() {
+        let after = Utc.with_ymd_and_hms(2025, 1, 13, 9, 0, 0).unwrap();
+        // 2025-01-13 is a Monday
+        let next = next_run_after("0 9 * * MON", "UTC", after).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2025, 1, 20, 9, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn rejects_invalid_expressions_and_timezones() {
+        let now = Utc::now();
+        assert!(matches!(
+            next_run_after("every day", "UTC", now),
+            Err(TaskScheduleError::InvalidCron(..))
+        ));
+        assert!(matches!(
+            next_run_after("0 3 * * *", "Mars/Olympus_Mons", now),
+            Err(TaskScheduleError::InvalidTimezone(_))
+        ));
+    }
+}
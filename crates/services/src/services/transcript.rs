@@ -0,0 +1,561 @@
+//! Render the normalized conversation of a session as a Markdown or standalone HTML
+//! transcript, e.g. for attaching to pull requests or incident write-ups.
+
+use std::{fmt::Write as _, sync::LazyLock};
+
+use chrono::{DateTime, Utc};
+use executors::logs::{
+    ActionType, CommandExitStatus, CommandRunResult, FileChange, NormalizedEntry,
+    NormalizedEntryType, ToolResult, ToolResultValueType, ToolStatus,
+};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, html};
+use regex::Regex;
+use serde::Deserialize;
+use ts_rs::TS;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// `NAME=value` assignments, as found in shell commands, `.env` files and `env` output
+static ENV_ASSIGNMENT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"\b([A-Z][A-Z0-9_]*)=("[^"\n]*"|'[^'\n]*'|[^\s"'`]+)"#).unwrap());
+
+/// `"NAME": "value"` pairs, as found in JSON env blocks and tool arguments
+static ENV_JSON_PAIR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""([A-Z][A-Z0-9_]*)"(\s*:\s*)"[^"\n]*""#).unwrap());
+
+static SECRET_ENV_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(TOKEN|SECRET|PASSWORD|PASSWD|API_?KEY|PRIVATE_?KEY|CREDENTIAL|AUTH)").unwrap()
+});
+
+/// The only raw HTML passed through to HTML transcripts: the `<details>` markup emitted by
+/// [`details`], whose summary is escaped when written
+static ALLOWED_HTML: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(<details>|</details>|<summary>[^<>]*</summary>)$").unwrap());
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, TS, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl TranscriptFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "text/markdown; charset=utf-8",
+            TranscriptFormat::Html => "text/html; charset=utf-8",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, TS)]
+pub struct TranscriptOptions {
+    #[serde(default)]
+    pub format: TranscriptFormat,
+    /// Fold command output, tool results and diffs into `<details>` blocks
+    #[serde(default)]
+    pub collapse_tool_output: bool,
+    /// Mask `NAME=value` assignments and values of secret-looking environment variables
+    #[serde(default)]
+    pub redact_env: bool,
+}
+
+/// One coding agent execution within the session
+#[derive(Debug, Clone)]
+pub struct TranscriptSection {
+    pub executor: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub entries: Vec<NormalizedEntry>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Transcript {
+    pub title: String,
+    pub sections: Vec<TranscriptSection>,
+}
+
+impl Transcript {
+    pub fn render(&self, options: &TranscriptOptions) -> String {
+        let mut markdown = self.to_markdown(options.collapse_tool_output);
+        if options.redact_env {
+            markdown = redact_env(&markdown, &secret_env_values());
+        }
+
+        match options.format {
+            TranscriptFormat::Markdown => markdown,
+            TranscriptFormat::Html => to_html_document(&self.title, &markdown),
+        }
+    }
+
+    fn to_markdown(&self, collapse: bool) -> String {
+        let mut out = format!("# {}\n\n", self.title.trim());
+        for (i, section) in self.sections.iter().enumerate() {
+            let _ = write!(out, "## Run {}", i + 1);
+            if let Some(executor) = &section.executor {
+                let _ = write!(out, " · {executor}");
+            }
+            let _ = writeln!(
+                out,
+                " · {}\n",
+                section.started_at.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            for entry in &section.entries {
+                render_entry(&mut out, entry, collapse);
+            }
+        }
+        out
+    }
+}
+
+fn render_entry(out: &mut String, entry: &NormalizedEntry, collapse: bool) {
+    let content = entry.content.trim();
+    match &entry.entry_type {
+        NormalizedEntryType::UserMessage => {
+            let _ = writeln!(out, "### User\n\n{content}\n");
+        }
+        NormalizedEntryType::UserFeedback { denied_tool } => {
+            let _ = writeln!(out, "> **Feedback on denied `{denied_tool}`:** {content}\n");
+        }
+        NormalizedEntryType::AssistantMessage => {
+            let _ = writeln!(out, "### Assistant\n\n{content}\n");
+        }
+        NormalizedEntryType::Thinking => {
+            details(out, "Thinking", &quote(content), collapse);
+        }
+        NormalizedEntryType::SystemMessage => {
+            let _ = writeln!(out, "> **System:** {content}\n");
+        }
+        NormalizedEntryType::ErrorMessage { .. } => {
+            let _ = writeln!(out, "> **Error:** {content}\n");
+        }
+        NormalizedEntryType::ToolUse {
+            tool_name,
+            action_type,
+            status,
+        } => render_tool_use(out, tool_name, action_type, status, content, collapse),
+        NormalizedEntryType::Loading | NormalizedEntryType::NextAction { .. } => {}
+    }
+}
+
+fn render_tool_use(
+    out: &mut String,
+    tool_name: &str,
+    action_type: &ActionType,
+    status: &ToolStatus,
+    content: &str,
+    collapse: bool,
+) {
+    let _ = write!(out, "**Tool: `{tool_name}`** ({})", status_label(status));
+    if !content.is_empty() {
+        let _ = write!(out, " — {}", content.replace('\n', " "));
+    }
+    out.push_str("\n\n");
+
+    match action_type {
+        ActionType::FileRead { path } => {
+            let _ = writeln!(out, "Read `{path}`\n");
+        }
+        ActionType::FileEdit { path, changes } => {
+            for change in changes {
+                match change {
+                    FileChange::Write { content } => {
+                        details(
+                            out,
+                            &format!("Wrote `{path}`"),
+                            &fenced(content, ""),
+                            collapse,
+                        );
+                    }
+                    FileChange::Delete => {
+                        let _ = writeln!(out, "Deleted `{path}`\n");
+                    }
+                    FileChange::Rename { new_path } => {
+                        let _ = writeln!(out, "Renamed `{path}` to `{new_path}`\n");
+                    }
+                    FileChange::Edit { unified_diff, .. } => {
+                        details(
+                            out,
+                            &format!("Edited `{path}`"),
+                            &fenced(unified_diff, "diff"),
+                            collapse,
+                        );
+                    }
+                }
+            }
+        }
+        ActionType::CommandRun { command, result } => {
+            out.push_str(&fenced(&format!("$ {command}"), "sh"));
+            out.push_str("\n\n");
+            if let Some(CommandRunResult {
+                exit_status,
+                output,
+            }) = result
+            {
+                if let Some(output) = output.as_deref().filter(|o| !o.trim().is_empty()) {
+                    details(out, "Output", &fenced(output, ""), collapse);
+                }
+                match exit_status {
+                    Some(CommandExitStatus::ExitCode { code }) => {
+                        let _ = writeln!(out, "Exit code: {code}\n");
+                    }
+                    Some(CommandExitStatus::Success { success: false }) => {
+                        out.push_str("Command failed\n\n");
+                    }
+                    _ => {}
+                }
+            }
+        }
+        ActionType::Search { query } => {
+            let _ = writeln!(out, "Searched for `{query}`\n");
+        }
+        ActionType::WebFetch { url } => {
+            let _ = writeln!(out, "Fetched <{url}>\n");
+        }
+        ActionType::Tool {
+            arguments, result, ..
+        } => {
+            if let Some(arguments) = arguments {
+                let pretty = serde_json::to_string_pretty(arguments).unwrap_or_default();
+                details(out, "Arguments", &fenced(&pretty, "json"), collapse);
+            }
+            if let Some(result) = result {
+                details(out, "Result", &tool_result_markdown(result), collapse);
+            }
+        }
+        ActionType::TaskCreate { description } => {
+            let _ = writeln!(out, "Created subtask: {description}\n");
+        }
+        ActionType::PlanPresentation { plan } => {
+            let _ = writeln!(out, "{}\n", quote(plan));
+        }
+        ActionType::TodoManagement { todos, .. } => {
+            for todo in todos {
+                let mark = if todo.status == "completed" { "x" } else { " " };
+                let _ = writeln!(out, "- [{mark}] {}", todo.content);
+            }
+            out.push('\n');
+        }
+        ActionType::Other { .. } => {}
+    }
+}
+
+fn status_label(status: &ToolStatus) -> String {
+    match status {
+        ToolStatus::Created => "started".to_string(),
+        ToolStatus::Success => "succeeded".to_string(),
+        ToolStatus::Failed => "failed".to_string(),
+        ToolStatus::Denied { reason, rule } => {
+            let mut label = "denied".to_string();
+            if let Some(rule) = rule {
+                label.push_str(&format!(" by rule \"{}\"", rule.rule_name));
+            }
+            if let Some(reason) = reason {
+                label.push_str(&format!(": {reason}"));
+            }
+            label
+        }
+        ToolStatus::AutoApproved { rule } => {
+            format!("auto-approved by rule \"{}\"", rule.rule_name)
+        }
+        ToolStatus::PendingApproval { .. } => "pending approval".to_string(),
+        ToolStatus::TimedOut => "approval timed out".to_string(),
+    }
+}
+
+fn tool_result_markdown(result: &ToolResult) -> String {
+    match (&result.r#type, &result.value) {
+        (ToolResultValueType::Markdown, serde_json::Value::String(markdown)) => {
+            markdown.trim().to_string()
+        }
+        (_, value) => fenced(
+            &serde_json::to_string_pretty(value).unwrap_or_default(),
+            "json",
+        ),
+    }
+}
+
+/// Emit `body` under a label, folded into a `<details>` block when collapsing
+fn details(out: &mut String, summary: &str, body: &str, collapse: bool) {
+    if collapse {
+        let _ = writeln!(
+            out,
+            "<details>\n<summary>{}</summary>\n\n{body}\n\n</details>\n",
+            escape_html(summary)
+        );
+    } else {
+        let _ = writeln!(out, "{summary}\n\n{body}\n");
+    }
+}
+
+/// Wrap `content` in a code fence longer than any backtick run inside it
+fn fenced(content: &str, lang: &str) -> String {
+    let longest_run = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{fence}{lang}\n{}\n{fence}", content.trim_end_matches('\n'))
+}
+
+fn quote(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| format!("> {line}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Values of environment variables of this process whose names look like secrets
+fn secret_env_values() -> Vec<String> {
+    std::env::vars()
+        .filter(|(name, value)| SECRET_ENV_NAME.is_match(name) && value.len() >= 8)
+        .map(|(_, value)| value)
+        .collect()
+}
+
+fn redact_env(text: &str, secret_values: &[String]) -> String {
+    let mut redacted = ENV_ASSIGNMENT
+        .replace_all(text, format!("$1={REDACTED}"))
+        .into_owned();
+    redacted = ENV_JSON_PAIR
+        .replace_all(&redacted, format!("\"$1\"$2\"{REDACTED}\""))
+        .into_owned();
+    for value in secret_values {
+        redacted = redacted.replace(value.as_str(), REDACTED);
+    }
+    redacted
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn to_html_document(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    // Agent output can contain arbitrary HTML; render anything but our own markup as text
+    let events = Parser::new_ext(markdown, options).map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) if !ALLOWED_HTML.is_match(raw.trim()) => {
+            Event::Text(raw)
+        }
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: safe_url(dest_url),
+            title,
+            id,
+        }),
+        event => event,
+    });
+    html::push_html(&mut body, events);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{title}</title>
+<style>
+body {{ font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", Helvetica, Arial, sans-serif; line-height: 1.5; color: #1f2328; max-width: 960px; margin: 2rem auto; padding: 0 1rem; }}
+pre {{ background: #f6f8fa; padding: 0.75rem; overflow-x: auto; border-radius: 6px; }}
+code {{ font-family: ui-monospace, SFMono-Regular, Menlo, monospace; font-size: 0.875em; }}
+blockquote {{ margin: 0; padding: 0 1rem; color: #59636e; border-left: 0.25rem solid #d1d9e0; }}
+details {{ margin-bottom: 1rem; }}
+summary {{ cursor: pointer; }}
+</style>
+</head>
+<body>
+{body}</body>
+</html>
+"#,
+        title = escape_html(title.trim()),
+    )
+}
+
+/// Keep link and image destinations that are relative or use http, https or mailto; anything
+/// else (`javascript:`, `data:`, ...) is replaced with `#`
+fn safe_url(dest_url: CowStr<'_>) -> CowStr<'_> {
+    // Browsers ignore whitespace and control characters when reading the scheme
+    let cleaned: String = dest_url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .collect();
+    let scheme = match cleaned.find([':', '/', '?', '#']) {
+        Some(end) if cleaned[end..].starts_with(':') => &cleaned[..end],
+        _ => return dest_url,
+    };
+    if ["http", "https", "mailto"]
+        .iter()
+        .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+    {
+        dest_url
+    } else {
+        CowStr::Borrowed("#")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(entry_type: NormalizedEntryType, content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn transcript() -> Transcript {
+        Transcript {
+            title: "Fix login".to_string(),
+            sections: vec![TranscriptSection {
+                executor: Some("CLAUDE_CODE".to_string()),
+                started_at: Utc::now(),
+                entries: vec![
+                    entry(NormalizedEntryType::UserMessage, "Fix the login bug"),
+                    entry(
+                        NormalizedEntryType::ToolUse {
+                            tool_name: "Bash".to_string(),
+                            action_type: ActionType::CommandRun {
+                                command: "DATABASE_URL=postgres://u:p@db cargo test".to_string(),
+                                result: Some(CommandRunResult {
+                                    exit_status: Some(CommandExitStatus::ExitCode { code: 0 }),
+                                    output: Some("test result: ok".to_string()),
+                                }),
+                            },
+                            status: ToolStatus::Success,
+                        },
+                        "",
+                    ),
+                    entry(NormalizedEntryType::AssistantMessage, "Done."),
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_markdown_collapses_tool_output() {
+        let options = TranscriptOptions {
+            collapse_tool_output: true,
+            ..Default::default()
+        };
+        let md = transcript().render(&options);
+
+        assert!(md.starts_with("# Fix login\n"));
+        assert!(md.contains("### User\n\nFix the login bug"));
+        assert!(md.contains("<summary>Output</summary>"));
+        assert!(md.contains("Exit code: 0"));
+    }
+
+    #[test]
+    fn test_redacts_env_assignments() {
+        let options = TranscriptOptions {
+            redact_env: true,
+            ..Default::default()
+        };
+        let md = transcript().render(&options);
+
+        assert!(md.contains("DATABASE_URL=[REDACTED] cargo test"));
+        assert!(!md.contains("postgres://"));
+    }
+
+    #[test]
+    fn test_redacts_json_pairs_and_secret_values() {
+        let text = r#"{"API_KEY": "abc", "path": "/tmp"} using s3cr3t-value"#;
+        let redacted = redact_env(text, &["s3cr3t-value".to_string()]);
+
+        assert_eq!(
+            redacted,
+            r#"{"API_KEY": "[REDACTED]", "path": "/tmp"} using [REDACTED]"#
+        );
+    }
+
+    #[test]
+    fn test_fence_outgrows_backticks_in_content() {
+        assert_eq!(fenced("a ```b``` c", ""), "````\na ```b``` c\n````");
+    }
+
+    #[test]
+    fn test_html_document_escapes_title() {
+        let options = TranscriptOptions {
+            format: TranscriptFormat::Html,
+            ..Default::default()
+        };
+        let mut transcript = transcript();
+        transcript.title = "<script>".to_string();
+        let html = transcript.render(&options);
+
+        assert!(html.contains("<title>&lt;script&gt;</title>"));
+        assert!(html.contains("<h3>User</h3>"));
+    }
+
+    #[test]
+    fn test_html_document_escapes_raw_html_in_entries() {
+        let options = TranscriptOptions {
+            format: TranscriptFormat::Html,
+            collapse_tool_output: true,
+            ..Default::default()
+        };
+        let mut transcript = transcript();
+        transcript.sections[0].entries.push(entry(
+            NormalizedEntryType::AssistantMessage,
+            "<img src=x onerror=alert(1)>\n\nInline <script>alert(1)</script> too",
+        ));
+        let html = transcript.render(&options);
+
+        assert!(!html.contains("<img"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+        assert!(html.contains("<details>"));
+        assert!(html.contains("<summary>Output</summary>"));
+    }
+
+    #[test]
+    fn test_html_document_drops_unsafe_link_schemes() {
+        let options = TranscriptOptions {
+            format: TranscriptFormat::Html,
+            ..Default::default()
+        };
+        let mut transcript = transcript();
+        transcript.sections[0].entries.push(entry(
+            NormalizedEntryType::AssistantMessage,
+            "[x](javascript:alert(1)) [y](<JavaScript\t:alert(1)>) ![z](data:image/svg+xml,x)\n\n\
+             [docs](https://example.com/a) [mail](mailto:a@example.com) [file](src/lib.rs)",
+        ));
+        let html = transcript.render(&options);
+
+        assert!(!html.to_lowercase().contains("javascript"));
+        assert!(!html.contains("data:"));
+        assert!(html.contains(r#"<a href="#">x</a>"#));
+        assert!(html.contains(r#"href="https://example.com/a""#));
+        assert!(html.contains(r#"href="mailto:a@example.com""#));
+        assert!(html.contains(r#"href="src/lib.rs""#));
+    }
+}
@@ -0,0 +1,447 @@
+//! Outbound webhooks for task, execution and PR lifecycle events. Every matching subscription
+//! gets a delivery row; bodies are HMAC-signed JSON, retried with exponential backoff, and can
+//! be redelivered from the API.
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use backon::{ExponentialBuilder, Retryable};
+use chrono::{DateTime, Utc};
+use db::{
+    DBService,
+    models::{
+        execution_process::ExecutionContext,
+        merge::PullRequestInfo,
+        task::{Task, TaskStatus, TaskWithAttemptStatus},
+        webhook::{WebhookDelivery, WebhookDeliveryStatus, WebhookEventType, WebhookSubscription},
+    },
+};
+use hmac::{Hmac, Mac};
+use json_patch::PatchOperation;
+use serde::Serialize;
+use serde_json::{Value, json};
+use sha2::Sha256;
+use thiserror::Error;
+use tokio::sync::broadcast::error::RecvError;
+use ts_rs::TS;
+use utils::{log_msg::LogMsg, msg_store::MsgStore};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const EVENT_HEADER: &str = "X-Vibe-Kanban-Event";
+pub const DELIVERY_HEADER: &str = "X-Vibe-Kanban-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-Vibe-Kanban-Timestamp";
+/// `sha256=<hex HMAC of "{timestamp}.{body}">`, present when the subscription has a secret
+pub const SIGNATURE_HEADER: &str = "X-Vibe-Kanban-Signature";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error(transparent)]
+    Database(#[from] sqlx::Error),
+    #[error("webhook delivery not found")]
+    DeliveryNotFound,
+    #[error("webhook subscription not found")]
+    SubscriptionNotFound,
+}
+
+#[derive(Debug, Error)]
+pub enum SendError {
+    #[error("endpoint responded with HTTP {0}")]
+    Status(u16),
+    #[error(transparent)]
+    Transport(#[from] reqwest::Error),
+}
+
+impl SendError {
+    pub fn should_retry(&self) -> bool {
+        match self {
+            // Client errors other than rate limiting won't succeed on retry
+            SendError::Status(status) => *status == 429 || *status >= 500,
+            SendError::Transport(_) => true,
+        }
+    }
+
+    fn status(&self) -> Option<i64> {
+        match self {
+            SendError::Status(status) => Some(i64::from(*status)),
+            SendError::Transport(e) => e.status().map(|s| i64::from(s.as_u16())),
+        }
+    }
+}
+
+/// JSON body of every webhook delivery
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct WebhookEnvelope {
+    pub delivery_id: Uuid,
+    pub event: WebhookEventType,
+    pub project_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    #[ts(type = "JsonValue")]
+    pub data: Value,
+}
+
+#[derive(Clone)]
+pub struct WebhookService {
+    db: DBService,
+    http: reqwest::Client,
+}
+
+impl WebhookService {
+    pub fn new(db: DBService) -> Self {
+        Self {
+            db,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Queue `event` for every subscription that matches it
+    pub async fn emit(&self, event: WebhookEventType, project_id: Option<Uuid>, data: Value) {
+        let subscriptions = match WebhookSubscription::find_all(&self.db.pool).await {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                tracing::error!("Failed to load webhook subscriptions: {}", e);
+                return;
+            }
+        };
+
+        for subscription in subscriptions
+            .into_iter()
+            .filter(|s| s.subscribes_to(event, project_id))
+        {
+            let envelope = WebhookEnvelope {
+                delivery_id: Uuid::new_v4(),
+                event,
+                project_id,
+                created_at: Utc::now(),
+                data: data.clone(),
+            };
+            let payload = serde_json::to_value(&envelope).unwrap_or_default();
+            match WebhookDelivery::create(
+                &self.db.pool,
+                envelope.delivery_id,
+                subscription.id,
+                event,
+                &payload,
+            )
+            .await
+            {
+                Ok(delivery) => self.spawn_delivery(subscription, delivery),
+                Err(e) => tracing::error!(
+                    "Failed to record {} delivery for webhook {}: {}",
+                    event,
+                    subscription.id,
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Send a recorded delivery again with its original body
+    pub async fn redeliver(&self, delivery_id: Uuid) -> Result<WebhookDelivery, WebhookError> {
+        let delivery = WebhookDelivery::find_by_id(&self.db.pool, delivery_id)
+            .await?
+            .ok_or(WebhookError::DeliveryNotFound)?;
+        let subscription = WebhookSubscription::find_by_id(&self.db.pool, delivery.subscription_id)
+            .await?
+            .ok_or(WebhookError::SubscriptionNotFound)?;
+
+        WebhookDelivery::set_status(&self.db.pool, delivery.id, WebhookDeliveryStatus::Pending)
+            .await?;
+        let delivery = WebhookDelivery::find_by_id(&self.db.pool, delivery_id)
+            .await?
+            .ok_or(WebhookError::DeliveryNotFound)?;
+        self.spawn_delivery(subscription, delivery.clone());
+        Ok(delivery)
+    }
+
+    pub async fn task_status_changed(&self, task: &Task, previous_status: &TaskStatus) {
+        self.emit(
+            WebhookEventType::TaskStatusChanged,
+            Some(task.project_id),
+            json!({
+                "task": task,
+                "previous_status": previous_status,
+                "status": task.status,
+            }),
+        )
+        .await;
+    }
+
+    pub async fn execution_finished(&self, ctx: &ExecutionContext) {
+        self.emit(
+            WebhookEventType::ExecutionFinished,
+            Some(ctx.task.project_id),
+            json!({
+                "task": ctx.task,
+                "workspace_id": ctx.workspace.id,
+                "branch": ctx.workspace.branch,
+                "session_id": ctx.session.id,
+                "executor": ctx.session.executor,
+                "execution_process_id": ctx.execution_process.id,
+                "run_reason": ctx.execution_process.run_reason,
+                "status": ctx.execution_process.status,
+                "exit_code": ctx.execution_process.exit_code,
+            }),
+        )
+        .await;
+    }
+
+    pub async fn pr_opened(&self, task: &Task, workspace_id: Uuid, pr: &PullRequestInfo) {
+        self.emit(
+            WebhookEventType::PrOpened,
+            Some(task.project_id),
+            json!({ "task": task, "workspace_id": workspace_id, "pr": pr }),
+        )
+        .await;
+    }
+
+    pub async fn pr_merged(&self, task: &Task, workspace_id: Uuid, pr: &PullRequestInfo) {
+        self.emit(
+            WebhookEventType::PrMerged,
+            Some(task.project_id),
+            json!({ "task": task, "workspace_id": workspace_id, "pr": pr }),
+        )
+        .await;
+    }
+
+    /// Watch the events stream for task patches and emit `task.status_changed` when a task's
+    /// status differs from the last one seen
+    pub fn spawn_task_status_listener(&self, events: Arc<MsgStore>) -> tokio::task::JoinHandle<()> {
+        let service = self.clone();
+        let mut receiver = events.get_receiver();
+        tokio::spawn(async move {
+            let mut statuses: HashMap<Uuid, TaskStatus> =
+                match Task::find_all(&service.db.pool).await {
+                    Ok(tasks) => tasks.into_iter().map(|t| (t.id, t.status)).collect(),
+                    Err(e) => {
+                        tracing::error!("Failed to load task statuses for webhooks: {}", e);
+                        HashMap::new()
+                    }
+                };
+
+            loop {
+                let patch = match receiver.recv().await {
+                    Ok(LogMsg::JsonPatch(patch)) => patch,
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Webhook task listener skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => return,
+                };
+
+                for op in patch.0 {
+                    let (path, value) = match op {
+                        PatchOperation::Add(op) => (op.path, op.value),
+                        PatchOperation::Replace(op) => (op.path, op.value),
+                        PatchOperation::Remove(op) => {
+                            if let Some(task_id) = task_id_from_path(op.path.as_str()) {
+                                statuses.remove(&task_id);
+                            }
+                            continue;
+                        }
+                        _ => continue,
+                    };
+                    if task_id_from_path(path.as_str()).is_none() {
+                        continue;
+                    }
+                    let Ok(task) = serde_json::from_value::<TaskWithAttemptStatus>(value) else {
+                        continue;
+                    };
+                    let task = task.task;
+
+                    if let Some(previous) = statuses.insert(task.id, task.status.clone())
+                        && previous != task.status
+                    {
+                        service.task_status_changed(&task, &previous).await;
+                    }
+                }
+            }
+        })
+    }
+
+    fn spawn_delivery(&self, subscription: WebhookSubscription, delivery: WebhookDelivery) {
+        let db = self.db.clone();
+        let http = self.http.clone();
+        tokio::spawn(async move {
+            let body = delivery.payload.0.to_string();
+            let event = delivery.event_type.to_string();
+            let result = (|| async {
+                let outcome = send(
+                    &http,
+                    &subscription.url,
+                    subscription.secret.as_deref(),
+                    &event,
+                    delivery.id,
+                    &body,
+                )
+                .await;
+                if let Err(e) = &outcome {
+                    // The delivery stays pending until the final outcome is known
+                    let _ = WebhookDelivery::record_attempt(
+                        &db.pool,
+                        delivery.id,
+                        WebhookDeliveryStatus::Pending,
+                        e.status(),
+                        Some(&e.to_string()),
+                    )
+                    .await;
+                }
+                outcome
+            })
+            .retry(retry_policy())
+            .when(SendError::should_retry)
+            .notify(|e, dur| {
+                tracing::warn!(
+                    "Webhook delivery {} failed, retrying after {:.2}s: {}",
+                    delivery.id,
+                    dur.as_secs_f64(),
+                    e
+                )
+            })
+            .await;
+
+            let recorded = match &result {
+                Ok(status) => {
+                    WebhookDelivery::record_attempt(
+                        &db.pool,
+                        delivery.id,
+                        WebhookDeliveryStatus::Succeeded,
+                        Some(i64::from(*status)),
+                        None,
+                    )
+                    .await
+                }
+                Err(e) => {
+                    tracing::warn!("Webhook delivery {} failed: {}", delivery.id, e);
+                    WebhookDelivery::set_status(
+                        &db.pool,
+                        delivery.id,
+                        WebhookDeliveryStatus::Failed,
+                    )
+                    .await
+                }
+            };
+            if let Err(e) = recorded {
+                tracing::error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+            }
+        });
+    }
+}
+
+/// `sha256=` followed by the hex HMAC-SHA256 of `{timestamp}.{body}`
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key size");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Backoff between attempts of a webhook delivery
+pub fn retry_policy() -> ExponentialBuilder {
+    ExponentialBuilder::default()
+        .with_min_delay(Duration::from_secs(1))
+        .with_max_delay(Duration::from_secs(60))
+        .with_max_times(5)
+        .with_jitter()
+}
+
+/// POST `body` with the event and delivery headers, signed when `secret` is set. Returns the
+/// HTTP status of a successful response.
+pub async fn send(
+    http: &reqwest::Client,
+    url: &str,
+    secret: Option<&str>,
+    event: &str,
+    delivery_id: Uuid,
+    body: &str,
+) -> Result<u16, SendError> {
+    let timestamp = Utc::now().timestamp();
+    let mut request = http
+        .post(url)
+        .timeout(REQUEST_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(EVENT_HEADER, event)
+        .header(DELIVERY_HEADER, delivery_id.to_string())
+        .header(TIMESTAMP_HEADER, timestamp.to_string());
+    if let Some(secret) = secret.filter(|s| !s.is_empty()) {
+        request = request.header(SIGNATURE_HEADER, sign(secret, timestamp, body));
+    }
+
+    let response = request.body(body.to_string()).send().await?;
+    let status = response.status().as_u16();
+    if response.status().is_success() {
+        Ok(status)
+    } else {
+        Err(SendError::Status(status))
+    }
+}
+
+fn task_id_from_path(path: &str) -> Option<Uuid> {
+    path.strip_prefix("/tasks/")
+        .and_then(|id| Uuid::parse_str(id).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_sign_is_stable_and_keyed() {
+        let signature = sign("secret", 1_700_000_000, r#"{"a":1}"#);
+        assert!(signature.starts_with("sha256="));
+        assert_eq!(signature.len(), "sha256=".len() + 64);
+        assert_eq!(signature, sign("secret", 1_700_000_000, r#"{"a":1}"#));
+        assert_ne!(signature, sign("other", 1_700_000_000, r#"{"a":1}"#));
+        assert_ne!(signature, sign("secret", 1_700_000_001, r#"{"a":1}"#));
+    }
+
+    #[test]
+    fn test_task_id_from_path() {
+        let id = Uuid::new_v4();
+        assert_eq!(task_id_from_path(&format!("/tasks/{id}")), Some(id));
+        assert_eq!(task_id_from_path(&format!("/projects/{id}")), None);
+    }
+
+    #[tokio::test]
+    async fn test_send_signs_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 16 * 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_lowercase()
+        });
+
+        let delivery_id = Uuid::new_v4();
+        let result = send(
+            &reqwest::Client::new(),
+            &url,
+            Some("secret"),
+            &WebhookEventType::PrMerged.to_string(),
+            delivery_id,
+            "{}",
+        )
+        .await;
+
+        let err = result.expect_err("500 is a failure");
+        assert!(err.should_retry());
+        assert_eq!(err.status(), Some(500));
+
+        let request = server.await.unwrap();
+        assert!(request.contains("x-vibe-kanban-event: pr.merged"));
+        assert!(request.contains(&format!("x-vibe-kanban-delivery: {delivery_id}")));
+        assert!(request.contains("x-vibe-kanban-signature: sha256="));
+    }
+}
@@ -56,6 +56,12 @@ pub struct WorkspaceManager;
 impl WorkspaceManager {
     /// Create a workspace with worktrees for all repositories.
     /// On failure, rolls back any already-created worktrees.
+    #[tracing::instrument(
+        target = "vk::lifecycle",
+        name = "workspace.create",
+        skip_all,
+        fields(workspace_dir = %workspace_dir.display(), repo_count = repos.len(), branch = branch_name)
+    )]
     pub async fn create_workspace(
         workspace_dir: &Path,
         repos: &[RepoWorkspaceInput],
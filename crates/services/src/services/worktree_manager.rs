@@ -55,6 +55,12 @@ pub struct WorktreeManager;
 
 impl WorktreeManager {
     /// Create a worktree with a new branch
+    #[tracing::instrument(
+        target = "vk::lifecycle",
+        name = "worktree.add",
+        skip_all,
+        fields(repo_path = %repo_path.display(), branch = branch_name, base_branch = base_branch)
+    )]
     pub async fn create_worktree(
         repo_path: &Path,
         branch_name: &str,
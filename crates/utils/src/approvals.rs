@@ -29,6 +29,12 @@ impl ApprovalRequest {
             timeout_at: now + Duration::seconds(APPROVAL_TIMEOUT_SECONDS),
         }
     }
+
+    /// Replace the default timeout, counted from `created_at`
+    pub fn with_timeout_seconds(mut self, seconds: i64) -> Self {
+        self.timeout_at = self.created_at + Duration::seconds(seconds);
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
@@ -52,6 +58,14 @@ pub enum ApprovalStatus {
     TimedOut,
 }
 
+/// Approval policy rule that decided a tool call without asking the user
+#[derive(Debug, Clone, Serialize, Deserialize, TS, PartialEq)]
+#[ts(export)]
+pub struct ApprovalRuleRef {
+    pub rule_id: Uuid,
+    pub rule_name: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct ApprovalResponse {
@@ -17,6 +17,7 @@ pub mod response;
 pub mod sentry;
 pub mod shell;
 pub mod stream_lines;
+pub mod telemetry;
 pub mod text;
 pub mod tokio;
 pub mod version;
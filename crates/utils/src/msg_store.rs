@@ -83,6 +83,11 @@ impl MsgStore {
         self.push(LogMsg::Finished);
     }
 
+    /// Bytes currently retained in the history buffer
+    pub fn history_bytes(&self) -> usize {
+        self.inner.read().unwrap().total_bytes
+    }
+
     pub fn get_receiver(&self) -> broadcast::Receiver<LogMsg> {
         self.sender.subscribe()
     }
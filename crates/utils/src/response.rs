@@ -53,4 +53,9 @@ impl<T, E> ApiResponse<T, E> {
     pub fn message(&self) -> Option<&str> {
         self.message.as_deref()
     }
+
+    /// Returns a reference to the structured error data if present.
+    pub fn error_data(&self) -> Option<&E> {
+        self.error_data.as_ref()
+    }
 }
@@ -0,0 +1,267 @@
+//! OpenTelemetry export of task attempt lifecycle spans. Disabled unless `VK_OTEL_ENDPOINT`
+//! (an OTLP/HTTP collector, e.g. `http://localhost:4318`) or `VK_OTEL_FILE` (a JSON-lines file)
+//! is set. Only spans with the [`LIFECYCLE_TARGET`] target are exported.
+
+use std::{
+    fs::{File, OpenOptions},
+    future::Future,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use chrono::{DateTime, Utc};
+use opentelemetry::{
+    Context,
+    trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider as _,
+    },
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    Resource,
+    error::{OTelSdkError, OTelSdkResult},
+    trace::{SdkTracerProvider, SpanData, SpanExporter},
+};
+use serde_json::{Map, Value, json};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::{Layer, filter::filter_fn, registry::LookupSpan};
+use uuid::Uuid;
+
+/// Target of every span meant for export, e.g.
+/// `tracing::info_span!(target: "vk::lifecycle", "workspace.create")`
+pub const LIFECYCLE_TARGET: &str = "vk::lifecycle";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Default)]
+pub struct TelemetryConfig {
+    pub otlp_endpoint: Option<String>,
+    pub file: Option<PathBuf>,
+}
+
+impl TelemetryConfig {
+    pub fn from_env() -> Self {
+        let var = |name| {
+            std::env::var(name)
+                .ok()
+                .filter(|v: &String| !v.trim().is_empty())
+        };
+        Self {
+            otlp_endpoint: var("VK_OTEL_ENDPOINT"),
+            file: var("VK_OTEL_FILE").map(PathBuf::from),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.otlp_endpoint.is_some() || self.file.is_some()
+    }
+}
+
+/// Flushes and shuts down the exporters when dropped
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("Failed to shut down OpenTelemetry exporter: {e}");
+        }
+    }
+}
+
+/// Whether lifecycle spans are being exported; lets callers skip work that only feeds spans
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Build the export layer, or `None` when telemetry is not configured. Runs before the
+/// subscriber is installed, so setup errors go to stderr.
+#[allow(clippy::type_complexity)]
+pub fn layer<S>(
+    config: &TelemetryConfig,
+    service_name: &'static str,
+) -> Option<(Box<dyn Layer<S> + Send + Sync + 'static>, TelemetryGuard)>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    if !config.is_enabled() {
+        return None;
+    }
+
+    let mut builder = SdkTracerProvider::builder()
+        .with_resource(Resource::builder().with_service_name(service_name).build());
+
+    if let Some(endpoint) = &config.otlp_endpoint {
+        match opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(traces_endpoint(endpoint))
+            .build()
+        {
+            Ok(exporter) => builder = builder.with_batch_exporter(exporter),
+            Err(e) => eprintln!("Failed to create OTLP exporter for {endpoint}: {e}"),
+        }
+    }
+    if let Some(path) = &config.file {
+        match FileSpanExporter::create(path) {
+            Ok(exporter) => builder = builder.with_batch_exporter(exporter),
+            Err(e) => eprintln!("Failed to open span file {}: {e}", path.display()),
+        }
+    }
+
+    let provider = builder.build();
+    let tracer = provider.tracer(service_name);
+    ENABLED.store(true, Ordering::Relaxed);
+
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(tracer)
+        .with_filter(filter_fn(|meta| meta.target() == LIFECYCLE_TARGET));
+    Some((Box::new(layer), TelemetryGuard { provider }))
+}
+
+/// Make `span` part of the trace for `workspace_id`, so every stage of one attempt (workspace
+/// setup, each execution, PR creation) lands in the same trace even across requests.
+pub fn attach_to_attempt(span: &tracing::Span, workspace_id: Uuid) {
+    if !is_enabled() {
+        return;
+    }
+    span.set_parent(Context::new().with_remote_span_context(attempt_span_context(workspace_id)));
+}
+
+fn attempt_span_context(workspace_id: Uuid) -> SpanContext {
+    let bytes = workspace_id.as_bytes();
+    let mut span_id = [0u8; 8];
+    span_id.copy_from_slice(&bytes[8..]);
+    SpanContext::new(
+        TraceId::from_bytes(*bytes),
+        SpanId::from_bytes(span_id),
+        TraceFlags::SAMPLED,
+        true,
+        TraceState::default(),
+    )
+}
+
+fn traces_endpoint(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}/v1/traces")
+    }
+}
+
+/// Appends one JSON object per finished span
+#[derive(Debug)]
+struct FileSpanExporter {
+    file: Mutex<File>,
+}
+
+impl FileSpanExporter {
+    fn create(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn write(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| OTelSdkError::InternalFailure("span file lock poisoned".to_string()))?;
+        for span in &batch {
+            writeln!(file, "{}", span_to_json(span))
+                .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+        }
+        file.flush()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))
+    }
+}
+
+impl SpanExporter for FileSpanExporter {
+    fn export(&self, batch: Vec<SpanData>) -> impl Future<Output = OTelSdkResult> + Send {
+        std::future::ready(self.write(batch))
+    }
+}
+
+fn span_to_json(span: &SpanData) -> Value {
+    let attributes: Map<String, Value> = span
+        .attributes
+        .iter()
+        .map(|kv| {
+            (
+                kv.key.as_str().to_string(),
+                Value::String(kv.value.as_str().into_owned()),
+            )
+        })
+        .collect();
+    let duration_ms = span
+        .end_time
+        .duration_since(span.start_time)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    json!({
+        "trace_id": span.span_context.trace_id().to_string(),
+        "span_id": span.span_context.span_id().to_string(),
+        "parent_span_id": span.parent_span_id.to_string(),
+        "name": span.name,
+        "start_time": DateTime::<Utc>::from(span.start_time),
+        "end_time": DateTime::<Utc>::from(span.end_time),
+        "duration_ms": duration_ms,
+        "status": format!("{:?}", span.status),
+        "attributes": attributes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_endpoint() {
+        assert_eq!(
+            traces_endpoint("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_endpoint("http://localhost:4318/v1/traces/"),
+            "http://localhost:4318/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_attempt_span_context_is_stable_per_workspace() {
+        let workspace_id = Uuid::new_v4();
+        let first = attempt_span_context(workspace_id);
+        let second = attempt_span_context(workspace_id);
+
+        assert_eq!(first.trace_id(), second.trace_id());
+        assert_eq!(first.span_id(), second.span_id());
+        assert_eq!(first.trace_id().to_bytes(), *workspace_id.as_bytes());
+        assert_ne!(
+            attempt_span_context(Uuid::new_v4()).trace_id(),
+            first.trace_id()
+        );
+    }
+
+    #[test]
+    fn test_disabled_without_config() {
+        assert!(!TelemetryConfig::default().is_enabled());
+        assert!(
+            TelemetryConfig {
+                otlp_endpoint: None,
+                file: Some(PathBuf::from("/tmp/spans.jsonl")),
+            }
+            .is_enabled()
+        );
+    }
+}
@@ -172,6 +172,11 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
             title: value.title,
             description: value.description,
             status: value.status,
+            priority: null,
+            due_date: null,
+            estimate_minutes: null,
+            clear: { priority: false, due_date: false, estimate_minutes: false },
+            labels: null,
             parent_workspace_id: null,
             image_ids: images.length > 0 ? images.map((img) => img.id) : null,
           },
@@ -186,6 +191,10 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
         title: value.title,
         description: value.description,
         status: null,
+        priority: null,
+        due_date: null,
+        estimate_minutes: null,
+        labels: null,
         parent_workspace_id:
           mode === 'subtask' ? props.parentTaskAttemptId : null,
         image_ids: imageIds,
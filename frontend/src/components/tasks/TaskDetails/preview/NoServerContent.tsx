@@ -131,6 +131,10 @@ export function NoServerContent({
         title: COMPANION_INSTALL_TASK_TITLE,
         description: COMPANION_INSTALL_TASK_DESCRIPTION,
         status: null,
+        priority: null,
+        due_date: null,
+        estimate_minutes: null,
+        labels: null,
         parent_workspace_id: null,
         image_ids: null,
         shared_task_id: null,
@@ -61,6 +61,10 @@ export function useAutoLinkSharedTasks({
           title: task.title,
           description: task.description,
           status: task.status,
+          priority: task.priority,
+          due_date: task.due_date,
+          estimate_minutes: task.estimate_minutes,
+          labels: task.labels,
         } as SharedTaskDetails,
         {
           onError: () => {
@@ -3,6 +3,7 @@
 import {
   ApprovalStatus,
   ApiResponse,
+  AuthStatus,
   Config,
   CreateFollowUpAttempt,
   EditorType,
@@ -117,16 +118,34 @@ export class ApiError<E = unknown> extends Error {
   }
 }
 
+// In remote access mode a 401 can mean the session expired; other 401s come from the
+// remote service and must not log the user out of the board
+const redirectIfSignedOut = async () => {
+  try {
+    const res = await fetch('/api/auth/status');
+    const result: ApiResponse<AuthStatus> = await res.json();
+    if (result.data?.required && !result.data.authenticated) {
+      window.location.assign('/api/auth/login');
+    }
+  } catch {
+    // Leave the original error to the caller
+  }
+};
+
 const makeRequest = async (url: string, options: RequestInit = {}) => {
   const headers = new Headers(options.headers ?? {});
   if (!headers.has('Content-Type')) {
     headers.set('Content-Type', 'application/json');
   }
 
-  return fetch(url, {
+  const response = await fetch(url, {
     ...options,
     headers,
   });
+  if (response.status === 401) {
+    await redirectIfSignedOut();
+  }
+  return response;
 };
 
 export type Ok<T> = { success: true; data: T };
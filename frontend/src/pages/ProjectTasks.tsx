@@ -762,6 +762,11 @@ export function ProjectTasks() {
           title: task.title,
           description: task.description,
           status: newStatus,
+          priority: null,
+          due_date: null,
+          estimate_minutes: null,
+          clear: { priority: false, due_date: false, estimate_minutes: false },
+          labels: null,
           parent_workspace_id: task.parent_workspace_id,
           image_ids: null,
         });
@@ -27,6 +27,12 @@ zip -q vibe-kanban-mcp.zip vibe-kanban-mcp
 rm -f vibe-kanban-mcp
 mv vibe-kanban-mcp.zip npx-cli/dist/macos-arm64/vibe-kanban-mcp.zip
 
+# Copy the vk command-line client
+cp target/release/vk vibe-kanban-vk
+zip -q vibe-kanban-vk.zip vibe-kanban-vk
+rm -f vibe-kanban-vk
+mv vibe-kanban-vk.zip npx-cli/dist/macos-arm64/vibe-kanban-vk.zip
+
 # Copy the Review CLI binary
 cp target/release/review vibe-kanban-review
 zip -q vibe-kanban-review.zip vibe-kanban-review
@@ -37,6 +43,7 @@ echo "✅ Build complete!"
 echo "📁 Files created:"
 echo "   - npx-cli/dist/macos-arm64/vibe-kanban.zip"
 echo "   - npx-cli/dist/macos-arm64/vibe-kanban-mcp.zip"
+echo "   - npx-cli/dist/macos-arm64/vibe-kanban-vk.zip"
 echo "   - npx-cli/dist/macos-arm64/vibe-kanban-review.zip"
 echo ""
 echo "🚀 To test locally, run:"
@@ -8,11 +8,15 @@ export type SharedTaskResponse = { task: SharedTask, user: UserData | null, };
 
 export type AssigneesQuery = { project_id: string, };
 
-export type SharedTask = { id: string, organization_id: string, project_id: string, creator_user_id: string | null, assignee_user_id: string | null, deleted_by_user_id: string | null, title: string, description: string | null, status: TaskStatus, deleted_at: string | null, shared_at: string | null, created_at: string, updated_at: string, };
+export type SharedTask = { id: string, organization_id: string, project_id: string, creator_user_id: string | null, assignee_user_id: string | null, deleted_by_user_id: string | null, title: string, description: string | null, status: TaskStatus, priority: TaskPriority | null, due_date: string | null, estimate_minutes: number | null, labels: Array<string>, deleted_at: string | null, shared_at: string | null, created_at: string, updated_at: string, };
 
 export type UserData = { user_id: string, first_name: string | null, last_name: string | null, username: string | null, };
 
-export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, created_at: Date, updated_at: Date, };
+export type Project = { id: string, name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, remote_project_id: string | null, 
+/**
+ * Set while the project is archived; archived projects are hidden from the project list
+ */
+archived_at: Date | null, created_at: Date, updated_at: Date, };
 
 export type CreateProject = { name: string, repositories: Array<CreateProjectRepo>, };
 
@@ -42,21 +46,238 @@ export type CreateTag = { tag_name: string, content: string, };
 
 export type UpdateTag = { tag_name: string | null, content: string | null, };
 
+export type Label = { id: string, project_id: string, 
+/**
+ * Unique within the project, ignoring case
+ */
+name: string, 
+/**
+ * CSS color, e.g. `#d73a4a`
+ */
+color: string | null, created_at: string, updated_at: string, };
+
+export type CreateLabel = { project_id: string, name: string, color: string | null, };
+
+export type UpdateLabel = { name: string | null, 
+/**
+ * An empty string removes the color
+ */
+color: string | null, };
+
+export type ApprovalPolicyAction = "approve" | "deny";
+
+export type ApprovalPatternKind = "glob" | "regex";
+
+export type ApprovalPolicyRule = { id: string, project_id: string, 
+/**
+ * Evaluation order within the project, lowest first
+ */
+position: bigint, name: string, 
+/**
+ * Glob on the tool name (case-insensitive); matches every tool when unset
+ */
+tool_name: string | null, 
+/**
+ * Pattern on the tool input: each segment of a command line, file paths relative to
+ * the worktree, and (deny rules only) the JSON input; matches every input when unset
+ */
+pattern: string | null, pattern_kind: ApprovalPatternKind, action: ApprovalPolicyAction, enabled: boolean, created_at: string, updated_at: string, };
+
+export type CreateApprovalPolicyRule = { name: string, tool_name: string | null, pattern: string | null, pattern_kind: ApprovalPatternKind, action: ApprovalPolicyAction, };
+
+export type UpdateApprovalPolicyRule = { name: string | null, tool_name: string | null, pattern: string | null, pattern_kind: ApprovalPatternKind | null, action: ApprovalPolicyAction | null, enabled: boolean | null, };
+
+export type ReorderApprovalPolicyRules = { 
+/**
+ * Rule ids in their new evaluation order
+ */
+rule_ids: Array<string>, };
+
+export type ApprovalOutcome = "pending" | "approved" | "denied" | "timed_out";
+
+/**
+ * Who resolved an approval request
+ */
+export type ApprovalDecisionSource = "user" | "policy" | "timeout";
+
+export type ApprovalRecord = { id: string, execution_process_id: string, tool_name: string, tool_input: JsonValue, tool_call_id: string, status: ApprovalOutcome, denial_reason: string | null, decided_by: ApprovalDecisionSource | null, 
+/**
+ * Approval policy rule that decided the request, if any
+ */
+rule_id: string | null, responder: string | null, 
+/**
+ * Time from request to decision
+ */
+latency_ms: bigint | null, created_at: string, timeout_at: string, responded_at: string | null, };
+
+export type WebhookEventType = "task.status_changed" | "execution.finished" | "pr.opened" | "pr.merged";
+
+export type WebhookSubscription = { id: string, name: string, url: string, 
+/**
+ * Events to deliver; empty means every event
+ */
+events: Array<WebhookEventType>, 
+/**
+ * Only deliver events for this project; `null` means every project
+ */
+project_id: string | null, enabled: boolean, created_at: string, updated_at: string, };
+
+export type CreateWebhookSubscription = { name: string, url: string, secret: string | null, events: Array<WebhookEventType>, project_id: string | null, };
+
+export type UpdateWebhookSubscription = { name: string | null, url: string | null, 
+/**
+ * An empty string removes the secret
+ */
+secret: string | null, events: Array<WebhookEventType> | null, project_id: string | null, enabled: boolean | null, };
+
+export type WebhookDeliveryStatus = "pending" | "succeeded" | "failed";
+
+export type WebhookDelivery = { id: string, subscription_id: string, event_type: WebhookEventType, 
+/**
+ * The JSON body that was sent
+ */
+payload: JsonValue, status: WebhookDeliveryStatus, attempts: bigint, 
+/**
+ * HTTP status of the last attempt, if the endpoint answered
+ */
+response_status: bigint | null, last_error: string | null, created_at: string, updated_at: string, delivered_at: string | null, };
+
+export type TaskScheduleAction = "create_task" | "restart_attempt";
+
+export type TaskScheduleRunStatus = "started" | "skipped" | "failed";
+
+export type TaskSchedule = { id: string, project_id: string, name: string, 
+/**
+ * Standard five-field cron expression, e.g. `0 3 * * MON`
+ */
+cron_expression: string, 
+/**
+ * IANA timezone the cron expression is evaluated in, e.g. `Europe/Berlin`
+ */
+timezone: string, action: TaskScheduleAction, 
+/**
+ * Title of the tasks created by `create_task` schedules
+ */
+title: string | null, description: string | null, 
+/**
+ * Task that `restart_attempt` schedules start attempts on
+ */
+task_id: string | null, executor_profile_id: ExecutorProfileId, 
+/**
+ * Repositories and target branches of each attempt; empty reuses those of the task's
+ * latest attempt
+ */
+repos: Array<CreateWorkspaceRepo>, enabled: boolean, next_run_at: string | null, last_run_at: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskSchedule = { project_id: string, name: string, cron_expression: string, 
+/**
+ * Defaults to UTC
+ */
+timezone: string | null, action: TaskScheduleAction, title: string | null, description: string | null, task_id: string | null, executor_profile_id: ExecutorProfileId, repos: Array<CreateWorkspaceRepo>, };
+
+export type UpdateTaskSchedule = { name: string | null, cron_expression: string | null, timezone: string | null, action: TaskScheduleAction | null, title: string | null, description: string | null, task_id: string | null, executor_profile_id: ExecutorProfileId | null, repos: Array<CreateWorkspaceRepo> | null, enabled: boolean | null, };
+
+export type TaskScheduleRun = { id: string, schedule_id: string, scheduled_for: string, status: TaskScheduleRunStatus, task_id: string | null, workspace_id: string | null, error: string | null, created_at: string, };
+
+export type AccessToken = { id: string, name: string, 
+/**
+ * First characters of the token, to tell tokens apart
+ */
+token_prefix: string, created_at: string, last_used_at: string | null, };
+
+export type CreateAccessToken = { name: string, };
+
 export type TaskStatus = "todo" | "inprogress" | "inreview" | "done" | "cancelled";
 
-export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+export type TaskPriority = "low" | "medium" | "high" | "urgent";
+
+export type Task = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, priority: TaskPriority | null, due_date: string | null, 
+/**
+ * Estimated effort in minutes
+ */
+estimate_minutes: bigint | null, parent_workspace_id: string | null, shared_task_id: string | null, 
+/**
+ * Set while the task is archived; archived tasks are hidden from the board
+ */
+archived_at: string | null, created_at: string, updated_at: string, };
 
 export type TaskWithAttemptStatus = { has_in_progress_attempt: boolean, last_attempt_failed: boolean, executor: string, 
 /**
  * Suggested init prompt for task execution
  */
-suggested_prompt: string, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, parent_workspace_id: string | null, shared_task_id: string | null, created_at: string, updated_at: string, };
+suggested_prompt: string, labels: Array<Label>, id: string, project_id: string, title: string, description: string | null, status: TaskStatus, priority: TaskPriority | null, due_date: string | null, 
+/**
+ * Estimated effort in minutes
+ */
+estimate_minutes: bigint | null, parent_workspace_id: string | null, shared_task_id: string | null, 
+/**
+ * Set while the task is archived; archived tasks are hidden from the board
+ */
+archived_at: string | null, created_at: string, updated_at: string, };
 
 export type TaskRelationships = { parent_task: Task | null, current_workspace: Workspace, children: Array<Task>, };
 
-export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
+export type CreateTask = { project_id: string, title: string, description: string | null, status: TaskStatus | null, priority: TaskPriority | null, due_date: string | null, estimate_minutes: bigint | null, 
+/**
+ * Label names; labels the project doesn't have yet are created
+ */
+labels: Array<string> | null, parent_workspace_id: string | null, image_ids: Array<string> | null, shared_task_id: string | null, };
 
-export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, priority: TaskPriority | null, due_date: string | null, estimate_minutes: bigint | null, 
+/**
+ * Remove the priority, due date and estimate whose field is `true`
+ */
+clear: TaskFieldsToClear, 
+/**
+ * Replaces the task's labels when present
+ */
+labels: Array<string> | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
+
+export type TaskFieldsToClear = { priority: boolean, due_date: boolean, estimate_minutes: boolean, };
+
+export type TaskSortField = "created_at" | "updated_at" | "priority" | "due_date" | "title";
+
+export type SortOrder = "asc" | "desc";
+
+export type TaskFilter = { status: TaskStatus | null, priority: TaskPriority | null, 
+/**
+ * Label name, matched case-insensitively
+ */
+label: string | null, 
+/**
+ * Only tasks due strictly before this time
+ */
+due_before: string | null, sort: TaskSortField | null, 
+/**
+ * Defaults to the natural order of `sort`
+ */
+order: SortOrder | null, };
+
+export type TaskActivityKind = "status_changed" | "attempt_created" | "attempt_finished" | "merged" | "approval" | "comment";
+
+export type TaskActivityDetails = { from_status: TaskStatus | null, to_status: TaskStatus | null, branch: string | null, execution_process_id: string | null, 
+/**
+ * Final status of the execution process, or how the approval was resolved
+ */
+outcome: string | null, tool_name: string | null, decided_by: string | null, denial_reason: string | null, merge_commit: string | null, pr_number: bigint | null, pr_url: string | null, };
+
+export type TaskActivity = { id: string, task_id: string, workspace_id: string | null, kind: TaskActivityKind, details: TaskActivityDetails, 
+/**
+ * Markdown body of a comment
+ */
+body: string | null, 
+/**
+ * GitHub username of the commenter, or the responder of an approval
+ */
+author: string | null, created_at: string, updated_at: string, };
+
+export type CreateTaskComment = { body: string, 
+/**
+ * Uploaded images referenced by the comment
+ */
+image_ids: Array<string>, };
+
+export type UpdateTaskComment = { body: string, };
 
 export type DraftFollowUpData = { message: string, variant: string | null, };
 
@@ -70,6 +291,14 @@ export type CreateScratch = { payload: ScratchPayload, };
 
 export type UpdateScratch = { payload: ScratchPayload, };
 
+export type SearchHitKind = "task" | "prompt" | "summary" | "assistant_message" | "command" | "file_edit";
+
+export type SearchHit = { kind: SearchHitKind, project_id: string, task_id: string, task_title: string, task_status: TaskStatus, workspace_id: string | null, session_id: string | null, execution_process_id: string | null, 
+/**
+ * Matching excerpt with hits wrapped in `**`
+ */
+snippet: string, created_at: string, };
+
 export type Image = { id: string, file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, created_at: string, updated_at: string, };
 
 export type CreateImage = { file_path: string, original_name: string, mime_type: string | null, size_bytes: bigint, hash: string, };
@@ -104,10 +333,17 @@ export type PullRequestInfo = { number: bigint, url: string, status: MergeStatus
 
 export type ApprovalStatus = { "status": "pending" } | { "status": "approved" } | { "status": "denied", reason?: string, } | { "status": "timed_out" };
 
+export type ApprovalRequest = { id: string, tool_name: string, tool_input: JsonValue, tool_call_id: string, execution_process_id: string, created_at: string, timeout_at: string, };
+
 export type CreateApprovalRequest = { tool_name: string, tool_input: JsonValue, tool_call_id: string, };
 
 export type ApprovalResponse = { execution_process_id: string, status: ApprovalStatus, };
 
+/**
+ * Approval policy rule that decided a tool call without asking the user
+ */
+export type ApprovalRuleRef = { rule_id: string, rule_name: string, };
+
 export type Diff = { change: DiffChangeKind, oldPath: string | null, newPath: string | null, oldContent: string | null, newContent: string | null, 
 /**
  * True when file contents are intentionally omitted (e.g., too large)
@@ -188,6 +424,71 @@ export type InitRepoRequest = { parent_path: string, folder_name: string, };
 
 export type TagSearchParams = { search: string | null, };
 
+export type LabelQuery = { project_id: string, };
+
+export type SearchQuery = { q: string, project_id: string | null, 
+/**
+ * Only return hits created at or after this time
+ */
+since: string | null, limit: bigint | null, };
+
+export type ApprovalHistoryQuery = { task_id: string | null, session_id: string | null, };
+
+export type ApprovalLinkQuery = { decision: ApprovalLinkDecision, token: string, 
+/**
+ * Passed to the agent when denying
+ */
+reason: string | null, };
+
+/**
+ * Decision carried by a signed approval link
+ */
+export type ApprovalLinkDecision = "approve" | "deny";
+
+/**
+ * Body POSTed to the approval webhook
+ */
+export type ApprovalWebhookPayload = { 
+/**
+ * Always `approval.requested`
+ */
+event: string, approval: ApprovalRequest, task_id: string | null, task_title: string | null, 
+/**
+ * POST to approve the tool call, or open (GET) it for a confirmation page; valid until
+ * the approval is answered
+ */
+approve_url: string, 
+/**
+ * POST to deny the tool call, or open (GET) it for a confirmation page; an optional
+ * `reason` query or form parameter is passed to the agent
+ */
+deny_url: string, };
+
+export type WebhookDeliveriesQuery = { limit: bigint | null, };
+
+export type TaskScheduleQuery = { project_id: string | null, };
+
+export type TaskScheduleRunsQuery = { limit: bigint | null, };
+
+/**
+ * JSON body of every webhook delivery
+ */
+export type WebhookEnvelope = { delivery_id: string, event: WebhookEventType, project_id: string | null, created_at: string, data: JsonValue, };
+
+export type AuthStatus = { 
+/**
+ * Whether the server requires authentication
+ */
+required: boolean, authenticated: boolean, };
+
+export type LoginRequest = { token: string, };
+
+export type CreatedAccessToken = { 
+/**
+ * The token itself; it is only returned once
+ */
+token: string, } & AccessToken;
+
 export type TokenResponse = { access_token: string, expires_at: string | null, };
 
 export type UserSystemInfo = { config: Config, analytics_user_id: string, login_status: LoginStatus, environment: Environment, 
@@ -272,6 +573,179 @@ export type ImportPlansResponse = { imported_count: number, task_ids: Array<stri
 
 export type PlanFileContent = { content: string, file_name: string, };
 
+export type GitHubIssue = { number: bigint, title: string, body: string, url: string, 
+/**
+ * `OPEN` or `CLOSED`
+ */
+state: string, 
+/**
+ * Why a closed issue was closed: `COMPLETED` or `NOT_PLANNED`
+ */
+stateReason: string | null, labels: Array<GitHubIssueLabel>, milestone: GitHubIssueMilestone | null, };
+
+export type GitHubIssueLabel = { name: string, };
+
+export type GitHubIssueMilestone = { title: string, };
+
+export type TaskGitHubIssue = { id: string, task_id: string, repo_id: string, owner: string, repo_name: string, issue_number: bigint, issue_url: string, 
+/**
+ * `open` or `closed`, as last seen on GitHub
+ */
+state: string, created_at: string, updated_at: string, };
+
+export type GitHubIssueQuery = { project_id: string, 
+/**
+ * Repository to read issues from; defaults to the project's first repository
+ */
+repo_id: string | null, 
+/**
+ * Issues must have every one of these labels
+ */
+labels: Array<string>, 
+/**
+ * Milestone title
+ */
+milestone: string | null, 
+/**
+ * GitHub search query, e.g. `no:assignee sort:created-asc`
+ */
+search: string | null, 
+/**
+ * Maximum number of issues to fetch (default 100)
+ */
+limit: number | null, };
+
+export type GitHubIssueCandidate = { task_id: string | null, } & GitHubIssue;
+
+export type ImportGitHubIssuesRequest = { 
+/**
+ * Only import these issue numbers from the matching issues
+ */
+issue_numbers: Array<bigint> | null, } & GitHubIssueQuery;
+
+export type ImportGitHubIssuesResponse = { imported: Array<TaskGitHubIssue>, 
+/**
+ * Matching issues that were already imported into the project
+ */
+skipped_count: number, errors: Array<string>, };
+
+export type ProjectExportFormat = "json" | "csv";
+
+export type ProjectExportOptions = { format: ProjectExportFormat, 
+/**
+ * Include sessions, normalized agent logs and merges of every attempt (JSON only)
+ */
+include_history: boolean, };
+
+export type ProjectArchive = { version: number, exported_at: string, project: ArchivedProject, repositories: Array<ArchivedRepo>, tags: Array<ArchivedTag>, 
+/**
+ * Oldest first
+ */
+tasks: Array<ArchivedTask>, };
+
+/**
+ * Project settings, including the dev server script
+ */
+export type ArchivedProject = { name: string, dev_script: string | null, dev_script_working_dir: string | null, default_agent_working_dir: string | null, };
+
+export type ArchivedRepo = { 
+/**
+ * Repository ID on the exporting machine, referenced by attempts and merges
+ */
+id: string, name: string, display_name: string, 
+/**
+ * Path on the exporting machine
+ */
+path: string, setup_script: string | null, cleanup_script: string | null, copy_files: string | null, parallel_setup_script: boolean, };
+
+export type ArchivedTag = { tag_name: string, content: string, };
+
+export type ArchivedTask = { id: string, title: string, description: string | null, status: TaskStatus, priority: TaskPriority | null, due_date: string | null, estimate_minutes: bigint | null, 
+/**
+ * Label names; labels missing from the target project are created on import
+ */
+labels: Array<string>, 
+/**
+ * Attempt this task was created from; one of the `attempts` of another archived task
+ */
+parent_workspace_id: string | null, created_at: string, updated_at: string, 
+/**
+ * Oldest first. Always present so that `parent_workspace_id` can be resolved.
+ */
+attempts: Array<ArchivedAttempt>, };
+
+export type ArchivedAttempt = { id: string, branch: string, agent_working_dir: string | null, repos: Array<ArchivedAttemptRepo>, created_at: string, 
+/**
+ * Empty unless exported with history
+ */
+sessions: Array<ArchivedSession>, 
+/**
+ * Empty unless exported with history
+ */
+merges: Array<Merge>, };
+
+export type ArchivedAttemptRepo = { repo_id: string, target_branch: string, };
+
+export type ArchivedSession = { executor: string | null, created_at: string, processes: Array<ArchivedExecutionProcess>, };
+
+export type ArchivedExecutionProcess = { run_reason: ExecutionProcessRunReason, executor_action: ExecutorAction, status: ExecutionProcessStatus, exit_code: bigint | null, dropped: boolean, started_at: string, completed_at: string | null, 
+/**
+ * The normalized conversation; raw process output is not exported
+ */
+entries: Array<NormalizedEntry>, };
+
+/**
+ * What an import created
+ */
+export type ProjectImportSummary = { task_count: number, attempt_count: number, session_count: number, tags_created: number, 
+/**
+ * Archived tags skipped because a tag with the same name exists
+ */
+tags_skipped: number, };
+
+export type ImportProjectRequest = { archive: ProjectArchive, 
+/**
+ * Name of the new project; defaults to the archived name
+ */
+name: string | null, 
+/**
+ * Path on this machine of each archived repository, by archived repository ID.
+ * Repositories left out are expected at their archived path.
+ */
+repo_paths: { [key in string]?: string }, };
+
+export type ImportProjectResponse = { project: Project, } & ProjectImportSummary;
+
+export type BackupInfo = { file_name: string, path: string, size_bytes: bigint, created_at: string, };
+
+export type PurgeArchivedResult = { purged_task_ids: Array<string>, purged_project_ids: Array<string>, };
+
+export type PurgeArchivedRequest = { 
+/**
+ * Only purge items archived at least this many days ago (default 30); 0 purges everything
+ * archived
+ */
+retention_days: number | null, };
+
+export type BulkTaskOperation = { "type": "set_status", status: TaskStatus, } | { "type": "delete" } | { "type": "add_labels", labels: Array<string>, } | { "type": "remove_labels", labels: Array<string>, } | { "type": "start_attempt", executor_profile_id: ExecutorProfileId, 
+/**
+ * Repositories and target branches of the attempts. Defaults to those of each
+ * task's latest attempt.
+ */
+repos: Array<CreateWorkspaceRepo>, };
+
+export type BulkTaskRequest = { task_ids: Array<string>, operation: BulkTaskOperation, };
+
+export type BulkTaskResult = { task_id: string, success: boolean, 
+/**
+ * Why the task was skipped, or why its attempt failed to start
+ */
+error: string | null, 
+/**
+ * Attempt created by a `start_attempt` operation
+ */
+workspace_id: string | null, };
+
 export type CreateTaskAttemptBody = { task_id: string, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, 
 /**
  * Optional custom prompt that overrides task.to_prompt() for this attempt
@@ -338,11 +812,30 @@ conflict_op: ConflictOp | null,
  */
 conflicted_files: Array<string>, };
 
+export type RepoDiff = { repo_id: string, repo_name: string, diffs: Array<Diff>, };
+
 export type DirectoryEntry = { name: string, path: string, is_directory: boolean, is_git_repo: boolean, last_modified: bigint | null, };
 
 export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_path: string, };
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, };
+export type TranscriptFormat = "markdown" | "html";
+
+export type TranscriptOptions = { format: TranscriptFormat, 
+/**
+ * Fold command output, tool results and diffs into `<details>` blocks
+ */
+collapse_tool_output: boolean, 
+/**
+ * Mask `NAME=value` assignments and values of secret-looking environment variables
+ */
+redact_env: boolean, };
+
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, log_retention: LogRetentionConfig, approval_webhook: ApprovalWebhookConfig, 
+/**
+ * Remote channels (chat, push, email) that receive notifications in addition to the
+ * local sound and desktop push
+ */
+notification_channels: Array<NotificationChannelConfig>, backup: BackupConfig, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -362,9 +855,61 @@ export type UiLanguage = "BROWSER" | "EN" | "JA" | "ES" | "KO" | "ZH_HANS";
 
 export type ShowcaseState = { seen_features: Array<string>, };
 
+export type LogRetentionConfig = { 
+/**
+ * Days after an execution finishes before its raw stdout/stderr is replaced by the
+ * normalized conversation. Only applies to tasks that are Done. `null` keeps raw logs.
+ */
+raw_log_retention_days: number | null, interval_hours: number, };
+
+export type BackupConfig = { 
+/**
+ * Back up the database and settings every `interval_hours`
+ */
+enabled: boolean, interval_hours: number, 
+/**
+ * Number of backups to keep; older ones are deleted after each scheduled backup
+ */
+keep: number, 
+/**
+ * Directory backups are written to. Defaults to `backups` in the data directory.
+ */
+directory: string | null, };
+
+export type ApprovalWebhookConfig = { 
+/**
+ * URL that receives a POST with the approval request and signed approve/deny links
+ * whenever a tool call waits for approval. `null` disables the webhook.
+ */
+url: string | null, 
+/**
+ * Base URL the links point at, e.g. when the server is reachable through a tunnel.
+ * Defaults to the local server address.
+ */
+public_base_url: string | null, };
+
+/**
+ * Event that can be routed to notification channels
+ */
+export type NotificationEvent = "task_complete" | "task_failed" | "approval_pending" | "pr_merged";
+
+export type SmtpSecurity = "start_tls" | "tls" | "none";
+
+export type NotificationChannelKind = { "type": "webhook", url: string, 
+/**
+ * Key for the `X-Vibe-Kanban-Signature` HMAC
+ */
+secret: string | null, } | { "type": "slack", webhook_url: string, } | { "type": "discord", webhook_url: string, } | { "type": "ntfy", server_url: string, topic: string, token: string | null, } | { "type": "email", smtp_host: string, smtp_port: number, security: SmtpSecurity, username: string | null, password: string | null, from: string, to: Array<string>, };
+
+export type NotificationChannelConfig = { name: string, enabled: boolean, 
+/**
+ * Events delivered to this channel
+ */
+events: Array<NotificationEvent>, } & NotificationChannelKind;
+
 export type GitBranch = { name: string, is_current: boolean, is_remote: boolean, last_commit_date: Date, };
 
-export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, };
+export type SharedTaskDetails = { id: string, project_id: string, title: string, description: string | null, status: TaskStatus, priority: TaskPriority | null, due_date: string | null, estimate_minutes: number | null, labels: Array<string>, };
 
 export type QueuedMessage = { 
 /**
@@ -433,13 +978,19 @@ export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: Executor
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+/**
+ * What happens to a pending tool approval when its timeout elapses: deny the tool call,
+ * let it run, or start another timeout period and keep waiting for a response.
+ */
+export type ApprovalTimeoutAction = "deny" | "approve" | "keep_waiting";
+
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -451,19 +1002,19 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
 export type Opencode = { append_prompt: AppendPrompt, model?: string | null, mode?: string | null, 
 /**
  * Auto-approve agent actions
  */
-auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+auto_approve: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -477,7 +1028,7 @@ provider: string, append_prompt: AppendPrompt, model?: string | null, dangerousl
 /**
  * Enable interactive approvals via protocol (like Claude)
  */
-approvals?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+approvals?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, approval_timeout_seconds?: number | null, approval_timeout_action?: ApprovalTimeoutAction | null, };
 
 export type AppendPrompt = string | null;
 
@@ -535,7 +1086,11 @@ value: JsonValue, };
 
 export type ToolResultValueType = { "type": "markdown" } | { "type": "json" };
 
-export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "timed_out" };
+export type ToolStatus = { "status": "created" } | { "status": "success" } | { "status": "failed" } | { "status": "denied", reason: string | null, 
+/**
+ * Set when an approval policy rule denied the call
+ */
+rule?: ApprovalRuleRef, } | { "status": "auto_approved", rule: ApprovalRuleRef, } | { "status": "pending_approval", approval_id: string, requested_at: string, timeout_at: string, } | { "status": "timed_out" };
 
 export type PatchType = { "type": "NORMALIZED_ENTRY", "content": NormalizedEntry } | { "type": "STDOUT", "content": string } | { "type": "STDERR", "content": string } | { "type": "DIFF", "content": Diff };
 